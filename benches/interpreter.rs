@@ -0,0 +1,64 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use mini_wasm::execution::engine::{Backend, Config, Engine};
+use mini_wasm::execution::instance::{instantiate, invoke};
+use mini_wasm::execution::structure::{Store, Val};
+use mini_wasm::text;
+
+const PROGRAM: &str = r#"(module
+    (memory 1)
+    (func $fib (export "fib") (param i32) (result i32)
+        (if (result i32) (i32.lt_s (local.get 0) (i32.const 2))
+            (then (local.get 0))
+            (else (i32.add
+                (call $fib (i32.sub (local.get 0) (i32.const 1)))
+                (call $fib (i32.sub (local.get 0) (i32.const 2)))))))
+    (func $sum (export "sum") (param i32) (result i32)
+        (if (result i32) (i32.eqz (local.get 0))
+            (then (i32.const 0))
+            (else (i32.add
+                (local.get 0)
+                (call $sum (i32.sub (local.get 0) (i32.const 1)))))))
+    (func $copy (export "copy") (param i32)
+        (if (local.get 0)
+            (then
+                (i32.store offset=4096 (local.get 0) (i32.load (local.get 0)))
+                (call $copy (i32.sub (local.get 0) (i32.const 4)))))))"#;
+
+#[cfg(not(feature = "jit"))]
+const BACKENDS: [Backend; 2] = [Backend::Stack, Backend::Register];
+#[cfg(feature = "jit")]
+const BACKENDS: [Backend; 3] = [Backend::Stack, Backend::Register, Backend::Jit];
+
+fn bench(c: &mut Criterion) {
+    let cases = [
+        ("fib", Val::I32(20)),
+        ("sum", Val::I32(5_000)),
+        ("copy", Val::I32(4_000)),
+    ];
+    for (name, arg) in cases {
+        let mut group = c.benchmark_group(name);
+        for backend in BACKENDS {
+            let module = text::parse(PROGRAM).unwrap();
+            let mut store = Store::new(&Engine::with_config(Config {
+                backend,
+                ..Default::default()
+            }));
+            let module_inst = instantiate(&mut store, module, &[]).unwrap();
+            group.bench_function(BenchmarkId::from_parameter(format!("{:?}", backend)), |b| {
+                b.iter(|| {
+                    invoke(
+                        &mut store,
+                        &module_inst,
+                        name.to_string(),
+                        vec![arg.clone()],
+                    )
+                    .unwrap()
+                })
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
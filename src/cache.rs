@@ -0,0 +1,146 @@
+use crate::decoder::{Decoder, DecodingError};
+use crate::execution::engine::Engine;
+use crate::structure::modules::module::Module;
+use crate::validation::{validate_module, Bodies, ValidationError};
+use sha2::{Digest, Sha256};
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A directory of serialized modules, addressed by the SHA-256 of their binary and the engine
+/// configuration, so that an unchanged binary isn't decoded again.
+///
+/// Modules read back from an entry are validated when they are instantiated, as anyone who can
+/// write the directory can change them.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+#[derive(Debug)]
+pub enum CacheError {
+    Decoding(DecodingError),
+    Invalid(ValidationError),
+}
+
+impl fmt::Display for CacheError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CacheError::Decoding(error) => write!(f, "{}", error),
+            CacheError::Invalid(error) => write!(f, "invalid module: {}", error),
+        }
+    }
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// `mini-wasm` in `$XDG_CACHE_HOME`, or in `~/.cache` if that isn't set.
+    pub fn default_dir() -> Option<PathBuf> {
+        let base = match env::var_os("XDG_CACHE_HOME") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => PathBuf::from(env::var_os("HOME")?).join(".cache"),
+        };
+        Some(base.join("mini-wasm"))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the module of a binary from the cache, or decodes and validates it and adds it
+    /// to the cache. Failing to write the cache isn't an error.
+    pub fn load(&self, binary: &[u8], engine: &Engine) -> Result<Module, CacheError> {
+        let path = self.dir.join(self.key(binary, engine));
+        if let Ok(bytes) = fs::read(&path) {
+            if let Ok(module) = Module::deserialize(&bytes, engine) {
+                return Ok(module);
+            }
+        }
+
+        let mut module = Decoder::new(binary)
+            .decode()
+            .map_err(CacheError::Decoding)?;
+        validate_module(&module, Bodies::Decoded, engine.config()).map_err(CacheError::Invalid)?;
+        module.validated = true;
+        if let Ok(bytes) = module.serialize(engine) {
+            let _ = self.write(&path, &bytes);
+        }
+        Ok(module)
+    }
+
+    fn key(&self, binary: &[u8], engine: &Engine) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(binary);
+        hasher.update(format!("{:?}", engine.config()));
+        hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    /// Writes an entry through a temporary file, so readers never see a partial entry.
+    fn write(&self, path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let temp = path.with_extension(format!("tmp{}", std::process::id()));
+        fs::write(&temp, bytes)?;
+        fs::rename(&temp, path).inspect_err(|_| {
+            let _ = fs::remove_file(&temp);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::Encoder;
+    use crate::execution::instance::{instantiate, InstantiationError};
+    use crate::execution::structure::Store;
+    use crate::text;
+
+    #[test]
+    fn test_load() {
+        let dir = env::temp_dir().join(format!("mini-wasm-cache-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let cache = Cache::new(&dir);
+        let engine = Engine::new();
+        let module = text::parse(r#"(module (func (export "f") (result i32) (i32.const 1)))"#);
+        let binary = Encoder::encode(&module.unwrap());
+
+        // Only a module that was just decoded is known to be valid.
+        let decoded = cache.load(&binary, &engine).unwrap();
+        assert!(decoded.validated);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        let cached = cache.load(&binary, &engine).unwrap();
+        assert!(!cached.validated);
+        assert_eq!(cached.exports, decoded.exports);
+
+        // A corrupt entry is replaced.
+        let entry = fs::read_dir(&dir).unwrap().next().unwrap().unwrap().path();
+        fs::write(&entry, b"corrupt").unwrap();
+        assert!(cache.load(&binary, &engine).unwrap().validated);
+        assert!(!cache.load(&binary, &engine).unwrap().validated);
+
+        // An entry that deserializes but isn't valid fails instantiation.
+        let invalid = text::parse("(module (func (export \"f\") (result i32)))");
+        let mut invalid = invalid.unwrap();
+        invalid.validated = true;
+        fs::write(&entry, invalid.serialize(&engine).unwrap()).unwrap();
+        let module = cache.load(&binary, &engine).unwrap();
+        let result = instantiate(&mut Store::new(&engine), module, &[]);
+        assert!(matches!(result, Err(InstantiationError::Invalid(_))));
+
+        let invalid = text::parse("(module (func (result i32)))").unwrap();
+        let result = cache.load(&Encoder::encode(&invalid), &engine);
+        assert!(matches!(result, Err(CacheError::Invalid(_))));
+        assert!(matches!(
+            cache.load(b"\0asm", &engine),
+            Err(CacheError::Decoding(_))
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,41 +1,103 @@
 use crate::structure::instructions::expression::Instr::If;
-use crate::structure::instructions::expression::{BlockType, Expr, Instr};
+use crate::structure::instructions::expression::{BlockType, Expr, Instr, MemArg};
+use crate::structure::modules::custom::{CustomSection, IndirectNameMap, NameMap, Names};
+use crate::structure::modules::data::{Data, DataMode};
 use crate::structure::modules::export::{Export, ExportDesc};
-use crate::structure::modules::function::Func;
-use crate::structure::modules::indice::{FuncIdx, LocalIdx, TypeIdx};
+use crate::structure::modules::function::{Func, LazyBody};
+use crate::structure::modules::import::{Import, ImportDesc};
+use crate::structure::modules::indice::{FuncIdx, LocalIdx, MemIdx, TableIdx, TypeIdx};
+use crate::structure::modules::memory::Mem;
 use crate::structure::modules::module::Module;
+use crate::structure::modules::table::Table;
 use crate::structure::types::function::FuncType;
-use crate::structure::types::value::NumType;
+use crate::structure::types::memory::{Limits, MemType};
+use crate::structure::types::table::TableType;
 use crate::structure::types::value::ValType;
+use crate::structure::types::value::{NumType, RefType};
+use std::fmt;
 use std::iter::repeat_n;
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub struct DecodingError {}
 
 impl DecodingError {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self {}
     }
 }
 
+impl fmt::Display for DecodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "malformed module")
+    }
+}
+
+/// A section header as laid out in the binary, used for dumping.
+#[derive(Debug, PartialEq)]
+pub struct Section {
+    pub id: u8,
+    /// Offset of the section contents, just after the size field.
+    pub offset: usize,
+    pub size: u32,
+    /// The name of a custom section.
+    pub name: Option<String>,
+    /// The number of entries in a vector-shaped section.
+    pub count: Option<u32>,
+}
+
+impl Section {
+    pub fn kind(&self) -> &'static str {
+        match self.id {
+            0 => "custom",
+            1 => "type",
+            2 => "import",
+            3 => "function",
+            4 => "table",
+            5 => "memory",
+            6 => "global",
+            7 => "export",
+            8 => "start",
+            9 => "element",
+            10 => "code",
+            11 => "data",
+            12 => "datacount",
+            _ => "unknown",
+        }
+    }
+}
+
 pub struct Decoder<'a> {
     input: &'a [u8],
     pos: usize,
+    /// Offsets of the decoded instructions, in the order they start.
+    offsets: Vec<usize>,
+    /// Whether function bodies are left undecoded.
+    lazy: bool,
 }
 
 impl<'a> Decoder<'a> {
-    pub fn new(input: &'a [u8]) -> Decoder {
-        Self { input, pos: 0 }
+    pub fn new(input: &'a [u8]) -> Decoder<'a> {
+        Self {
+            input,
+            pos: 0,
+            offsets: Vec::new(),
+            lazy: false,
+        }
+    }
+
+    /// Makes `decode` only record where each function body is, so that bodies are decoded
+    /// when they are first needed. The functions of the module then have empty bodies until
+    /// `Module::decode_bodies` is called, and `validate` leaves them to `validate_all`.
+    pub fn lazy_bodies(mut self, lazy: bool) -> Self {
+        self.lazy = lazy;
+        self
     }
 }
 
 impl Decoder<'_> {
     pub fn decode(&mut self) -> Result<Module, DecodingError> {
-        let mut module = Module {
-            types: Vec::new(),
-            funcs: Vec::new(),
-            exports: Vec::new(),
-        };
+        let mut module = Module::default();
 
         let magic_number = self.decode_magic_number()?;
         if magic_number != vec![0x00, 0x61, 0x73, 0x6d] {
@@ -49,32 +111,108 @@ impl Decoder<'_> {
 
         let mut type_idxs = Vec::new();
         while self.pos < self.input.len() {
-            let section_id = self.input[self.pos];
-            self.pos += 1;
+            let section_id = self.decode_byte()?;
+            self.decode_section(section_id, &mut module, &mut type_idxs)?;
+        }
 
-            match section_id {
-                1 => {
-                    module.types = self.decode_type_section()?;
-                }
-                3 => {
-                    type_idxs = self.decode_function_section()?;
-                }
-                7 => {
-                    module.exports = self.decode_export_section()?;
-                }
-                10 => {
-                    module.funcs = self.decode_code_section(&type_idxs)?;
-                }
-                _ => {
-                    let section_size = self.input[self.pos];
-                    self.pos += 1;
+        Ok(module)
+    }
 
-                    self.pos += section_size as usize;
-                }
+    /// Decodes a section into the module, starting at its size. `type_idxs` holds the types
+    /// of the function section for the code section.
+    pub(crate) fn decode_section(
+        &mut self,
+        id: u8,
+        module: &mut Module,
+        type_idxs: &mut Vec<TypeIdx>,
+    ) -> Result<(), DecodingError> {
+        match id {
+            1 => {
+                module.types = self.decode_type_section()?;
+            }
+            2 => {
+                module.imports = self.decode_import_section()?;
+            }
+            3 => {
+                *type_idxs = self.decode_function_section()?;
+            }
+            4 => {
+                module.tables = self.decode_table_section()?;
+            }
+            5 => {
+                module.mems = self.decode_memory_section()?;
+            }
+            7 => {
+                module.exports = self.decode_export_section()?;
+            }
+            8 => {
+                let _section_size = self.decode_u32()?;
+                module.start = Some(self.decode_u32()? as FuncIdx);
+            }
+            10 if self.lazy => {
+                (module.funcs, module.lazy_bodies) = self.decode_lazy_code_section(type_idxs)?;
+            }
+            10 => {
+                module.funcs = self.decode_code_section(type_idxs)?;
+            }
+            11 => {
+                module.datas = self.decode_data_section()?;
+            }
+            0 => self.decode_custom_section(module)?,
+            _ => {
+                let section_size = self.decode_u32()?;
+                self.pos += section_size as usize;
             }
         }
+        Ok(())
+    }
 
-        Ok(module)
+    /// Lists the sections of the module without decoding their contents.
+    pub fn decode_sections(&mut self) -> Result<Vec<Section>, DecodingError> {
+        if self.decode_magic_number()? != vec![0x00, 0x61, 0x73, 0x6d] {
+            return Err(DecodingError::new());
+        }
+        if self.decode_version()? != vec![0x01, 0x00, 0x00, 0x00] {
+            return Err(DecodingError::new());
+        }
+
+        let mut sections = Vec::new();
+        while self.pos < self.input.len() {
+            let id = self.decode_byte()?;
+            let size = self.decode_u32()?;
+            let end = self
+                .pos
+                .checked_add(size as usize)
+                .filter(|end| *end <= self.input.len())
+                .ok_or_else(DecodingError::new)?;
+            sections.push(self.decode_section_header(id, size)?);
+            self.pos = end;
+        }
+
+        Ok(sections)
+    }
+
+    /// Describes the section whose contents start at `pos`, reading its name or entry count.
+    pub(crate) fn decode_section_header(
+        &mut self,
+        id: u8,
+        size: u32,
+    ) -> Result<Section, DecodingError> {
+        let offset = self.pos;
+        let mut name = None;
+        let mut count = None;
+        match id {
+            0 => name = Some(self.decode_name()?),
+            1..=7 | 9..=11 => count = Some(self.decode_u32()?),
+            _ => {}
+        }
+        Ok(Section {
+            id,
+            offset,
+            size,
+            name,
+            count,
+        })
     }
 
     fn decode_magic_number(&mut self) -> Result<Vec<u8>, DecodingError> {
@@ -110,46 +248,24 @@ impl Decoder<'_> {
     fn decode_type_section(&mut self) -> Result<Vec<FuncType>, DecodingError> {
         let mut types = Vec::new();
 
-        let section_size = self.input[self.pos];
-        self.pos += 1;
-
-        let num_of_func_types = self.input[self.pos];
-        self.pos += 1;
+        let _section_size = self.decode_u32()?;
 
-        for i in 0..num_of_func_types {
-            if self.input[self.pos] != 0x60 {
+        let num_of_func_types = self.decode_u32()?;
+        for _ in 0..num_of_func_types {
+            if self.decode_byte()? != 0x60 {
                 return Err(DecodingError::new());
             }
-            self.pos += 1;
 
-            let num_of_parameters = self.input[self.pos];
-            self.pos += 1;
+            let num_of_parameters = self.decode_u32()?;
             let mut parameters = Vec::new();
             for _ in 0..num_of_parameters {
-                let val_type = match self.input[self.pos] {
-                    0x7f => ValType::NumType(NumType::I32),
-                    0x7e => ValType::NumType(NumType::I64),
-                    0x7d => ValType::NumType(NumType::F32),
-                    0x7c => ValType::NumType(NumType::F64),
-                    _ => unimplemented!("unimplemented value type"),
-                };
-                parameters.push(val_type);
-                self.pos += 1;
+                parameters.push(self.decode_val_type()?);
             }
 
-            let mut num_of_results = self.input[self.pos];
-            self.pos += 1;
+            let num_of_results = self.decode_u32()?;
             let mut results = Vec::new();
             for _ in 0..num_of_results {
-                let val_type = match self.input[self.pos] {
-                    0x7f => ValType::NumType(NumType::I32),
-                    0x7e => ValType::NumType(NumType::I64),
-                    0x7d => ValType::NumType(NumType::F32),
-                    0x7c => ValType::NumType(NumType::F64),
-                    _ => unimplemented!("unimplemented value type"),
-                };
-                results.push(val_type);
-                self.pos += 1;
+                results.push(self.decode_val_type()?);
             }
 
             let func_type = FuncType {
@@ -162,51 +278,88 @@ impl Decoder<'_> {
         Ok(types)
     }
 
+    fn decode_import_section(&mut self) -> Result<Vec<Import>, DecodingError> {
+        let mut imports = Vec::new();
+
+        let _section_size = self.decode_u32()?;
+
+        let num_of_imports = self.decode_u32()?;
+        for _ in 0..num_of_imports {
+            let module = self.decode_name()?;
+            let name = self.decode_name()?;
+
+            let desc_type = self.decode_byte()?;
+
+            let desc = match desc_type {
+                0x00 => ImportDesc::Func(self.decode_u32()? as TypeIdx),
+                0x02 => ImportDesc::Mem(self.decode_mem_type()?),
+                // Tables and globals aren't supported.
+                _ => return Err(DecodingError::new()),
+            };
+
+            let import = Import { module, name, desc };
+            imports.push(import);
+        }
+
+        Ok(imports)
+    }
+
     fn decode_function_section(&mut self) -> Result<Vec<TypeIdx>, DecodingError> {
         let mut idxs = Vec::new();
 
-        let section_size = self.input[self.pos];
-        self.pos += 1;
-
-        let num_of_idxs = self.input[self.pos];
-        self.pos += 1;
+        let _section_size = self.decode_u32()?;
 
+        let num_of_idxs = self.decode_u32()?;
         for _ in 0..num_of_idxs {
-            // TODO: LEB128 https://webassembly.github.io/spec/core/binary/values.html#integers
-            idxs.push(self.input[self.pos] as TypeIdx);
-            self.pos += 1;
+            idxs.push(self.decode_u32()? as TypeIdx);
         }
 
         Ok(idxs)
     }
 
-    fn decode_export_section(&mut self) -> Result<Vec<Export>, DecodingError> {
-        let mut exports = Vec::new();
+    fn decode_table_section(&mut self) -> Result<Vec<Table>, DecodingError> {
+        let mut tables = Vec::new();
 
-        let section_size = self.decode_u32()?;
+        let _section_size = self.decode_u32()?;
 
-        let num_of_exports = self.input[self.pos];
-        self.pos += 1;
+        let num_of_tables = self.decode_u32()?;
+        for _ in 0..num_of_tables {
+            let type_ = self.decode_table_type()?;
+            tables.push(Table { type_ });
+        }
 
-        for _ in 0..num_of_exports {
-            let name_length = self.input[self.pos];
-            self.pos += 1;
+        Ok(tables)
+    }
 
-            let mut name = String::new();
-            for _ in 0..name_length {
-                name.push(self.input[self.pos] as char);
-                self.pos += 1;
-            }
+    fn decode_memory_section(&mut self) -> Result<Vec<Mem>, DecodingError> {
+        let mut mems = Vec::new();
 
-            let desc_type = self.input[self.pos];
-            self.pos += 1;
+        let _section_size = self.decode_u32()?;
 
-            let idx = self.input[self.pos];
-            self.pos += 1;
+        let num_of_mems = self.decode_u32()?;
+        for _ in 0..num_of_mems {
+            let type_ = self.decode_mem_type()?;
+            mems.push(Mem { type_ });
+        }
+
+        Ok(mems)
+    }
+
+    fn decode_export_section(&mut self) -> Result<Vec<Export>, DecodingError> {
+        let mut exports = Vec::new();
+
+        let _section_size = self.decode_u32()?;
+
+        let num_of_exports = self.decode_u32()?;
+        for _ in 0..num_of_exports {
+            let name = self.decode_name()?;
+            let desc_type = self.decode_byte()?;
+            let idx = self.decode_u32()?;
 
             let desc = match desc_type {
                 0x00 => ExportDesc::Func(idx as FuncIdx),
-                _ => unimplemented!("unimplemented export desc"),
+                0x02 => ExportDesc::Mem(idx as MemIdx),
+                _ => return Err(DecodingError::new()),
             };
 
             let export = Export { name, desc };
@@ -217,72 +370,309 @@ impl Decoder<'_> {
     }
 
     fn decode_code_section(&mut self, type_idxs: &[TypeIdx]) -> Result<Vec<Func>, DecodingError> {
-        let section_size = self.decode_u32()?;
+        let _section_size = self.decode_u32()?;
+        let section_start = self.pos;
 
         let num_of_funcs = self.decode_u32()?;
 
         let mut funcs = Vec::new();
         for i in 0..num_of_funcs {
-            let size = self.decode_u32()?;
-            let num_of_locals = self.decode_u32()?;
-
-            let mut locals = Vec::new();
-            for _ in 0..num_of_locals {
-                let n = self.decode_u32()?;
-
-                let val_type = match self.input[self.pos] {
-                    0x7f => ValType::NumType(NumType::I32),
-                    0x7e => ValType::NumType(NumType::I64),
-                    0x7d => ValType::NumType(NumType::F32),
-                    0x7c => ValType::NumType(NumType::F64),
-                    _ => unimplemented!("unimplemented value type"),
-                };
-                self.pos += 1;
+            let _size = self.decode_u32()?;
+            let type_ = *type_idxs.get(i as usize).ok_or_else(DecodingError::new)?;
+            funcs.push(self.decode_func(type_, section_start)?);
+        }
+
+        Ok(funcs)
+    }
+
+    /// Splits the code section into bodies without decoding them. The functions get empty
+    /// bodies, which are decoded from the returned ones.
+    fn decode_lazy_code_section(
+        &mut self,
+        type_idxs: &[TypeIdx],
+    ) -> Result<(Vec<Func>, Vec<LazyBody>), DecodingError> {
+        let section_size = self.decode_u32()? as usize;
+        let section_start = self.pos;
+        let section_end = section_start
+            .checked_add(section_size)
+            .filter(|end| *end <= self.input.len())
+            .ok_or_else(DecodingError::new)?;
+        let code: Arc<[u8]> = Arc::from(&self.input[section_start..section_end]);
+
+        let num_of_funcs = self.decode_u32()?;
+
+        let mut funcs = Vec::new();
+        let mut bodies = Vec::new();
+        for i in 0..num_of_funcs {
+            let size = self.decode_u32()? as usize;
+            let start = self.pos;
+            let end = start
+                .checked_add(size)
+                .filter(|end| *end <= section_end)
+                .ok_or_else(DecodingError::new)?;
+            self.pos = end;
+
+            funcs.push(Func {
+                type_: *type_idxs.get(i as usize).ok_or_else(DecodingError::new)?,
+                locals: Vec::new(),
+                body: Expr(Vec::new()),
+                offsets: Vec::new(),
+            });
+            bodies.push(LazyBody {
+                code: Arc::clone(&code),
+                range: start - section_start..end - section_start,
+            });
+        }
+        self.pos = section_end;
+
+        Ok((funcs, bodies))
+    }
 
-                locals.extend(repeat_n(val_type, n as usize));
+    /// Decodes a function body that starts at `pos`, with offsets relative to the start of
+    /// the code section contents.
+    pub(crate) fn decode_func_at(
+        &mut self,
+        pos: usize,
+        type_: TypeIdx,
+    ) -> Result<Func, DecodingError> {
+        self.pos = pos;
+        self.decode_func(type_, 0)
+    }
+
+    fn decode_func(&mut self, type_: TypeIdx, section_start: usize) -> Result<Func, DecodingError> {
+        let num_of_locals = self.decode_u32()?;
+
+        let mut locals = Vec::new();
+        for _ in 0..num_of_locals {
+            let n = self.decode_u32()?;
+            let val_type = self.decode_val_type()?;
+
+            locals.extend(repeat_n(val_type, n as usize));
+        }
+
+        self.offsets.clear();
+        let mut body = Expr(Vec::new());
+        loop {
+            let instr = self.decode_instruction()?;
+            if instr == Instr::End {
+                break;
             }
+            body.0.push(instr);
+        }
+        let offsets = self
+            .offsets
+            .iter()
+            .map(|offset| (offset - section_start) as u32)
+            .collect();
+        Ok(Func {
+            type_,
+            locals,
+            body,
+            offsets,
+        })
+    }
+
+    fn decode_data_section(&mut self) -> Result<Vec<Data>, DecodingError> {
+        let mut datas = Vec::new();
 
-            let mut body = Expr(Vec::new());
-            loop {
-                let instr = self.decode_instruction()?;
-                if instr == Instr::End {
-                    break;
+        let _section_size = self.decode_u32()?;
+
+        let num_of_datas = self.decode_u32()?;
+        for _ in 0..num_of_datas {
+            let mode = match self.decode_u32()? {
+                0 => {
+                    let offset = self.decode_expr()?;
+                    DataMode::Active { memory: 0, offset }
                 }
-                body.0.push(instr);
-            }
-            println!("{:?}", body);
-            let func = Func {
-                type_: type_idxs[i as usize],
-                locals,
-                body,
+                1 => DataMode::Passive,
+                2 => {
+                    let memory = self.decode_u32()? as MemIdx;
+                    let offset = self.decode_expr()?;
+                    DataMode::Active { memory, offset }
+                }
+                _ => return Err(DecodingError::new()),
             };
-            funcs.push(func);
+
+            let len = self.decode_u32()? as usize;
+            if self.input.len() < self.pos + len {
+                return Err(DecodingError::new());
+            }
+            let init = self.input[self.pos..self.pos + len].to_vec();
+            self.pos += len;
+
+            datas.push(Data { init, mode });
         }
 
-        Ok(funcs)
+        Ok(datas)
+    }
+
+    fn decode_custom_section(&mut self, module: &mut Module) -> Result<(), DecodingError> {
+        let section_size = self.decode_u32()? as usize;
+        let end = self
+            .pos
+            .checked_add(section_size)
+            .filter(|end| *end <= self.input.len())
+            .ok_or_else(DecodingError::new)?;
+
+        let name = self.decode_name()?;
+        if self.pos > end {
+            return Err(DecodingError::new());
+        }
+        let data = &self.input[self.pos..end];
+        self.pos = end;
+
+        // A malformed name section must not make the module invalid, so it is kept as raw bytes.
+        if name == "name" && module.names.is_empty() {
+            if let Ok(names) = Decoder::new(data).decode_names() {
+                module.names = names;
+                return Ok(());
+            }
+        }
+        module.customs.push(CustomSection {
+            name,
+            data: data.to_vec(),
+        });
+        Ok(())
+    }
+
+    fn decode_names(&mut self) -> Result<Names, DecodingError> {
+        let mut names = Names::default();
+        while self.pos < self.input.len() {
+            let id = self.decode_byte()?;
+            let size = self.decode_u32()? as usize;
+            let end = self
+                .pos
+                .checked_add(size)
+                .filter(|end| *end <= self.input.len())
+                .ok_or_else(DecodingError::new)?;
+
+            let mut subsection = Decoder::new(&self.input[self.pos..end]);
+            match id {
+                0 => names.module = Some(subsection.decode_name()?),
+                1 => names.funcs = subsection.decode_name_map()?,
+                2 => names.locals = subsection.decode_indirect_name_map()?,
+                3 => names.labels = subsection.decode_indirect_name_map()?,
+                4 => names.types = subsection.decode_name_map()?,
+                5 => names.tables = subsection.decode_name_map()?,
+                6 => names.mems = subsection.decode_name_map()?,
+                7 => names.globals = subsection.decode_name_map()?,
+                8 => names.elems = subsection.decode_name_map()?,
+                9 => names.datas = subsection.decode_name_map()?,
+                // Subsections from later proposals, such as field names, are skipped.
+                _ => subsection.pos = subsection.input.len(),
+            }
+            if subsection.pos != subsection.input.len() {
+                return Err(DecodingError::new());
+            }
+            self.pos = end;
+        }
+        Ok(names)
+    }
+
+    fn decode_name_map(&mut self) -> Result<NameMap, DecodingError> {
+        let mut map = NameMap::new();
+        let len = self.decode_u32()?;
+        for _ in 0..len {
+            let idx = self.decode_u32()?;
+            let name = self.decode_name()?;
+            map.insert(idx, name);
+        }
+        Ok(map)
+    }
+
+    fn decode_indirect_name_map(&mut self) -> Result<IndirectNameMap, DecodingError> {
+        let mut map = IndirectNameMap::new();
+        let len = self.decode_u32()?;
+        for _ in 0..len {
+            let idx = self.decode_u32()?;
+            map.insert(idx, self.decode_name_map()?);
+        }
+        Ok(map)
+    }
+
+    pub(crate) fn decode_expr(&mut self) -> Result<Expr, DecodingError> {
+        let mut expr = Expr(Vec::new());
+        loop {
+            let instr = self.decode_instruction()?;
+            if instr == Instr::End {
+                break;
+            }
+            expr.0.push(instr);
+        }
+        Ok(expr)
+    }
+
+    fn decode_mem_type(&mut self) -> Result<MemType, DecodingError> {
+        let limits = self.decode_limits()?;
+        Ok(MemType(limits))
+    }
+
+    fn decode_table_type(&mut self) -> Result<TableType, DecodingError> {
+        let ref_type = self.decode_ref_type()?;
+        let limits = self.decode_limits()?;
+        Ok(TableType(limits, ref_type))
+    }
+
+    fn decode_limits(&mut self) -> Result<Limits, DecodingError> {
+        let flag = self.decode_byte()?;
+
+        let limits = match flag {
+            0x00 => Limits {
+                min: self.decode_u32()?,
+                max: None,
+            },
+            0x01 => Limits {
+                min: self.decode_u32()?,
+                max: Some(self.decode_u32()?),
+            },
+            _ => return Err(DecodingError::new()),
+        };
+        Ok(limits)
+    }
+
+    fn decode_memarg(&mut self) -> Result<MemArg, DecodingError> {
+        let align = self.decode_u32()?;
+        let offset = self.decode_u32()?;
+        Ok(MemArg { align, offset })
+    }
+
+    fn decode_name(&mut self) -> Result<String, DecodingError> {
+        let len = self.decode_u32()? as usize;
+        if self.input.len() < self.pos + len {
+            return Err(DecodingError::new());
+        }
+        let bytes = self.input[self.pos..self.pos + len].to_vec();
+        self.pos += len;
+        String::from_utf8(bytes).map_err(|_| DecodingError::new())
     }
 
     fn decode_instruction(&mut self) -> Result<Instr, DecodingError> {
-        let byte = self.input[self.pos];
-        self.pos += 1;
+        // The offset is recorded before decoding nested instructions, so that an `if` comes
+        // before the instructions in its branches.
+        let index = self.offsets.len();
+        self.offsets.push(self.pos);
+        let byte = self.decode_byte()?;
 
         let instr = match byte {
             0x04 => {
-                let block_type = match self.input[self.pos] {
+                let block_type = match self.decode_byte()? {
+                    0x40 => BlockType::Empty,
                     0x7f => BlockType::ValType(ValType::NumType(NumType::I32)),
                     0x7e => BlockType::ValType(ValType::NumType(NumType::I64)),
                     0x7d => BlockType::ValType(ValType::NumType(NumType::F32)),
                     0x7c => BlockType::ValType(ValType::NumType(NumType::F64)),
-                    _ => unimplemented!("unimplemented block type"),
+                    0x70 => BlockType::ValType(ValType::RefType(RefType::FuncRef)),
+                    _ => return Err(DecodingError::new()),
                 };
-                self.pos += 1;
 
                 let mut instructions1 = Vec::new();
                 let mut instructions2 = Vec::new();
                 'outer: loop {
                     let instr = self.decode_instruction()?;
 
-                    if instr == Instr::Else {
+                    if instr == Instr::End {
+                        break;
+                    } else if instr == Instr::Else {
                         loop {
                             let instr = self.decode_instruction()?;
 
@@ -310,14 +700,57 @@ impl Decoder<'_> {
                 let idx = self.decode_u32()?;
                 Instr::LocalGet(idx as LocalIdx)
             }
+            0x28 => Instr::I32Load(self.decode_memarg()?),
+            0x29 => Instr::I64Load(self.decode_memarg()?),
+            0x2a => Instr::F32Load(self.decode_memarg()?),
+            0x2b => Instr::F64Load(self.decode_memarg()?),
+            0x2c => Instr::I32Load8S(self.decode_memarg()?),
+            0x2d => Instr::I32Load8U(self.decode_memarg()?),
+            0x2e => Instr::I32Load16S(self.decode_memarg()?),
+            0x2f => Instr::I32Load16U(self.decode_memarg()?),
+            0x30 => Instr::I64Load8S(self.decode_memarg()?),
+            0x31 => Instr::I64Load8U(self.decode_memarg()?),
+            0x32 => Instr::I64Load16S(self.decode_memarg()?),
+            0x33 => Instr::I64Load16U(self.decode_memarg()?),
+            0x34 => Instr::I64Load32S(self.decode_memarg()?),
+            0x35 => Instr::I64Load32U(self.decode_memarg()?),
+            0x36 => Instr::I32Store(self.decode_memarg()?),
+            0x37 => Instr::I64Store(self.decode_memarg()?),
+            0x38 => Instr::F32Store(self.decode_memarg()?),
+            0x39 => Instr::F64Store(self.decode_memarg()?),
+            0x3a => Instr::I32Store8(self.decode_memarg()?),
+            0x3b => Instr::I32Store16(self.decode_memarg()?),
+            0x3c => Instr::I64Store8(self.decode_memarg()?),
+            0x3d => Instr::I64Store16(self.decode_memarg()?),
+            0x3e => Instr::I64Store32(self.decode_memarg()?),
+            0x3f => {
+                if self.decode_byte()? != 0x00 {
+                    return Err(DecodingError::new());
+                }
+                Instr::MemorySize
+            }
+            0x40 => {
+                if self.decode_byte()? != 0x00 {
+                    return Err(DecodingError::new());
+                }
+                Instr::MemoryGrow
+            }
             0x41 => {
-                let x = self.decode_u32()? as i32; // FIXME: decode_i32
+                let x = self.decode_i32()?;
                 Instr::I32Const(x)
             }
             0x42 => {
                 let x = self.decode_i64()?;
                 Instr::I64Const(x)
             }
+            0x43 => {
+                let bytes = self.decode_bytes::<4>()?;
+                Instr::F32Const(f32::from_le_bytes(bytes))
+            }
+            0x44 => {
+                let bytes = self.decode_bytes::<8>()?;
+                Instr::F64Const(f64::from_le_bytes(bytes))
+            }
             0x45 => Instr::I32Eqz,
             0x46 => Instr::I32Eq,
             0x47 => Instr::I32Ne,
@@ -418,18 +851,89 @@ impl Decoder<'_> {
             0xa6 => Instr::F64Copysign,
             0xc0 => Instr::I32Extend8S,
             0xc1 => Instr::I32Extend16S,
-            _ => unimplemented!("unimplemented instr {:#x}", byte),
+            0xd0 => Instr::RefNull(self.decode_ref_type()?),
+            0xfc => match self.decode_u32()? {
+                15 => Instr::TableGrow(self.decode_u32()? as TableIdx),
+                16 => Instr::TableSize(self.decode_u32()? as TableIdx),
+                _ => return Err(DecodingError::new()),
+            },
+            _ => return Err(DecodingError::new()),
         };
+        // `else` and `end` are delimiters rather than instructions of the body.
+        if matches!(instr, Instr::Else | Instr::End) {
+            self.offsets.truncate(index);
+        }
         Ok(instr)
     }
 
+    fn decode_byte(&mut self) -> Result<u8, DecodingError> {
+        let byte = *self.input.get(self.pos).ok_or_else(DecodingError::new)?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn decode_val_type(&mut self) -> Result<ValType, DecodingError> {
+        let val_type = match self.decode_byte()? {
+            0x7f => ValType::NumType(NumType::I32),
+            0x7e => ValType::NumType(NumType::I64),
+            0x7d => ValType::NumType(NumType::F32),
+            0x7c => ValType::NumType(NumType::F64),
+            0x70 => ValType::RefType(RefType::FuncRef),
+            _ => return Err(DecodingError::new()),
+        };
+        Ok(val_type)
+    }
+
+    fn decode_ref_type(&mut self) -> Result<RefType, DecodingError> {
+        match self.decode_byte()? {
+            0x70 => Ok(RefType::FuncRef),
+            _ => Err(DecodingError::new()),
+        }
+    }
+
+    fn decode_bytes<const N: usize>(&mut self) -> Result<[u8; N], DecodingError> {
+        let bytes = self
+            .input
+            .get(self.pos..self.pos + N)
+            .ok_or_else(DecodingError::new)?;
+        self.pos += N;
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn decode_i32(&mut self) -> Result<i32, DecodingError> {
+        let mut result: i32 = 0;
+        let mut shift = 0;
+
+        loop {
+            if shift >= 32 {
+                return Err(DecodingError::new());
+            }
+            let byte = self.decode_byte()?;
+
+            let value = (byte & 0b01111111) as i32;
+            result |= value.wrapping_shl(shift);
+            shift += 7;
+
+            if (byte & 0b10000000) == 0 {
+                if shift < 32 && byte & 0x40 != 0 {
+                    result |= !0 << shift;
+                }
+                break;
+            }
+        }
+
+        Ok(result)
+    }
+
     fn decode_i64(&mut self) -> Result<i64, DecodingError> {
         let mut result: i64 = 0;
         let mut shift = 0;
 
         loop {
-            let byte = self.input[self.pos];
-            self.pos += 1;
+            if shift >= 64 {
+                return Err(DecodingError::new());
+            }
+            let byte = self.decode_byte()?;
 
             let value = (byte & 0b01111111) as i64;
             result |= value << shift;
@@ -451,7 +955,10 @@ impl Decoder<'_> {
         let mut shift: u32 = 0;
 
         loop {
-            let byte = self.input[self.pos];
+            if shift > 28 {
+                return Err(DecodingError::new());
+            }
+            let byte = *self.input.get(self.pos).ok_or_else(DecodingError::new)?;
             self.pos += 1;
 
             let value = (byte & 0b01111111) as u32;
@@ -501,6 +1008,7 @@ mod tests {
                 type_: 0,
                 locals: Vec::new(),
                 body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32Add]),
+                offsets: vec![3, 5, 7],
             }],
         );
         assert_eq!(
@@ -532,6 +1040,7 @@ mod tests {
                 type_: 0,
                 locals: Vec::new(),
                 body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32DivS]),
+                offsets: vec![3, 5, 7],
             }],
         );
         assert_eq!(
@@ -560,6 +1069,7 @@ mod tests {
                 type_: 0,
                 locals: Vec::new(),
                 body: Expr(vec![Instr::LocalGet(0), Instr::I32Clz]),
+                offsets: vec![3, 5],
             }],
         );
         assert_eq!(
@@ -591,6 +1101,7 @@ mod tests {
                 type_: 0,
                 locals: Vec::new(),
                 body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32Eq]),
+                offsets: vec![3, 5, 7],
             }],
         );
         assert_eq!(
@@ -619,6 +1130,7 @@ mod tests {
                 type_: 0,
                 locals: Vec::new(),
                 body: Expr(vec![Instr::LocalGet(0), Instr::I32Eqz]),
+                offsets: vec![3, 5],
             }],
         );
         assert_eq!(
@@ -647,6 +1159,7 @@ mod tests {
                 type_: 0,
                 locals: Vec::new(),
                 body: Expr(vec![Instr::LocalGet(0), Instr::I32Extend8S]),
+                offsets: vec![3, 5],
             }],
         );
         assert_eq!(
@@ -685,156 +1198,187 @@ mod tests {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32Add]),
+                    offsets: vec![3, 5, 7],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32Sub]),
+                    offsets: vec![11, 13, 15],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32Mul]),
+                    offsets: vec![19, 21, 23],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32DivS]),
+                    offsets: vec![27, 29, 31],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32DivU]),
+                    offsets: vec![35, 37, 39],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32RemS]),
+                    offsets: vec![43, 45, 47],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32RemU]),
+                    offsets: vec![51, 53, 55],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32And]),
+                    offsets: vec![59, 61, 63],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32Or]),
+                    offsets: vec![67, 69, 71],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32Xor]),
+                    offsets: vec![75, 77, 79],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32Shl]),
+                    offsets: vec![83, 85, 87],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32ShrS]),
+                    offsets: vec![91, 93, 95],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32ShrU]),
+                    offsets: vec![99, 101, 103],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32Rotl]),
+                    offsets: vec![107, 109, 111],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32Rotr]),
+                    offsets: vec![115, 117, 119],
                 },
                 Func {
                     type_: 1,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::I32Clz]),
+                    offsets: vec![123, 125],
                 },
                 Func {
                     type_: 1,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::I32Ctz]),
+                    offsets: vec![129, 131],
                 },
                 Func {
                     type_: 1,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::I32Popcnt]),
+                    offsets: vec![135, 137],
                 },
                 Func {
                     type_: 1,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::I32Extend8S]),
+                    offsets: vec![141, 143],
                 },
                 Func {
                     type_: 1,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::I32Extend16S]),
+                    offsets: vec![147, 149],
                 },
                 Func {
                     type_: 1,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::I32Eqz]),
+                    offsets: vec![153, 155],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32Eq]),
+                    offsets: vec![159, 161, 163],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32Ne]),
+                    offsets: vec![167, 169, 171],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32LtS]),
+                    offsets: vec![175, 177, 179],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32LtU]),
+                    offsets: vec![183, 185, 187],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32LeS]),
+                    offsets: vec![191, 193, 195],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32LeU]),
+                    offsets: vec![199, 201, 203],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32GtS]),
+                    offsets: vec![207, 209, 211],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32GtU]),
+                    offsets: vec![215, 217, 219],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32GeS]),
+                    offsets: vec![223, 225, 227],
                 },
                 Func {
                     type_: 0,
                     locals: Vec::new(),
                     body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32GeU]),
+                    offsets: vec![231, 233, 235],
                 },
             ],
         );
@@ -1001,21 +1545,25 @@ mod tests {
                     type_: 0,
                     locals: vec![ValType::NumType(NumType::I32)],
                     body: Expr(vec![Instr::LocalGet(0)]),
+                    offsets: vec![5],
                 },
                 Func {
                     type_: 1,
                     locals: vec![ValType::NumType(NumType::I64)],
                     body: Expr(vec![Instr::LocalGet(0)]),
+                    offsets: vec![12],
                 },
                 Func {
                     type_: 2,
                     locals: vec![ValType::NumType(NumType::F32)],
                     body: Expr(vec![Instr::LocalGet(0)]),
+                    offsets: vec![19],
                 },
                 Func {
                     type_: 3,
                     locals: vec![ValType::NumType(NumType::F64)],
                     body: Expr(vec![Instr::LocalGet(0)]),
+                    offsets: vec![26],
                 },
             ],
         );
@@ -1098,6 +1646,10 @@ mod tests {
                     Instr::F64Neg,
                     Instr::Drop,
                 ]),
+                offsets: vec![
+                    9, 11, 12, 13, 15, 16, 17, 19, 20, 21, 23, 24, 25, 27, 28, 29, 31, 32, 33, 35,
+                    36, 37, 39, 40, 41, 43, 44
+                ],
             }],
         );
         assert_eq!(
@@ -1108,4 +1660,108 @@ mod tests {
             }],
         );
     }
+
+    #[test]
+    fn test_decode_sections() {
+        let mut file = File::open("./tests/inputs/factorial.wasm").unwrap();
+        let mut input = Vec::new();
+        file.read_to_end(&mut input).unwrap();
+        let sections = Decoder::new(&input).decode_sections().unwrap();
+
+        let summary: Vec<_> = sections
+            .iter()
+            .map(|section| (section.kind(), section.offset, section.size, section.count))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("type", 10, 6, Some(1)),
+                ("function", 18, 2, Some(1)),
+                ("export", 22, 7, Some(1)),
+                ("code", 31, 23, Some(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_start_section() {
+        let input = [
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+            0x08, 0x01, 0x02, // start 2
+        ];
+        let module = Decoder::new(&input).decode().unwrap();
+        assert_eq!(module.start, Some(2));
+    }
+
+    #[test]
+    fn test_custom_sections() {
+        let input = [
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+            0x00, 0x0d, 0x04, b'n', b'a', b'm', b'e', // name section
+            0x01, 0x06, 0x01, 0x00, 0x03, b'f', b'i', b'b', // function names
+            0x00, 0x04, 0x01, b'x', 0xaa, 0xbb, // custom section "x"
+        ];
+        let module = Decoder::new(&input).decode().unwrap();
+        assert_eq!(module.names.funcs.get(&0).map(String::as_str), Some("fib"));
+        assert_eq!(
+            module.customs,
+            vec![CustomSection {
+                name: "x".to_string(),
+                data: vec![0xaa, 0xbb],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_malformed_name_section() {
+        let input = [
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+            0x00, 0x07, 0x04, b'n', b'a', b'm', b'e', 0x01, 0x05, // truncated subsection
+        ];
+        let module = Decoder::new(&input).decode().unwrap();
+        assert!(module.names.is_empty());
+        assert_eq!(module.customs[0].data, vec![0x01, 0x05]);
+    }
+
+    #[test]
+    fn test_lazy_bodies() {
+        let mut file = File::open("./tests/inputs/factorial.wasm").unwrap();
+        let mut input = Vec::new();
+        file.read_to_end(&mut input).unwrap();
+        let eager = Decoder::new(&input).decode().unwrap();
+        let mut lazy = Decoder::new(&input).lazy_bodies(true).decode().unwrap();
+        assert_eq!(lazy.lazy_bodies.len(), 1);
+        assert!(lazy.funcs[0].body.0.is_empty());
+
+        lazy.decode_bodies().unwrap();
+        assert_eq!(lazy, eager);
+
+        // Bodies are split by their sizes, which must stay within the section.
+        input[32] += 1;
+        assert!(Decoder::new(&input).lazy_bodies(true).decode().is_err());
+
+        // A body without its final `end` is only malformed once it's decoded.
+        input[30] -= 1;
+        input[32] -= 2;
+        input.pop();
+        let mut lazy = Decoder::new(&input).lazy_bodies(true).decode().unwrap();
+        assert!(lazy.decode_bodies().is_err());
+    }
+
+    #[test]
+    fn test_unsupported_import() {
+        let input = [
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+            0x02, 0x08, 0x01, // import section with one import
+            0x01, b'm', 0x01, b'g', // "m" "g"
+            0x03, 0x7f, 0x00, // global i32 const
+        ];
+        assert!(Decoder::new(&input).decode().is_err());
+    }
+
+    #[test]
+    fn test_truncated_section() {
+        let input = [0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x80];
+        assert!(Decoder::new(&input).decode_sections().is_err());
+    }
 }
@@ -0,0 +1,210 @@
+use crate::structure::modules::custom::CustomSection;
+use addr2line::Context;
+use gimli::{Dwarf, EndianRcSlice, RunTimeEndian, SectionId};
+use std::fmt;
+use std::rc::Rc;
+
+type Reader = EndianRcSlice<RunTimeEndian>;
+
+/// Source-level debug information from the `.debug_*` custom sections of a module.
+///
+/// Addresses are offsets into the code section, as recorded in `Func::offsets`.
+pub struct DebugInfo {
+    context: Context<Reader>,
+}
+
+/// A source location of an instruction. Inlined calls make up several frames for one instruction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceFrame {
+    pub function: Option<String>,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+}
+
+impl DebugInfo {
+    /// Loads the DWARF sections, or returns `None` if the module has no `.debug_info` section.
+    pub fn new(customs: &[CustomSection]) -> Result<Option<Self>, gimli::Error> {
+        if !customs.iter().any(|custom| custom.name == ".debug_info") {
+            return Ok(None);
+        }
+
+        let dwarf = Dwarf::load(|id: SectionId| -> Result<Reader, gimli::Error> {
+            let data = customs
+                .iter()
+                .find(|custom| custom.name == id.name())
+                .map(|custom| custom.data.as_slice())
+                .unwrap_or_default();
+            Ok(EndianRcSlice::new(Rc::from(data), RunTimeEndian::Little))
+        })?;
+        let context = Context::from_dwarf(dwarf)?;
+        Ok(Some(Self { context }))
+    }
+
+    /// Returns the source frames of the instruction at `offset`, innermost first.
+    pub fn frames(&self, offset: u32) -> Result<Vec<SourceFrame>, gimli::Error> {
+        let mut frames = Vec::new();
+        let mut iter = self.context.find_frames(offset as u64).skip_all_loads()?;
+        while let Some(frame) = iter.next()? {
+            let function = match frame.function {
+                Some(name) => Some(name.raw_name()?.into_owned()),
+                None => None,
+            };
+            let location = frame.location;
+            frames.push(SourceFrame {
+                function,
+                file: location.as_ref().and_then(|l| l.file).map(str::to_string),
+                line: location.as_ref().and_then(|l| l.line),
+                column: location.as_ref().and_then(|l| l.column),
+            });
+        }
+        Ok(frames)
+    }
+}
+
+impl fmt::Display for SourceFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.file.as_deref().unwrap_or("<unknown>"))?;
+        if let Some(line) = self.line {
+            write!(f, ":{}", line)?;
+            if let Some(column) = self.column {
+                write!(f, ":{}", column)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gimli::write::{
+        Address, AttributeValue, DwarfUnit, EndianVec, LineProgram, LineString, Sections,
+    };
+    use gimli::{Encoding, Format, LineEncoding, LittleEndian};
+
+    /// Builds the debug sections of a function `outer` spanning `0..0x40`, into which `inner` is
+    /// inlined at `0x10..0x20`.
+    fn debug_sections() -> Vec<CustomSection> {
+        let encoding = Encoding {
+            format: Format::Dwarf32,
+            version: 4,
+            address_size: 4,
+        };
+        let mut dwarf = DwarfUnit::new(encoding);
+
+        let mut program = LineProgram::new(
+            encoding,
+            LineEncoding::default(),
+            LineString::String(b"src".to_vec()),
+            LineString::String(b"lib.rs".to_vec()),
+            None,
+        );
+        let directory = program.default_directory();
+        let file = program.add_file(LineString::String(b"lib.rs".to_vec()), directory, None);
+        program.begin_sequence(Some(Address::Constant(0)));
+        for (address, line, column) in [(0, 48, 1), (0x10, 42, 7), (0x20, 51, 1)] {
+            program.row().file = file;
+            program.row().address_offset = address;
+            program.row().line = line;
+            program.row().column = column;
+            program.generate_row();
+        }
+        program.end_sequence(0x40);
+        dwarf.unit.line_program = program;
+
+        let root = dwarf.unit.root();
+        let entry = dwarf.unit.get_mut(root);
+        entry.set(
+            gimli::DW_AT_name,
+            AttributeValue::String(b"lib.rs".to_vec()),
+        );
+        entry.set(
+            gimli::DW_AT_comp_dir,
+            AttributeValue::String(b"src".to_vec()),
+        );
+        entry.set(
+            gimli::DW_AT_low_pc,
+            AttributeValue::Address(Address::Constant(0)),
+        );
+        entry.set(gimli::DW_AT_high_pc, AttributeValue::Udata(0x40));
+
+        let inner = dwarf.unit.add(root, gimli::DW_TAG_subprogram);
+        let entry = dwarf.unit.get_mut(inner);
+        entry.set(gimli::DW_AT_name, AttributeValue::String(b"inner".to_vec()));
+        entry.set(
+            gimli::DW_AT_inline,
+            AttributeValue::Inline(gimli::DW_INL_inlined),
+        );
+
+        let outer = dwarf.unit.add(root, gimli::DW_TAG_subprogram);
+        let entry = dwarf.unit.get_mut(outer);
+        entry.set(gimli::DW_AT_name, AttributeValue::String(b"outer".to_vec()));
+        entry.set(
+            gimli::DW_AT_low_pc,
+            AttributeValue::Address(Address::Constant(0)),
+        );
+        entry.set(gimli::DW_AT_high_pc, AttributeValue::Udata(0x40));
+
+        let inlined = dwarf.unit.add(outer, gimli::DW_TAG_inlined_subroutine);
+        let entry = dwarf.unit.get_mut(inlined);
+        entry.set(gimli::DW_AT_abstract_origin, AttributeValue::UnitRef(inner));
+        entry.set(
+            gimli::DW_AT_low_pc,
+            AttributeValue::Address(Address::Constant(0x10)),
+        );
+        entry.set(gimli::DW_AT_high_pc, AttributeValue::Udata(0x10));
+        entry.set(
+            gimli::DW_AT_call_file,
+            AttributeValue::FileIndex(Some(file)),
+        );
+        entry.set(gimli::DW_AT_call_line, AttributeValue::Udata(50));
+        entry.set(gimli::DW_AT_call_column, AttributeValue::Udata(3));
+
+        let mut sections = Sections::new(EndianVec::new(LittleEndian));
+        dwarf.write(&mut sections).unwrap();
+        let mut customs = Vec::new();
+        sections
+            .for_each(|id, data| {
+                if !data.slice().is_empty() {
+                    customs.push(CustomSection {
+                        name: id.name().to_string(),
+                        data: data.slice().to_vec(),
+                    });
+                }
+                Ok::<(), gimli::Error>(())
+            })
+            .unwrap();
+        customs
+    }
+
+    fn frame(function: &str, line: u32, column: u32) -> SourceFrame {
+        SourceFrame {
+            function: Some(function.to_string()),
+            file: Some("src/lib.rs".to_string()),
+            line: Some(line),
+            column: Some(column),
+        }
+    }
+
+    #[test]
+    fn test_frames() {
+        let debug_info = DebugInfo::new(&debug_sections()).unwrap().unwrap();
+        assert_eq!(debug_info.frames(0x4).unwrap(), vec![frame("outer", 48, 1)]);
+        assert_eq!(
+            debug_info.frames(0x18).unwrap(),
+            vec![frame("inner", 42, 7), frame("outer", 50, 3)]
+        );
+        assert_eq!(debug_info.frames(0x40).unwrap(), Vec::new());
+        assert_eq!(frame("outer", 50, 3).to_string(), "src/lib.rs:50:3");
+    }
+
+    #[test]
+    fn test_without_debug_info() {
+        let customs = [CustomSection {
+            name: "producers".to_string(),
+            data: Vec::new(),
+        }];
+        assert!(DebugInfo::new(&customs).unwrap().is_none());
+    }
+}
@@ -0,0 +1,659 @@
+use crate::structure::instructions::expression::{BlockType, Expr, Instr, MemArg};
+use crate::structure::modules::custom::{IndirectNameMap, NameMap, Names};
+use crate::structure::modules::data::DataMode;
+use crate::structure::modules::export::ExportDesc;
+use crate::structure::modules::import::ImportDesc;
+use crate::structure::modules::module::Module;
+use crate::structure::types::memory::Limits;
+use crate::structure::types::value::{NumType, RefType, ValType};
+
+/// Writes a module in the binary format, the inverse of `Decoder`.
+#[derive(Default)]
+pub struct Encoder {
+    output: Vec<u8>,
+}
+
+impl Encoder {
+    pub fn encode(module: &Module) -> Vec<u8> {
+        let mut encoder = Encoder::default();
+        encoder.bytes(&[0x00, 0x61, 0x73, 0x6d]);
+        encoder.bytes(&[0x01, 0x00, 0x00, 0x00]);
+        encoder.module(module);
+        encoder.output
+    }
+
+    /// Encodes an expression, terminated by `end`.
+    pub(crate) fn encode_expr(expr: &Expr) -> Vec<u8> {
+        let mut encoder = Encoder::default();
+        encoder.expr(expr);
+        encoder.output
+    }
+
+    fn module(&mut self, module: &Module) {
+        if !module.types.is_empty() {
+            self.section(1, |e| {
+                e.vec(&module.types, |e, type_| {
+                    e.byte(0x60);
+                    e.vec(&type_.parameters, Encoder::val_type);
+                    e.vec(&type_.results, Encoder::val_type);
+                });
+            });
+        }
+
+        if !module.imports.is_empty() {
+            self.section(2, |e| {
+                e.vec(&module.imports, |e, import| {
+                    e.name(&import.module);
+                    e.name(&import.name);
+                    match &import.desc {
+                        ImportDesc::Func(type_idx) => {
+                            e.byte(0x00);
+                            e.u32(*type_idx);
+                        }
+                        ImportDesc::Mem(mem_type) => {
+                            e.byte(0x02);
+                            e.limits(&mem_type.0);
+                        }
+                    }
+                });
+            });
+        }
+
+        if !module.funcs.is_empty() {
+            self.section(3, |e| e.vec(&module.funcs, |e, func| e.u32(func.type_)));
+        }
+
+        if !module.tables.is_empty() {
+            self.section(4, |e| {
+                e.vec(&module.tables, |e, table| {
+                    e.ref_type(&table.type_.1);
+                    e.limits(&table.type_.0);
+                })
+            });
+        }
+
+        if !module.mems.is_empty() {
+            self.section(5, |e| e.vec(&module.mems, |e, mem| e.limits(&mem.type_.0)));
+        }
+
+        if !module.exports.is_empty() {
+            self.section(7, |e| {
+                e.vec(&module.exports, |e, export| {
+                    e.name(&export.name);
+                    match export.desc {
+                        ExportDesc::Func(idx) => {
+                            e.byte(0x00);
+                            e.u32(idx);
+                        }
+                        ExportDesc::Mem(idx) => {
+                            e.byte(0x02);
+                            e.u32(idx);
+                        }
+                    }
+                });
+            });
+        }
+
+        if let Some(start) = module.start {
+            self.section(8, |e| e.u32(start));
+        }
+
+        if !module.funcs.is_empty() {
+            self.section(10, |e| {
+                e.vec(&module.funcs, |e, func| {
+                    let mut code = Encoder::default();
+
+                    // Locals are compressed into runs of the same type.
+                    let mut runs: Vec<(u32, &ValType)> = Vec::new();
+                    for local in &func.locals {
+                        match runs.last_mut() {
+                            Some((n, type_)) if *type_ == local => *n += 1,
+                            _ => runs.push((1, local)),
+                        }
+                    }
+                    code.vec(&runs, |e, (n, type_)| {
+                        e.u32(*n);
+                        e.val_type(type_);
+                    });
+                    code.expr(&func.body);
+
+                    e.u32(code.output.len() as u32);
+                    e.bytes(&code.output);
+                });
+            });
+        }
+
+        if !module.datas.is_empty() {
+            self.section(11, |e| {
+                e.vec(&module.datas, |e, data| {
+                    match &data.mode {
+                        DataMode::Active { memory: 0, offset } => {
+                            e.u32(0);
+                            e.expr(offset);
+                        }
+                        DataMode::Passive => e.u32(1),
+                        DataMode::Active { memory, offset } => {
+                            e.u32(2);
+                            e.u32(*memory);
+                            e.expr(offset);
+                        }
+                    }
+                    e.u32(data.init.len() as u32);
+                    e.bytes(&data.init);
+                });
+            });
+        }
+
+        if !module.names.is_empty() {
+            self.section(0, |e| {
+                e.name("name");
+                e.names(&module.names);
+            });
+        }
+
+        for custom in &module.customs {
+            self.section(0, |e| {
+                e.name(&custom.name);
+                e.bytes(&custom.data);
+            });
+        }
+    }
+
+    /// Writes the subsections of the name section, which must be ordered by id.
+    fn names(&mut self, names: &Names) {
+        if let Some(name) = &names.module {
+            self.section(0, |e| e.name(name));
+        }
+        self.name_subsection(1, &names.funcs);
+        self.indirect_name_subsection(2, &names.locals);
+        self.indirect_name_subsection(3, &names.labels);
+        self.name_subsection(4, &names.types);
+        self.name_subsection(5, &names.tables);
+        self.name_subsection(6, &names.mems);
+        self.name_subsection(7, &names.globals);
+        self.name_subsection(8, &names.elems);
+        self.name_subsection(9, &names.datas);
+    }
+
+    fn name_subsection(&mut self, id: u8, map: &NameMap) {
+        if !map.is_empty() {
+            self.section(id, |e| e.name_map(map));
+        }
+    }
+
+    fn indirect_name_subsection(&mut self, id: u8, map: &IndirectNameMap) {
+        if !map.is_empty() {
+            self.section(id, |e| {
+                e.u32(map.len() as u32);
+                for (idx, names) in map {
+                    e.u32(*idx);
+                    e.name_map(names);
+                }
+            });
+        }
+    }
+
+    fn name_map(&mut self, map: &NameMap) {
+        self.u32(map.len() as u32);
+        for (idx, name) in map {
+            self.u32(*idx);
+            self.name(name);
+        }
+    }
+
+    fn section(&mut self, id: u8, contents: impl FnOnce(&mut Encoder)) {
+        let mut section = Encoder::default();
+        contents(&mut section);
+        self.byte(id);
+        self.u32(section.output.len() as u32);
+        self.bytes(&section.output);
+    }
+
+    fn vec<T>(&mut self, items: &[T], mut item: impl FnMut(&mut Encoder, &T)) {
+        self.u32(items.len() as u32);
+        for x in items {
+            item(self, x);
+        }
+    }
+
+    fn byte(&mut self, byte: u8) {
+        self.output.push(byte);
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.output.extend_from_slice(bytes);
+    }
+
+    fn name(&mut self, name: &str) {
+        self.u32(name.len() as u32);
+        self.bytes(name.as_bytes());
+    }
+
+    fn val_type(&mut self, type_: &ValType) {
+        let byte = match type_ {
+            ValType::NumType(NumType::I32) => 0x7f,
+            ValType::NumType(NumType::I64) => 0x7e,
+            ValType::NumType(NumType::F32) => 0x7d,
+            ValType::NumType(NumType::F64) => 0x7c,
+            ValType::RefType(type_) => return self.ref_type(type_),
+        };
+        self.byte(byte);
+    }
+
+    fn ref_type(&mut self, type_: &RefType) {
+        match type_ {
+            RefType::FuncRef => self.byte(0x70),
+        }
+    }
+
+    fn limits(&mut self, limits: &Limits) {
+        match limits.max {
+            Some(max) => {
+                self.byte(0x01);
+                self.u32(limits.min);
+                self.u32(max);
+            }
+            None => {
+                self.byte(0x00);
+                self.u32(limits.min);
+            }
+        }
+    }
+
+    fn memarg(&mut self, memarg: &MemArg) {
+        self.u32(memarg.align);
+        self.u32(memarg.offset);
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        for instr in &expr.0 {
+            self.instr(instr);
+        }
+        self.byte(0x0b);
+    }
+
+    fn instr(&mut self, instr: &Instr) {
+        match instr {
+            Instr::If(block_type, instructions1, instructions2) => {
+                self.byte(0x04);
+                match block_type {
+                    BlockType::Empty => self.byte(0x40),
+                    BlockType::ValType(type_) => self.val_type(type_),
+                }
+                for instr in instructions1 {
+                    self.instr(instr);
+                }
+                if !instructions2.is_empty() {
+                    self.byte(0x05);
+                    for instr in instructions2 {
+                        self.instr(instr);
+                    }
+                }
+                self.byte(0x0b);
+            }
+            Instr::Else => self.byte(0x05),
+            Instr::End => self.byte(0x0b),
+            Instr::Call(idx) => {
+                self.byte(0x10);
+                self.u32(*idx);
+            }
+            Instr::LocalGet(idx) => {
+                self.byte(0x20);
+                self.u32(*idx);
+            }
+            Instr::RefNull(type_) => {
+                self.byte(0xd0);
+                self.ref_type(type_);
+            }
+            Instr::TableGrow(idx) => {
+                self.byte(0xfc);
+                self.u32(15);
+                self.u32(*idx);
+            }
+            Instr::TableSize(idx) => {
+                self.byte(0xfc);
+                self.u32(16);
+                self.u32(*idx);
+            }
+            Instr::MemorySize => self.bytes(&[0x3f, 0x00]),
+            Instr::MemoryGrow => self.bytes(&[0x40, 0x00]),
+            Instr::I32Const(x) => {
+                self.byte(0x41);
+                self.i64(*x as i64);
+            }
+            Instr::I64Const(x) => {
+                self.byte(0x42);
+                self.i64(*x);
+            }
+            Instr::F32Const(x) => {
+                self.byte(0x43);
+                self.bytes(&x.to_le_bytes());
+            }
+            Instr::F64Const(x) => {
+                self.byte(0x44);
+                self.bytes(&x.to_le_bytes());
+            }
+            Instr::I32Load(memarg) => {
+                self.byte(0x28);
+                self.memarg(memarg);
+            }
+            Instr::I64Load(memarg) => {
+                self.byte(0x29);
+                self.memarg(memarg);
+            }
+            Instr::F32Load(memarg) => {
+                self.byte(0x2a);
+                self.memarg(memarg);
+            }
+            Instr::F64Load(memarg) => {
+                self.byte(0x2b);
+                self.memarg(memarg);
+            }
+            Instr::I32Load8S(memarg) => {
+                self.byte(0x2c);
+                self.memarg(memarg);
+            }
+            Instr::I32Load8U(memarg) => {
+                self.byte(0x2d);
+                self.memarg(memarg);
+            }
+            Instr::I32Load16S(memarg) => {
+                self.byte(0x2e);
+                self.memarg(memarg);
+            }
+            Instr::I32Load16U(memarg) => {
+                self.byte(0x2f);
+                self.memarg(memarg);
+            }
+            Instr::I64Load8S(memarg) => {
+                self.byte(0x30);
+                self.memarg(memarg);
+            }
+            Instr::I64Load8U(memarg) => {
+                self.byte(0x31);
+                self.memarg(memarg);
+            }
+            Instr::I64Load16S(memarg) => {
+                self.byte(0x32);
+                self.memarg(memarg);
+            }
+            Instr::I64Load16U(memarg) => {
+                self.byte(0x33);
+                self.memarg(memarg);
+            }
+            Instr::I64Load32S(memarg) => {
+                self.byte(0x34);
+                self.memarg(memarg);
+            }
+            Instr::I64Load32U(memarg) => {
+                self.byte(0x35);
+                self.memarg(memarg);
+            }
+            Instr::I32Store(memarg) => {
+                self.byte(0x36);
+                self.memarg(memarg);
+            }
+            Instr::I64Store(memarg) => {
+                self.byte(0x37);
+                self.memarg(memarg);
+            }
+            Instr::F32Store(memarg) => {
+                self.byte(0x38);
+                self.memarg(memarg);
+            }
+            Instr::F64Store(memarg) => {
+                self.byte(0x39);
+                self.memarg(memarg);
+            }
+            Instr::I32Store8(memarg) => {
+                self.byte(0x3a);
+                self.memarg(memarg);
+            }
+            Instr::I32Store16(memarg) => {
+                self.byte(0x3b);
+                self.memarg(memarg);
+            }
+            Instr::I64Store8(memarg) => {
+                self.byte(0x3c);
+                self.memarg(memarg);
+            }
+            Instr::I64Store16(memarg) => {
+                self.byte(0x3d);
+                self.memarg(memarg);
+            }
+            Instr::I64Store32(memarg) => {
+                self.byte(0x3e);
+                self.memarg(memarg);
+            }
+            Instr::Drop => self.byte(0x1a),
+            Instr::I32Eqz => self.byte(0x45),
+            Instr::I32Eq => self.byte(0x46),
+            Instr::I32Ne => self.byte(0x47),
+            Instr::I32LtS => self.byte(0x48),
+            Instr::I32LtU => self.byte(0x49),
+            Instr::I32GtS => self.byte(0x4a),
+            Instr::I32GtU => self.byte(0x4b),
+            Instr::I32LeS => self.byte(0x4c),
+            Instr::I32LeU => self.byte(0x4d),
+            Instr::I32GeS => self.byte(0x4e),
+            Instr::I32GeU => self.byte(0x4f),
+            Instr::I64Eqz => self.byte(0x50),
+            Instr::I64Eq => self.byte(0x51),
+            Instr::I64Ne => self.byte(0x52),
+            Instr::I64LtS => self.byte(0x53),
+            Instr::I64LtU => self.byte(0x54),
+            Instr::I64GtS => self.byte(0x55),
+            Instr::I64GtU => self.byte(0x56),
+            Instr::I64LeS => self.byte(0x57),
+            Instr::I64LeU => self.byte(0x58),
+            Instr::I64GeS => self.byte(0x59),
+            Instr::I64GeU => self.byte(0x5a),
+            Instr::F32Eq => self.byte(0x5b),
+            Instr::F32Ne => self.byte(0x5c),
+            Instr::F32Lt => self.byte(0x5d),
+            Instr::F32Gt => self.byte(0x5e),
+            Instr::F32Le => self.byte(0x5f),
+            Instr::F32Ge => self.byte(0x60),
+            Instr::F64Eq => self.byte(0x61),
+            Instr::F64Ne => self.byte(0x62),
+            Instr::F64Lt => self.byte(0x63),
+            Instr::F64Gt => self.byte(0x64),
+            Instr::F64Le => self.byte(0x65),
+            Instr::F64Ge => self.byte(0x66),
+            Instr::I32Clz => self.byte(0x67),
+            Instr::I32Ctz => self.byte(0x68),
+            Instr::I32Popcnt => self.byte(0x69),
+            Instr::I32Add => self.byte(0x6a),
+            Instr::I32Sub => self.byte(0x6b),
+            Instr::I32Mul => self.byte(0x6c),
+            Instr::I32DivS => self.byte(0x6d),
+            Instr::I32DivU => self.byte(0x6e),
+            Instr::I32RemS => self.byte(0x6f),
+            Instr::I32RemU => self.byte(0x70),
+            Instr::I32And => self.byte(0x71),
+            Instr::I32Or => self.byte(0x72),
+            Instr::I32Xor => self.byte(0x73),
+            Instr::I32Shl => self.byte(0x74),
+            Instr::I32ShrS => self.byte(0x75),
+            Instr::I32ShrU => self.byte(0x76),
+            Instr::I32Rotl => self.byte(0x77),
+            Instr::I32Rotr => self.byte(0x78),
+            Instr::I64Clz => self.byte(0x79),
+            Instr::I64Ctz => self.byte(0x7a),
+            Instr::I64Popcnt => self.byte(0x7b),
+            Instr::I64Add => self.byte(0x7c),
+            Instr::I64Sub => self.byte(0x7d),
+            Instr::I64Mul => self.byte(0x7e),
+            Instr::I64DivS => self.byte(0x7f),
+            Instr::I64DivU => self.byte(0x80),
+            Instr::I64RemS => self.byte(0x81),
+            Instr::I64RemU => self.byte(0x82),
+            Instr::I64And => self.byte(0x83),
+            Instr::I64Or => self.byte(0x84),
+            Instr::I64Xor => self.byte(0x85),
+            Instr::I64Shl => self.byte(0x86),
+            Instr::I64ShrS => self.byte(0x87),
+            Instr::I64ShrU => self.byte(0x88),
+            Instr::I64Rotl => self.byte(0x89),
+            Instr::I64Rotr => self.byte(0x8a),
+            Instr::F32Abs => self.byte(0x8b),
+            Instr::F32Neg => self.byte(0x8c),
+            Instr::F32Ceil => self.byte(0x8d),
+            Instr::F32Floor => self.byte(0x8e),
+            Instr::F32Trunc => self.byte(0x8f),
+            Instr::F32Nearest => self.byte(0x90),
+            Instr::F32Sqrt => self.byte(0x91),
+            Instr::F32Add => self.byte(0x92),
+            Instr::F32Sub => self.byte(0x93),
+            Instr::F32Mul => self.byte(0x94),
+            Instr::F32Div => self.byte(0x95),
+            Instr::F32Min => self.byte(0x96),
+            Instr::F32Max => self.byte(0x97),
+            Instr::F32Copysign => self.byte(0x98),
+            Instr::F64Abs => self.byte(0x99),
+            Instr::F64Neg => self.byte(0x9a),
+            Instr::F64Ceil => self.byte(0x9b),
+            Instr::F64Floor => self.byte(0x9c),
+            Instr::F64Trunc => self.byte(0x9d),
+            Instr::F64Nearest => self.byte(0x9e),
+            Instr::F64Sqrt => self.byte(0x9f),
+            Instr::F64Add => self.byte(0xa0),
+            Instr::F64Sub => self.byte(0xa1),
+            Instr::F64Mul => self.byte(0xa2),
+            Instr::F64Div => self.byte(0xa3),
+            Instr::F64Min => self.byte(0xa4),
+            Instr::F64Max => self.byte(0xa5),
+            Instr::F64Copysign => self.byte(0xa6),
+            Instr::I32Extend8S => self.byte(0xc0),
+            Instr::I32Extend16S => self.byte(0xc1),
+        }
+    }
+
+    /// Writes an unsigned LEB128 integer in its shortest form.
+    fn u32(&mut self, mut value: u32) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                self.byte(byte);
+                return;
+            }
+            self.byte(byte | 0x80);
+        }
+    }
+
+    /// Writes a signed LEB128 integer in its shortest form.
+    fn i64(&mut self, mut value: i64) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            let done = (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0);
+            if done {
+                self.byte(byte);
+                return;
+            }
+            self.byte(byte | 0x80);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::text;
+    use std::fs;
+
+    /// Decodes a module for comparison with a parsed one, which has no instruction offsets.
+    fn decode_without_offsets(input: &[u8]) -> Module {
+        let mut module = Decoder::new(input).decode().unwrap();
+        for func in &mut module.funcs {
+            func.offsets.clear();
+        }
+        module
+    }
+
+    #[test]
+    fn test_round_trip_inputs() {
+        for entry in fs::read_dir("./tests/inputs").unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_none_or(|extension| extension != "wasm") {
+                continue;
+            }
+            let binary = fs::read(&path).unwrap();
+            let module = Decoder::new(&binary).decode().unwrap();
+            let encoded = Encoder::encode(&module);
+            assert_eq!(
+                Decoder::new(&encoded).decode().unwrap(),
+                module,
+                "{}",
+                path.display()
+            );
+            assert_eq!(encoded, binary, "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn test_round_trip_sections() {
+        let module = text::parse(
+            r#"(module
+                (import "env" "log" (func $log (param i32)))
+                (import "env" "memory" (memory 1))
+                (table $t 1 8 funcref)
+                (memory 1 2)
+                (func $main (local i32 i32 i64 f32 funcref)
+                    (call $log (i32.load8_u offset=300 (i32.const -1)))
+                    (drop (i64.const -9223372036854775808))
+                    (drop (f64.const -0x1.8p3))
+                    (drop (memory.grow (memory.size)))
+                    (drop (table.grow $t (ref.null func) (table.size $t)))
+                    (if (i32.const 1) (then (call $log (i32.const 2)))))
+                (export "main" (func $main))
+                (start $main)
+                (data (i32.const 8) "hi")
+                (data (memory 1) (i32.const 0) "")
+                (data "passive"))"#,
+        )
+        .unwrap();
+        let encoded = Encoder::encode(&module);
+        assert_eq!(decode_without_offsets(&encoded), module);
+    }
+
+    #[test]
+    fn test_many_functions() {
+        let mut source = String::from("(module");
+        for i in 0..200 {
+            source.push_str(&format!(
+                " (func (export \"f{}\") (result i32) i32.const {})",
+                i,
+                i * 1000
+            ));
+        }
+        source.push(')');
+        let module = text::parse(&source).unwrap();
+        let encoded = Encoder::encode(&module);
+        assert_eq!(decode_without_offsets(&encoded), module);
+    }
+
+    #[test]
+    fn test_leb128() {
+        let mut encoder = Encoder::default();
+        encoder.u32(624485);
+        encoder.i64(-123456);
+        encoder.i64(63);
+        encoder.i64(64);
+        encoder.i64(-64);
+        encoder.i64(-65);
+        assert_eq!(
+            encoder.output,
+            vec![
+                0xe5, 0x8e, 0x26, // 624485
+                0xc0, 0xbb, 0x78, // -123456
+                0x3f, // 63
+                0xc0, 0x00, // 64
+                0x40, // -64
+                0xbf, 0x7f, // -65
+            ]
+        );
+    }
+}
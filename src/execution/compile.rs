@@ -0,0 +1,422 @@
+use crate::execution::structure::Val;
+use crate::structure::instructions::expression::{BlockType, Instr};
+use crate::structure::modules::function::Func;
+use crate::structure::modules::indice::{FuncIdx, TableIdx};
+use crate::structure::types::function::FuncType;
+use crate::structure::types::value::{NumType, RefType, ValType};
+use crate::validation::{load_signature, numeric_signature, store_signature};
+
+/// The index of a value in the frame of a call.
+///
+/// A frame holds the parameters, then the declared locals, then the operand stack, so an
+/// operand at height `h` is in slot `local_count + h`. The heights are known when compiling,
+/// so ops address their operands directly instead of pushing and popping them.
+pub type Slot = u32;
+
+/// A function body flattened into a sequence of ops, so that it can be run with a program
+/// counter instead of walking and cloning the instruction tree.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Code {
+    pub ops: Vec<Op>,
+    /// The instruction each op charges fuel for, or `None` for the jumps and returns inserted
+    /// by the compiler. An if is charged without its branches, which are charged as they run.
+    pub charges: Vec<Option<Instr>>,
+    /// The code section offset of each op, or empty if the module wasn't decoded from a binary.
+    pub offsets: Vec<u32>,
+    pub param_count: usize,
+    /// The initial values of the declared locals, which follow the parameters.
+    pub locals: Vec<Val>,
+    /// The maximum height of the operand stack, which follows the locals in the frame.
+    pub max_height: usize,
+}
+
+impl Code {
+    /// The number of slots in a frame of this function.
+    pub fn frame_size(&self) -> usize {
+        self.param_count + self.locals.len() + self.max_height
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Op {
+    LocalGet {
+        local: Slot,
+        dst: Slot,
+    },
+    Const {
+        dst: Slot,
+        /// The value as an untyped slot.
+        value: u64,
+    },
+    /// Drops the top operand. The heights of later ops already account for it, so this only
+    /// charges fuel.
+    Drop,
+    /// Replaces the operand in `slot` with the result.
+    Unary {
+        instr: Instr,
+        slot: Slot,
+    },
+    /// Replaces the operands in `lhs` and the slot above it with the result.
+    Binary {
+        instr: Instr,
+        lhs: Slot,
+    },
+    /// Replaces the address in `addr` with the loaded value.
+    Load {
+        instr: Instr,
+        addr: Slot,
+    },
+    /// Stores the value in the slot above `addr`.
+    Store {
+        instr: Instr,
+        addr: Slot,
+    },
+    TableSize {
+        table: TableIdx,
+        dst: Slot,
+    },
+    /// Replaces the initial value in `init` and the delta above it with the result.
+    TableGrow {
+        table: TableIdx,
+        init: Slot,
+    },
+    MemorySize {
+        dst: Slot,
+    },
+    /// Replaces the delta in `delta` with the result.
+    MemoryGrow {
+        delta: Slot,
+    },
+    /// Jumps to the op at `else_pc` if the condition in `cond` is zero.
+    If {
+        cond: Slot,
+        else_pc: usize,
+    },
+    /// Jumps over the else branch at the end of a then branch.
+    Jump(usize),
+    /// Calls a function with the arguments in the slots from `args`, which become the first
+    /// slots of its frame and receive its results.
+    Call {
+        func_idx: FuncIdx,
+        args: Slot,
+    },
+    /// Moves `count` results from the slots from `from` to the start of the frame.
+    Return {
+        from: Slot,
+        count: u32,
+    },
+}
+
+/// Compiles `func`. `func_types` are the types of the functions in the module, by index.
+pub fn compile(func: &Func, type_: &FuncType, func_types: &[FuncType]) -> Code {
+    let stack_base = (type_.parameters.len() + func.locals.len()) as Slot;
+    let mut compiler = Compiler {
+        func_types,
+        ops: Vec::new(),
+        charges: Vec::new(),
+        offsets: Vec::new(),
+        source_offsets: &func.offsets,
+        index: 0,
+        stack_base,
+        height: 0,
+        max_height: 0,
+    };
+    compiler.block(&func.body.0);
+
+    let count = type_.results.len() as u32;
+    if count > 0 {
+        let offset = compiler.offsets.last().copied();
+        compiler.push(
+            Op::Return {
+                from: stack_base,
+                count,
+            },
+            None,
+            offset,
+        );
+    }
+    Code {
+        ops: compiler.ops,
+        charges: compiler.charges,
+        offsets: compiler.offsets,
+        param_count: type_.parameters.len(),
+        locals: func.locals.iter().map(default_val).collect(),
+        max_height: compiler.max_height,
+    }
+}
+
+pub(crate) fn default_val(type_: &ValType) -> Val {
+    match type_ {
+        ValType::NumType(NumType::I32) => Val::I32(0),
+        ValType::NumType(NumType::I64) => Val::I64(0),
+        ValType::NumType(NumType::F32) => Val::F32(0.0),
+        ValType::NumType(NumType::F64) => Val::F64(0.0),
+        ValType::RefType(RefType::FuncRef) => Val::FuncRef(None),
+    }
+}
+
+struct Compiler<'a> {
+    func_types: &'a [FuncType],
+    ops: Vec<Op>,
+    charges: Vec<Option<Instr>>,
+    offsets: Vec<u32>,
+    /// The offsets of the instructions in pre-order.
+    source_offsets: &'a [u32],
+    /// The pre-order index of the next instruction.
+    index: usize,
+    /// The slot of the bottom of the operand stack.
+    stack_base: Slot,
+    height: usize,
+    max_height: usize,
+}
+
+impl Compiler<'_> {
+    fn block(&mut self, instrs: &[Instr]) {
+        for instr in instrs {
+            let offset = self.source_offsets.get(self.index).copied();
+            self.index += 1;
+            if let Instr::If(block_type, instructions1, instructions2) = instr {
+                self.if_block(block_type, instructions1, instructions2, offset);
+                continue;
+            }
+            let charge = Some(instr.clone());
+            match instr {
+                Instr::Call(func_idx) => {
+                    let type_ = &self.func_types[*func_idx as usize];
+                    let (param_count, result_count) = (type_.parameters.len(), type_.results.len());
+                    self.height -= param_count;
+                    let args = self.slot(self.height);
+                    for _ in 0..result_count {
+                        self.grow();
+                    }
+                    let func_idx = *func_idx;
+                    self.push(Op::Call { func_idx, args }, charge, offset);
+                }
+                Instr::Drop => {
+                    self.pop();
+                    self.push(Op::Drop, charge, offset);
+                }
+                Instr::LocalGet(local) => {
+                    let (local, dst) = (*local, self.grow());
+                    self.push(Op::LocalGet { local, dst }, charge, offset);
+                }
+                Instr::I32Const(x) => self.constant(Val::I32(*x), charge, offset),
+                Instr::I64Const(x) => self.constant(Val::I64(*x), charge, offset),
+                Instr::F32Const(x) => self.constant(Val::F32(*x), charge, offset),
+                Instr::F64Const(x) => self.constant(Val::F64(*x), charge, offset),
+                Instr::RefNull(_) => self.constant(Val::FuncRef(None), charge, offset),
+                Instr::TableSize(table) => {
+                    let (table, dst) = (*table, self.grow());
+                    self.push(Op::TableSize { table, dst }, charge, offset);
+                }
+                Instr::TableGrow(table) => {
+                    self.pop();
+                    let init = self.slot(self.height - 1);
+                    let table = *table;
+                    self.push(Op::TableGrow { table, init }, charge, offset);
+                }
+                Instr::MemorySize => {
+                    let dst = self.grow();
+                    self.push(Op::MemorySize { dst }, charge, offset);
+                }
+                Instr::MemoryGrow => {
+                    let delta = self.slot(self.height - 1);
+                    self.push(Op::MemoryGrow { delta }, charge, offset);
+                }
+                Instr::Else | Instr::End => unreachable!("block delimiters are not in the tree"),
+                _ if load_signature(instr).is_some() => {
+                    let addr = self.slot(self.height - 1);
+                    let instr = instr.clone();
+                    self.push(Op::Load { instr, addr }, charge, offset);
+                }
+                _ if store_signature(instr).is_some() => {
+                    self.pop();
+                    let addr = self.pop();
+                    let instr = instr.clone();
+                    self.push(Op::Store { instr, addr }, charge, offset);
+                }
+                _ if numeric_signature(instr).0.len() == 1 => {
+                    let slot = self.slot(self.height - 1);
+                    let instr = instr.clone();
+                    self.push(Op::Unary { instr, slot }, charge, offset);
+                }
+                _ => {
+                    self.pop();
+                    let lhs = self.slot(self.height - 1);
+                    let instr = instr.clone();
+                    self.push(Op::Binary { instr, lhs }, charge, offset);
+                }
+            }
+        }
+    }
+
+    fn if_block(
+        &mut self,
+        block_type: &BlockType,
+        instructions1: &[Instr],
+        instructions2: &[Instr],
+        offset: Option<u32>,
+    ) {
+        let cond = self.pop();
+        let height = self.height;
+        let charge = Instr::If(block_type.clone(), Vec::new(), Vec::new());
+        let if_pc = self.push(Op::If { cond, else_pc: 0 }, Some(charge), offset);
+        self.block(instructions1);
+        if !instructions2.is_empty() {
+            let jump_pc = self.push(Op::Jump(0), None, offset);
+            self.ops[if_pc] = Op::If {
+                cond,
+                else_pc: self.ops.len(),
+            };
+            self.height = height;
+            self.block(instructions2);
+            self.ops[jump_pc] = Op::Jump(self.ops.len());
+        } else {
+            self.ops[if_pc] = Op::If {
+                cond,
+                else_pc: self.ops.len(),
+            };
+        }
+        self.height = match block_type {
+            BlockType::Empty => height,
+            BlockType::ValType(_) => height + 1,
+        };
+    }
+
+    fn constant(&mut self, value: Val, charge: Option<Instr>, offset: Option<u32>) {
+        let dst = self.grow();
+        let value = value.to_bits();
+        self.push(Op::Const { dst, value }, charge, offset);
+    }
+
+    fn slot(&self, height: usize) -> Slot {
+        self.stack_base + height as Slot
+    }
+
+    /// Pushes an operand and returns its slot.
+    fn grow(&mut self) -> Slot {
+        let slot = self.slot(self.height);
+        self.height += 1;
+        self.max_height = self.max_height.max(self.height);
+        slot
+    }
+
+    /// Pops an operand and returns the slot it was in.
+    fn pop(&mut self) -> Slot {
+        self.height -= 1;
+        self.slot(self.height)
+    }
+
+    fn push(&mut self, op: Op, charge: Option<Instr>, offset: Option<u32>) -> usize {
+        self.ops.push(op);
+        self.charges.push(charge);
+        self.offsets.extend(offset);
+        self.ops.len() - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structure::instructions::expression::Expr;
+
+    #[test]
+    fn test_compile_if() {
+        let func = Func {
+            type_: 0,
+            locals: vec![ValType::NumType(NumType::I64)],
+            body: Expr(vec![
+                Instr::LocalGet(0),
+                Instr::If(
+                    BlockType::Empty,
+                    vec![Instr::I32Const(1), Instr::Drop],
+                    vec![Instr::I32Const(2), Instr::Drop],
+                ),
+                Instr::LocalGet(0),
+                Instr::If(
+                    BlockType::Empty,
+                    vec![Instr::LocalGet(1), Instr::Drop],
+                    Vec::new(),
+                ),
+            ]),
+            offsets: (0..11).map(|i| i * 2).collect(),
+        };
+        let type_ = FuncType {
+            parameters: vec![ValType::NumType(NumType::I32)],
+            results: Vec::new(),
+        };
+        let code = compile(&func, &type_, &[]);
+        assert_eq!(
+            code.ops,
+            vec![
+                Op::LocalGet { local: 0, dst: 2 },
+                Op::If {
+                    cond: 2,
+                    else_pc: 5
+                },
+                Op::Const { dst: 2, value: 1 },
+                Op::Drop,
+                Op::Jump(7),
+                Op::Const { dst: 2, value: 2 },
+                Op::Drop,
+                Op::LocalGet { local: 0, dst: 2 },
+                Op::If {
+                    cond: 2,
+                    else_pc: 11
+                },
+                Op::LocalGet { local: 1, dst: 2 },
+                Op::Drop,
+            ]
+        );
+        assert_eq!(
+            code.charges[1],
+            Some(Instr::If(BlockType::Empty, Vec::new(), Vec::new()))
+        );
+        assert_eq!(code.charges[4], None);
+        assert_eq!(code.charges[5], Some(Instr::I32Const(2)));
+        assert_eq!(code.offsets, vec![0, 2, 4, 6, 2, 8, 10, 12, 14, 16, 18]);
+        assert_eq!(code.param_count, 1);
+        assert_eq!(code.locals, vec![Val::I64(0)]);
+        assert_eq!(code.max_height, 1);
+    }
+
+    #[test]
+    fn test_compile_call() {
+        let i32_ = ValType::NumType(NumType::I32);
+        let func = Func {
+            type_: 0,
+            locals: Vec::new(),
+            body: Expr(vec![
+                Instr::I32Const(7),
+                Instr::LocalGet(0),
+                Instr::Call(0),
+                Instr::I32Add,
+            ]),
+            offsets: Vec::new(),
+        };
+        let type_ = FuncType {
+            parameters: vec![i32_.clone()],
+            results: vec![i32_],
+        };
+        let code = compile(&func, &type_, std::slice::from_ref(&type_));
+        assert_eq!(
+            code.ops,
+            vec![
+                Op::Const { dst: 1, value: 7 },
+                Op::LocalGet { local: 0, dst: 2 },
+                Op::Call {
+                    func_idx: 0,
+                    args: 2
+                },
+                Op::Binary {
+                    instr: Instr::I32Add,
+                    lhs: 1
+                },
+                Op::Return { from: 1, count: 1 },
+            ]
+        );
+        assert_eq!(code.charges[4], None);
+        assert_eq!(code.max_height, 2);
+        assert_eq!(code.frame_size(), 3);
+    }
+}
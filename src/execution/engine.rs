@@ -0,0 +1,314 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// State shared by the stores of an embedding, which may be used from other threads.
+///
+/// A watchdog thread can hold a clone of the engine and call `increment_epoch` to interrupt
+/// guests whose epoch deadline has passed.
+#[derive(Debug, Clone, Default)]
+pub struct Engine {
+    epoch: Arc<AtomicU64>,
+    config: Config,
+}
+
+impl Engine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_config(config: Config) -> Self {
+        Self {
+            epoch: Arc::default(),
+            config,
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch.load(Ordering::Relaxed)
+    }
+
+    pub fn increment_epoch(&self) {
+        self.epoch.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// The epoch counter, which compiled code reads directly.
+    #[cfg(feature = "jit")]
+    pub(crate) fn epoch_counter(&self) -> &AtomicU64 {
+        &self.epoch
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Config {
+    pub backend: Backend,
+    /// Validates and compiles each function on its first call instead of at instantiation,
+    /// which also decodes the bodies a lazy decoder left undecoded. Invalid functions trap
+    /// when they are called. The JIT doesn't compile these functions, so they are run by the
+    /// stack backend. Without it, undecoded bodies are decoded and validated at instantiation.
+    pub lazy_compilation: bool,
+    /// Validates and compiles function bodies on the rayon thread pool. Results, and the
+    /// error reported for an invalid module, are the same as without it.
+    #[cfg(feature = "parallel")]
+    pub parallel_compilation: bool,
+}
+
+impl Config {
+    /// Maps the function bodies of a module, in parallel if the configuration says so. The
+    /// results are in the order of `items` either way.
+    pub(crate) fn map_funcs<T, R>(&self, items: &[T], f: impl Fn(usize, &T) -> R + Sync) -> Vec<R>
+    where
+        T: Sync,
+        R: Send,
+    {
+        #[cfg(feature = "parallel")]
+        if self.parallel_compilation {
+            use rayon::prelude::*;
+            return items
+                .par_iter()
+                .enumerate()
+                .map(|(i, item)| f(i, item))
+                .collect();
+        }
+        items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| f(i, item))
+            .collect()
+    }
+}
+
+/// How function bodies are executed.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Backend {
+    /// Runs the Wasm operand stack as is.
+    #[default]
+    Stack,
+    /// Maps locals and operands to register slots, which removes most pushes and pops. Fuel
+    /// metering isn't supported.
+    Register,
+    /// Compiles functions to x86-64 machine code at instantiation. Functions with unsupported
+    /// instructions fall back to the stack backend. Fuel metering isn't supported.
+    #[cfg(feature = "jit")]
+    Jit,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::instance::{instantiate, invoke};
+    use crate::execution::structure::{Store, Val};
+    use crate::execution::trap::TrapKind;
+    use crate::text;
+    use std::thread;
+
+    const I32_BINARY: &[&str] = &[
+        "add", "sub", "mul", "div_s", "div_u", "rem_s", "rem_u", "and", "or", "xor", "shl",
+        "shr_s", "shr_u", "rotl", "rotr", "eq", "ne", "lt_s", "lt_u", "gt_s", "gt_u", "le_s",
+        "le_u", "ge_s", "ge_u",
+    ];
+    const FLOAT_BINARY: &[&str] = &[
+        "add", "sub", "mul", "div", "min", "max", "copysign", "eq", "ne", "lt", "gt", "le", "ge",
+    ];
+    const FLOAT_UNARY: &[&str] = &["abs", "neg", "ceil", "floor", "trunc", "nearest", "sqrt"];
+
+    /// A module that exports every numeric instruction as a function named after it, and the
+    /// values to call them with.
+    fn numeric_module() -> (String, Vec<(String, Vec<Val>)>) {
+        let ints = [
+            0,
+            1,
+            -1,
+            2,
+            -8,
+            33,
+            i64::from(i32::MIN),
+            i64::from(i32::MAX),
+            i64::MIN,
+        ];
+        let floats = [
+            0.0,
+            -0.0,
+            0.5,
+            1.5,
+            -2.5,
+            f64::NAN,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+        ];
+        let mut ops: Vec<(String, usize, &str)> = Vec::new();
+        for (type_, unary) in [
+            (
+                "i32",
+                &["clz", "ctz", "popcnt", "eqz", "extend8_s", "extend16_s"][..],
+            ),
+            ("i64", &["clz", "ctz", "popcnt", "eqz"][..]),
+        ] {
+            ops.extend(
+                unary
+                    .iter()
+                    .map(|op| (format!("{}.{}", type_, op), 1, type_)),
+            );
+            ops.extend(
+                I32_BINARY
+                    .iter()
+                    .map(|op| (format!("{}.{}", type_, op), 2, type_)),
+            );
+        }
+        for type_ in ["f32", "f64"] {
+            ops.extend(
+                FLOAT_UNARY
+                    .iter()
+                    .map(|op| (format!("{}.{}", type_, op), 1, type_)),
+            );
+            ops.extend(
+                FLOAT_BINARY
+                    .iter()
+                    .map(|op| (format!("{}.{}", type_, op), 2, type_)),
+            );
+        }
+
+        let mut source = String::from("(module\n");
+        let mut cases = Vec::new();
+        for (name, arity, type_) in ops {
+            let params = vec![type_; arity].join(" ");
+            let result = match &name[4..] {
+                "eqz" | "eq" | "ne" | "lt" | "gt" | "le" | "ge" => "i32",
+                op if op.starts_with("lt_") || op.starts_with("gt_") => "i32",
+                op if op.starts_with("le_") || op.starts_with("ge_") => "i32",
+                _ => type_,
+            };
+            let args: Vec<String> = (0..arity).map(|i| format!("(local.get {})", i)).collect();
+            source += &format!(
+                "(func (export \"{}\") (param {}) (result {}) ({} {}))\n",
+                name,
+                params,
+                result,
+                name,
+                args.join(" ")
+            );
+            let vals: Vec<Val> = match type_ {
+                "i32" => ints.iter().map(|x| Val::I32(*x as i32)).collect(),
+                "i64" => ints.iter().map(|x| Val::I64(*x)).collect(),
+                "f32" => floats.iter().map(|x| Val::F32(*x as f32)).collect(),
+                _ => floats.iter().map(|x| Val::F64(*x)).collect(),
+            };
+            for lhs in &vals {
+                if arity == 1 {
+                    cases.push((name.clone(), vec![lhs.clone()]));
+                    continue;
+                }
+                for rhs in &vals {
+                    cases.push((name.clone(), vec![lhs.clone(), rhs.clone()]));
+                }
+            }
+        }
+        source += r#"(func (export "eqz_add") (param i64) (result i32)
+            (i32.add (i64.eqz (local.get 0)) (i32.const 1))))"#;
+        cases.push(("eqz_add".to_string(), vec![Val::I64(0)]));
+        (source, cases)
+    }
+
+    #[test]
+    fn test_backends_agree() {
+        let (source, cases) = numeric_module();
+        let run = |backend| {
+            let mut store = Store::new(&Engine::with_config(Config {
+                backend,
+                ..Default::default()
+            }));
+            let module_inst = instantiate(&mut store, text::parse(&source).unwrap(), &[]).unwrap();
+            cases
+                .iter()
+                .map(|(name, args)| {
+                    match invoke(&mut store, &module_inst, name.clone(), args.clone()) {
+                        // NaNs are compared by their bits.
+                        Ok(results) => Ok(results.iter().map(Val::to_bits).collect::<Vec<_>>()),
+                        Err(error) => Err(error.unwrap_trap().kind),
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let expected = run(Backend::Stack);
+        let result = |name: &str, args: &[Val]| {
+            let i = cases
+                .iter()
+                .position(|case| case.0 == name && case.1 == args)
+                .unwrap();
+            expected[i].clone()
+        };
+        assert_eq!(
+            result("i32.add", &[Val::I32(i32::MAX), Val::I32(1)]),
+            Ok(vec![0x8000_0000])
+        );
+        assert_eq!(
+            result("i32.shr_u", &[Val::I32(-8), Val::I32(1)]),
+            Ok(vec![2147483644])
+        );
+        assert_eq!(
+            result("i32.div_s", &[Val::I32(i32::MIN), Val::I32(-1)]),
+            Err(TrapKind::IntegerOverflow)
+        );
+        assert_eq!(
+            result("i64.rem_u", &[Val::I64(1), Val::I64(0)]),
+            Err(TrapKind::IntegerDivideByZero)
+        );
+        assert_eq!(result("eqz_add", &[Val::I64(0)]), Ok(vec![2]));
+
+        for backend in [
+            Backend::Register,
+            #[cfg(feature = "jit")]
+            Backend::Jit,
+        ] {
+            for (i, actual) in run(backend).into_iter().enumerate() {
+                assert_eq!(actual, expected[i], "{:?}: {:?}", backend, cases[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_epoch_deadline() {
+        let engine = Engine::new();
+        let mut store = Store::new(&engine);
+        let module = text::parse(
+            r#"(module
+                (func $inc (param i32) (result i32) (i32.add (local.get 0) (i32.const 1)))
+                (func (export "f") (result i32) (call $inc (i32.const 1))))"#,
+        )
+        .unwrap();
+        let module_inst = instantiate(&mut store, module, &[]).unwrap();
+        let f = || "f".to_string();
+
+        // Without a deadline the epoch doesn't matter.
+        engine.increment_epoch();
+        assert_eq!(
+            invoke(&mut store, &module_inst, f(), vec![]),
+            Ok(vec![Val::I32(2)])
+        );
+
+        store.set_epoch_deadline(1);
+        assert_eq!(
+            invoke(&mut store, &module_inst, f(), vec![]),
+            Ok(vec![Val::I32(2)])
+        );
+
+        let watchdog = engine.clone();
+        thread::spawn(move || watchdog.increment_epoch())
+            .join()
+            .unwrap();
+        let trap = invoke(&mut store, &module_inst, f(), vec![])
+            .unwrap_err()
+            .unwrap_trap();
+        assert_eq!(trap.kind, TrapKind::Interrupted);
+
+        store.set_epoch_deadline(1);
+        assert_eq!(
+            invoke(&mut store, &module_inst, f(), vec![]),
+            Ok(vec![Val::I32(2)])
+        );
+    }
+}
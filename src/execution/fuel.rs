@@ -0,0 +1,218 @@
+use crate::execution::engine::Backend;
+use crate::execution::trap::{Trap, TrapKind};
+use crate::structure::instructions::expression::Instr;
+use std::fmt;
+use std::rc::Rc;
+
+type CostFn = dyn Fn(&Instr) -> u64;
+type RefillFn = dyn FnMut() -> Option<u64>;
+
+/// Fuel that instructions consume as they execute, to bound how long a guest can run.
+///
+/// The costs depend only on the executed instructions, so the same budget stops a guest at
+/// the same point on every machine.
+pub struct Fuel {
+    remaining: u64,
+    costs: Rc<CostFn>,
+    refill: Option<Box<RefillFn>>,
+}
+
+impl Fuel {
+    /// Creates fuel where every instruction costs 1.
+    pub fn new(remaining: u64) -> Self {
+        Self {
+            remaining,
+            costs: Rc::new(|_| 1),
+            refill: None,
+        }
+    }
+
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    pub fn set(&mut self, fuel: u64) {
+        self.remaining = fuel;
+    }
+
+    pub fn add(&mut self, fuel: u64) {
+        self.remaining = self.remaining.saturating_add(fuel);
+    }
+
+    pub fn set_costs<F>(&mut self, costs: F)
+    where
+        F: Fn(&Instr) -> u64 + 'static,
+    {
+        self.costs = Rc::new(costs);
+    }
+
+    /// Sets a callback that is asked for more fuel when it runs out. Execution resumes at the
+    /// instruction that ran out if it returns `Some`, and traps with `OutOfFuel` otherwise.
+    pub fn set_refill<F>(&mut self, refill: F)
+    where
+        F: FnMut() -> Option<u64> + 'static,
+    {
+        self.refill = Some(Box::new(refill));
+    }
+
+    /// Consumes the cost of `instr`. Nothing is consumed if there isn't enough fuel for it.
+    pub fn consume(&mut self, instr: &Instr) -> Result<(), Trap> {
+        let cost = (self.costs)(instr);
+        while self.remaining < cost {
+            let Some(refill) = self.refill.as_mut() else {
+                return Err(TrapKind::OutOfFuel.into());
+            };
+            match refill() {
+                Some(fuel) => self.add(fuel),
+                None => return Err(TrapKind::OutOfFuel.into()),
+            }
+        }
+        self.remaining -= cost;
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Fuel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Fuel")
+            .field("remaining", &self.remaining)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An error configuring fuel for a store.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FuelError {
+    /// Only the stack backend meters execution.
+    UnsupportedBackend(Backend),
+}
+
+impl fmt::Display for FuelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FuelError::UnsupportedBackend(backend) => {
+                write!(f, "the {:?} backend doesn't support fuel metering", backend)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::engine::{Config, Engine};
+    use crate::execution::instance::{instantiate, invoke, ModuleInst};
+    use crate::execution::structure::{Store, Val};
+    use crate::text;
+    use std::cell::Cell;
+
+    fn store_with(source: &str) -> (Store, Rc<ModuleInst>) {
+        let mut store = Store::default();
+        let module = text::parse(source).unwrap();
+        let module_inst = instantiate(&mut store, module, &[]).unwrap();
+        (store, module_inst)
+    }
+
+    const ADD: &str = r#"(module
+        (func (export "add") (param i32 i32) (result i32)
+            (i32.add (local.get 0) (local.get 1))))"#;
+
+    const RECURSE: &str = r#"(module (func $f (export "f") (call $f)))"#;
+
+    #[test]
+    fn test_consume() {
+        let (mut store, module_inst) = store_with(ADD);
+        assert_eq!(store.fuel(), None);
+
+        store.set_fuel(10).unwrap();
+        let args = vec![Val::I32(1), Val::I32(2)];
+        let results = invoke(&mut store, &module_inst, "add".to_string(), args.clone());
+        assert_eq!(results, Ok(vec![Val::I32(3)]));
+        assert_eq!(store.fuel(), Some(7));
+
+        store
+            .set_fuel_costs(|instr| match instr {
+                Instr::I32Add => 5,
+                _ => 1,
+            })
+            .unwrap();
+        invoke(&mut store, &module_inst, "add".to_string(), args).unwrap();
+        assert_eq!(store.fuel(), Some(0));
+    }
+
+    #[test]
+    fn test_out_of_fuel() {
+        let (mut store, module_inst) = store_with(RECURSE);
+        store.set_fuel(50).unwrap();
+        let trap = invoke(&mut store, &module_inst, "f".to_string(), vec![])
+            .unwrap_err()
+            .unwrap_trap();
+        assert_eq!(trap.kind, TrapKind::OutOfFuel);
+        assert_eq!(store.fuel(), Some(0));
+
+        // Once fuel is added, the guest can be run again.
+        let (mut store, module_inst) = store_with(ADD);
+        store.set_fuel(2).unwrap();
+        let args = vec![Val::I32(1), Val::I32(2)];
+        let trap = invoke(&mut store, &module_inst, "add".to_string(), args.clone())
+            .unwrap_err()
+            .unwrap_trap();
+        assert_eq!(trap.kind, TrapKind::OutOfFuel);
+        store.add_fuel(3).unwrap();
+        let results = invoke(&mut store, &module_inst, "add".to_string(), args);
+        assert_eq!(results, Ok(vec![Val::I32(3)]));
+    }
+
+    #[test]
+    fn test_refill() {
+        let (mut store, module_inst) = store_with(RECURSE);
+        let refills = Rc::new(Cell::new(0));
+        store.set_fuel(10).unwrap();
+        let counter = Rc::clone(&refills);
+        store
+            .set_fuel_refill(move || {
+                counter.set(counter.get() + 1);
+                (counter.get() <= 3).then_some(10)
+            })
+            .unwrap();
+        let trap = invoke(&mut store, &module_inst, "f".to_string(), vec![])
+            .unwrap_err()
+            .unwrap_trap();
+        assert_eq!(trap.kind, TrapKind::OutOfFuel);
+        assert_eq!(refills.get(), 4);
+        assert_eq!(trap.backtrace.len(), 41);
+    }
+
+    #[test]
+    fn test_if() {
+        let (mut store, module_inst) = store_with(
+            r#"(module (func (export "f") (param i32) (result i32)
+                (if (result i32) (local.get 0) (then (i32.const 1)) (else (i32.const 2)))))"#,
+        );
+        store
+            .set_fuel_costs(|instr| match instr {
+                Instr::If(..) => 10,
+                _ => 1,
+            })
+            .unwrap();
+        store.set_fuel(100).unwrap();
+        invoke(&mut store, &module_inst, "f".to_string(), vec![Val::I32(1)]).unwrap();
+        assert_eq!(store.fuel(), Some(88));
+        invoke(&mut store, &module_inst, "f".to_string(), vec![Val::I32(0)]).unwrap();
+        assert_eq!(store.fuel(), Some(76));
+    }
+
+    #[test]
+    fn test_unsupported_backend() {
+        let engine = Engine::with_config(Config {
+            backend: Backend::Register,
+            ..Default::default()
+        });
+        let mut store = Store::new(&engine);
+        let error = FuelError::UnsupportedBackend(Backend::Register);
+        assert_eq!(store.set_fuel(10), Err(error.clone()));
+        assert_eq!(store.add_fuel(10), Err(error.clone()));
+        assert_eq!(store.set_fuel_costs(|_| 2), Err(error));
+        assert_eq!(store.fuel(), None);
+    }
+}
@@ -0,0 +1,49 @@
+use crate::execution::instance::ModuleInst;
+use crate::execution::memory::Memory;
+use crate::execution::structure::{Store, Val};
+use crate::execution::trap::Trap;
+use std::fmt;
+use std::rc::Rc;
+
+type HostFn = dyn Fn(&mut Caller, &[Val]) -> Result<Vec<Val>, Trap>;
+
+#[derive(Clone)]
+pub struct HostFunc(Rc<HostFn>);
+
+impl HostFunc {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(&mut Caller, &[Val]) -> Result<Vec<Val>, Trap> + 'static,
+    {
+        Self(Rc::new(f))
+    }
+
+    pub fn call(&self, caller: &mut Caller, arguments: &[Val]) -> Result<Vec<Val>, Trap> {
+        (self.0)(caller, arguments)
+    }
+}
+
+impl fmt::Debug for HostFunc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "HostFunc")
+    }
+}
+
+impl PartialEq for HostFunc {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// The context handed to a host function: the store and the instance of the calling function.
+pub struct Caller<'a> {
+    pub store: &'a mut Store,
+    pub module: Option<Rc<ModuleInst>>,
+}
+
+impl Caller<'_> {
+    pub fn get_memory(&mut self, name: &str) -> Option<Memory<'_>> {
+        let module = self.module.as_ref()?;
+        module.get_memory(self.store, name)
+    }
+}
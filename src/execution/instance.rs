@@ -1,71 +1,218 @@
+use crate::execution::compile::compile;
+use crate::execution::engine::Backend;
+use crate::execution::host::HostFunc;
+#[cfg(feature = "jit")]
+use crate::execution::jit;
+use crate::execution::limits::ResourceLimiter;
+use crate::execution::memory::Memory;
+use crate::execution::register;
 use crate::execution::structure::{
-    AdministrativeInstruction, Frame, FuncAddr, FuncInst, Runtime, Stack, StackValue, Store, Val,
+    AdministrativeInstruction, FuncAddr, FuncInst, MemAddr, MemInst, PendingFunc, Runtime, Stack,
+    Store, TableAddr, TableInst, Val, PAGE_SIZE,
 };
-use crate::structure::instructions::expression::Instr;
+use crate::execution::trap::{Trap, TrapKind};
+use crate::structure::instructions::expression::{Expr, Instr};
+use crate::structure::modules::custom::NameMap;
+use crate::structure::modules::data::DataMode;
 use crate::structure::modules::export::ExportDesc;
-use crate::structure::modules::function::Func;
+use crate::structure::modules::import::ImportDesc;
 use crate::structure::modules::module::Module;
 use crate::structure::types::function::FuncType;
-use crate::structure::types::value::NumType;
-use crate::structure::types::value::ValType;
-use std::cell::RefCell;
+use crate::validation::{validate_body, validate_module, Bodies, ValidationError};
+use std::fmt;
 use std::rc::Rc;
 
-pub fn invoke(store: Store, module: &ModuleInst, func_name: String, values: Vec<Val>) {
-    let Some(export_inst) = module.exports.iter().find(|e| e.name == func_name) else {
-        return;
+pub fn invoke(
+    store: &mut Store,
+    module: &ModuleInst,
+    func_name: String,
+    values: Vec<Val>,
+) -> Result<Vec<Val>, InvokeError> {
+    let Some(func_address) = module.get_func(&func_name) else {
+        return Err(InvokeError::UnknownFunction(func_name));
     };
-    let ExternVal::Func(func_address) = &export_inst.value;
 
-    let func_inst = &store.funcs[*func_address as usize];
-    let func_type = &func_inst.type_;
-    if func_type.parameters.len() != values.len() {
-        println!("{:?}", func_type.parameters);
-        println!("number of arguments mismatch");
-        return;
+    let expected = store.funcs[func_address as usize].type_().parameters.len();
+    if expected != values.len() {
+        return Err(InvokeError::ArgumentCountMismatch {
+            expected,
+            actual: values.len(),
+        });
     }
 
-    let mut stack = Stack::new();
+    Ok(invoke_func(store, func_address, values)?)
+}
 
-    let dummy_frame = Frame {
-        return_arity: 0,
-        locals: Vec::new(),
-    };
-    stack.push(StackValue::Frame(Rc::new(RefCell::new(dummy_frame))));
+pub fn invoke_func(
+    store: &mut Store,
+    func_address: FuncAddr,
+    values: Vec<Val>,
+) -> Result<Vec<Val>, Trap> {
+    match store.engine.config().backend {
+        Backend::Stack => {}
+        Backend::Register => return register::invoke(store, func_address, values),
+        #[cfg(feature = "jit")]
+        Backend::Jit => {
+            if let Some(results) = jit::invoke(store, func_address, &values) {
+                return results;
+            }
+        }
+    }
+    interpret(store, func_address, values)
+}
+
+/// Runs a function on the stack backend.
+pub(crate) fn interpret(
+    store: &mut Store,
+    func_address: FuncAddr,
+    values: Vec<Val>,
+) -> Result<Vec<Val>, Trap> {
+    let func_type = store.funcs[func_address as usize].type_().clone();
 
-    for value in values {
-        stack.push(StackValue::Value(value));
+    let mut stack = Stack::new();
+    for value in &values {
+        stack.push(value.to_bits());
     }
 
-    let mut runtime = Runtime {
-        store: store.clone(),
-        stack,
-        current_frame: None,
-    };
-    runtime.run(AdministrativeInstruction::Invoke(*func_address));
+    let mut runtime = Runtime::new(store, stack);
+    runtime.run(AdministrativeInstruction::Invoke(func_address))?;
 
     let mut results = Vec::new();
-    for _ in 0..func_type.results.len() {
-        results.push(runtime.stack.pop());
+    for result in func_type.results.iter().rev() {
+        results.push(Val::from_bits(runtime.stack.pop(), result));
     }
+    results.reverse();
 
-    // pop the dummy frame
-    runtime.stack.pop();
-
-    println!("👻 {:?}", results);
+    Ok(results)
 }
 
-pub fn instantiate(store: &mut Store, module: Module) -> ModuleInst {
-    let mut module_inst = ModuleInst {
-        types: module.types.clone(),
-        func_addrs: Vec::new(),
-        exports: Vec::new(),
-    };
+pub fn instantiate(
+    store: &mut Store,
+    mut module: Module,
+    externvals: &[ExternVal],
+) -> Result<Rc<ModuleInst>, InstantiationError> {
+    // Only a lazy engine leaves bodies for their first call, whichever way they were decoded.
+    if !store.engine.config().lazy_compilation {
+        module
+            .decode_bodies()
+            .map_err(|error| InstantiationError::Invalid(ValidationError(error.to_string())))?;
+    }
+    if !module.validated {
+        let bodies = match store.engine.config().lazy_compilation {
+            true => Bodies::None,
+            false => Bodies::Decoded,
+        };
+        validate_module(&module, bodies, store.engine.config())
+            .map_err(InstantiationError::Invalid)?;
+    }
+
+    if module.imports.len() != externvals.len() {
+        return Err(InstantiationError::ImportCountMismatch);
+    }
+    for (import, externval) in module.imports.iter().zip(externvals) {
+        let matched = match (&import.desc, externval) {
+            (ImportDesc::Func(type_idx), ExternVal::Func(func_addr)) => {
+                store.funcs[*func_addr as usize].type_() == &module.types[*type_idx as usize]
+            }
+            (ImportDesc::Mem(mem_type), ExternVal::Mem(mem_addr)) => {
+                let mem_inst = &store.mems[*mem_addr as usize];
+                mem_inst.size() >= mem_type.0.min
+                    && match mem_type.0.max {
+                        Some(max) => mem_inst.type_.0.max.is_some_and(|m| m <= max),
+                        None => true,
+                    }
+            }
+            _ => false,
+        };
+        if !matched {
+            return Err(InstantiationError::IncompatibleImportType(
+                import.module.clone(),
+                import.name.clone(),
+            ));
+        }
+    }
+
+    if let Some(limiter) = &mut store.limiter {
+        if store.instance_count >= limiter.instances() {
+            return Err(InstantiationError::ResourceLimitExceeded(
+                "too many instances".to_string(),
+            ));
+        }
+        for mem in &module.mems {
+            let limits = &mem.type_.0;
+            let desired = limits.min as usize * PAGE_SIZE;
+            let maximum = limits
+                .max
+                .map(|max| (max as usize).saturating_mul(PAGE_SIZE));
+            if !limiter.memory_growing(0, desired, maximum) {
+                return Err(InstantiationError::ResourceLimitExceeded(
+                    "memory size".to_string(),
+                ));
+            }
+        }
+        for table in &module.tables {
+            let limits = &table.type_.0;
+            let maximum = limits.max.map(|max| max as usize);
+            if !limiter.table_growing(0, limits.min as usize, maximum) {
+                return Err(InstantiationError::ResourceLimitExceeded(
+                    "table size".to_string(),
+                ));
+            }
+        }
+    }
+
+    // Offsets are evaluated before anything is allocated, since they can only be checked here
+    // when validation was skipped.
+    let mut datas = Vec::new();
+    for data in &module.datas {
+        if let DataMode::Active { memory, offset } = &data.mode {
+            datas.push((*memory, evaluate_const_expr(offset)?, data.init.clone()));
+        }
+    }
+    let start = module.start;
+    let module_inst = alloc_module(store, module, externvals);
 
+    for (memory, offset, init) in datas {
+        let mem_addr = module_inst.mem_addrs[memory as usize];
+        let mut memory = Memory::new(&mut store.mems[mem_addr as usize]);
+        memory
+            .write(offset as u32 as usize, &init)
+            .map_err(|_| InstantiationError::Trap(TrapKind::MemoryOutOfBounds.into()))?;
+    }
+
+    if let Some(start) = start {
+        let func_addr = module_inst.func_addrs[start as usize];
+        invoke_func(store, func_addr, vec![]).map_err(InstantiationError::Trap)?;
+    }
+
+    Ok(module_inst)
+}
+
+pub fn alloc_module(store: &mut Store, module: Module, externvals: &[ExternVal]) -> Rc<ModuleInst> {
     let mut func_addrs = Vec::new();
-    for func in &module.funcs {
-        let func_addr = allocate_function(store, func.clone(), &module_inst);
-        func_addrs.push(func_addr);
+    let mut mem_addrs = Vec::new();
+    for externval in externvals {
+        match externval {
+            ExternVal::Func(func_addr) => func_addrs.push(*func_addr),
+            ExternVal::Mem(mem_addr) => mem_addrs.push(*mem_addr),
+        }
+    }
+
+    // The functions refer back to the module instance, so their addresses are reserved up front.
+    let first_func_addr = store.funcs.len() as FuncAddr;
+    for i in 0..module.funcs.len() {
+        func_addrs.push(first_func_addr + i as FuncAddr);
+    }
+
+    let mut table_addrs = Vec::new();
+    for table in &module.tables {
+        let table_addr = allocate_table(store, TableInst::new(table.type_.clone()));
+        table_addrs.push(table_addr);
+    }
+
+    for mem in &module.mems {
+        let mem_addr = allocate_memory(store, MemInst::new(mem.type_.clone()));
+        mem_addrs.push(mem_addr);
     }
 
     let mut exports = Vec::new();
@@ -73,62 +220,348 @@ pub fn instantiate(store: &mut Store, module: Module) -> ModuleInst {
         let export_inst = ExportInst {
             name: export.name,
             value: match export.desc {
-                ExportDesc::Func(func_index) => ExternVal::Func(func_index),
+                ExportDesc::Func(func_index) => ExternVal::Func(func_addrs[func_index as usize]),
+                ExportDesc::Mem(mem_index) => ExternVal::Mem(mem_addrs[mem_index as usize]),
             },
         };
         exports.push(export_inst);
     }
 
-    module_inst.func_addrs.extend(func_addrs);
-    module_inst.exports.extend(exports);
+    store.instance_count += 1;
+    let module_inst = Rc::new(ModuleInst {
+        types: module.types,
+        func_addrs,
+        table_addrs,
+        mem_addrs,
+        exports,
+        func_names: module.names.funcs,
+    });
+
+    let func_types: Vec<FuncType> = module_inst
+        .func_addrs
+        .iter()
+        .map(|addr| match store.funcs.get(*addr as usize) {
+            Some(func_inst) => func_inst.type_().clone(),
+            None => {
+                let func = &module.funcs[(addr - first_func_addr) as usize];
+                module_inst.types[func.type_ as usize].clone()
+            }
+        })
+        .collect();
+    // `instantiate` decodes the bodies unless the engine is lazy, but modules allocated
+    // directly may still have undecoded, unvalidated ones.
+    let config = store.engine.config();
+    let types = &module_inst.types;
+    let compiled = config.map_funcs(&module.funcs, |i, func| {
+        if config.lazy_compilation || i < module.lazy_bodies.len() {
+            return None;
+        }
+        let type_ = &types[func.type_ as usize];
+        let register_code = match config.backend {
+            Backend::Register => Some(register::compile(func, type_, &func_types)),
+            _ => None,
+        };
+        Some((compile(func, type_, &func_types), register_code))
+    });
+
+    let mut bodies = module.lazy_bodies.into_iter();
+    for (func, compiled) in module.funcs.into_iter().zip(compiled) {
+        let type_ = module_inst.types[func.type_ as usize].clone();
+        let body = bodies.next();
+        let func_inst = match compiled {
+            Some((code, register_code)) => FuncInst::Module {
+                code: Rc::new(code),
+                register_code: register_code.map(Rc::new),
+                #[cfg(feature = "jit")]
+                jit: None,
+                pending: None,
+                type_,
+                module: Rc::clone(&module_inst),
+            },
+            None => FuncInst::Module {
+                code: Rc::default(),
+                register_code: None,
+                #[cfg(feature = "jit")]
+                jit: None,
+                pending: Some(Box::new(PendingFunc { func, body })),
+                type_,
+                module: Rc::clone(&module_inst),
+            },
+        };
+        store.funcs.push(func_inst);
+    }
+
+    #[cfg(feature = "jit")]
+    if store.engine.config().backend == Backend::Jit {
+        let funcs = &mut store.funcs[first_func_addr as usize..];
+        let codes: Vec<_> = funcs
+            .iter()
+            .map(|func_inst| match func_inst {
+                FuncInst::Module {
+                    pending: None,
+                    code,
+                    ..
+                } => Some(Rc::clone(code)),
+                FuncInst::Module { .. } => None,
+                FuncInst::Host { .. } => unreachable!(),
+            })
+            .collect();
+        let entries = jit::compile(&codes, &module_inst.func_addrs, &module_inst.mem_addrs);
+        for (func_inst, entry) in funcs.iter_mut().zip(entries) {
+            if let FuncInst::Module { jit, .. } = func_inst {
+                *jit = entry;
+            }
+        }
+    }
 
     module_inst
 }
 
-fn allocate_function(store: &mut Store, func: Func, module_inst: &ModuleInst) -> FuncAddr {
-    let func_inst = FuncInst {
-        type_: module_inst.types[func.type_ as usize].clone(),
-        code: func,
+/// Decodes, validates and compiles a function that was left for its first call. If it is
+/// invalid, it stays pending so that every call traps.
+pub(crate) fn compile_pending(store: &mut Store, func_addr: FuncAddr) -> Result<(), Trap> {
+    let FuncInst::Module {
+        module,
+        type_,
+        pending: Some(pending),
+        ..
+    } = &store.funcs[func_addr as usize]
+    else {
+        return Ok(());
     };
-    let addr = store.funcs.len() as u32;
-    store.funcs.push(func_inst);
+    let module = Rc::clone(module);
+    let func_idx = module
+        .func_addrs
+        .iter()
+        .position(|addr| *addr == func_addr)
+        .expect("functions belong to their module");
+    let func_types: Vec<&FuncType> = module
+        .func_addrs
+        .iter()
+        .map(|addr| store.funcs[*addr as usize].type_())
+        .collect();
+
+    let decoded;
+    let func = match &pending.body {
+        Some(body) => {
+            decoded = body
+                .decode(pending.func.type_)
+                .map_err(|error| invalid(ValidationError(error.to_string()), func_idx))?;
+            &decoded
+        }
+        None => &pending.func,
+    };
+    let tables = module
+        .table_addrs
+        .iter()
+        .map(|addr| store.tables[*addr as usize].type_.1.clone())
+        .collect();
+    validate_body(
+        &module.types,
+        func_types.clone(),
+        tables,
+        module.mem_addrs.len(),
+        func,
+    )
+    .map_err(|error| invalid(error, func_idx))?;
+
+    let func_types: Vec<FuncType> = func_types.into_iter().cloned().collect();
+    let compiled = Rc::new(compile(func, type_, &func_types));
+    let compiled_registers = match store.engine.config().backend {
+        Backend::Register => Some(Rc::new(register::compile(func, type_, &func_types))),
+        _ => None,
+    };
+    if let FuncInst::Module {
+        code,
+        register_code,
+        pending,
+        ..
+    } = &mut store.funcs[func_addr as usize]
+    {
+        *code = compiled;
+        *register_code = compiled_registers;
+        *pending = None;
+    }
+    Ok(())
+}
+
+fn invalid(error: ValidationError, func_idx: usize) -> Trap {
+    TrapKind::Invalid(ValidationError(format!("func {}: {}", func_idx, error))).into()
+}
+
+pub fn alloc_host_func(store: &mut Store, type_: FuncType, code: HostFunc) -> FuncAddr {
+    let addr = store.funcs.len() as FuncAddr;
+    store.funcs.push(FuncInst::Host { type_, code });
     addr
 }
 
+fn allocate_table(store: &mut Store, table_inst: TableInst) -> TableAddr {
+    let addr = store.tables.len() as TableAddr;
+    store.tables.push(table_inst);
+    addr
+}
+
+fn allocate_memory(store: &mut Store, mem_inst: MemInst) -> MemAddr {
+    let addr = store.mems.len() as MemAddr;
+    store.mems.push(mem_inst);
+    addr
+}
+
+fn evaluate_const_expr(expr: &Expr) -> Result<i32, InstantiationError> {
+    match expr.0.as_slice() {
+        [Instr::I32Const(x)] => Ok(*x),
+        _ => Err(InstantiationError::Invalid(ValidationError(
+            "constant expression required".to_string(),
+        ))),
+    }
+}
+
 #[derive(Debug, PartialEq)]
+pub enum InvokeError {
+    /// The module doesn't export a function with the name.
+    UnknownFunction(String),
+    ArgumentCountMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    Trap(Trap),
+}
+
+impl From<Trap> for InvokeError {
+    fn from(trap: Trap) -> Self {
+        InvokeError::Trap(trap)
+    }
+}
+
+#[cfg(test)]
+impl InvokeError {
+    pub(crate) fn unwrap_trap(self) -> Trap {
+        match self {
+            InvokeError::Trap(trap) => trap,
+            error => panic!("expected a trap, got {}", error),
+        }
+    }
+}
+
+impl fmt::Display for InvokeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvokeError::UnknownFunction(name) => write!(f, "unknown function \"{}\"", name),
+            InvokeError::ArgumentCountMismatch { expected, actual } => {
+                write!(f, "expected {} argument(s), got {}", expected, actual)
+            }
+            InvokeError::Trap(trap) => write!(f, "{}", trap),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InstantiationError {
+    UnknownImport(String, String),
+    ImportCountMismatch,
+    IncompatibleImportType(String, String),
+    Invalid(ValidationError),
+    Trap(Trap),
+    ResourceLimitExceeded(String),
+}
+
+impl fmt::Display for InstantiationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InstantiationError::UnknownImport(module, name) => {
+                write!(f, "unknown import \"{}\" \"{}\"", module, name)
+            }
+            InstantiationError::ImportCountMismatch => write!(f, "import count mismatch"),
+            InstantiationError::IncompatibleImportType(module, name) => {
+                write!(
+                    f,
+                    "incompatible import type for \"{}\" \"{}\"",
+                    module, name
+                )
+            }
+            InstantiationError::Invalid(error) => write!(f, "invalid module: {}", error),
+            InstantiationError::Trap(trap) => write!(f, "{}", trap),
+            InstantiationError::ResourceLimitExceeded(resource) => {
+                write!(f, "resource limit exceeded: {}", resource)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
 pub struct ModuleInst {
     pub types: Vec<FuncType>,
     pub func_addrs: Vec<FuncAddr>,
+    pub table_addrs: Vec<TableAddr>,
+    pub mem_addrs: Vec<MemAddr>,
     pub exports: Vec<ExportInst>,
+    /// Function names from the name section, used to symbolize backtraces.
+    pub func_names: NameMap,
 }
 
-#[derive(Debug, PartialEq)]
-struct ExportInst {
-    name: String,
-    value: ExternVal,
+impl ModuleInst {
+    pub fn get_func(&self, name: &str) -> Option<FuncAddr> {
+        let export_inst = self.exports.iter().find(|e| e.name == name)?;
+        match export_inst.value {
+            ExternVal::Func(func_addr) => Some(func_addr),
+            _ => None,
+        }
+    }
+
+    pub fn get_memory<'a>(&self, store: &'a mut Store, name: &str) -> Option<Memory<'a>> {
+        let export_inst = self.exports.iter().find(|e| e.name == name)?;
+        match export_inst.value {
+            ExternVal::Mem(mem_addr) => Some(Memory::with_limiter(
+                &mut store.mems[mem_addr as usize],
+                store
+                    .limiter
+                    .as_deref_mut()
+                    .map(|l| l as &mut dyn ResourceLimiter),
+            )),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq)]
-enum ExternVal {
+pub struct ExportInst {
+    pub name: String,
+    pub value: ExternVal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExternVal {
     Func(FuncAddr),
+    Mem(MemAddr),
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::structure::instructions::expression::{Expr, Instr};
+    use crate::decoder::Decoder;
+    use crate::execution::compile::{Code, Op};
+    use crate::execution::engine::{Config, Engine};
+    use crate::execution::trap::FrameInfo;
+    use crate::structure::instructions::expression::{Expr, Instr, MemArg};
+    use crate::structure::modules::data::Data;
     use crate::structure::modules::export::{Export, ExportDesc};
+    use crate::structure::modules::function::Func;
+    use crate::structure::modules::import::Import;
+    use crate::structure::modules::memory::Mem;
+    use crate::structure::types::memory::{Limits, MemType};
     use crate::structure::types::value::{NumType, ValType};
+    use crate::validation::{validate, validate_all};
 
     #[test]
     fn test_empty() {
-        let store = Store { funcs: Vec::new() };
+        let mut store = Store::default();
         let module = Module {
             types: Vec::new(),
             funcs: Vec::new(),
             exports: Vec::new(),
+            ..Default::default()
         };
-        let (store, module_inst) = alloc_module(store, module);
+        let module_inst = alloc_module(&mut store, module, &[]);
         assert_eq!(store.funcs, Vec::new());
         assert_eq!(module_inst.func_addrs, Vec::new());
         assert_eq!(module_inst.types, Vec::new());
@@ -137,7 +570,7 @@ mod tests {
 
     #[test]
     fn test_func() {
-        let store = Store { funcs: Vec::new() };
+        let mut store = Store::default();
         let module = Module {
             types: vec![FuncType {
                 parameters: vec![ValType::NumType(NumType::I32)],
@@ -147,25 +580,48 @@ mod tests {
                 type_: 0,
                 locals: Vec::new(),
                 body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32Add]),
+                offsets: Vec::new(),
             }],
             exports: vec![Export {
                 name: "add".to_string(),
                 desc: ExportDesc::Func(0),
             }],
+            ..Default::default()
         };
-        let (store, module_inst) = alloc_module(store, module);
+        let module_inst = alloc_module(&mut store, module, &[]);
         assert_eq!(
             store.funcs,
-            vec![FuncInst {
+            vec![FuncInst::Module {
                 type_: FuncType {
                     parameters: vec![ValType::NumType(NumType::I32)],
                     results: vec![ValType::NumType(NumType::I32)],
                 },
-                code: Func {
-                    type_: 0,
+                module: Rc::clone(&module_inst),
+                code: Rc::new(Code {
+                    ops: vec![
+                        Op::LocalGet { local: 0, dst: 1 },
+                        Op::LocalGet { local: 1, dst: 2 },
+                        Op::Binary {
+                            instr: Instr::I32Add,
+                            lhs: 1
+                        },
+                        Op::Return { from: 1, count: 1 },
+                    ],
+                    charges: vec![
+                        Some(Instr::LocalGet(0)),
+                        Some(Instr::LocalGet(1)),
+                        Some(Instr::I32Add),
+                        None,
+                    ],
+                    offsets: Vec::new(),
+                    param_count: 1,
                     locals: Vec::new(),
-                    body: Expr(vec![Instr::LocalGet(0), Instr::LocalGet(1), Instr::I32Add]),
-                }
+                    max_height: 2,
+                }),
+                register_code: None,
+                #[cfg(feature = "jit")]
+                jit: None,
+                pending: None,
             }]
         );
         assert_eq!(
@@ -184,4 +640,403 @@ mod tests {
             },]
         );
     }
+
+    #[test]
+    fn test_host_func_memory() {
+        let mut store = Store::default();
+
+        let log_type = FuncType {
+            parameters: vec![
+                ValType::NumType(NumType::I32),
+                ValType::NumType(NumType::I32),
+            ],
+            results: vec![ValType::NumType(NumType::I32)],
+        };
+        let log = HostFunc::new(|caller, arguments| {
+            let (Val::I32(ptr), Val::I32(len)) = (&arguments[0], &arguments[1]) else {
+                panic!();
+            };
+            let mut memory = caller.get_memory("memory").unwrap();
+            let message = memory
+                .read_str(*ptr as usize, *len as usize)
+                .map_err(|e| TrapKind::Host(e.to_string()))?;
+            memory
+                .write(0, message.to_uppercase().as_bytes())
+                .map_err(|e| TrapKind::Host(e.to_string()))?;
+            Ok(vec![Val::I32(message.len() as i32)])
+        });
+        let log_addr = alloc_host_func(&mut store, log_type.clone(), log);
+
+        let module = Module {
+            types: vec![
+                log_type,
+                FuncType {
+                    parameters: vec![],
+                    results: vec![ValType::NumType(NumType::I32)],
+                },
+            ],
+            funcs: vec![Func {
+                type_: 1,
+                locals: Vec::new(),
+                body: Expr(vec![
+                    Instr::I32Const(16),
+                    Instr::I32Const(5),
+                    Instr::Call(0),
+                    Instr::Drop,
+                    Instr::I32Const(0),
+                    Instr::I32Load8U(MemArg {
+                        align: 0,
+                        offset: 1,
+                    }),
+                ]),
+                offsets: Vec::new(),
+            }],
+            mems: vec![Mem {
+                type_: MemType(Limits { min: 1, max: None }),
+            }],
+            datas: vec![Data {
+                init: b"hello".to_vec(),
+                mode: DataMode::Active {
+                    memory: 0,
+                    offset: Expr(vec![Instr::I32Const(16)]),
+                },
+            }],
+            imports: vec![Import {
+                module: "env".to_string(),
+                name: "log".to_string(),
+                desc: ImportDesc::Func(0),
+            }],
+            exports: vec![
+                Export {
+                    name: "run".to_string(),
+                    desc: ExportDesc::Func(1),
+                },
+                Export {
+                    name: "memory".to_string(),
+                    desc: ExportDesc::Mem(0),
+                },
+            ],
+            ..Default::default()
+        };
+        let module_inst = instantiate(&mut store, module, &[ExternVal::Func(log_addr)]).unwrap();
+
+        let results = invoke(&mut store, &module_inst, "run".to_string(), Vec::new());
+        assert_eq!(results, Ok(vec![Val::I32('E' as i32)]));
+
+        let memory = module_inst.get_memory(&mut store, "memory").unwrap();
+        assert_eq!(memory.read_str(0, 5), Ok("HELLO".to_string()));
+        assert_eq!(memory.read_str(16, 5), Ok("hello".to_string()));
+    }
+
+    #[test]
+    fn test_memory_out_of_bounds() {
+        let mut store = Store::default();
+        let module = Module {
+            types: vec![FuncType {
+                parameters: vec![],
+                results: vec![ValType::NumType(NumType::I64)],
+            }],
+            funcs: vec![Func {
+                type_: 0,
+                locals: Vec::new(),
+                body: Expr(vec![
+                    Instr::I32Const(65532),
+                    Instr::I64Load(MemArg {
+                        align: 3,
+                        offset: 0,
+                    }),
+                ]),
+                offsets: Vec::new(),
+            }],
+            mems: vec![Mem {
+                type_: MemType(Limits { min: 1, max: None }),
+            }],
+            exports: vec![Export {
+                name: "load".to_string(),
+                desc: ExportDesc::Func(0),
+            }],
+            ..Default::default()
+        };
+        let module_inst = instantiate(&mut store, module, &[]).unwrap();
+
+        let results = invoke(&mut store, &module_inst, "load".to_string(), Vec::new());
+        assert_eq!(
+            results.unwrap_err().unwrap_trap().kind,
+            TrapKind::MemoryOutOfBounds
+        );
+    }
+
+    #[test]
+    fn test_backtrace() {
+        let module = crate::text::parse(
+            r#"(module
+                (memory 1)
+                (func $main (export "main") (result i32)
+                    i32.const 0
+                    if (result i32)
+                        i32.const 1
+                    else
+                        i32.const 2
+                    end
+                    drop
+                    call $load)
+                (func $load (result i32)
+                    i32.const 0
+                    if (result i32)
+                        i32.const 1
+                    else
+                        i32.const 65536
+                        i32.load
+                    end))"#,
+        )
+        .unwrap();
+        let binary = crate::encoder::Encoder::encode(&module);
+        let module = crate::decoder::Decoder::new(&binary).decode().unwrap();
+        let code = crate::decoder::Decoder::new(&binary)
+            .decode_sections()
+            .unwrap()
+            .into_iter()
+            .find(|section| section.id == 10)
+            .unwrap();
+        let call_offset = module.funcs[0].offsets[5];
+        let load_offset = module.funcs[1].offsets[4];
+        assert_eq!(binary[code.offset + call_offset as usize], 0x10);
+        assert_eq!(binary[code.offset + load_offset as usize], 0x28);
+
+        let mut store = Store::default();
+        let module_inst = instantiate(&mut store, module, &[]).unwrap();
+        let trap = invoke(&mut store, &module_inst, "main".to_string(), Vec::new())
+            .unwrap_err()
+            .unwrap_trap();
+        assert_eq!(trap.kind, TrapKind::MemoryOutOfBounds);
+        assert_eq!(
+            trap.backtrace,
+            vec![
+                FrameInfo {
+                    func_index: 1,
+                    func_name: Some("load".to_string()),
+                    offset: Some(load_offset),
+                },
+                FrameInfo {
+                    func_index: 0,
+                    func_name: Some("main".to_string()),
+                    offset: Some(call_offset),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invoke_errors() {
+        let module = crate::text::parse(
+            r#"(module
+                (memory (export "memory") 1)
+                (func (export "f") (param i32)))"#,
+        )
+        .unwrap();
+        let mut store = Store::default();
+        let module_inst = instantiate(&mut store, module, &[]).unwrap();
+        let mut call =
+            |name: &str, values| invoke(&mut store, &module_inst, name.to_string(), values);
+
+        assert_eq!(call("f", vec![Val::I32(1)]), Ok(vec![]));
+        assert_eq!(
+            call("g", vec![]),
+            Err(InvokeError::UnknownFunction("g".to_string()))
+        );
+        assert_eq!(
+            call("memory", vec![]),
+            Err(InvokeError::UnknownFunction("memory".to_string()))
+        );
+        assert_eq!(
+            call("f", vec![]),
+            Err(InvokeError::ArgumentCountMismatch {
+                expected: 1,
+                actual: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_unvalidated_const_expr() {
+        let module = crate::text::parse(r#"(module (memory 1) (data (i64.const 0) "a"))"#);
+        let mut module = module.unwrap();
+        module.validated = true;
+        let mut store = Store::default();
+        assert!(matches!(
+            instantiate(&mut store, module, &[]),
+            Err(InstantiationError::Invalid(_))
+        ));
+        assert_eq!(store.instance_count, 0);
+    }
+
+    #[test]
+    fn test_stack_exhausted() {
+        let module = crate::text::parse(
+            r#"(module
+                (func $count (export "count") (param i32) (result i32)
+                    local.get 0
+                    i32.eqz
+                    if (result i32)
+                        i32.const 0
+                    else
+                        local.get 0
+                        i32.const 1
+                        i32.sub
+                        call $count
+                        i32.const 1
+                        i32.add
+                    end))"#,
+        )
+        .unwrap();
+        let mut store = Store::default();
+        let module_inst = instantiate(&mut store, module, &[]).unwrap();
+        let count = |store: &mut Store, n| {
+            invoke(store, &module_inst, "count".to_string(), vec![Val::I32(n)])
+        };
+
+        // Guest calls don't use the host stack, so this doesn't overflow it.
+        assert_eq!(count(&mut store, 9_999), Ok(vec![Val::I32(9_999)]));
+        let trap = count(&mut store, 10_000).unwrap_err().unwrap_trap();
+        assert_eq!(trap.kind, TrapKind::StackExhausted);
+        assert_eq!(trap.backtrace.len(), 10_000);
+
+        store.stack_limits.max_call_depth = 100_000;
+        assert_eq!(count(&mut store, 50_000), Ok(vec![Val::I32(50_000)]));
+        store.stack_limits.max_values = 1_000;
+        let trap = count(&mut store, 50_000).unwrap_err().unwrap_trap();
+        assert_eq!(trap.kind, TrapKind::StackExhausted);
+    }
+
+    #[test]
+    fn test_lazy_compilation() {
+        let module = crate::text::parse(
+            r#"(module
+                (func $inc (param i32) (result i32) (i32.add (local.get 0) (i32.const 1)))
+                (func (export "f") (result i32) (call $inc (i32.const 1)))
+                (func (export "invalid") (result i32) (i64.const 1)))"#,
+        )
+        .unwrap();
+        let binary = crate::encoder::Encoder::encode(&module);
+        let decode = || Decoder::new(&binary).lazy_bodies(true).decode().unwrap();
+        assert!(validate(&decode()).is_ok());
+        assert!(validate_all(&decode()).is_err());
+
+        // An engine that isn't lazy decodes and validates every body at instantiation.
+        let mut store = Store::default();
+        let error = instantiate(&mut store, decode(), &[]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid module: func 2: type mismatch: expected i32, found i64"
+        );
+        let valid =
+            crate::text::parse(r#"(module (func (export "f") (result i32) (i32.const 1)))"#);
+        let binary = crate::encoder::Encoder::encode(&valid.unwrap());
+        let lazy = Decoder::new(&binary).lazy_bodies(true).decode().unwrap();
+        let module_inst = instantiate(&mut store, lazy, &[]).unwrap();
+        assert!(matches!(
+            store.funcs[0],
+            FuncInst::Module { pending: None, .. }
+        ));
+        let f = invoke(&mut store, &module_inst, "f".to_string(), vec![]);
+        assert_eq!(f, Ok(vec![Val::I32(1)]));
+
+        for backend in [
+            Backend::Stack,
+            Backend::Register,
+            #[cfg(feature = "jit")]
+            Backend::Jit,
+        ] {
+            let mut config = Config {
+                backend,
+                ..Default::default()
+            };
+            config.lazy_compilation = true;
+            let mut store = Store::new(&Engine::with_config(config));
+            let module_inst = instantiate(&mut store, decode(), &[]).unwrap();
+            let pending = |store: &Store| {
+                store
+                    .funcs
+                    .iter()
+                    .map(|func| {
+                        matches!(
+                            func,
+                            FuncInst::Module {
+                                pending: Some(_),
+                                ..
+                            }
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(pending(&store), vec![true; 3]);
+
+            let f = invoke(&mut store, &module_inst, "f".to_string(), vec![]);
+            assert_eq!(f, Ok(vec![Val::I32(2)]));
+            assert_eq!(pending(&store), vec![false, false, true]);
+
+            // An invalid function traps on every call.
+            for _ in 0..2 {
+                let trap = invoke(&mut store, &module_inst, "invalid".to_string(), vec![])
+                    .unwrap_err()
+                    .unwrap_trap();
+                assert!(matches!(trap.kind, TrapKind::Invalid(_)), "{:?}", trap);
+            }
+        }
+
+        // Bodies that were decoded are only left for their first call by a lazy engine.
+        let mut store = Store::new(&Engine::with_config(Config {
+            lazy_compilation: true,
+            ..Default::default()
+        }));
+        let module_inst = instantiate(&mut store, module, &[]).unwrap();
+        let trap = invoke(&mut store, &module_inst, "invalid".to_string(), vec![])
+            .unwrap_err()
+            .unwrap_trap();
+        assert_eq!(
+            trap.to_string(),
+            "invalid module: func 2: type mismatch: expected i32, found i64"
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_compilation() {
+        let module = || {
+            crate::text::parse(
+                r#"(module
+                    (memory 1)
+                    (func $f (param i32) (result i32) (i32.add (local.get 0) (i32.const 1)))
+                    (func (param i32) (result i32)
+                        (if (result i32) (local.get 0)
+                            (then (call $f (i32.load (local.get 0))))
+                            (else (i32.const 0))))
+                    (func (result i64) (i64.const 1)))"#,
+            )
+            .unwrap()
+        };
+        for backend in [Backend::Stack, Backend::Register] {
+            let compile = |parallel_compilation| {
+                let mut store = Store::new(&Engine::with_config(Config {
+                    backend,
+                    parallel_compilation,
+                    ..Default::default()
+                }));
+                instantiate(&mut store, module(), &[]).unwrap();
+                store
+                    .funcs
+                    .into_iter()
+                    .map(|func| match func {
+                        FuncInst::Module {
+                            code,
+                            register_code,
+                            ..
+                        } => (code, register_code),
+                        FuncInst::Host { .. } => unreachable!(),
+                    })
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(compile(true), compile(false));
+        }
+    }
 }
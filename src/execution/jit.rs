@@ -0,0 +1,1268 @@
+use crate::execution::compile::{Code, Op, Slot};
+use crate::execution::host::Caller;
+use crate::execution::instance;
+use crate::execution::limits::ResourceLimiter;
+use crate::execution::structure::{FuncAddr, FuncInst, MemAddr, Store, Val};
+use crate::execution::trap::{FrameInfo, Trap, TrapKind};
+use crate::structure::instructions::expression::{Instr, MemArg};
+use std::any::Any;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt;
+use std::mem::offset_of;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::rc::Rc;
+use std::sync::atomic::AtomicU64;
+
+#[cfg(not(all(target_arch = "x86_64", target_os = "linux")))]
+compile_error!("the jit feature requires x86-64 Linux");
+
+/// The machine code of a function, which lives in the executable memory of its module.
+///
+/// Compiled functions take the context and a pointer to their frame, whose first slots hold
+/// the arguments and receive the results, and return a status.
+#[derive(Clone)]
+pub struct Entry {
+    memory: Rc<ExecutableMemory>,
+    offset: usize,
+}
+
+type EntryFn = unsafe extern "sysv64" fn(*mut Context, *mut u64) -> u32;
+
+impl Entry {
+    unsafe fn call(&self, ctx: *mut Context, frame: *mut u64) -> u32 {
+        let f: EntryFn = std::mem::transmute(self.memory.ptr.add(self.offset));
+        f(ctx, frame)
+    }
+}
+
+impl fmt::Debug for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Entry({:p})", self.memory.ptr.wrapping_add(self.offset))
+    }
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.memory, &other.memory) && self.offset == other.offset
+    }
+}
+
+struct ExecutableMemory {
+    ptr: *mut u8,
+    len: usize,
+}
+
+impl ExecutableMemory {
+    fn new(code: &[u8]) -> Self {
+        let len = code.len().max(1);
+        unsafe {
+            let ptr = libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            );
+            assert!(ptr != libc::MAP_FAILED, "mmap failed");
+            ptr::copy_nonoverlapping(code.as_ptr(), ptr as *mut u8, code.len());
+            let result = libc::mprotect(ptr, len, libc::PROT_READ | libc::PROT_EXEC);
+            assert!(result == 0, "mprotect failed");
+            Self {
+                ptr: ptr as *mut u8,
+                len,
+            }
+        }
+    }
+}
+
+impl Drop for ExecutableMemory {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(self.ptr as *mut libc::c_void, self.len);
+        }
+    }
+}
+
+// Statuses returned by compiled code. Traps raised by compiled code are encoded in the status,
+// while traps from helpers are left in the context.
+const OK: u32 = 0;
+const MEMORY_OUT_OF_BOUNDS: u32 = 1;
+const STACK_EXHAUSTED: u32 = 2;
+const INTERRUPTED: u32 = 3;
+const TRAPPED: u32 = 4;
+const PANICKED: u32 = 5;
+
+/// The state compiled code reads through `r12`.
+#[repr(C)]
+struct Context {
+    mems: *const MemView,
+    depth: u32,
+    max_depth: u32,
+    /// The lowest native stack pointer at which a function may be entered.
+    stack_limit: usize,
+    /// The start and end of `frames`, which moves when it grows.
+    frames_base: *mut u64,
+    frames_end: *const u64,
+    epoch: *const AtomicU64,
+    deadline: u64,
+    store: *mut Store,
+    mem_views: Vec<MemView>,
+    frames: Vec<u64>,
+    /// The most slots `frames` may grow to.
+    max_frames: usize,
+    trap: Option<Trap>,
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+#[repr(C)]
+struct MemView {
+    base: *mut u8,
+    len: u64,
+}
+
+impl Context {
+    /// Points the memory views at the current buffers, which growing a memory can move.
+    fn refresh(&mut self) {
+        let store = unsafe { &mut *self.store };
+        self.mem_views.clear();
+        for mem in &mut store.mems {
+            self.mem_views.push(MemView {
+                base: mem.data.as_mut_ptr(),
+                len: mem.data.len() as u64,
+            });
+        }
+        self.mems = self.mem_views.as_ptr();
+    }
+
+    fn refresh_frames(&mut self) {
+        self.frames_base = self.frames.as_mut_ptr();
+        self.frames_end = self.frames_base.wrapping_add(self.frames.len());
+    }
+}
+
+/// Native stack kept free below compiled code for helpers and the host functions they call.
+const STACK_RESERVE: usize = 256 * 1024;
+
+thread_local! {
+    static STACK_LIMIT: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+fn stack_limit() -> usize {
+    STACK_LIMIT.with(|limit| {
+        if let Some(limit) = limit.get() {
+            return limit;
+        }
+        let mut low = 0;
+        unsafe {
+            let mut attr = std::mem::zeroed();
+            if libc::pthread_getattr_np(libc::pthread_self(), &mut attr) == 0 {
+                let mut addr = ptr::null_mut();
+                let mut size = 0;
+                if libc::pthread_attr_getstack(&attr, &mut addr, &mut size) == 0 {
+                    low = addr as usize;
+                }
+                libc::pthread_attr_destroy(&mut attr);
+            }
+        }
+        // Without the stack bounds, assume a small stack below the current frame.
+        if low == 0 {
+            let here = &low as *const usize as usize;
+            low = here.saturating_sub(1 << 20);
+        }
+        limit.set(Some(low + STACK_RESERVE));
+        low + STACK_RESERVE
+    })
+}
+
+/// Runs a compiled function, or returns `None` if it wasn't compiled.
+pub fn invoke(
+    store: &mut Store,
+    func_addr: FuncAddr,
+    args: &[Val],
+) -> Option<Result<Vec<Val>, Trap>> {
+    let FuncInst::Module {
+        type_,
+        jit: Some(entry),
+        ..
+    } = &store.funcs[func_addr as usize]
+    else {
+        return None;
+    };
+    let type_ = type_.clone();
+    let entry = entry.clone();
+
+    let max_frames = (store.stack_limits.max_values)
+        .max(args.len())
+        .max(type_.results.len());
+    // The frames are kept in the store between calls and grow as calls need them. A host
+    // function that calls back into the guest finds them taken and starts its own.
+    let mut frames = std::mem::take(&mut store.jit_frames);
+    let slots = args.len().max(type_.results.len());
+    if frames.len() < slots {
+        frames.resize(slots, 0);
+    }
+    for (frame, arg) in frames.iter_mut().zip(args) {
+        *frame = arg.to_bits();
+    }
+    let mut ctx = Context {
+        mems: ptr::null(),
+        depth: 0,
+        max_depth: store.stack_limits.max_call_depth.min(u32::MAX as usize) as u32,
+        stack_limit: stack_limit(),
+        frames_base: ptr::null_mut(),
+        frames_end: ptr::null(),
+        epoch: store.engine.epoch_counter(),
+        deadline: store.epoch_deadline.unwrap_or(u64::MAX),
+        store,
+        mem_views: Vec::new(),
+        frames,
+        max_frames,
+        trap: None,
+        panic: None,
+    };
+    ctx.refresh();
+    ctx.refresh_frames();
+
+    let status = unsafe { entry.call(&mut ctx, ctx.frames_base) };
+    let Context {
+        panic,
+        trap,
+        frames,
+        ..
+    } = ctx;
+    let results = type_
+        .results
+        .iter()
+        .zip(&frames)
+        .map(|(result, bits)| Val::from_bits(*bits, result))
+        .collect();
+    store.jit_frames = frames;
+    if let Some(payload) = panic {
+        panic::resume_unwind(payload);
+    }
+    if status != OK {
+        return Some(Err(trap.unwrap_or_else(|| self::trap(status))));
+    }
+    Some(Ok(results))
+}
+
+fn trap(status: u32) -> Trap {
+    match status {
+        MEMORY_OUT_OF_BOUNDS => TrapKind::MemoryOutOfBounds.into(),
+        STACK_EXHAUSTED => TrapKind::StackExhausted.into(),
+        INTERRUPTED => TrapKind::Interrupted.into(),
+        _ => unreachable!("status {}", status),
+    }
+}
+
+/// Adds the frame of a compiled function to the trap that is propagating through it.
+unsafe extern "sysv64" fn unwind(ctx: *mut Context, status: u32, pc: u32, func_addr: u32) -> u32 {
+    let ctx = &mut *ctx;
+    if status == PANICKED {
+        return status;
+    }
+    let trap = ctx.trap.get_or_insert_with(|| trap(status));
+    let store = &*ctx.store;
+    if let FuncInst::Module { module, code, .. } = &store.funcs[func_addr as usize] {
+        let func_index = module
+            .func_addrs
+            .iter()
+            .position(|addr| *addr == func_addr)
+            .unwrap_or_default() as u32;
+        trap.backtrace.push(FrameInfo {
+            func_index,
+            func_name: module.func_names.get(&func_index).cloned(),
+            offset: code.offsets.get(pc as usize).copied(),
+        });
+    }
+    TRAPPED
+}
+
+/// Calls a function that compiled code can't call directly: a host function, a function of
+/// another module, or one that is run by the interpreter. The arguments are read from `args`,
+/// which receives the results.
+unsafe extern "sysv64" fn call(
+    ctx: *mut Context,
+    func_addr: u32,
+    args: *mut u64,
+    caller: u32,
+) -> u32 {
+    let store = &mut *(*ctx).store;
+    let func_inst = &store.funcs[func_addr as usize];
+    if let FuncInst::Module {
+        jit: Some(entry), ..
+    } = func_inst
+    {
+        let entry = entry.clone();
+        return entry.call(ctx, args);
+    }
+
+    let type_ = func_inst.type_().clone();
+    let arguments: Vec<Val> = type_
+        .parameters
+        .iter()
+        .enumerate()
+        .map(|(i, param)| Val::from_bits(*args.add(i), param))
+        .collect();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        match &store.funcs[func_addr as usize] {
+            FuncInst::Host { code, .. } => {
+                let code = code.clone();
+                let module = match &store.funcs[caller as usize] {
+                    FuncInst::Module { module, .. } => Some(Rc::clone(module)),
+                    FuncInst::Host { .. } => None,
+                };
+                code.call(&mut Caller { store, module }, &arguments)
+            }
+            FuncInst::Module { .. } => instance::interpret(store, func_addr, arguments),
+        }
+    }));
+
+    let ctx = &mut *ctx;
+    ctx.refresh();
+    match result {
+        Ok(Ok(results)) => {
+            for (i, result) in results.iter().enumerate() {
+                *args.add(i) = result.to_bits();
+            }
+            OK
+        }
+        Ok(Err(trap)) => {
+            ctx.trap = Some(trap);
+            TRAPPED
+        }
+        Err(payload) => {
+            ctx.panic = Some(payload);
+            PANICKED
+        }
+    }
+}
+
+/// Grows the frames so that a frame of `size` slots fits at `frame`, and returns where the
+/// frame is after the move, or null if the frames would exceed their limit.
+unsafe extern "sysv64" fn grow_frames(ctx: *mut Context, frame: *mut u64, size: u32) -> *mut u64 {
+    let ctx = &mut *ctx;
+    let offset = frame.offset_from(ctx.frames_base) as usize;
+    let needed = offset + size as usize;
+    if needed > ctx.max_frames {
+        return ptr::null_mut();
+    }
+    let len = (ctx.frames.len() * 2).clamp(needed, ctx.max_frames);
+    if ctx.frames.try_reserve(len - ctx.frames.len()).is_err() {
+        return ptr::null_mut();
+    }
+    ctx.frames.resize(len, 0);
+    ctx.refresh_frames();
+    ctx.frames_base.add(offset)
+}
+
+unsafe extern "sysv64" fn memory_size(ctx: *mut Context, mem_addr: u32, args: *mut u64) -> u32 {
+    let store = &*(*ctx).store;
+    *args = store.mems[mem_addr as usize].size() as u64;
+    OK
+}
+
+unsafe extern "sysv64" fn memory_grow(ctx: *mut Context, mem_addr: u32, args: *mut u64) -> u32 {
+    let ctx = &mut *ctx;
+    let store = &mut *ctx.store;
+    let Store { mems, limiter, .. } = store;
+    let limiter = limiter
+        .as_deref_mut()
+        .map(|l| l as &mut dyn ResourceLimiter);
+    let result = match mems[mem_addr as usize].grow(*args as u32, limiter) {
+        Some(old_size) => old_size as i32,
+        None => -1,
+    };
+    *args = result as u32 as u64;
+    ctx.refresh();
+    OK
+}
+
+/// Compiles the functions of a module. Functions without code, and functions with
+/// instructions the compiler doesn't support, are left to the interpreter and get no entry.
+///
+/// `func_addrs` covers the whole function index space, imports first.
+pub fn compile(
+    codes: &[Option<Rc<Code>>],
+    func_addrs: &[FuncAddr],
+    mem_addrs: &[MemAddr],
+) -> Vec<Option<Entry>> {
+    let import_count = func_addrs.len() - codes.len();
+    let mut asm = Assembler::default();
+    let labels: Vec<Option<Label>> = codes
+        .iter()
+        .map(|code| code.as_deref().is_some_and(supported).then(|| asm.label()))
+        .collect();
+    for (i, code) in codes.iter().enumerate() {
+        let (Some(entry), Some(code)) = (labels[i], code) else {
+            continue;
+        };
+        asm.bind(entry);
+        let mut compiler = Compiler {
+            asm: &mut asm,
+            code,
+            func_addr: func_addrs[import_count + i],
+            func_addrs,
+            // Direct calls are only made to compiled functions of this module.
+            direct: &|idx| {
+                idx.checked_sub(import_count)
+                    .and_then(|i| labels.get(i).copied().flatten())
+            },
+            mem_addr: mem_addrs.first().copied().unwrap_or_default(),
+            stubs: Vec::new(),
+            pc_labels: HashMap::new(),
+        };
+        compiler.function();
+    }
+
+    let buffer = asm.finish();
+    let memory = Rc::new(ExecutableMemory::new(&buffer));
+    labels
+        .iter()
+        .map(|label| {
+            label.map(|label| Entry {
+                memory: Rc::clone(&memory),
+                offset: asm.offset(label),
+            })
+        })
+        .collect()
+}
+
+fn supported(code: &Code) -> bool {
+    code.ops.iter().all(|op| match op {
+        Op::LocalGet { .. }
+        | Op::Const { .. }
+        | Op::Drop
+        | Op::MemorySize { .. }
+        | Op::MemoryGrow { .. }
+        | Op::If { .. }
+        | Op::Jump(_)
+        | Op::Call { .. }
+        | Op::Return { .. } => true,
+        Op::Unary { instr, .. } => matches!(
+            instr,
+            Instr::I32Eqz | Instr::I64Eqz | Instr::I32Extend8S | Instr::I32Extend16S
+        ),
+        Op::Binary { instr, .. } => binary(instr).is_some() || compare(instr).is_some(),
+        Op::Load { instr, .. } => load(instr).is_some(),
+        Op::Store { instr, .. } => store(instr).is_some(),
+        Op::TableSize { .. } | Op::TableGrow { .. } => false,
+    })
+}
+
+// Registers, by their encoding. `rbx` holds the frame and `r12` the context.
+const RAX: u8 = 0;
+const RCX: u8 = 1;
+const RDX: u8 = 2;
+const RBX: u8 = 3;
+const RSP: u8 = 4;
+const RSI: u8 = 6;
+const RDI: u8 = 7;
+const R12: u8 = 12;
+const R13: u8 = 13;
+
+// Condition codes.
+const CC_B: u8 = 0x2;
+const CC_AE: u8 = 0x3;
+const CC_E: u8 = 0x4;
+const CC_NE: u8 = 0x5;
+const CC_BE: u8 = 0x6;
+const CC_A: u8 = 0x7;
+const CC_L: u8 = 0xc;
+const CC_GE: u8 = 0xd;
+const CC_LE: u8 = 0xe;
+const CC_G: u8 = 0xf;
+
+/// How a load reads memory into `rax`: whether it needs REX.W, its opcode, and its width.
+fn load(instr: &Instr) -> Option<(bool, &'static [u8], &MemArg, u8)> {
+    Some(match instr {
+        Instr::I32Load(memarg) | Instr::F32Load(memarg) | Instr::I64Load32U(memarg) => {
+            (false, &[0x8b], memarg, 4)
+        }
+        Instr::I64Load(memarg) | Instr::F64Load(memarg) => (true, &[0x8b], memarg, 8),
+        Instr::I32Load8S(memarg) => (false, &[0x0f, 0xbe], memarg, 1),
+        Instr::I32Load8U(memarg) | Instr::I64Load8U(memarg) => (false, &[0x0f, 0xb6], memarg, 1),
+        Instr::I32Load16S(memarg) => (false, &[0x0f, 0xbf], memarg, 2),
+        Instr::I32Load16U(memarg) | Instr::I64Load16U(memarg) => (false, &[0x0f, 0xb7], memarg, 2),
+        Instr::I64Load8S(memarg) => (true, &[0x0f, 0xbe], memarg, 1),
+        Instr::I64Load16S(memarg) => (true, &[0x0f, 0xbf], memarg, 2),
+        Instr::I64Load32S(memarg) => (true, &[0x63], memarg, 4),
+        _ => return None,
+    })
+}
+
+/// The memory argument and width of a store.
+fn store(instr: &Instr) -> Option<(&MemArg, u8)> {
+    Some(match instr {
+        Instr::I32Store8(memarg) | Instr::I64Store8(memarg) => (memarg, 1),
+        Instr::I32Store16(memarg) | Instr::I64Store16(memarg) => (memarg, 2),
+        Instr::I32Store(memarg) | Instr::F32Store(memarg) | Instr::I64Store32(memarg) => {
+            (memarg, 4)
+        }
+        Instr::I64Store(memarg) | Instr::F64Store(memarg) => (memarg, 8),
+        _ => return None,
+    })
+}
+
+enum BinaryOp {
+    /// An instruction `op r/m, reg` with the opcode.
+    Arith(u8),
+    Mul,
+    /// A shift or rotate by `cl`, with the opcode extension.
+    Shift(u8),
+}
+
+/// A binary operator and whether it operates on 64 bits.
+fn binary(instr: &Instr) -> Option<(BinaryOp, bool)> {
+    Some(match instr {
+        Instr::I32Add => (BinaryOp::Arith(0x01), false),
+        Instr::I32Sub => (BinaryOp::Arith(0x29), false),
+        Instr::I32Mul => (BinaryOp::Mul, false),
+        Instr::I32And => (BinaryOp::Arith(0x21), false),
+        Instr::I32Or => (BinaryOp::Arith(0x09), false),
+        Instr::I32Xor => (BinaryOp::Arith(0x31), false),
+        Instr::I32Shl => (BinaryOp::Shift(4), false),
+        Instr::I32ShrS => (BinaryOp::Shift(7), false),
+        Instr::I32ShrU => (BinaryOp::Shift(5), false),
+        Instr::I32Rotl => (BinaryOp::Shift(0), false),
+        Instr::I32Rotr => (BinaryOp::Shift(1), false),
+        Instr::I64Add => (BinaryOp::Arith(0x01), true),
+        Instr::I64Sub => (BinaryOp::Arith(0x29), true),
+        Instr::I64Mul => (BinaryOp::Mul, true),
+        Instr::I64And => (BinaryOp::Arith(0x21), true),
+        Instr::I64Or => (BinaryOp::Arith(0x09), true),
+        Instr::I64Xor => (BinaryOp::Arith(0x31), true),
+        Instr::I64Shl => (BinaryOp::Shift(4), true),
+        Instr::I64ShrS => (BinaryOp::Shift(7), true),
+        Instr::I64ShrU => (BinaryOp::Shift(5), true),
+        Instr::I64Rotl => (BinaryOp::Shift(0), true),
+        Instr::I64Rotr => (BinaryOp::Shift(1), true),
+        _ => return None,
+    })
+}
+
+/// The condition code of a comparison and whether it compares 64 bits.
+fn compare(instr: &Instr) -> Option<(u8, bool)> {
+    Some(match instr {
+        Instr::I32Eq => (CC_E, false),
+        Instr::I32Ne => (CC_NE, false),
+        Instr::I32LtS => (CC_L, false),
+        Instr::I32LtU => (CC_B, false),
+        Instr::I32GtS => (CC_G, false),
+        Instr::I32GtU => (CC_A, false),
+        Instr::I32LeS => (CC_LE, false),
+        Instr::I32LeU => (CC_BE, false),
+        Instr::I32GeS => (CC_GE, false),
+        Instr::I32GeU => (CC_AE, false),
+        Instr::I64Eq => (CC_E, true),
+        Instr::I64Ne => (CC_NE, true),
+        Instr::I64LtS => (CC_L, true),
+        Instr::I64LtU => (CC_B, true),
+        Instr::I64GtS => (CC_G, true),
+        Instr::I64GtU => (CC_A, true),
+        Instr::I64LeS => (CC_LE, true),
+        Instr::I64LeU => (CC_BE, true),
+        Instr::I64GeS => (CC_GE, true),
+        Instr::I64GeU => (CC_AE, true),
+        _ => return None,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Label(usize);
+
+#[derive(Default)]
+struct Assembler {
+    buffer: Vec<u8>,
+    /// The offset of each label, once bound.
+    labels: Vec<Option<usize>>,
+    /// The offsets of rel32 operands and the labels they refer to.
+    fixups: Vec<(usize, Label)>,
+}
+
+impl Assembler {
+    fn label(&mut self) -> Label {
+        self.labels.push(None);
+        Label(self.labels.len() - 1)
+    }
+
+    fn bind(&mut self, label: Label) {
+        self.labels[label.0] = Some(self.buffer.len());
+    }
+
+    fn offset(&self, label: Label) -> usize {
+        self.labels[label.0].expect("unbound label")
+    }
+
+    fn finish(&mut self) -> Vec<u8> {
+        for (at, label) in std::mem::take(&mut self.fixups) {
+            let rel = self.offset(label) as i64 - (at as i64 + 4);
+            self.buffer[at..at + 4].copy_from_slice(&(rel as i32).to_le_bytes());
+        }
+        self.buffer.clone()
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    fn rel32(&mut self, label: Label) {
+        self.fixups.push((self.buffer.len(), label));
+        self.bytes(&[0; 4]);
+    }
+
+    fn rex(&mut self, w: bool, reg: u8, rm: u8) {
+        let rex = 0x40 | (w as u8) << 3 | (reg >> 3) << 2 | (rm >> 3);
+        if rex != 0x40 {
+            self.bytes(&[rex]);
+        }
+    }
+
+    /// An instruction with a `[base + disp32]` operand.
+    fn mem(&mut self, w: bool, opcode: &[u8], reg: u8, base: u8, disp: i32) {
+        self.rex(w, reg, base);
+        self.bytes(opcode);
+        self.bytes(&[0x80 | (reg & 7) << 3 | (base & 7)]);
+        if base & 7 == RSP {
+            self.bytes(&[0x24]);
+        }
+        self.bytes(&disp.to_le_bytes());
+    }
+
+    /// An instruction with a register operand.
+    fn reg(&mut self, w: bool, opcode: &[u8], reg: u8, rm: u8) {
+        self.rex(w, reg, rm);
+        self.bytes(opcode);
+        self.bytes(&[0xc0 | (reg & 7) << 3 | (rm & 7)]);
+    }
+
+    /// An instruction with a `[rdx + rax]` operand.
+    fn indexed(&mut self, w: bool, opcode: &[u8], reg: u8) {
+        self.rex(w, reg, 0);
+        self.bytes(opcode);
+        self.bytes(&[0x04 | (reg & 7) << 3, 0x02]);
+    }
+
+    fn push(&mut self, reg: u8) {
+        self.rex(false, 0, reg);
+        self.bytes(&[0x50 | (reg & 7)]);
+    }
+
+    fn pop(&mut self, reg: u8) {
+        self.rex(false, 0, reg);
+        self.bytes(&[0x58 | (reg & 7)]);
+    }
+
+    /// Moves a zero-extended immediate into a register.
+    fn mov_imm32(&mut self, reg: u8, imm: u32) {
+        self.rex(false, 0, reg);
+        self.bytes(&[0xb8 | (reg & 7)]);
+        self.bytes(&imm.to_le_bytes());
+    }
+
+    fn mov_imm64(&mut self, reg: u8, imm: u64) {
+        self.rex(true, 0, reg);
+        self.bytes(&[0xb8 | (reg & 7)]);
+        self.bytes(&imm.to_le_bytes());
+    }
+
+    fn jmp(&mut self, label: Label) {
+        self.bytes(&[0xe9]);
+        self.rel32(label);
+    }
+
+    fn jcc(&mut self, cc: u8, label: Label) {
+        self.bytes(&[0x0f, 0x80 | cc]);
+        self.rel32(label);
+    }
+
+    fn call(&mut self, label: Label) {
+        self.bytes(&[0xe8]);
+        self.rel32(label);
+    }
+
+    /// Calls a helper through `rax`.
+    fn call_helper(&mut self, helper: *const ()) {
+        self.mov_imm64(RAX, helper as u64);
+        self.reg(false, &[0xff], 2, RAX);
+    }
+
+    fn ret(&mut self) {
+        self.bytes(&[0xc3]);
+    }
+}
+
+/// A jump to the trap path of a function, which records the frame.
+struct Stub {
+    label: Label,
+    /// The status to return, or `None` if it's already in `eax`.
+    status: Option<u32>,
+    pc: usize,
+}
+
+struct Compiler<'a> {
+    asm: &'a mut Assembler,
+    code: &'a Code,
+    func_addr: FuncAddr,
+    func_addrs: &'a [FuncAddr],
+    direct: &'a dyn Fn(usize) -> Option<Label>,
+    mem_addr: MemAddr,
+    stubs: Vec<Stub>,
+    pc_labels: HashMap<usize, Label>,
+}
+
+impl Compiler<'_> {
+    fn function(&mut self) {
+        let local_count = self.code.param_count + self.code.locals.len();
+        let exhausted = self.asm.label();
+        let interrupted = self.asm.label();
+        let epilogue = self.asm.label();
+        let grow = self.asm.label();
+        let framed = self.asm.label();
+
+        // The three pushes and the return address keep `rsp` 16-byte aligned for calls.
+        self.asm.push(RBX);
+        self.asm.push(R12);
+        self.asm.push(R13);
+        self.asm.reg(true, &[0x89], RDI, R12);
+        self.asm.reg(true, &[0x89], RSI, RBX);
+
+        self.asm
+            .mem(false, &[0xff], 0, R12, offset_of!(Context, depth) as i32);
+        self.asm
+            .mem(false, &[0x8b], RAX, R12, offset_of!(Context, depth) as i32);
+        self.asm.mem(
+            false,
+            &[0x3b],
+            RAX,
+            R12,
+            offset_of!(Context, max_depth) as i32,
+        );
+        self.asm.jcc(CC_A, exhausted);
+        self.asm.mem(
+            true,
+            &[0x3b],
+            RSP,
+            R12,
+            offset_of!(Context, stack_limit) as i32,
+        );
+        self.asm.jcc(CC_B, exhausted);
+        let frame_size = self.code.frame_size() as i32 * 8;
+        self.asm.mem(true, &[0x8d], RAX, RBX, frame_size);
+        self.asm.mem(
+            true,
+            &[0x3b],
+            RAX,
+            R12,
+            offset_of!(Context, frames_end) as i32,
+        );
+        self.asm.jcc(CC_A, grow);
+        self.asm.bind(framed);
+        self.asm
+            .mem(true, &[0x8b], RAX, R12, offset_of!(Context, epoch) as i32);
+        self.asm.bytes(&[0x48, 0x8b, 0x00]);
+        self.asm.mem(
+            true,
+            &[0x3b],
+            RAX,
+            R12,
+            offset_of!(Context, deadline) as i32,
+        );
+        self.asm.jcc(CC_AE, interrupted);
+
+        // Declared locals start out zero whatever their type.
+        self.asm.reg(false, &[0x31], RAX, RAX);
+        for local in self.code.param_count..local_count {
+            self.asm.mem(true, &[0x89], RAX, RBX, local as i32 * 8);
+        }
+
+        for (pc, op) in self.code.ops.iter().enumerate() {
+            self.bind_pc(pc);
+            self.op(pc, op);
+        }
+        self.bind_pc(self.code.ops.len());
+
+        self.asm
+            .mem(false, &[0xff], 1, R12, offset_of!(Context, depth) as i32);
+        self.asm.reg(false, &[0x31], RAX, RAX);
+        self.asm.bind(epilogue);
+        self.asm.pop(R13);
+        self.asm.pop(R12);
+        self.asm.pop(RBX);
+        self.asm.ret();
+
+        // A function that can't be entered traps in its caller, like in the interpreter.
+        self.asm.bind(exhausted);
+        self.asm.mov_imm32(RAX, STACK_EXHAUSTED);
+        self.asm.jmp(epilogue);
+        self.asm.bind(interrupted);
+        self.asm.mov_imm32(RAX, INTERRUPTED);
+        self.asm.jmp(epilogue);
+        self.asm.bind(grow);
+        self.asm.reg(true, &[0x89], R12, RDI);
+        self.asm.reg(true, &[0x89], RBX, RSI);
+        self.asm.mov_imm32(RDX, self.code.frame_size() as u32);
+        self.asm.call_helper(grow_frames as *const ());
+        self.asm.reg(true, &[0x85], RAX, RAX);
+        self.asm.jcc(CC_E, exhausted);
+        self.asm.reg(true, &[0x89], RAX, RBX);
+        self.asm.jmp(framed);
+
+        let unwind_label = self.asm.label();
+        for stub in std::mem::take(&mut self.stubs) {
+            self.asm.bind(stub.label);
+            if let Some(status) = stub.status {
+                self.asm.mov_imm32(RAX, status);
+            }
+            self.asm.mov_imm32(RDX, stub.pc as u32);
+            self.asm.jmp(unwind_label);
+        }
+        self.asm.bind(unwind_label);
+        self.asm.reg(true, &[0x89], R12, RDI);
+        self.asm.reg(false, &[0x89], RAX, RSI);
+        self.asm.mov_imm32(RCX, self.func_addr);
+        self.asm.call_helper(unwind as *const ());
+        self.asm.jmp(epilogue);
+    }
+
+    fn op(&mut self, pc: usize, op: &Op) {
+        match op {
+            Op::LocalGet { local, dst } => {
+                self.asm.mem(true, &[0x8b], RAX, RBX, slot(*local));
+                self.asm.mem(true, &[0x89], RAX, RBX, slot(*dst));
+            }
+            Op::Const { dst, value } => {
+                match u32::try_from(*value) {
+                    Ok(value) => self.asm.mov_imm32(RAX, value),
+                    Err(_) => self.asm.mov_imm64(RAX, *value),
+                }
+                self.asm.mem(true, &[0x89], RAX, RBX, slot(*dst));
+            }
+            Op::Drop => {}
+            Op::Unary { instr, slot: at } => {
+                let at = slot(*at);
+                match instr {
+                    Instr::I32Eqz | Instr::I64Eqz => {
+                        let w = matches!(instr, Instr::I64Eqz);
+                        self.asm.mem(w, &[0x8b], RAX, RBX, at);
+                        self.asm.reg(w, &[0x85], RAX, RAX);
+                        self.set(CC_E, at);
+                    }
+                    Instr::I32Extend8S | Instr::I32Extend16S => {
+                        let opcode = match instr {
+                            Instr::I32Extend8S => 0xbe,
+                            _ => 0xbf,
+                        };
+                        self.asm.mem(false, &[0x8b], RAX, RBX, at);
+                        self.asm.reg(false, &[0x0f, opcode], RAX, RAX);
+                        self.asm.mem(true, &[0x89], RAX, RBX, at);
+                    }
+                    _ => unreachable!("unsupported instruction {:?}", instr),
+                }
+            }
+            Op::Binary { instr, lhs } => {
+                let (rhs, lhs) = (slot(lhs + 1), slot(*lhs));
+                if let Some((op, w)) = binary(instr) {
+                    self.asm.mem(w, &[0x8b], RAX, RBX, lhs);
+                    self.asm.mem(w, &[0x8b], RCX, RBX, rhs);
+                    match op {
+                        BinaryOp::Arith(opcode) => self.asm.reg(w, &[opcode], RCX, RAX),
+                        BinaryOp::Mul => self.asm.reg(w, &[0x0f, 0xaf], RAX, RCX),
+                        BinaryOp::Shift(ext) => self.asm.reg(w, &[0xd3], ext, RAX),
+                    }
+                    // 32-bit results are zero-extended, as in `Val::to_bits`.
+                    self.asm.mem(true, &[0x89], RAX, RBX, lhs);
+                } else if let Some((cc, w)) = compare(instr) {
+                    self.asm.mem(w, &[0x8b], RAX, RBX, lhs);
+                    self.asm.mem(w, &[0x8b], RCX, RBX, rhs);
+                    self.asm.reg(w, &[0x39], RCX, RAX);
+                    self.set(cc, lhs);
+                } else {
+                    unreachable!("unsupported instruction {:?}", instr);
+                }
+            }
+            Op::Load { instr, addr } => {
+                let (w, opcode, memarg, width) = load(instr).expect("supported load");
+                let addr = slot(*addr);
+                self.address(pc, addr, memarg, width);
+                self.asm.indexed(w, opcode, RAX);
+                self.asm.mem(true, &[0x89], RAX, RBX, addr);
+            }
+            Op::Store { instr, addr } => {
+                let (memarg, width) = store(instr).expect("supported store");
+                let (value, addr) = (slot(addr + 1), slot(*addr));
+                self.address(pc, addr, memarg, width);
+                self.asm.mem(true, &[0x8b], RCX, RBX, value);
+                match width {
+                    1 => self.asm.indexed(false, &[0x88], RCX),
+                    2 => {
+                        self.asm.bytes(&[0x66]);
+                        self.asm.indexed(false, &[0x89], RCX);
+                    }
+                    _ => self.asm.indexed(width == 8, &[0x89], RCX),
+                }
+            }
+            Op::MemorySize { dst } => {
+                self.call_memory_helper(memory_size as *const (), slot(*dst));
+            }
+            Op::MemoryGrow { delta } => {
+                self.call_memory_helper(memory_grow as *const (), slot(*delta));
+            }
+            Op::If { cond, else_pc } => {
+                self.asm.mem(true, &[0x8b], RAX, RBX, slot(*cond));
+                self.asm.reg(true, &[0x85], RAX, RAX);
+                let label = self.pc_label(*else_pc);
+                self.asm.jcc(CC_E, label);
+            }
+            Op::Jump(target) => {
+                let label = self.pc_label(*target);
+                self.asm.jmp(label);
+            }
+            Op::Call { func_idx, args } => self.call(pc, *func_idx as usize, slot(*args)),
+            Op::Return { from, count } => {
+                for i in 0..*count {
+                    self.asm.mem(true, &[0x8b], RAX, RBX, slot(from + i));
+                    self.asm.mem(true, &[0x89], RAX, RBX, slot(i));
+                }
+            }
+            Op::TableSize { .. } | Op::TableGrow { .. } => {
+                unreachable!("unsupported op {:?}", op)
+            }
+        }
+    }
+
+    fn call(&mut self, pc: usize, idx: usize, args: i32) {
+        // The callee can move the frames, so the frame is found again by its offset, which is
+        // pushed twice to keep `rsp` aligned.
+        let frames_base = offset_of!(Context, frames_base) as i32;
+        self.asm.reg(true, &[0x89], RBX, RAX);
+        self.asm.mem(true, &[0x2b], RAX, R12, frames_base);
+        self.asm.push(RAX);
+        self.asm.push(RAX);
+        match (self.direct)(idx) {
+            Some(label) => {
+                self.asm.reg(true, &[0x89], R12, RDI);
+                self.asm.mem(true, &[0x8d], RSI, RBX, args);
+                self.asm.call(label);
+            }
+            None => {
+                self.asm.reg(true, &[0x89], R12, RDI);
+                self.asm.mov_imm32(RSI, self.func_addrs[idx]);
+                self.asm.mem(true, &[0x8d], RDX, RBX, args);
+                self.asm.mov_imm32(RCX, self.func_addr);
+                self.asm.call_helper(call as *const ());
+            }
+        }
+        self.asm.pop(RBX);
+        self.asm.pop(RBX);
+        self.asm.mem(true, &[0x03], RBX, R12, frames_base);
+        self.asm.reg(false, &[0x85], RAX, RAX);
+        self.trap_if(CC_NE, None, pc);
+    }
+
+    fn call_memory_helper(&mut self, helper: *const (), slot: i32) {
+        self.asm.reg(true, &[0x89], R12, RDI);
+        self.asm.mov_imm32(RSI, self.mem_addr);
+        self.asm.mem(true, &[0x8d], RDX, RBX, slot);
+        self.asm.call_helper(helper);
+    }
+
+    /// Computes the effective address of an access into `rax` and the memory base into
+    /// `rdx`, trapping if the access is out of bounds.
+    fn address(&mut self, pc: usize, slot: i32, memarg: &MemArg, width: u8) {
+        let view = self.mem_addr as i32 * std::mem::size_of::<MemView>() as i32;
+        self.asm.mem(false, &[0x8b], RAX, RBX, slot);
+        self.asm.mov_imm32(RCX, memarg.offset);
+        self.asm.reg(true, &[0x01], RCX, RAX);
+        self.asm.mem(true, &[0x8d], RCX, RAX, width as i32);
+        self.asm
+            .mem(true, &[0x8b], RDX, R12, offset_of!(Context, mems) as i32);
+        self.asm.mem(
+            true,
+            &[0x3b],
+            RCX,
+            RDX,
+            view + offset_of!(MemView, len) as i32,
+        );
+        self.trap_if(CC_A, Some(MEMORY_OUT_OF_BOUNDS), pc);
+        self.asm.mem(
+            true,
+            &[0x8b],
+            RDX,
+            RDX,
+            view + offset_of!(MemView, base) as i32,
+        );
+    }
+
+    /// Stores the condition as 0 or 1 into the slot.
+    fn set(&mut self, cc: u8, slot: i32) {
+        self.asm.reg(false, &[0x0f, 0x90 | cc], 0, RAX);
+        self.asm.reg(false, &[0x0f, 0xb6], RAX, RAX);
+        self.asm.mem(true, &[0x89], RAX, RBX, slot);
+    }
+
+    fn trap_if(&mut self, cc: u8, status: Option<u32>, pc: usize) {
+        let label = self.asm.label();
+        self.asm.jcc(cc, label);
+        self.stubs.push(Stub { label, status, pc });
+    }
+
+    fn pc_label(&mut self, pc: usize) -> Label {
+        if let Some(label) = self.pc_labels.get(&pc) {
+            return *label;
+        }
+        let label = self.asm.label();
+        self.pc_labels.insert(pc, label);
+        label
+    }
+
+    fn bind_pc(&mut self, pc: usize) {
+        if let Some(label) = self.pc_labels.get(&pc) {
+            self.asm.bind(*label);
+        }
+    }
+}
+
+/// The frame offset of a slot.
+fn slot(slot: Slot) -> i32 {
+    slot as i32 * 8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::engine::{Backend, Config, Engine};
+    use crate::execution::host::HostFunc;
+    use crate::execution::instance::{
+        alloc_host_func, instantiate, invoke, ExternVal, InvokeError,
+    };
+    use crate::structure::modules::module::Module;
+    use crate::structure::types::function::FuncType;
+    use crate::structure::types::value::{NumType, ValType};
+    use crate::text;
+
+    #[test]
+    fn test_assembler() {
+        let mut asm = Assembler::default();
+        asm.mem(true, &[0x8b], RAX, RBX, 8);
+        asm.mem(false, &[0xff], 0, R12, 16);
+        asm.indexed(true, &[0x0f, 0xbe], RAX);
+        asm.reg(true, &[0x89], R12, RDI);
+        asm.push(R13);
+        let label = asm.label();
+        asm.jcc(CC_A, label);
+        asm.bind(label);
+        assert_eq!(
+            asm.finish(),
+            [
+                0x48, 0x8b, 0x83, 8, 0, 0, 0, // mov rax, [rbx + 8]
+                0x41, 0xff, 0x84, 0x24, 16, 0, 0, 0, // inc dword [r12 + 16]
+                0x48, 0x0f, 0xbe, 0x04, 0x02, // movsx rax, byte [rdx + rax]
+                0x4c, 0x89, 0xe7, // mov rdi, r12
+                0x41, 0x55, // push r13
+                0x0f, 0x87, 0, 0, 0, 0, // ja +0
+            ]
+        );
+    }
+
+    const PROGRAM: &str = r#"(module
+        (import "host" "double" (func $double (param i32) (result i32)))
+        (memory 1)
+        (func $fib (export "fib") (param i32) (result i32)
+            (if (result i32) (i32.lt_s (local.get 0) (i32.const 2))
+                (then (local.get 0))
+                (else (i32.add
+                    (call $fib (i32.sub (local.get 0) (i32.const 1)))
+                    (call $fib (i32.sub (local.get 0) (i32.const 2)))))))
+        (func (export "bits") (param i64 i64) (result i64)
+            (i32.store8 (i32.const 3) (i32.const 200))
+            (i64.sub
+                (i64.mul (local.get 0) (local.get 1))
+                (i64.load8_s (i32.const 3))))
+        (func (export "memory") (param i32) (result i64)
+            (i32.store16 (i32.const 8) (call $double (local.get 0)))
+            (i32.store8 (i32.const 3) (i32.const 200))
+            (drop (memory.grow (i32.const 1)))
+            (i64.mul (i64.load32_u (i32.const 8)) (i64.const 3)))
+        (func (export "size") (result i32) (memory.size))
+        (func (export "oob") (param i32) (result i32)
+            (call $double (i32.load (local.get 0))))
+        (func $interpreted (param i32) (result i32)
+            (i32.div_s (local.get 0) (i32.const 2)))
+        (func (export "mixed") (param i32) (result i32)
+            (i32.add (call $interpreted (local.get 0)) (i32.const 1)))
+        (func $forever (export "forever") (call $forever)))"#;
+
+    fn run(backend: Backend, name: &str, args: Vec<Val>) -> Result<Vec<Val>, Trap> {
+        let module = text::parse(PROGRAM).unwrap();
+        let mut store = Store::new(&Engine::with_config(Config {
+            backend,
+            ..Default::default()
+        }));
+        run_in(&mut store, module, name, args)
+    }
+
+    fn run_in(
+        store: &mut Store,
+        module: Module,
+        name: &str,
+        args: Vec<Val>,
+    ) -> Result<Vec<Val>, Trap> {
+        let double = alloc_host_func(
+            store,
+            FuncType {
+                parameters: vec![ValType::NumType(NumType::I32)],
+                results: vec![ValType::NumType(NumType::I32)],
+            },
+            HostFunc::new(|_, args| match args[0] {
+                Val::I32(x) => Ok(vec![Val::I32(x * 2)]),
+                _ => unreachable!(),
+            }),
+        );
+        let externvals = [ExternVal::Func(double)];
+        let module_inst = instantiate(store, module, &externvals).unwrap();
+        invoke(store, &module_inst, name.to_string(), args).map_err(InvokeError::unwrap_trap)
+    }
+
+    #[test]
+    fn test_backends_agree() {
+        let cases = [
+            ("fib", vec![Val::I32(15)]),
+            ("bits", vec![Val::I64(-3), Val::I64(5)]),
+            ("memory", vec![Val::I32(7)]),
+            ("size", vec![]),
+            ("oob", vec![Val::I32(65535)]),
+            ("mixed", vec![Val::I32(9)]),
+            ("forever", vec![]),
+        ];
+        for (name, args) in cases {
+            let expected = run(Backend::Stack, name, args.clone());
+            assert_eq!(run(Backend::Jit, name, args), expected, "{}", name);
+        }
+        assert_eq!(
+            run(Backend::Jit, "fib", vec![Val::I32(20)]),
+            Ok(vec![Val::I32(6765)])
+        );
+        assert_eq!(
+            run(Backend::Jit, "bits", vec![Val::I64(2), Val::I64(3)]),
+            Ok(vec![Val::I64(62)])
+        );
+        let trap = run(Backend::Jit, "oob", vec![Val::I32(65535)]).unwrap_err();
+        assert_eq!(trap.kind, TrapKind::MemoryOutOfBounds);
+        assert_eq!(trap.backtrace.len(), 1);
+        let trap = run(Backend::Jit, "forever", vec![]).unwrap_err();
+        assert_eq!(trap.kind, TrapKind::StackExhausted);
+    }
+
+    #[test]
+    fn test_compiled() {
+        let module = text::parse(PROGRAM).unwrap();
+        let mut store = Store::new(&Engine::with_config(Config {
+            backend: Backend::Jit,
+            ..Default::default()
+        }));
+        run_in(&mut store, module, "size", vec![]).unwrap();
+        let compiled: Vec<_> = store.funcs[1..]
+            .iter()
+            .map(|func_inst| matches!(func_inst, FuncInst::Module { jit: Some(_), .. }))
+            .collect();
+        // Only the function with a division is left to the interpreter.
+        assert_eq!(compiled, [true, true, true, true, true, false, true, true]);
+    }
+
+    #[test]
+    fn test_frames_are_reused() {
+        let mut store = Store::new(&Engine::with_config(Config {
+            backend: Backend::Jit,
+            ..Default::default()
+        }));
+        run_in(
+            &mut store,
+            text::parse(PROGRAM).unwrap(),
+            "fib",
+            vec![Val::I32(5)],
+        )
+        .unwrap();
+        let frames = store.jit_frames.as_ptr();
+        let len = store.jit_frames.len();
+        assert!(len < 1_000, "{}", len);
+        run_in(
+            &mut store,
+            text::parse(PROGRAM).unwrap(),
+            "fib",
+            vec![Val::I32(5)],
+        )
+        .unwrap();
+        assert_eq!(store.jit_frames.as_ptr(), frames);
+        assert_eq!(store.jit_frames.len(), len);
+    }
+
+    #[test]
+    fn test_frames_grow() {
+        let module = text::parse(
+            r#"(module
+                (func $count (export "count") (param i32) (result i32)
+                    (if (result i32) (i32.eqz (local.get 0))
+                        (then (i32.const 0))
+                        (else (i32.add
+                            (call $count (i32.sub (local.get 0) (i32.const 1)))
+                            (i32.const 1))))))"#,
+        )
+        .unwrap();
+        let mut store = Store::new(&Engine::with_config(Config {
+            backend: Backend::Jit,
+            ..Default::default()
+        }));
+        let module_inst = instantiate(&mut store, module, &[]).unwrap();
+        let count = |store: &mut Store, n| {
+            invoke(store, &module_inst, "count".to_string(), vec![Val::I32(n)])
+        };
+
+        // Callers find their frames again after the frames move.
+        assert_eq!(count(&mut store, 1), Ok(vec![Val::I32(1)]));
+        let len = store.jit_frames.len();
+        assert_eq!(count(&mut store, 5_000), Ok(vec![Val::I32(5_000)]));
+        assert!(store.jit_frames.len() > len);
+        assert!(store.jit_frames.len() <= store.stack_limits.max_values);
+
+        store.stack_limits.max_values = 1_000;
+        store.jit_frames = Vec::new();
+        let trap = count(&mut store, 5_000).unwrap_err().unwrap_trap();
+        assert_eq!(trap.kind, TrapKind::StackExhausted);
+        assert!(store.jit_frames.len() <= 1_000);
+    }
+
+    #[test]
+    fn test_stack_exhausted() {
+        let module = text::parse(
+            r#"(module
+                (func $count (export "count") (param i32) (result i32)
+                    (if (result i32) (i32.eqz (local.get 0))
+                        (then (i32.const 0))
+                        (else (i32.add
+                            (call $count (i32.sub (local.get 0) (i32.const 1)))
+                            (i32.const 1))))))"#,
+        )
+        .unwrap();
+        let mut store = Store::new(&Engine::with_config(Config {
+            backend: Backend::Jit,
+            ..Default::default()
+        }));
+        let module_inst = instantiate(&mut store, module, &[]).unwrap();
+        let mut count = |n| {
+            invoke(
+                &mut store,
+                &module_inst,
+                "count".to_string(),
+                vec![Val::I32(n)],
+            )
+        };
+        assert_eq!(count(9_999), Ok(vec![Val::I32(9_999)]));
+        let trap = count(10_000).unwrap_err().unwrap_trap();
+        assert_eq!(trap.kind, TrapKind::StackExhausted);
+        assert_eq!(trap.backtrace.len(), 10_000);
+    }
+}
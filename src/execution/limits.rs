@@ -0,0 +1,155 @@
+/// Decides whether guests may allocate more resources, so that untrusted modules can't exhaust
+/// the host.
+pub trait ResourceLimiter {
+    /// Returns whether a memory may grow from `current` to `desired` bytes. `maximum` is the
+    /// maximum declared by the memory type, if any. New memories grow from 0.
+    fn memory_growing(&mut self, current: usize, desired: usize, maximum: Option<usize>) -> bool;
+
+    /// Returns whether a table may grow from `current` to `desired` elements. `maximum` is the
+    /// maximum declared by the table type, if any. New tables grow from 0.
+    fn table_growing(&mut self, _current: usize, desired: usize, _maximum: Option<usize>) -> bool {
+        desired <= DEFAULT_TABLE_ELEMENTS
+    }
+
+    /// The maximum number of module instances in a store.
+    fn instances(&self) -> usize {
+        DEFAULT_INSTANCE_LIMIT
+    }
+}
+
+pub const DEFAULT_TABLE_ELEMENTS: usize = 10_000;
+pub const DEFAULT_INSTANCE_LIMIT: usize = 10_000;
+
+/// A `ResourceLimiter` with fixed caps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoreLimits {
+    /// The maximum size of each memory in bytes.
+    pub memory_size: Option<usize>,
+    /// The maximum number of elements of each table.
+    pub table_elements: usize,
+    pub instances: usize,
+}
+
+impl Default for StoreLimits {
+    fn default() -> Self {
+        Self {
+            memory_size: None,
+            table_elements: DEFAULT_TABLE_ELEMENTS,
+            instances: DEFAULT_INSTANCE_LIMIT,
+        }
+    }
+}
+
+impl ResourceLimiter for StoreLimits {
+    fn memory_growing(&mut self, _current: usize, desired: usize, _maximum: Option<usize>) -> bool {
+        self.memory_size.is_none_or(|size| desired <= size)
+    }
+
+    fn table_growing(&mut self, _current: usize, desired: usize, _maximum: Option<usize>) -> bool {
+        desired <= self.table_elements
+    }
+
+    fn instances(&self) -> usize {
+        self.instances
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::instance::{instantiate, invoke, InstantiationError};
+    use crate::execution::structure::{Store, Val, PAGE_SIZE};
+    use crate::text;
+
+    const GROW: &str = r#"(module
+        (memory 1)
+        (func (export "grow") (param i32) (result i32) (memory.grow (local.get 0))))"#;
+
+    #[test]
+    fn test_memory_size() {
+        let mut store = Store::default();
+        store.set_limiter(StoreLimits {
+            memory_size: Some(3 * PAGE_SIZE),
+            ..Default::default()
+        });
+        let module_inst = instantiate(&mut store, text::parse(GROW).unwrap(), &[]).unwrap();
+        let mut grow = |delta| invoke(&mut store, &module_inst, "grow".to_string(), vec![delta]);
+        assert_eq!(grow(Val::I32(3)), Ok(vec![Val::I32(-1)]));
+        assert_eq!(grow(Val::I32(2)), Ok(vec![Val::I32(1)]));
+        assert_eq!(grow(Val::I32(1)), Ok(vec![Val::I32(-1)]));
+        assert_eq!(store.mems[0].size(), 3);
+
+        let module = text::parse("(module (memory 4))").unwrap();
+        assert!(matches!(
+            instantiate(&mut store, module, &[]),
+            Err(InstantiationError::ResourceLimitExceeded(_))
+        ));
+    }
+
+    #[test]
+    fn test_table_elements() {
+        let mut store = Store::default();
+        store.set_limiter(StoreLimits {
+            table_elements: 3,
+            ..Default::default()
+        });
+        let module = text::parse(
+            r#"(module
+                (table 1 funcref)
+                (func (export "grow") (param i32) (result i32)
+                    (table.grow (ref.null func) (local.get 0))))"#,
+        )
+        .unwrap();
+        let module_inst = instantiate(&mut store, module, &[]).unwrap();
+        let mut grow = |delta| invoke(&mut store, &module_inst, "grow".to_string(), vec![delta]);
+        assert_eq!(grow(Val::I32(3)), Ok(vec![Val::I32(-1)]));
+        assert_eq!(grow(Val::I32(2)), Ok(vec![Val::I32(1)]));
+        assert_eq!(grow(Val::I32(1)), Ok(vec![Val::I32(-1)]));
+        assert_eq!(store.tables[0].size(), 3);
+
+        let module = text::parse("(module (table 4 funcref))").unwrap();
+        assert!(matches!(
+            instantiate(&mut store, module, &[]),
+            Err(InstantiationError::ResourceLimitExceeded(_))
+        ));
+    }
+
+    #[test]
+    fn test_default_table_elements() {
+        struct Memories;
+
+        impl ResourceLimiter for Memories {
+            fn memory_growing(&mut self, _: usize, _: usize, _: Option<usize>) -> bool {
+                true
+            }
+        }
+
+        let mut store = Store::default();
+        store.set_limiter(Memories);
+        let module = text::parse(&format!(
+            "(module (table {} funcref))",
+            DEFAULT_TABLE_ELEMENTS + 1
+        ))
+        .unwrap();
+        assert!(matches!(
+            instantiate(&mut store, module, &[]),
+            Err(InstantiationError::ResourceLimitExceeded(_))
+        ));
+    }
+
+    #[test]
+    fn test_instances() {
+        let mut store = Store::default();
+        store.set_limiter(StoreLimits {
+            instances: 2,
+            ..Default::default()
+        });
+        for _ in 0..2 {
+            instantiate(&mut store, text::parse("(module)").unwrap(), &[]).unwrap();
+        }
+        assert!(matches!(
+            instantiate(&mut store, text::parse("(module)").unwrap(), &[]),
+            Err(InstantiationError::ResourceLimitExceeded(_))
+        ));
+    }
+}
@@ -0,0 +1,63 @@
+use crate::execution::host::HostFunc;
+use crate::execution::instance::{
+    alloc_host_func, instantiate, ExternVal, InstantiationError, ModuleInst,
+};
+use crate::execution::structure::Store;
+use crate::structure::modules::module::Module;
+use crate::structure::types::function::FuncType;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Resolves the imports of a module by their module and field names.
+#[derive(Debug, Default)]
+pub struct Linker {
+    definitions: HashMap<(String, String), ExternVal>,
+}
+
+impl Linker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn define(&mut self, module: &str, name: &str, externval: ExternVal) {
+        self.definitions
+            .insert((module.to_string(), name.to_string()), externval);
+    }
+
+    pub fn func(
+        &mut self,
+        store: &mut Store,
+        module: &str,
+        name: &str,
+        type_: FuncType,
+        code: HostFunc,
+    ) {
+        let func_addr = alloc_host_func(store, type_, code);
+        self.define(module, name, ExternVal::Func(func_addr));
+    }
+
+    pub fn get(&self, module: &str, name: &str) -> Option<&ExternVal> {
+        self.definitions
+            .get(&(module.to_string(), name.to_string()))
+    }
+
+    pub fn instantiate(
+        &self,
+        store: &mut Store,
+        module: Module,
+    ) -> Result<Rc<ModuleInst>, InstantiationError> {
+        let mut externvals = Vec::new();
+        for import in &module.imports {
+            match self.get(&import.module, &import.name) {
+                Some(externval) => externvals.push(externval.clone()),
+                None => {
+                    return Err(InstantiationError::UnknownImport(
+                        import.module.clone(),
+                        import.name.clone(),
+                    ))
+                }
+            }
+        }
+        instantiate(store, module, &externvals)
+    }
+}
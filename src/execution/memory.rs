@@ -0,0 +1,228 @@
+use crate::execution::limits::ResourceLimiter;
+use crate::execution::structure::MemInst;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MemoryAccessError {
+    OutOfBounds,
+    InvalidUtf8,
+}
+
+impl fmt::Display for MemoryAccessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MemoryAccessError::OutOfBounds => write!(f, "out of bounds memory access"),
+            MemoryAccessError::InvalidUtf8 => write!(f, "invalid utf-8 string"),
+        }
+    }
+}
+
+/// A bounds-checked view of a linear memory for use by the host.
+pub struct Memory<'a> {
+    inst: &'a mut MemInst,
+    limiter: Option<&'a mut dyn ResourceLimiter>,
+}
+
+impl fmt::Debug for Memory<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Memory")
+            .field("inst", &self.inst)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> Memory<'a> {
+    pub fn new(inst: &'a mut MemInst) -> Memory<'a> {
+        Self {
+            inst,
+            limiter: None,
+        }
+    }
+
+    /// Creates a view whose growth is checked by `limiter`.
+    pub fn with_limiter(
+        inst: &'a mut MemInst,
+        limiter: Option<&'a mut dyn ResourceLimiter>,
+    ) -> Memory<'a> {
+        Self { inst, limiter }
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.inst.data
+    }
+
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        &mut self.inst.data
+    }
+
+    /// Returns the size of the memory in pages.
+    pub fn size(&self) -> u32 {
+        self.inst.size()
+    }
+
+    /// Grows the memory by `delta` pages and returns the previous size in pages.
+    pub fn grow(&mut self, delta: u32) -> Option<u32> {
+        let limiter = self
+            .limiter
+            .as_deref_mut()
+            .map(|l| l as &mut dyn ResourceLimiter);
+        self.inst.grow(delta, limiter)
+    }
+
+    pub fn read(&self, offset: usize, buf: &mut [u8]) -> Result<(), MemoryAccessError> {
+        let bytes = self.slice(offset, buf.len())?;
+        buf.copy_from_slice(bytes);
+        Ok(())
+    }
+
+    pub fn write(&mut self, offset: usize, buf: &[u8]) -> Result<(), MemoryAccessError> {
+        let end = Self::end(offset, buf.len(), self.inst.data.len())?;
+        self.inst.data[offset..end].copy_from_slice(buf);
+        Ok(())
+    }
+
+    /// Reads the UTF-8 string stored at `ptr` with `len` bytes.
+    pub fn read_str(&self, ptr: usize, len: usize) -> Result<String, MemoryAccessError> {
+        let bytes = self.slice(ptr, len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| MemoryAccessError::InvalidUtf8)
+    }
+
+    pub fn read_u8(&self, offset: usize) -> Result<u8, MemoryAccessError> {
+        Ok(u8::from_le_bytes(self.read_array(offset)?))
+    }
+
+    pub fn read_u16(&self, offset: usize) -> Result<u16, MemoryAccessError> {
+        Ok(u16::from_le_bytes(self.read_array(offset)?))
+    }
+
+    pub fn read_u32(&self, offset: usize) -> Result<u32, MemoryAccessError> {
+        Ok(u32::from_le_bytes(self.read_array(offset)?))
+    }
+
+    pub fn read_u64(&self, offset: usize) -> Result<u64, MemoryAccessError> {
+        Ok(u64::from_le_bytes(self.read_array(offset)?))
+    }
+
+    pub fn read_i32(&self, offset: usize) -> Result<i32, MemoryAccessError> {
+        Ok(i32::from_le_bytes(self.read_array(offset)?))
+    }
+
+    pub fn read_i64(&self, offset: usize) -> Result<i64, MemoryAccessError> {
+        Ok(i64::from_le_bytes(self.read_array(offset)?))
+    }
+
+    pub fn write_u8(&mut self, offset: usize, value: u8) -> Result<(), MemoryAccessError> {
+        self.write(offset, &value.to_le_bytes())
+    }
+
+    pub fn write_u16(&mut self, offset: usize, value: u16) -> Result<(), MemoryAccessError> {
+        self.write(offset, &value.to_le_bytes())
+    }
+
+    pub fn write_u32(&mut self, offset: usize, value: u32) -> Result<(), MemoryAccessError> {
+        self.write(offset, &value.to_le_bytes())
+    }
+
+    pub fn write_u64(&mut self, offset: usize, value: u64) -> Result<(), MemoryAccessError> {
+        self.write(offset, &value.to_le_bytes())
+    }
+
+    pub fn write_i32(&mut self, offset: usize, value: i32) -> Result<(), MemoryAccessError> {
+        self.write(offset, &value.to_le_bytes())
+    }
+
+    pub fn write_i64(&mut self, offset: usize, value: i64) -> Result<(), MemoryAccessError> {
+        self.write(offset, &value.to_le_bytes())
+    }
+
+    fn read_array<const N: usize>(&self, offset: usize) -> Result<[u8; N], MemoryAccessError> {
+        let mut bytes = [0; N];
+        self.read(offset, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Returns the `len` bytes at `offset`, so that the host can use them without copying.
+    pub fn slice(&self, offset: usize, len: usize) -> Result<&[u8], MemoryAccessError> {
+        let end = Self::end(offset, len, self.inst.data.len())?;
+        Ok(&self.inst.data[offset..end])
+    }
+
+    pub fn slice_mut(&mut self, offset: usize, len: usize) -> Result<&mut [u8], MemoryAccessError> {
+        let end = Self::end(offset, len, self.inst.data.len())?;
+        Ok(&mut self.inst.data[offset..end])
+    }
+
+    fn end(offset: usize, len: usize, size: usize) -> Result<usize, MemoryAccessError> {
+        match offset.checked_add(len) {
+            Some(end) if end <= size => Ok(end),
+            _ => Err(MemoryAccessError::OutOfBounds),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::structure::PAGE_SIZE;
+    use crate::structure::types::memory::{Limits, MemType};
+
+    fn mem_inst(min: u32, max: Option<u32>) -> MemInst {
+        MemInst::new(MemType(Limits { min, max }))
+    }
+
+    #[test]
+    fn test_read_write() {
+        let mut inst = mem_inst(1, None);
+        let mut memory = Memory::new(&mut inst);
+
+        memory.write(8, b"hello").unwrap();
+        let mut buf = [0; 5];
+        memory.read(8, &mut buf).unwrap();
+        assert_eq!(&buf, b"hello");
+        assert_eq!(memory.read_str(8, 5), Ok("hello".to_string()));
+        assert_eq!(&memory.data()[8..13], b"hello");
+
+        memory.write_u32(16, 0xdeadbeef).unwrap();
+        assert_eq!(&memory.data()[16..20], &[0xef, 0xbe, 0xad, 0xde]);
+        assert_eq!(memory.read_u32(16), Ok(0xdeadbeef));
+        memory.write_i64(24, -2).unwrap();
+        assert_eq!(memory.read_i64(24), Ok(-2));
+    }
+
+    #[test]
+    fn test_out_of_bounds() {
+        let mut inst = mem_inst(1, None);
+        let mut memory = Memory::new(&mut inst);
+
+        let mut buf = [0; 4];
+        assert_eq!(
+            memory.read(PAGE_SIZE - 3, &mut buf),
+            Err(MemoryAccessError::OutOfBounds)
+        );
+        assert_eq!(
+            memory.write(usize::MAX, &buf),
+            Err(MemoryAccessError::OutOfBounds)
+        );
+        assert_eq!(memory.read_u64(PAGE_SIZE - 8), Ok(0));
+        assert_eq!(
+            memory.read_u64(PAGE_SIZE - 7),
+            Err(MemoryAccessError::OutOfBounds)
+        );
+
+        memory.write(0, &[0xff, 0xfe]).unwrap();
+        assert_eq!(memory.read_str(0, 2), Err(MemoryAccessError::InvalidUtf8));
+    }
+
+    #[test]
+    fn test_grow() {
+        let mut inst = mem_inst(1, Some(2));
+        let mut memory = Memory::new(&mut inst);
+
+        assert_eq!(memory.size(), 1);
+        assert_eq!(memory.grow(1), Some(1));
+        assert_eq!(memory.size(), 2);
+        assert_eq!(memory.data().len(), 2 * PAGE_SIZE);
+        assert_eq!(memory.grow(1), None);
+        assert_eq!(memory.size(), 2);
+    }
+}
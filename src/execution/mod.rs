@@ -1,2 +1,15 @@
+pub mod compile;
+pub mod engine;
+pub mod fuel;
+pub mod host;
 pub mod instance;
+#[cfg(feature = "jit")]
+pub mod jit;
+pub mod limits;
+pub mod linker;
+pub mod memory;
+mod numeric;
+pub mod register;
 pub mod structure;
+pub mod trap;
+pub mod value;
@@ -0,0 +1,280 @@
+use crate::execution::trap::{Trap, TrapKind};
+use crate::structure::instructions::expression::Instr;
+
+// The numeric instructions on untyped 64-bit slots, as in `Val::to_bits`, so that every
+// backend computes the same results. Validation guarantees the types of the operands.
+
+/// Computes an instruction with one operand.
+pub(crate) fn unary(instr: &Instr, x: u64) -> u64 {
+    let (i32_, i64_, f32_, f64_) = (x as i32, x as i64, f32_of(x), f64_of(x));
+    match instr {
+        Instr::I32Eqz => bool_(i32_ == 0),
+        Instr::I64Eqz => bool_(i64_ == 0),
+        Instr::I32Clz => i32_.leading_zeros() as u64,
+        Instr::I32Ctz => i32_.trailing_zeros() as u64,
+        Instr::I32Popcnt => i32_.count_ones() as u64,
+        Instr::I32Extend8S => i32_bits(i32_ as i8 as i32),
+        Instr::I32Extend16S => i32_bits(i32_ as i16 as i32),
+        Instr::I64Clz => i64_.leading_zeros() as u64,
+        Instr::I64Ctz => i64_.trailing_zeros() as u64,
+        Instr::I64Popcnt => i64_.count_ones() as u64,
+        // `abs` and `neg` only change the sign bit, even of a NaN.
+        Instr::F32Abs => x & 0x7fff_ffff,
+        Instr::F32Neg => (x ^ 0x8000_0000) & 0xffff_ffff,
+        Instr::F32Ceil => f32_bits(f32_.ceil()),
+        Instr::F32Floor => f32_bits(f32_.floor()),
+        Instr::F32Trunc => f32_bits(f32_.trunc()),
+        Instr::F32Nearest => f32_bits(f32_.round_ties_even()),
+        Instr::F32Sqrt => f32_bits(f32_.sqrt()),
+        Instr::F64Abs => x & 0x7fff_ffff_ffff_ffff,
+        Instr::F64Neg => x ^ 0x8000_0000_0000_0000,
+        Instr::F64Ceil => f64_bits(f64_.ceil()),
+        Instr::F64Floor => f64_bits(f64_.floor()),
+        Instr::F64Trunc => f64_bits(f64_.trunc()),
+        Instr::F64Nearest => f64_bits(f64_.round_ties_even()),
+        Instr::F64Sqrt => f64_bits(f64_.sqrt()),
+        _ => unreachable!("{:?} is not a unary numeric instruction", instr),
+    }
+}
+
+/// Computes an instruction with two operands, which traps on integer division by zero and on
+/// signed division overflow.
+pub(crate) fn binary(instr: &Instr, lhs: u64, rhs: u64) -> Result<u64, Trap> {
+    let (a32, b32, a64, b64) = (lhs as i32, rhs as i32, lhs as i64, rhs as i64);
+    let (u32a, u32b) = (lhs as u32, rhs as u32);
+    let (f32a, f32b, f64a, f64b) = (f32_of(lhs), f32_of(rhs), f64_of(lhs), f64_of(rhs));
+    let result = match instr {
+        Instr::I32Eq => bool_(a32 == b32),
+        Instr::I32Ne => bool_(a32 != b32),
+        Instr::I32LtS => bool_(a32 < b32),
+        Instr::I32LtU => bool_(u32a < u32b),
+        Instr::I32GtS => bool_(a32 > b32),
+        Instr::I32GtU => bool_(u32a > u32b),
+        Instr::I32LeS => bool_(a32 <= b32),
+        Instr::I32LeU => bool_(u32a <= u32b),
+        Instr::I32GeS => bool_(a32 >= b32),
+        Instr::I32GeU => bool_(u32a >= u32b),
+        Instr::I64Eq => bool_(lhs == rhs),
+        Instr::I64Ne => bool_(lhs != rhs),
+        Instr::I64LtS => bool_(a64 < b64),
+        Instr::I64LtU => bool_(lhs < rhs),
+        Instr::I64GtS => bool_(a64 > b64),
+        Instr::I64GtU => bool_(lhs > rhs),
+        Instr::I64LeS => bool_(a64 <= b64),
+        Instr::I64LeU => bool_(lhs <= rhs),
+        Instr::I64GeS => bool_(a64 >= b64),
+        Instr::I64GeU => bool_(lhs >= rhs),
+        Instr::F32Eq => bool_(f32a == f32b),
+        Instr::F32Ne => bool_(f32a != f32b),
+        Instr::F32Lt => bool_(f32a < f32b),
+        Instr::F32Gt => bool_(f32a > f32b),
+        Instr::F32Le => bool_(f32a <= f32b),
+        Instr::F32Ge => bool_(f32a >= f32b),
+        Instr::F64Eq => bool_(f64a == f64b),
+        Instr::F64Ne => bool_(f64a != f64b),
+        Instr::F64Lt => bool_(f64a < f64b),
+        Instr::F64Gt => bool_(f64a > f64b),
+        Instr::F64Le => bool_(f64a <= f64b),
+        Instr::F64Ge => bool_(f64a >= f64b),
+        Instr::I32Add => i32_bits(a32.wrapping_add(b32)),
+        Instr::I32Sub => i32_bits(a32.wrapping_sub(b32)),
+        Instr::I32Mul => i32_bits(a32.wrapping_mul(b32)),
+        Instr::I32DivS => {
+            divisor(b32 == 0, a32 == i32::MIN && b32 == -1)?;
+            i32_bits(a32 / b32)
+        }
+        Instr::I32DivU => {
+            divisor(b32 == 0, false)?;
+            (u32a / u32b) as u64
+        }
+        // The remainder of `MIN / -1` is 0, although the quotient overflows.
+        Instr::I32RemS => {
+            divisor(b32 == 0, false)?;
+            i32_bits(a32.wrapping_rem(b32))
+        }
+        Instr::I32RemU => {
+            divisor(b32 == 0, false)?;
+            (u32a % u32b) as u64
+        }
+        Instr::I32And => i32_bits(a32 & b32),
+        Instr::I32Or => i32_bits(a32 | b32),
+        Instr::I32Xor => i32_bits(a32 ^ b32),
+        // Shift and rotate counts are taken modulo the bit width.
+        Instr::I32Shl => i32_bits(a32.wrapping_shl(u32b)),
+        Instr::I32ShrS => i32_bits(a32.wrapping_shr(u32b)),
+        Instr::I32ShrU => u32a.wrapping_shr(u32b) as u64,
+        Instr::I32Rotl => u32a.rotate_left(u32b % 32) as u64,
+        Instr::I32Rotr => u32a.rotate_right(u32b % 32) as u64,
+        Instr::I64Add => a64.wrapping_add(b64) as u64,
+        Instr::I64Sub => a64.wrapping_sub(b64) as u64,
+        Instr::I64Mul => a64.wrapping_mul(b64) as u64,
+        Instr::I64DivS => {
+            divisor(b64 == 0, a64 == i64::MIN && b64 == -1)?;
+            (a64 / b64) as u64
+        }
+        Instr::I64DivU => {
+            divisor(b64 == 0, false)?;
+            lhs / rhs
+        }
+        Instr::I64RemS => {
+            divisor(b64 == 0, false)?;
+            a64.wrapping_rem(b64) as u64
+        }
+        Instr::I64RemU => {
+            divisor(b64 == 0, false)?;
+            lhs % rhs
+        }
+        Instr::I64And => lhs & rhs,
+        Instr::I64Or => lhs | rhs,
+        Instr::I64Xor => lhs ^ rhs,
+        Instr::I64Shl => lhs.wrapping_shl(rhs as u32),
+        Instr::I64ShrS => a64.wrapping_shr(rhs as u32) as u64,
+        Instr::I64ShrU => lhs.wrapping_shr(rhs as u32),
+        Instr::I64Rotl => lhs.rotate_left((rhs % 64) as u32),
+        Instr::I64Rotr => lhs.rotate_right((rhs % 64) as u32),
+        Instr::F32Add => f32_bits(f32a + f32b),
+        Instr::F32Sub => f32_bits(f32a - f32b),
+        Instr::F32Mul => f32_bits(f32a * f32b),
+        Instr::F32Div => f32_bits(f32a / f32b),
+        Instr::F32Min => f32_bits(f32_min(f32a, f32b)),
+        Instr::F32Max => f32_bits(f32_max(f32a, f32b)),
+        Instr::F32Copysign => (lhs & 0x7fff_ffff) | (rhs & 0x8000_0000),
+        Instr::F64Add => f64_bits(f64a + f64b),
+        Instr::F64Sub => f64_bits(f64a - f64b),
+        Instr::F64Mul => f64_bits(f64a * f64b),
+        Instr::F64Div => f64_bits(f64a / f64b),
+        Instr::F64Min => f64_bits(f64_min(f64a, f64b)),
+        Instr::F64Max => f64_bits(f64_max(f64a, f64b)),
+        Instr::F64Copysign => (lhs & 0x7fff_ffff_ffff_ffff) | (rhs & 0x8000_0000_0000_0000),
+        _ => unreachable!("{:?} is not a binary numeric instruction", instr),
+    };
+    Ok(result)
+}
+
+fn divisor(zero: bool, overflow: bool) -> Result<(), Trap> {
+    if zero {
+        return Err(TrapKind::IntegerDivideByZero.into());
+    }
+    if overflow {
+        return Err(TrapKind::IntegerOverflow.into());
+    }
+    Ok(())
+}
+
+// `min` and `max` return NaN if either operand is NaN, and order -0 below +0. Only the zeros
+// compare equal with different bits, so their bits are combined.
+fn f32_min(a: f32, b: f32) -> f32 {
+    match (a.is_nan() || b.is_nan(), a == b) {
+        (true, _) => a + b,
+        (false, true) => f32::from_bits(a.to_bits() | b.to_bits()),
+        (false, false) => a.min(b),
+    }
+}
+
+fn f32_max(a: f32, b: f32) -> f32 {
+    match (a.is_nan() || b.is_nan(), a == b) {
+        (true, _) => a + b,
+        (false, true) => f32::from_bits(a.to_bits() & b.to_bits()),
+        (false, false) => a.max(b),
+    }
+}
+
+fn f64_min(a: f64, b: f64) -> f64 {
+    match (a.is_nan() || b.is_nan(), a == b) {
+        (true, _) => a + b,
+        (false, true) => f64::from_bits(a.to_bits() | b.to_bits()),
+        (false, false) => a.min(b),
+    }
+}
+
+fn f64_max(a: f64, b: f64) -> f64 {
+    match (a.is_nan() || b.is_nan(), a == b) {
+        (true, _) => a + b,
+        (false, true) => f64::from_bits(a.to_bits() & b.to_bits()),
+        (false, false) => a.max(b),
+    }
+}
+
+fn bool_(x: bool) -> u64 {
+    x as u64
+}
+
+fn i32_bits(x: i32) -> u64 {
+    x as u32 as u64
+}
+
+fn f32_of(x: u64) -> f32 {
+    f32::from_bits(x as u32)
+}
+
+fn f64_of(x: u64) -> f64 {
+    f64::from_bits(x)
+}
+
+fn f32_bits(x: f32) -> u64 {
+    x.to_bits() as u64
+}
+
+fn f64_bits(x: f64) -> u64 {
+    x.to_bits()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn i32_(instr: Instr, lhs: i32, rhs: i32) -> Result<i32, TrapKind> {
+        binary(&instr, i32_bits(lhs), i32_bits(rhs))
+            .map(|x| x as i32)
+            .map_err(|trap| trap.kind)
+    }
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(i32_(Instr::I32Add, i32::MAX, 1), Ok(i32::MIN));
+        assert_eq!(i32_(Instr::I32Mul, 0x10000, 0x10000), Ok(0));
+        assert_eq!(i32_(Instr::I32ShrU, -8, 1), Ok(0x7fff_fffc));
+        assert_eq!(i32_(Instr::I32ShrS, -8, 33), Ok(-4));
+        assert_eq!(i32_(Instr::I32DivU, -1, 2), Ok(0x7fff_ffff));
+        assert_eq!(i32_(Instr::I32RemS, i32::MIN, -1), Ok(0));
+        assert_eq!(
+            i32_(Instr::I32DivS, i32::MIN, -1),
+            Err(TrapKind::IntegerOverflow)
+        );
+        assert_eq!(
+            i32_(Instr::I32RemU, 1, 0),
+            Err(TrapKind::IntegerDivideByZero)
+        );
+        assert_eq!(unary(&Instr::I64Eqz, 0), 1);
+        assert_eq!(unary(&Instr::I32Clz, 0), 32);
+        assert_eq!(
+            binary(&Instr::I64Rotl, 1, 65).unwrap(),
+            binary(&Instr::I64Rotl, 1, 1).unwrap()
+        );
+        assert_eq!(
+            binary(&Instr::I64DivS, i64::MIN as u64, u64::MAX).map_err(|trap| trap.kind),
+            Err(TrapKind::IntegerOverflow)
+        );
+    }
+
+    #[test]
+    fn test_floats() {
+        let f32_ = |instr, lhs: f32, rhs: f32| {
+            f32_of(binary(&instr, f32_bits(lhs), f32_bits(rhs)).unwrap())
+        };
+        assert_eq!(
+            f32_(Instr::F32Min, 0.0, -0.0).to_bits(),
+            (-0.0f32).to_bits()
+        );
+        assert_eq!(f32_(Instr::F32Max, -0.0, 0.0).to_bits(), 0.0f32.to_bits());
+        assert!(f32_(Instr::F32Min, f32::NAN, 1.0).is_nan());
+        assert!(f32_(Instr::F32Max, 1.0, f32::NAN).is_nan());
+        assert_eq!(f32_(Instr::F32Copysign, 2.0, -0.0), -2.0);
+        assert_eq!(f32_of(unary(&Instr::F32Nearest, f32_bits(2.5))), 2.0);
+        assert_eq!(f64_of(unary(&Instr::F64Nearest, f64_bits(-3.5))), -4.0);
+        // `neg` keeps the NaN payload.
+        let nan = 0x7fa0_0001;
+        assert_eq!(unary(&Instr::F32Neg, nan), nan | 0x8000_0000);
+        assert_eq!(unary(&Instr::F32Abs, nan | 0x8000_0000), nan);
+    }
+}
@@ -0,0 +1,790 @@
+use crate::execution::compile::default_val;
+use crate::execution::host::Caller;
+use crate::execution::instance::{self, ModuleInst};
+use crate::execution::limits::ResourceLimiter;
+use crate::execution::numeric;
+use crate::execution::structure::{FuncAddr, FuncInst, MemInst, Store, Val};
+use crate::execution::trap::{FrameInfo, Trap};
+use crate::execution::value;
+use crate::structure::instructions::expression::{BlockType, Instr};
+use crate::structure::modules::function::Func;
+use crate::structure::modules::indice::TableIdx;
+use crate::structure::types::function::FuncType;
+use crate::validation::{load_signature, numeric_signature, store_signature};
+use std::rc::Rc;
+
+/// The index of a register in the frame of a call.
+pub type Reg = u32;
+
+/// A function body lowered for the register backend.
+///
+/// The registers of a frame hold the parameters, then the declared locals, then one register
+/// per operand stack slot. The arguments of a call are the caller's slot registers, which
+/// become the first registers of the callee's frame and receive its results.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Code {
+    pub ops: Vec<RegOp>,
+    /// The code section offset of each op, or empty if the module wasn't decoded from a binary.
+    pub offsets: Vec<u32>,
+    pub param_count: usize,
+    /// The initial values of the declared locals, which follow the parameters.
+    pub locals: Vec<Val>,
+    pub frame_size: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RegOp {
+    Const {
+        dst: Reg,
+        /// The value as an untyped slot.
+        value: u64,
+    },
+    Copy {
+        dst: Reg,
+        src: Reg,
+    },
+    Unary {
+        instr: Instr,
+        dst: Reg,
+        src: Reg,
+    },
+    Binary {
+        instr: Instr,
+        dst: Reg,
+        lhs: Reg,
+        rhs: Reg,
+    },
+    Load {
+        instr: Instr,
+        dst: Reg,
+        addr: Reg,
+    },
+    Store {
+        instr: Instr,
+        addr: Reg,
+        value: Reg,
+    },
+    TableSize {
+        dst: Reg,
+        table: TableIdx,
+    },
+    TableGrow {
+        dst: Reg,
+        init: Reg,
+        delta: Reg,
+        table: TableIdx,
+    },
+    MemorySize {
+        dst: Reg,
+    },
+    MemoryGrow {
+        dst: Reg,
+        delta: Reg,
+    },
+    /// Jumps to the op at `else_pc` if `cond` is zero.
+    If {
+        cond: Reg,
+        else_pc: usize,
+    },
+    Jump(usize),
+    /// Calls a function with the arguments in the registers from `args`, which receive the
+    /// results.
+    Call {
+        func_idx: u32,
+        args: Reg,
+    },
+    /// Moves `count` results from the registers from `from` to the start of the frame.
+    Return {
+        from: Reg,
+        count: u32,
+    },
+}
+
+/// Lowers `func`. `func_types` are the types of the functions in the module, by index.
+pub fn compile(func: &Func, type_: &FuncType, func_types: &[FuncType]) -> Code {
+    let stack_base = (type_.parameters.len() + func.locals.len()) as Reg;
+    let mut compiler = Compiler {
+        func_types,
+        ops: Vec::new(),
+        offsets: Vec::new(),
+        source_offsets: &func.offsets,
+        index: 0,
+        offset: None,
+        stack_base,
+        stack: Vec::new(),
+        max_height: 0,
+    };
+    compiler.block(&func.body.0);
+
+    let count = type_.results.len();
+    if count > 0 {
+        compiler.materialize(0..count);
+        compiler.emit(RegOp::Return {
+            from: stack_base,
+            count: count as u32,
+        });
+    }
+    Code {
+        ops: compiler.ops,
+        offsets: compiler.offsets,
+        param_count: type_.parameters.len(),
+        locals: func.locals.iter().map(default_val).collect(),
+        frame_size: stack_base as usize + compiler.max_height,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operand {
+    /// A `local.get` that hasn't been copied to its slot, since locals can't change.
+    Local(Reg),
+    Slot,
+}
+
+struct Compiler<'a> {
+    func_types: &'a [FuncType],
+    ops: Vec<RegOp>,
+    offsets: Vec<u32>,
+    /// The offsets of the instructions in pre-order.
+    source_offsets: &'a [u32],
+    /// The pre-order index of the next instruction.
+    index: usize,
+    /// The offset of the instruction being lowered.
+    offset: Option<u32>,
+    /// The register of the bottom operand stack slot.
+    stack_base: Reg,
+    stack: Vec<Operand>,
+    max_height: usize,
+}
+
+impl Compiler<'_> {
+    fn block(&mut self, instrs: &[Instr]) {
+        for instr in instrs {
+            self.offset = self.source_offsets.get(self.index).copied();
+            self.index += 1;
+            self.instr(instr);
+        }
+    }
+
+    fn instr(&mut self, instr: &Instr) {
+        match instr {
+            Instr::If(block_type, instructions1, instructions2) => {
+                let cond = self.pop();
+                let height = self.stack.len();
+                let count = match block_type {
+                    BlockType::Empty => 0,
+                    BlockType::ValType(_) => 1,
+                };
+                let entry = self.stack.clone();
+                let if_pc = self.emit(RegOp::If { cond, else_pc: 0 });
+                self.block(instructions1);
+                self.materialize(height..height + count);
+                if !instructions2.is_empty() {
+                    let jump_pc = self.emit(RegOp::Jump(0));
+                    self.patch_if(if_pc);
+                    self.stack.clone_from(&entry);
+                    self.block(instructions2);
+                    self.materialize(height..height + count);
+                    self.ops[jump_pc] = RegOp::Jump(self.ops.len());
+                } else {
+                    self.patch_if(if_pc);
+                }
+                self.stack = entry;
+                for _ in 0..count {
+                    self.push();
+                }
+            }
+            Instr::Call(func_idx) => {
+                let type_ = &self.func_types[*func_idx as usize];
+                let (param_count, result_count) = (type_.parameters.len(), type_.results.len());
+                let args = self.stack.len() - param_count;
+                self.materialize(args..self.stack.len());
+                self.stack.truncate(args);
+                self.emit(RegOp::Call {
+                    func_idx: *func_idx,
+                    args: self.slot(args),
+                });
+                for _ in 0..result_count {
+                    self.push();
+                }
+            }
+            Instr::Drop => {
+                self.pop();
+            }
+            Instr::LocalGet(idx) => {
+                self.push_operand(Operand::Local(*idx));
+            }
+            Instr::I32Const(x) => self.constant(Val::I32(*x).to_bits()),
+            Instr::I64Const(x) => self.constant(Val::I64(*x).to_bits()),
+            Instr::F32Const(x) => self.constant(Val::F32(*x).to_bits()),
+            Instr::F64Const(x) => self.constant(Val::F64(*x).to_bits()),
+            Instr::RefNull(_) => self.constant(Val::FuncRef(None).to_bits()),
+            Instr::TableSize(table) => {
+                let dst = self.push();
+                self.emit(RegOp::TableSize { dst, table: *table });
+            }
+            Instr::TableGrow(table) => {
+                let delta = self.pop();
+                let init = self.pop();
+                let dst = self.push();
+                self.emit(RegOp::TableGrow {
+                    dst,
+                    init,
+                    delta,
+                    table: *table,
+                });
+            }
+            Instr::MemorySize => {
+                let dst = self.push();
+                self.emit(RegOp::MemorySize { dst });
+            }
+            Instr::MemoryGrow => {
+                let delta = self.pop();
+                let dst = self.push();
+                self.emit(RegOp::MemoryGrow { dst, delta });
+            }
+            Instr::Else | Instr::End => unreachable!("block delimiters are not in the tree"),
+            _ if load_signature(instr).is_some() => {
+                let addr = self.pop();
+                let dst = self.push();
+                self.emit(RegOp::Load {
+                    instr: instr.clone(),
+                    dst,
+                    addr,
+                });
+            }
+            _ if store_signature(instr).is_some() => {
+                let value = self.pop();
+                let addr = self.pop();
+                self.emit(RegOp::Store {
+                    instr: instr.clone(),
+                    addr,
+                    value,
+                });
+            }
+            _ => {
+                let instr = instr.clone();
+                if numeric_signature(&instr).0.len() == 1 {
+                    let src = self.pop();
+                    let dst = self.push();
+                    self.emit(RegOp::Unary { instr, dst, src });
+                } else {
+                    let rhs = self.pop();
+                    let lhs = self.pop();
+                    let dst = self.push();
+                    self.emit(RegOp::Binary {
+                        instr,
+                        dst,
+                        lhs,
+                        rhs,
+                    });
+                }
+            }
+        }
+    }
+
+    fn constant(&mut self, value: u64) {
+        let dst = self.push();
+        self.emit(RegOp::Const { dst, value });
+    }
+
+    fn slot(&self, height: usize) -> Reg {
+        self.stack_base + height as Reg
+    }
+
+    /// Pushes an operand that is stored in its slot and returns the slot's register.
+    fn push(&mut self) -> Reg {
+        self.push_operand(Operand::Slot)
+    }
+
+    fn push_operand(&mut self, operand: Operand) -> Reg {
+        let reg = self.slot(self.stack.len());
+        self.stack.push(operand);
+        self.max_height = self.max_height.max(self.stack.len());
+        reg
+    }
+
+    /// Pops an operand and returns the register it can be read from.
+    fn pop(&mut self) -> Reg {
+        match self.stack.pop().unwrap() {
+            Operand::Local(idx) => idx,
+            Operand::Slot => self.slot(self.stack.len()),
+        }
+    }
+
+    /// Copies the operands at `heights` that are still locals to their slots.
+    fn materialize(&mut self, heights: std::ops::Range<usize>) {
+        for height in heights {
+            if let Operand::Local(src) = self.stack[height] {
+                let dst = self.slot(height);
+                self.emit(RegOp::Copy { dst, src });
+                self.stack[height] = Operand::Slot;
+            }
+        }
+    }
+
+    fn patch_if(&mut self, if_pc: usize) {
+        let RegOp::If { cond, .. } = self.ops[if_pc] else {
+            unreachable!()
+        };
+        self.ops[if_pc] = RegOp::If {
+            cond,
+            else_pc: self.ops.len(),
+        };
+    }
+
+    fn emit(&mut self, op: RegOp) -> usize {
+        self.ops.push(op);
+        self.offsets.extend(self.offset);
+        self.ops.len() - 1
+    }
+}
+
+/// Invokes a function with the register backend.
+pub fn invoke(
+    store: &mut Store,
+    func_addr: FuncAddr,
+    arguments: Vec<Val>,
+) -> Result<Vec<Val>, Trap> {
+    let results = store.funcs[func_addr as usize].type_().results.clone();
+    let mut executor = Executor {
+        store,
+        registers: arguments.iter().map(Val::to_bits).collect(),
+        activations: Vec::new(),
+    };
+    executor.call(func_addr, 0)?;
+    executor.execute()?;
+    Ok(results
+        .iter()
+        .zip(&executor.registers)
+        .map(|(type_, bits)| Val::from_bits(*bits, type_))
+        .collect())
+}
+
+struct Executor<'a> {
+    store: &'a mut Store,
+    /// The registers of all frames, each of which starts at its activation's `base`. They
+    /// are untyped slots, as in `Val::to_bits`.
+    registers: Vec<u64>,
+    /// The calls in progress, innermost last.
+    activations: Vec<Activation>,
+}
+
+struct Activation {
+    func_addr: FuncAddr,
+    module: Rc<ModuleInst>,
+    code: Rc<Code>,
+    /// The index of the next op.
+    pc: usize,
+    base: usize,
+}
+
+impl Executor<'_> {
+    /// Calls a function whose frame starts at `base`. Host functions run to completion, while
+    /// module functions are pushed as an activation for `execute` to run.
+    fn call(&mut self, func_addr: FuncAddr, base: usize) -> Result<(), Trap> {
+        let (module, code) = match &self.store.funcs[func_addr as usize] {
+            FuncInst::Module {
+                pending: Some(_), ..
+            } => {
+                instance::compile_pending(self.store, func_addr)?;
+                return self.call(func_addr, base);
+            }
+            FuncInst::Module {
+                module,
+                register_code,
+                ..
+            } => {
+                let code = register_code
+                    .as_ref()
+                    .expect("functions are compiled for the register backend");
+                (Rc::clone(module), Rc::clone(code))
+            }
+            FuncInst::Host { type_, code } => {
+                let code = code.clone();
+                let arguments: Vec<Val> = type_
+                    .parameters
+                    .iter()
+                    .enumerate()
+                    .map(|(i, param)| Val::from_bits(self.registers[base + i], param))
+                    .collect();
+                let module = self
+                    .activations
+                    .last()
+                    .map(|activation| Rc::clone(&activation.module));
+                let mut caller = Caller {
+                    store: &mut *self.store,
+                    module,
+                };
+                let results = code.call(&mut caller, &arguments)?;
+                if self.registers.len() < base + results.len() {
+                    self.registers.resize(base + results.len(), 0);
+                }
+                for (i, result) in results.iter().enumerate() {
+                    self.registers[base + i] = result.to_bits();
+                }
+                return Ok(());
+            }
+        };
+
+        let end = base + code.frame_size;
+        self.store.check_entry(self.activations.len(), end)?;
+        if self.registers.len() < end {
+            self.registers.resize(end, 0);
+        }
+        let locals = base + code.param_count;
+        for (register, local) in self.registers[locals..].iter_mut().zip(&code.locals) {
+            *register = local.to_bits();
+        }
+
+        self.activations.push(Activation {
+            func_addr,
+            module,
+            code,
+            pc: 0,
+            base,
+        });
+        Ok(())
+    }
+
+    fn execute(&mut self) -> Result<(), Trap> {
+        while let Some(activation) = self.activations.last() {
+            let calls = self.activations.len();
+            let code = Rc::clone(&activation.code);
+            let base = activation.base;
+            let mut pc = activation.pc;
+
+            // Run the innermost activation until it returns or calls a module function.
+            let mut returned = true;
+            while let Some(op) = code.ops.get(pc) {
+                pc += 1;
+                if let Err(trap) = self.execute_op(op, base, &mut pc) {
+                    self.activations[calls - 1].pc = pc;
+                    return Err(self.unwind(trap));
+                }
+                if self.activations.len() > calls {
+                    returned = false;
+                    break;
+                }
+            }
+
+            if returned {
+                self.activations.pop();
+            } else {
+                self.activations[calls - 1].pc = pc;
+            }
+        }
+        Ok(())
+    }
+
+    /// Abandons all calls, adding them to the backtrace of `trap`.
+    fn unwind(&mut self, mut trap: Trap) -> Trap {
+        for activation in self.activations.drain(..).rev() {
+            let func_index = activation
+                .module
+                .func_addrs
+                .iter()
+                .position(|addr| *addr == activation.func_addr)
+                .unwrap_or_default() as u32;
+            // The pc of an activation is past the op that trapped or made the call.
+            let offset = activation
+                .pc
+                .checked_sub(1)
+                .and_then(|pc| activation.code.offsets.get(pc).copied());
+            trap.backtrace.push(FrameInfo {
+                func_index,
+                func_name: activation.module.func_names.get(&func_index).cloned(),
+                offset,
+            });
+        }
+        trap
+    }
+
+    fn execute_op(&mut self, op: &RegOp, base: usize, pc: &mut usize) -> Result<(), Trap> {
+        let reg = |reg: Reg| base + reg as usize;
+        match op {
+            RegOp::Const { dst, value } => {
+                self.registers[reg(*dst)] = *value;
+            }
+            RegOp::Copy { dst, src } => {
+                self.registers[reg(*dst)] = self.registers[reg(*src)];
+            }
+            RegOp::Unary { instr, dst, src } => {
+                let x = self.registers[reg(*src)];
+                self.registers[reg(*dst)] = numeric::unary(instr, x);
+            }
+            RegOp::Binary {
+                instr,
+                dst,
+                lhs,
+                rhs,
+            } => {
+                let lhs = self.registers[reg(*lhs)];
+                let rhs = self.registers[reg(*rhs)];
+                self.registers[reg(*dst)] = numeric::binary(instr, lhs, rhs)?;
+            }
+            RegOp::Load { instr, dst, addr } => {
+                let base = self.registers[reg(*addr)] as u32;
+                let mem_addr = self.activations.last().unwrap().module.mem_addrs[0];
+                self.registers[reg(*dst)] = load(&self.store.mems[mem_addr as usize], instr, base)?;
+            }
+            RegOp::Store { instr, addr, value } => {
+                let base = self.registers[reg(*addr)] as u32;
+                let value = self.registers[reg(*value)];
+                let mem_addr = self.activations.last().unwrap().module.mem_addrs[0];
+                store(&mut self.store.mems[mem_addr as usize], instr, base, value)?;
+            }
+            RegOp::TableSize { dst, table } => {
+                let table_addr =
+                    self.activations.last().unwrap().module.table_addrs[*table as usize];
+                let size = self.store.tables[table_addr as usize].size();
+                self.registers[reg(*dst)] = size as u64;
+            }
+            RegOp::TableGrow {
+                dst,
+                init,
+                delta,
+                table,
+            } => {
+                let init = value::func_ref(self.registers[reg(*init)]);
+                let delta = self.registers[reg(*delta)] as u32;
+                let table_addr =
+                    self.activations.last().unwrap().module.table_addrs[*table as usize];
+                let Store {
+                    tables, limiter, ..
+                } = &mut *self.store;
+                let limiter = limiter
+                    .as_deref_mut()
+                    .map(|l| l as &mut dyn ResourceLimiter);
+                let result = match tables[table_addr as usize].grow(delta, init, limiter) {
+                    Some(old_size) => old_size as i32,
+                    None => -1,
+                };
+                self.registers[reg(*dst)] = Val::I32(result).to_bits();
+            }
+            RegOp::MemorySize { dst } => {
+                let mem_addr = self.activations.last().unwrap().module.mem_addrs[0];
+                let size = self.store.mems[mem_addr as usize].size();
+                self.registers[reg(*dst)] = size as u64;
+            }
+            RegOp::MemoryGrow { dst, delta } => {
+                let delta = self.registers[reg(*delta)] as u32;
+                let mem_addr = self.activations.last().unwrap().module.mem_addrs[0];
+                let Store { mems, limiter, .. } = &mut *self.store;
+                let limiter = limiter
+                    .as_deref_mut()
+                    .map(|l| l as &mut dyn ResourceLimiter);
+                let result = match mems[mem_addr as usize].grow(delta, limiter) {
+                    Some(old_size) => old_size as i32,
+                    None => -1,
+                };
+                self.registers[reg(*dst)] = Val::I32(result).to_bits();
+            }
+            RegOp::If { cond, else_pc } => {
+                if self.registers[reg(*cond)] == 0 {
+                    *pc = *else_pc;
+                }
+            }
+            RegOp::Jump(target) => {
+                *pc = *target;
+            }
+            RegOp::Call { func_idx, args } => {
+                let func_addr =
+                    self.activations.last().unwrap().module.func_addrs[*func_idx as usize];
+                self.call(func_addr, reg(*args))?;
+            }
+            RegOp::Return { from, count } => {
+                for i in 0..*count {
+                    self.registers[reg(i)] = self.registers[reg(from + i)];
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Loads a value as an untyped slot.
+pub(crate) fn load(mem: &MemInst, instr: &Instr, base: u32) -> Result<u64, Trap> {
+    let value = match instr {
+        Instr::I32Load(memarg) | Instr::F32Load(memarg) | Instr::I64Load32U(memarg) => {
+            u32::from_le_bytes(mem.load(base, memarg.offset)?) as u64
+        }
+        Instr::I64Load(memarg) | Instr::F64Load(memarg) => {
+            u64::from_le_bytes(mem.load(base, memarg.offset)?)
+        }
+        Instr::I32Load8S(memarg) => i8::from_le_bytes(mem.load(base, memarg.offset)?) as u32 as u64,
+        Instr::I32Load8U(memarg) | Instr::I64Load8U(memarg) => {
+            u8::from_le_bytes(mem.load(base, memarg.offset)?) as u64
+        }
+        Instr::I32Load16S(memarg) => {
+            i16::from_le_bytes(mem.load(base, memarg.offset)?) as u32 as u64
+        }
+        Instr::I32Load16U(memarg) | Instr::I64Load16U(memarg) => {
+            u16::from_le_bytes(mem.load(base, memarg.offset)?) as u64
+        }
+        Instr::I64Load8S(memarg) => i8::from_le_bytes(mem.load(base, memarg.offset)?) as u64,
+        Instr::I64Load16S(memarg) => i16::from_le_bytes(mem.load(base, memarg.offset)?) as u64,
+        Instr::I64Load32S(memarg) => i32::from_le_bytes(mem.load(base, memarg.offset)?) as u64,
+        _ => unreachable!("{:?} is not a load", instr),
+    };
+    Ok(value)
+}
+
+/// Stores the low bytes of an untyped slot.
+pub(crate) fn store(mem: &mut MemInst, instr: &Instr, base: u32, value: u64) -> Result<(), Trap> {
+    let (memarg, width) = match instr {
+        Instr::I32Store8(memarg) | Instr::I64Store8(memarg) => (memarg, 1),
+        Instr::I32Store16(memarg) | Instr::I64Store16(memarg) => (memarg, 2),
+        Instr::I32Store(memarg) | Instr::F32Store(memarg) | Instr::I64Store32(memarg) => {
+            (memarg, 4)
+        }
+        Instr::I64Store(memarg) | Instr::F64Store(memarg) => (memarg, 8),
+        _ => unreachable!("{:?} is not a store", instr),
+    };
+    mem.store(base, memarg.offset, &value.to_le_bytes()[..width])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+    use crate::execution::engine::{Backend, Config, Engine};
+    use crate::execution::instance::{instantiate, invoke, InvokeError};
+    use crate::execution::trap::TrapKind;
+    use crate::structure::instructions::expression::Expr;
+    use crate::structure::types::value::{NumType, ValType};
+    use crate::text;
+
+    #[test]
+    fn test_compile() {
+        let i32_ = ValType::NumType(NumType::I32);
+        let func = Func {
+            type_: 0,
+            locals: vec![i32_.clone()],
+            body: Expr(vec![
+                Instr::LocalGet(1),
+                Instr::LocalGet(0),
+                Instr::I32Const(1),
+                Instr::I32Add,
+                Instr::Call(0),
+                Instr::LocalGet(0),
+                Instr::If(
+                    BlockType::ValType(i32_.clone()),
+                    vec![Instr::LocalGet(2)],
+                    vec![Instr::I32Const(0)],
+                ),
+                Instr::I32Sub,
+            ]),
+            offsets: Vec::new(),
+        };
+        let type_ = FuncType {
+            parameters: vec![i32_.clone(), i32_.clone()],
+            results: vec![i32_.clone()],
+        };
+        let code = compile(&func, &type_, std::slice::from_ref(&type_));
+        assert_eq!(
+            code.ops,
+            vec![
+                RegOp::Const { dst: 5, value: 1 },
+                RegOp::Binary {
+                    instr: Instr::I32Add,
+                    dst: 4,
+                    lhs: 0,
+                    rhs: 5
+                },
+                RegOp::Copy { dst: 3, src: 1 },
+                RegOp::Call {
+                    func_idx: 0,
+                    args: 3
+                },
+                RegOp::If {
+                    cond: 0,
+                    else_pc: 7
+                },
+                RegOp::Copy { dst: 4, src: 2 },
+                RegOp::Jump(8),
+                RegOp::Const { dst: 4, value: 0 },
+                RegOp::Binary {
+                    instr: Instr::I32Sub,
+                    dst: 3,
+                    lhs: 3,
+                    rhs: 4
+                },
+                RegOp::Return { from: 3, count: 1 },
+            ]
+        );
+        assert_eq!(code.frame_size, 6);
+        assert_eq!(code.locals, vec![Val::I32(0)]);
+    }
+
+    const PROGRAM: &str = r#"(module
+        (import "host" "double" (func $double (param i32) (result i32)))
+        (memory 1)
+        (func $fib (export "fib") (param i32) (result i32)
+            (if (result i32) (i32.lt_s (local.get 0) (i32.const 2))
+                (then (local.get 0))
+                (else (i32.add
+                    (call $fib (i32.sub (local.get 0) (i32.const 1)))
+                    (call $fib (i32.sub (local.get 0) (i32.const 2)))))))
+        (func $swap (export "swap") (param i32 i32) (result i32)
+            (call $pair (local.get 1) (local.get 0)))
+        (func $pair (param i32 i32) (result i32)
+            (i32.sub (local.get 0) (local.get 1)))
+        (func (export "memory") (param i32) (result i64)
+            (i32.store (i32.const 8) (call $double (local.get 0)))
+            (drop (memory.grow (i32.const 1)))
+            (i64.mul (i64.load32_u (i32.const 8)) (i64.const 3)))
+        (func (export "size") (result i32) (memory.size))
+        (func (export "oob") (param i32) (result i32)
+            (call $double (i32.load (local.get 0))))
+        (func $forever (export "forever") (call $forever)))"#;
+
+    fn run(backend: Backend, name: &str, args: Vec<Val>) -> Result<Vec<Val>, Trap> {
+        let module = text::parse(PROGRAM).unwrap();
+        let module = Decoder::new(&Encoder::encode(&module)).decode().unwrap();
+        let mut store = Store::new(&Engine::with_config(Config {
+            backend,
+            ..Default::default()
+        }));
+        let double = store.funcs.len() as FuncAddr;
+        crate::execution::instance::alloc_host_func(
+            &mut store,
+            FuncType {
+                parameters: vec![ValType::NumType(NumType::I32)],
+                results: vec![ValType::NumType(NumType::I32)],
+            },
+            crate::execution::host::HostFunc::new(|_, args| match args[0] {
+                Val::I32(x) => Ok(vec![Val::I32(x * 2)]),
+                _ => unreachable!(),
+            }),
+        );
+        let externvals = [crate::execution::instance::ExternVal::Func(double)];
+        let module_inst = instantiate(&mut store, module, &externvals).unwrap();
+        invoke(&mut store, &module_inst, name.to_string(), args).map_err(InvokeError::unwrap_trap)
+    }
+
+    #[test]
+    fn test_backends_agree() {
+        let cases = [
+            ("fib", vec![Val::I32(15)]),
+            ("swap", vec![Val::I32(1), Val::I32(5)]),
+            ("memory", vec![Val::I32(7)]),
+            ("size", vec![]),
+            ("oob", vec![Val::I32(65535)]),
+            ("forever", vec![]),
+        ];
+        for (name, args) in cases {
+            let expected = run(Backend::Stack, name, args.clone());
+            assert_eq!(run(Backend::Register, name, args), expected, "{}", name);
+        }
+        assert_eq!(
+            run(Backend::Register, "fib", vec![Val::I32(15)]),
+            Ok(vec![Val::I32(610)])
+        );
+        let trap = run(Backend::Register, "oob", vec![Val::I32(65535)]).unwrap_err();
+        assert_eq!(trap.kind, TrapKind::MemoryOutOfBounds);
+        assert!(trap.backtrace[0].offset.is_some());
+        let trap = run(Backend::Register, "forever", vec![]).unwrap_err();
+        assert_eq!(trap.kind, TrapKind::StackExhausted);
+    }
+}
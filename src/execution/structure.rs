@@ -1,352 +1,614 @@
+use crate::execution::compile::{Code, Op, Slot};
+use crate::execution::engine::{Backend, Engine};
+use crate::execution::fuel::{Fuel, FuelError};
+use crate::execution::host::{Caller, HostFunc};
+use crate::execution::instance::{self, ModuleInst};
+#[cfg(feature = "jit")]
+use crate::execution::jit;
+use crate::execution::limits::ResourceLimiter;
+use crate::execution::numeric;
+use crate::execution::register;
+use crate::execution::trap::{FrameInfo, Trap, TrapKind};
+use crate::execution::value;
 use crate::structure::instructions::expression::Instr;
-use crate::structure::modules::function::Func;
+use crate::structure::modules::function::{Func, LazyBody};
 use crate::structure::types::function::FuncType;
-use crate::structure::types::value::{NumType, ValType};
-use std::cell::RefCell;
+use crate::structure::types::memory::MemType;
+use crate::structure::types::table::TableType;
+use crate::wasi::WasiCtx;
+use std::fmt;
 use std::rc::Rc;
 
+pub const PAGE_SIZE: usize = 65536;
+
 #[derive(Debug)]
-pub struct Runtime {
-    pub store: Store,
+pub struct Runtime<'a> {
+    pub store: &'a mut Store,
+    /// The frames of the calls in progress, above the arguments of the outermost call.
     pub stack: Stack,
-    pub current_frame: Option<Rc<RefCell<Frame>>>,
+    /// The calls in progress, innermost last. Guest calls are kept here instead of on the host
+    /// stack, so deep recursion can't overflow it.
+    activations: Vec<Activation>,
 }
 
-impl Runtime {
-    pub fn run(&mut self, administrative_instruction: AdministrativeInstruction) {
+impl<'a> Runtime<'a> {
+    pub fn new(store: &'a mut Store, stack: Stack) -> Self {
+        Self {
+            store,
+            stack,
+            activations: Vec::new(),
+        }
+    }
+
+    pub fn run(
+        &mut self,
+        administrative_instruction: AdministrativeInstruction,
+    ) -> Result<(), Trap> {
         match administrative_instruction {
             AdministrativeInstruction::Invoke(func_addr) => {
-                self.invoke_function(func_addr);
-            }
-            AdministrativeInstruction::Label(label) => {
-                self.execute_instructions(label);
-            }
-            AdministrativeInstruction::Frame => {}
+                let type_ = self.store.funcs[func_addr as usize].type_();
+                let result_count = type_.results.len();
+                let base = self.stack.len() - type_.parameters.len();
+                let depth = self.activations.len();
+                self.call(func_addr, base)?;
+                self.execute(depth)?;
+                self.stack.values.truncate(base + result_count);
+                Ok(())
+            }
+            AdministrativeInstruction::Frame => Ok(()),
         }
     }
 
-    fn invoke_function(&mut self, func_addr: FuncAddr) {
-        let func_inst = self.store.funcs[func_addr as usize].clone();
-        let func_type = func_inst.type_;
-
-        let mut locals = Vec::new();
-        for _ in 0..func_type.parameters.len() {
-            let value = match self.stack.pop() {
-                Some(StackValue::Value(value)) => value,
-                _ => panic!("parameters are not on the top of the stack"),
-            };
-            locals.push(value);
-            locals.reverse();
-
-            for local in &func_inst.code.locals {
-                let local = match local {
-                    ValType::NumType(NumType::I32) => Val::I32(0),
-                    ValType::NumType(NumType::I64) => Val::I64(0),
-                    ValType::NumType(NumType::F32) => Val::F32(0.0),
-                    ValType::NumType(NumType::F64) => Val::F64(0.0),
+    /// Calls a function whose frame starts at `base`. Host functions run to completion, while
+    /// module functions are pushed as an activation for `execute` to run.
+    fn call(&mut self, func_addr: FuncAddr, base: usize) -> Result<(), Trap> {
+        let (module, code) = match &self.store.funcs[func_addr as usize] {
+            FuncInst::Module {
+                pending: Some(_), ..
+            } => {
+                instance::compile_pending(self.store, func_addr)?;
+                return self.call(func_addr, base);
+            }
+            FuncInst::Module { module, code, .. } => (Rc::clone(module), Rc::clone(code)),
+            FuncInst::Host { type_, code } => {
+                let code = code.clone();
+                let arguments: Vec<Val> = type_
+                    .parameters
+                    .iter()
+                    .enumerate()
+                    .map(|(i, param)| Val::from_bits(self.stack.values[base + i], param))
+                    .collect();
+                let module = self
+                    .activations
+                    .last()
+                    .map(|activation| Rc::clone(&activation.module));
+                let mut caller = Caller {
+                    store: &mut *self.store,
+                    module,
                 };
-                locals.push(local);
+                let results = code.call(&mut caller, &arguments)?;
+                if self.stack.len() < base + results.len() {
+                    self.stack.values.resize(base + results.len(), 0);
+                }
+                for (i, result) in results.iter().enumerate() {
+                    self.stack.values[base + i] = result.to_bits();
+                }
+                return Ok(());
             }
+        };
+
+        let end = base + code.frame_size();
+        self.store.check_entry(self.activations.len(), end)?;
+        if self.stack.len() < end {
+            self.stack.values.resize(end, 0);
+        }
+        let locals = base + code.param_count;
+        for (slot, local) in self.stack.values[locals..].iter_mut().zip(&code.locals) {
+            *slot = local.to_bits();
         }
 
-        let return_arity = func_type.results.len() as u32;
-        let frame = Rc::new(RefCell::new(Frame {
-            return_arity,
-            locals,
-        }));
-        // self.stack.push(StackValue::Frame(Rc::clone(&frame)));
-        self.current_frame = Some(Rc::clone(&frame));
-
-        let label = Label {
-            argument_arity: return_arity,
-            instructions: func_inst.code.body.0.clone(),
-        };
-        // push label to the stack
-        // self.stack.push(StackValue::Label(label));
+        self.activations.push(Activation {
+            func_addr,
+            module,
+            code,
+            pc: 0,
+            base,
+        });
+        Ok(())
+    }
+
+    /// Executes ops until the calls above `depth` have returned.
+    fn execute(&mut self, depth: usize) -> Result<(), Trap> {
+        while self.activations.len() > depth {
+            let calls = self.activations.len();
+            let activation = &self.activations[calls - 1];
+            let code = Rc::clone(&activation.code);
+            let base = activation.base;
+            let mut pc = activation.pc;
+
+            // Run the innermost activation until it returns or calls a module function.
+            let mut returned = true;
+            while let Some(op) = code.ops.get(pc) {
+                let charged = match (&mut self.store.fuel, &code.charges[pc]) {
+                    (Some(fuel), Some(instr)) => fuel.consume(instr),
+                    _ => Ok(()),
+                };
+                pc += 1;
+                if let Err(trap) = charged.and_then(|()| self.execute_op(op, base, &mut pc)) {
+                    self.activations[calls - 1].pc = pc;
+                    return Err(self.unwind(trap, depth));
+                }
+                if self.activations.len() > calls {
+                    returned = false;
+                    break;
+                }
+            }
 
-        self.run(AdministrativeInstruction::Label(label));
+            if returned {
+                self.activations.pop();
+            } else {
+                self.activations[calls - 1].pc = pc;
+            }
+        }
+        Ok(())
     }
 
-    fn execute_instructions(&mut self, label: Label) {
-        for instr in label.instructions {
-            self.execute_instruction(instr);
+    /// Abandons the calls above `depth`, adding them to the backtrace of `trap`.
+    fn unwind(&mut self, mut trap: Trap, depth: usize) -> Trap {
+        for activation in self.activations.split_off(depth).into_iter().rev() {
+            let func_index = activation
+                .module
+                .func_addrs
+                .iter()
+                .position(|addr| *addr == activation.func_addr)
+                .unwrap_or_default() as u32;
+            // The pc of an activation is past the op that trapped or made the call.
+            let offset = activation
+                .pc
+                .checked_sub(1)
+                .and_then(|pc| activation.code.offsets.get(pc).copied());
+            trap.backtrace.push(FrameInfo {
+                func_index,
+                func_name: activation.module.func_names.get(&func_index).cloned(),
+                offset,
+            });
+            self.stack.values.truncate(activation.base);
         }
+        trap
     }
 
-    fn execute_instruction(&mut self, instr: Instr) {
-        match instr {
-            Instr::If(block_type, instructions1, instructions2) => {
-                let x = match self.stack.pop() {
-                    Some(StackValue::Value(Val::I32(x))) => x as i64,
-                    Some(StackValue::Value(Val::I64(x))) => x,
-                    _ => panic!(),
+    fn execute_op(&mut self, op: &Op, base: usize, pc: &mut usize) -> Result<(), Trap> {
+        let slot = |slot: Slot| base + slot as usize;
+        let Runtime {
+            store,
+            stack,
+            activations,
+        } = &mut *self;
+        let values = &mut stack.values;
+        let module = &activations.last().unwrap().module;
+        match op {
+            Op::LocalGet { local, dst } => {
+                values[slot(*dst)] = values[slot(*local)];
+            }
+            Op::Const { dst, value } => {
+                values[slot(*dst)] = *value;
+            }
+            Op::Drop => {}
+            Op::Unary { instr, slot: src } => {
+                let x = values[slot(*src)];
+                values[slot(*src)] = numeric::unary(instr, x);
+            }
+            Op::Binary { instr, lhs } => {
+                let at = slot(*lhs);
+                values[at] = numeric::binary(instr, values[at], values[at + 1])?;
+            }
+            Op::Load { instr, addr } => {
+                let at = slot(*addr);
+                let mem = &store.mems[module.mem_addrs[0] as usize];
+                values[at] = register::load(mem, instr, values[at] as u32)?;
+            }
+            Op::Store { instr, addr } => {
+                let at = slot(*addr);
+                let mem = &mut store.mems[module.mem_addrs[0] as usize];
+                register::store(mem, instr, values[at] as u32, values[at + 1])?;
+            }
+            Op::TableSize { table, dst } => {
+                let table_addr = module.table_addrs[*table as usize];
+                let size = store.tables[table_addr as usize].size();
+                values[slot(*dst)] = size as u64;
+            }
+            Op::TableGrow { table, init } => {
+                let at = slot(*init);
+                let init = value::func_ref(values[at]);
+                let delta = values[at + 1] as u32;
+                let table_addr = module.table_addrs[*table as usize];
+                let Store {
+                    tables, limiter, ..
+                } = &mut **store;
+                let limiter = limiter
+                    .as_deref_mut()
+                    .map(|l| l as &mut dyn ResourceLimiter);
+                let result = match tables[table_addr as usize].grow(delta, init, limiter) {
+                    Some(old_size) => old_size as i32,
+                    None => -1,
                 };
-                let label = Label {
-                    argument_arity: 0,
-                    instructions: if x != 0 { instructions1 } else { instructions2 },
+                values[at] = Val::I32(result).to_bits();
+            }
+            Op::MemorySize { dst } => {
+                let size = store.mems[module.mem_addrs[0] as usize].size();
+                values[slot(*dst)] = size as u64;
+            }
+            Op::MemoryGrow { delta } => {
+                let at = slot(*delta);
+                let mem_addr = module.mem_addrs[0];
+                let Store { mems, limiter, .. } = &mut **store;
+                let limiter = limiter
+                    .as_deref_mut()
+                    .map(|l| l as &mut dyn ResourceLimiter);
+                let result = match mems[mem_addr as usize].grow(values[at] as u32, limiter) {
+                    Some(old_size) => old_size as i32,
+                    None => -1,
                 };
-                // push label to the stack
-                // self.stack.push(StackValue::Label(label));
-                self.execute_instructions(label);
+                values[at] = Val::I32(result).to_bits();
             }
-            Instr::Call(idx) => {
-                self.invoke_function(idx);
-            }
-            Instr::LocalGet(idx) => {
-                if let Some(ref current_frame) = self.current_frame {
-                    let value = current_frame.borrow().locals[idx as usize].clone();
-                    self.stack.push(StackValue::Value(value));
-                } else {
-                    panic!()
+            Op::If { cond, else_pc } => {
+                if values[slot(*cond)] == 0 {
+                    *pc = *else_pc;
                 }
             }
-            Instr::I32Const(x) => {
-                self.stack.push(StackValue::Value(Val::I32(x)));
-            }
-            Instr::I64Const(x) => {
-                self.stack.push(StackValue::Value(Val::I64(x)));
-            }
-            Instr::I32Add => {
-                self.execute_i32_binop(|lhs, rhs| lhs + rhs);
-            }
-            Instr::I32Sub => {
-                self.execute_i32_binop(|lhs, rhs| lhs - rhs);
-            }
-            Instr::I32Mul => {
-                self.execute_i32_binop(|lhs, rhs| lhs * rhs);
-            }
-            Instr::I32DivS => {
-                self.execute_i32_binop(|lhs, rhs| lhs / rhs);
-            }
-            Instr::I32DivU => {
-                self.execute_i32_binop(|lhs, rhs| lhs / rhs);
-            }
-            Instr::I32RemS => {
-                self.execute_i32_binop(|lhs, rhs| lhs % rhs);
-            }
-            Instr::I32RemU => {
-                self.execute_i32_binop(|lhs, rhs| lhs % rhs);
-            }
-            Instr::I32And => {
-                self.execute_i32_binop(|lhs, rhs| lhs & rhs);
-            }
-            Instr::I32Or => {
-                self.execute_i32_binop(|lhs, rhs| lhs | rhs);
-            }
-            Instr::I32Xor => {
-                self.execute_i32_binop(|lhs, rhs| lhs ^ rhs);
-            }
-            Instr::I32Shl => {
-                self.execute_i32_binop(|lhs, rhs| lhs << rhs);
-            }
-            Instr::I32ShrS => {
-                self.execute_i32_binop(|lhs, rhs| lhs >> rhs);
-            }
-            Instr::I32ShrU => {
-                self.execute_i32_binop(|lhs, rhs| lhs >> rhs);
-            }
-            Instr::I32Rotl => {
-                self.execute_i32_binop(|lhs, rhs| (lhs << rhs) | (rhs >> (32 - rhs)));
-            }
-            Instr::I32Rotr => {
-                self.execute_i32_binop(|lhs, rhs| (lhs >> rhs) | lhs << (32 - rhs));
-            }
-            Instr::I32Clz => {
-                self.execute_i32_unop(|x| x.leading_zeros() as i32);
-            }
-            Instr::I32Ctz => {
-                self.execute_i32_unop(|x| x.trailing_zeros() as i32);
-            }
-            Instr::I32Popcnt => {
-                self.execute_i32_unop(|x| x.count_ones() as i32);
+            Op::Jump(target) => {
+                *pc = *target;
             }
-            Instr::I32Extend8S => {
-                self.execute_i32_unop(|x| (x as i8) as i32);
+            Op::Call { func_idx, args } => {
+                let func_addr = module.func_addrs[*func_idx as usize];
+                self.call(func_addr, slot(*args))?;
             }
-            Instr::I32Extend16S => {
-                self.execute_i32_unop(|x| (x as i16) as i32);
+            Op::Return { from, count } => {
+                let from = slot(*from);
+                values.copy_within(from..from + *count as usize, base);
             }
-            Instr::I32Eqz => {
-                self.execute_i32_unop(|x| x.eq(&0) as i32);
-            }
-            Instr::I32Eq => {
-                self.execute_i32_binop(|lhs, rhs| lhs.eq(&rhs) as i32);
-            }
-            Instr::I32Ne => {
-                self.execute_i32_binop(|lhs, rhs| lhs.ne(&rhs) as i32);
-            }
-            Instr::I32LtS => {
-                self.execute_i32_binop(|lhs, rhs| lhs.lt(&rhs) as i32);
-            }
-            Instr::I32LtU => self.execute_i32_binop(|lhs, rhs| {
-                let lhs = lhs as u32;
-                let rhs = rhs as u32;
-                lhs.lt(&rhs) as i32
-            }),
-            Instr::I32LeS => {
-                self.execute_i32_binop(|lhs, rhs| (lhs <= rhs) as i32);
-            }
-            Instr::I32LeU => {
-                self.execute_i32_binop(|lhs, rhs| {
-                    let lhs = lhs as u32;
-                    let rhs = rhs as u32;
-                    lhs.le(&rhs) as i32
-                });
-            }
-            Instr::I32GtS => {
-                self.execute_i32_binop(|lhs, rhs| lhs.gt(&rhs) as i32);
-            }
-            Instr::I32GtU => {
-                self.execute_i32_binop(|lhs, rhs| {
-                    let lhs = lhs as u32;
-                    let rhs = rhs as u32;
-                    lhs.gt(&rhs) as i32
-                });
-            }
-            Instr::I32GeS => {
-                self.execute_i32_binop(|lhs, rhs| lhs.ge(&lhs) as i32);
-            }
-            Instr::I32GeU => {
-                self.execute_i32_binop(|lhs, rhs| {
-                    let lhs = lhs as u32;
-                    let rhs = rhs as u32;
-                    lhs.ge(&rhs) as i32
-                });
-            }
-            Instr::I64Eqz => {
-                self.execute_i64_unop(|x| x.eq(&0) as i64);
-            }
-            Instr::I64Sub => {
-                self.execute_i64_binop(|lhs, rhs| lhs - rhs);
-            }
-            Instr::I64Mul => {
-                self.execute_i64_binop(|lhs, rhs| lhs * rhs);
-            }
-            _ => unimplemented!("{:?}", instr),
         }
+        Ok(())
     }
+}
 
-    fn execute_i32_unop<F>(&mut self, f: F)
-    where
-        F: FnOnce(i32) -> i32,
-    {
-        let x = match self.stack.pop() {
-            Some(StackValue::Value(Val::I32(value))) => value,
-            _ => panic!(),
-        };
-        let result = f(x);
-        self.stack.push(StackValue::Value(Val::I32(result)));
+/// The operand stack, which holds the frames of running calls. Values are untyped 64-bit
+/// slots, as in `Val::to_bits`.
+#[derive(Debug, Default)]
+pub struct Stack {
+    values: Vec<u64>,
+}
+
+impl Stack {
+    pub fn new() -> Self {
+        Self { values: Vec::new() }
+    }
+
+    pub fn push(&mut self, value: u64) {
+        self.values.push(value)
+    }
+
+    pub fn pop(&mut self) -> u64 {
+        self.values.pop().expect("operand stack underflow")
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
     }
+}
 
-    fn execute_i64_unop<F>(&mut self, f: F)
+/// A function call in progress.
+#[derive(Debug)]
+struct Activation {
+    func_addr: FuncAddr,
+    module: Rc<ModuleInst>,
+    code: Rc<Code>,
+    /// The index of the next op.
+    pc: usize,
+    /// The index of the frame's first slot in the stack.
+    base: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Val {
+    I32(i32),
+    I64(i64),
+    F32(f32),
+    F64(f64),
+    /// A function reference, or `None` for a null reference.
+    FuncRef(Option<FuncAddr>),
+}
+
+#[derive(Default)]
+pub struct Store {
+    pub funcs: Vec<FuncInst>,
+    pub tables: Vec<TableInst>,
+    pub mems: Vec<MemInst>,
+    pub wasi: Option<WasiCtx>,
+    /// Fuel for metering execution, or `None` to run unbounded.
+    pub(crate) fuel: Option<Fuel>,
+    pub engine: Engine,
+    /// The engine epoch at which running guests are interrupted, if any.
+    pub epoch_deadline: Option<u64>,
+    pub stack_limits: StackLimits,
+    pub limiter: Option<Box<dyn ResourceLimiter>>,
+    /// The number of module instances, checked against the limiter.
+    pub instance_count: usize,
+    /// The frames of compiled code, kept between calls.
+    #[cfg(feature = "jit")]
+    pub(crate) jit_frames: Vec<u64>,
+}
+
+impl fmt::Debug for Store {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Store")
+            .field("funcs", &self.funcs)
+            .field("tables", &self.tables)
+            .field("mems", &self.mems)
+            .field("wasi", &self.wasi)
+            .field("fuel", &self.fuel)
+            .field("engine", &self.engine)
+            .field("epoch_deadline", &self.epoch_deadline)
+            .field("stack_limits", &self.stack_limits)
+            .field("instance_count", &self.instance_count)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Limits on the stacks of running guests, which trap with `StackExhausted` when exceeded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StackLimits {
+    pub max_call_depth: usize,
+    /// The maximum number of operands and locals, checked on function entry.
+    pub max_values: usize,
+}
+
+impl Default for StackLimits {
+    fn default() -> Self {
+        Self {
+            max_call_depth: 10_000,
+            max_values: 1 << 20,
+        }
+    }
+}
+
+impl Store {
+    pub fn new(engine: &Engine) -> Self {
+        Self {
+            engine: engine.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Interrupts guests once the engine epoch has been incremented `delta` times from now.
+    pub fn set_epoch_deadline(&mut self, delta: u64) {
+        self.epoch_deadline = Some(self.engine.epoch().saturating_add(delta));
+    }
+
+    pub fn set_limiter<L>(&mut self, limiter: L)
     where
-        F: FnOnce(i64) -> i64,
+        L: ResourceLimiter + 'static,
     {
-        let x = match self.stack.pop() {
-            Some(StackValue::Value(Val::I64(value))) => value,
-            _ => panic!(),
-        };
-        let result = f(x);
-        self.stack.push(StackValue::Value(Val::I64(result)));
+        self.limiter = Some(Box::new(limiter));
+    }
+
+    /// Enables fuel metering with `fuel` remaining. Costs and refills set before are kept.
+    pub fn set_fuel(&mut self, fuel: u64) -> Result<(), FuelError> {
+        self.metered()?.set(fuel);
+        Ok(())
+    }
+
+    /// Adds fuel, enabling metering if it was disabled.
+    pub fn add_fuel(&mut self, fuel: u64) -> Result<(), FuelError> {
+        self.metered()?.add(fuel);
+        Ok(())
     }
 
-    fn execute_i32_binop<F>(&mut self, f: F)
+    /// Sets the cost of each instruction, enabling metering with no fuel if it was disabled.
+    pub fn set_fuel_costs<F>(&mut self, costs: F) -> Result<(), FuelError>
     where
-        F: FnOnce(i32, i32) -> i32,
+        F: Fn(&Instr) -> u64 + 'static,
     {
-        let rhs = match self.stack.pop() {
-            Some(StackValue::Value(Val::I32(value))) => value,
-            _ => panic!(),
-        };
-        let lhs = match self.stack.pop() {
-            Some(StackValue::Value(Val::I32(value))) => value,
-            _ => panic!(),
-        };
-        let result = f(lhs, rhs);
-        self.stack.push(StackValue::Value(Val::I32(result)));
+        self.metered()?.set_costs(costs);
+        Ok(())
     }
 
-    fn execute_i64_binop<F>(&mut self, f: F)
+    /// Sets a callback that is asked for more fuel when it runs out, enabling metering with no
+    /// fuel if it was disabled.
+    pub fn set_fuel_refill<F>(&mut self, refill: F) -> Result<(), FuelError>
     where
-        F: FnOnce(i64, i64) -> i64,
+        F: FnMut() -> Option<u64> + 'static,
     {
-        let rhs = match self.stack.pop() {
-            Some(StackValue::Value(Val::I64(value))) => value,
-            _ => panic!(),
-        };
-        let lhs = match self.stack.pop() {
-            Some(StackValue::Value(Val::I64(value))) => value,
-            _ => panic!(),
-        };
-        let result = f(lhs, rhs);
-        self.stack.push(StackValue::Value(Val::I64(result)));
+        self.metered()?.set_refill(refill);
+        Ok(())
     }
-}
 
-#[derive(Debug)]
-pub struct Stack {
-    values: Vec<StackValue>,
-}
-
-impl Stack {
-    pub fn new() -> Self {
-        Self { values: Vec::new() }
+    /// Returns the fuel of the store, enabling metering if the backend supports it.
+    fn metered(&mut self) -> Result<&mut Fuel, FuelError> {
+        let backend = self.engine.config().backend;
+        if backend != Backend::Stack {
+            return Err(FuelError::UnsupportedBackend(backend));
+        }
+        Ok(self.fuel.get_or_insert_with(|| Fuel::new(0)))
     }
 
-    pub fn push(&mut self, val: StackValue) {
-        self.values.push(val)
+    /// Checks the epoch deadline and the stack limits on entry to a module function, with
+    /// `depth` calls below it and `values` operands and locals in use once it's entered.
+    pub(crate) fn check_entry(&self, depth: usize, values: usize) -> Result<(), Trap> {
+        // Calls are the only back-edges of the supported instructions, so checking the epoch
+        // on function entry bounds how long a guest runs past its deadline.
+        if let Some(deadline) = self.epoch_deadline {
+            if self.engine.epoch() >= deadline {
+                return Err(TrapKind::Interrupted.into());
+            }
+        }
+        if depth >= self.stack_limits.max_call_depth || values > self.stack_limits.max_values {
+            return Err(TrapKind::StackExhausted.into());
+        }
+        Ok(())
     }
 
-    pub fn pop(&mut self) -> Option<StackValue> {
-        self.values.pop()
+    /// Returns the remaining fuel, or `None` if execution isn't metered.
+    pub fn fuel(&self) -> Option<u64> {
+        self.fuel.as_ref().map(Fuel::remaining)
     }
 }
 
-#[derive(Debug)]
-pub enum StackValue {
-    Value(Val),
-    Label(Label),
-    Frame(Rc<RefCell<Frame>>),
+#[derive(Debug, Clone, PartialEq)]
+pub enum FuncInst {
+    Module {
+        type_: FuncType,
+        module: Rc<ModuleInst>,
+        code: Rc<Code>,
+        /// The code for the register backend, if the engine uses it.
+        register_code: Option<Rc<register::Code>>,
+        /// The machine code, if the engine uses the JIT and the function could be compiled.
+        #[cfg(feature = "jit")]
+        jit: Option<jit::Entry>,
+        /// The function, if it is validated and compiled on its first call. Until then `code`
+        /// is empty.
+        pending: Option<Box<PendingFunc>>,
+    },
+    Host {
+        type_: FuncType,
+        code: HostFunc,
+    },
 }
 
-#[derive(Debug)]
-pub struct Label {
-    pub argument_arity: u32,
-    pub instructions: Vec<Instr>,
+/// A function of a module instance that hasn't been validated and compiled yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingFunc {
+    pub(crate) func: Func,
+    /// The body of `func`, if the decoder left it undecoded.
+    pub(crate) body: Option<LazyBody>,
 }
 
-#[derive(Debug)]
-pub struct Frame {
-    pub return_arity: u32,
-    pub locals: Vec<Val>,
-    // pub module_inst: Weak<ModuleInst>,
+impl FuncInst {
+    pub fn type_(&self) -> &FuncType {
+        match self {
+            FuncInst::Module { type_, .. } => type_,
+            FuncInst::Host { type_, .. } => type_,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
-pub enum Val {
-    I32(i32),
-    I64(i64),
-    F32(f32),
-    F64(f64),
+pub struct TableInst {
+    pub type_: TableType,
+    pub elements: Vec<Option<FuncAddr>>,
 }
 
-#[derive(Debug, Clone)]
-pub struct Store {
-    pub funcs: Vec<FuncInst>,
+impl TableInst {
+    pub fn new(type_: TableType) -> Self {
+        let elements = vec![None; type_.0.min as usize];
+        Self { type_, elements }
+    }
+
+    pub fn size(&self) -> u32 {
+        self.elements.len() as u32
+    }
+
+    /// Grows the table by `delta` elements set to `init` and returns the previous size, or
+    /// `None` if the table would exceed its maximum, the limiter refuses or the elements
+    /// can't be allocated.
+    pub fn grow(
+        &mut self,
+        delta: u32,
+        init: Option<FuncAddr>,
+        limiter: Option<&mut dyn ResourceLimiter>,
+    ) -> Option<u32> {
+        let old_size = self.size();
+        let new_size = old_size.checked_add(delta)?;
+        if self.type_.0.max.is_some_and(|max| new_size > max) {
+            return None;
+        }
+        if let Some(limiter) = limiter {
+            let maximum = self.type_.0.max.map(|max| max as usize);
+            if !limiter.table_growing(old_size as usize, new_size as usize, maximum) {
+                return None;
+            }
+        }
+        self.elements.try_reserve(delta as usize).ok()?;
+        self.elements.resize(new_size as usize, init);
+        Some(old_size)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct FuncInst {
-    pub type_: FuncType,
-    // module: Weak<ModuleInst>,
-    pub code: Func,
+pub struct MemInst {
+    pub type_: MemType,
+    pub data: Vec<u8>,
+}
+
+impl MemInst {
+    pub fn new(type_: MemType) -> Self {
+        let data = vec![0; type_.0.min as usize * PAGE_SIZE];
+        Self { type_, data }
+    }
+
+    pub fn size(&self) -> u32 {
+        (self.data.len() / PAGE_SIZE) as u32
+    }
+
+    /// Reads `N` bytes at the effective address `base + offset`.
+    pub fn load<const N: usize>(&self, base: u32, offset: u32) -> Result<[u8; N], Trap> {
+        let ea = self.effective_address(base, offset, N)?;
+        let mut bytes = [0; N];
+        bytes.copy_from_slice(&self.data[ea..ea + N]);
+        Ok(bytes)
+    }
+
+    /// Writes `bytes` at the effective address `base + offset`.
+    pub fn store(&mut self, base: u32, offset: u32, bytes: &[u8]) -> Result<(), Trap> {
+        let ea = self.effective_address(base, offset, bytes.len())?;
+        self.data[ea..ea + bytes.len()].copy_from_slice(bytes);
+        Ok(())
+    }
+
+    fn effective_address(&self, base: u32, offset: u32, len: usize) -> Result<usize, Trap> {
+        let ea = base as usize + offset as usize;
+        if self.data.len() < ea + len {
+            return Err(TrapKind::MemoryOutOfBounds.into());
+        }
+        Ok(ea)
+    }
+
+    /// Grows the memory by `delta` pages and returns the previous size, or `None` if the
+    /// memory would exceed its maximum, the limiter refuses or the pages can't be allocated.
+    pub fn grow(&mut self, delta: u32, limiter: Option<&mut dyn ResourceLimiter>) -> Option<u32> {
+        let old_size = self.size();
+        let new_size = old_size.checked_add(delta)?;
+        let max = self.type_.0.max.unwrap_or(65536);
+        if new_size > max || new_size > 65536 {
+            return None;
+        }
+        let desired = (new_size as usize).checked_mul(PAGE_SIZE)?;
+        if let Some(limiter) = limiter {
+            let maximum = self
+                .type_
+                .0
+                .max
+                .map(|max| (max as usize).saturating_mul(PAGE_SIZE));
+            if !limiter.memory_growing(self.data.len(), desired, maximum) {
+                return None;
+            }
+        }
+        self.data.try_reserve(desired - self.data.len()).ok()?;
+        self.data.resize(desired, 0);
+        Some(old_size)
+    }
 }
 
 pub type Addr = u32;
 pub type FuncAddr = Addr;
+pub type TableAddr = Addr;
+pub type MemAddr = Addr;
 
 pub enum AdministrativeInstruction {
     Invoke(FuncAddr),
-    Label(Label),
     Frame,
 }
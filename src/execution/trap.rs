@@ -0,0 +1,84 @@
+use crate::validation::ValidationError;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trap {
+    pub kind: TrapKind,
+    /// The wasm frames that were active when the trap happened, innermost first.
+    pub backtrace: Vec<FrameInfo>,
+}
+
+impl Trap {
+    pub fn new(kind: TrapKind) -> Self {
+        Self {
+            kind,
+            backtrace: Vec::new(),
+        }
+    }
+}
+
+impl From<TrapKind> for Trap {
+    fn from(kind: TrapKind) -> Self {
+        Trap::new(kind)
+    }
+}
+
+impl fmt::Display for Trap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrapKind {
+    MemoryOutOfBounds,
+    IntegerDivideByZero,
+    /// A signed division whose quotient doesn't fit, e.g. `i32.div_s` of `MIN` by -1.
+    IntegerOverflow,
+    Exit(i32),
+    Host(String),
+    OutOfFuel,
+    Interrupted,
+    StackExhausted,
+    /// A lazily compiled function turned out to be invalid when it was first called.
+    Invalid(ValidationError),
+}
+
+impl fmt::Display for TrapKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrapKind::MemoryOutOfBounds => write!(f, "out of bounds memory access"),
+            TrapKind::IntegerDivideByZero => write!(f, "integer divide by zero"),
+            TrapKind::IntegerOverflow => write!(f, "integer overflow"),
+            TrapKind::Exit(code) => write!(f, "exited with code {}", code),
+            TrapKind::Host(message) => write!(f, "{}", message),
+            TrapKind::OutOfFuel => write!(f, "all fuel consumed"),
+            TrapKind::Interrupted => write!(f, "interrupted"),
+            TrapKind::StackExhausted => write!(f, "call stack exhausted"),
+            TrapKind::Invalid(error) => write!(f, "invalid module: {}", error),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameInfo {
+    pub func_index: u32,
+    /// The name of the function in the name section.
+    pub func_name: Option<String>,
+    /// The code section offset of the executing instruction, if the module was decoded from a
+    /// binary.
+    pub offset: Option<u32>,
+}
+
+impl fmt::Display for FrameInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.offset {
+            Some(offset) => write!(f, "{:#08x} - ", offset)?,
+            None => write!(f, "{:>8} - ", "?")?,
+        }
+        match &self.func_name {
+            Some(name) => write!(f, "${} (func {})", name, self.func_index),
+            None => write!(f, "func[{}]", self.func_index),
+        }
+    }
+}
@@ -0,0 +1,363 @@
+use crate::execution::structure::{FuncAddr, Val};
+use crate::structure::types::value::{NumType, RefType, ValType};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseValError {
+    pub text: String,
+    pub type_: ValType,
+}
+
+impl fmt::Display for ParseValError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is not a valid {} value", self.text, self.type_)
+    }
+}
+
+impl Val {
+    /// Parses a value written as in the text format: decimal or `0x` hexadecimal integers,
+    /// and decimal or hexadecimal floats, `inf` and `nan` (optionally `nan:0x<payload>`). The
+    /// only reference that can be written is `null`.
+    pub fn parse(text: &str, type_: &ValType) -> Result<Val, ParseValError> {
+        let val = match type_ {
+            ValType::NumType(NumType::I32) => parse_i32(text).map(Val::I32),
+            ValType::NumType(NumType::I64) => parse_i64(text).map(Val::I64),
+            ValType::NumType(NumType::F32) => parse_f32(text).map(Val::F32),
+            ValType::NumType(NumType::F64) => parse_f64(text).map(Val::F64),
+            ValType::RefType(RefType::FuncRef) => (text == "null").then_some(Val::FuncRef(None)),
+        };
+        val.ok_or_else(|| ParseValError {
+            text: text.to_string(),
+            type_: type_.clone(),
+        })
+    }
+
+    pub fn type_(&self) -> ValType {
+        match self {
+            Val::I32(_) => ValType::NumType(NumType::I32),
+            Val::I64(_) => ValType::NumType(NumType::I64),
+            Val::F32(_) => ValType::NumType(NumType::F32),
+            Val::F64(_) => ValType::NumType(NumType::F64),
+            Val::FuncRef(_) => ValType::RefType(RefType::FuncRef),
+        }
+    }
+
+    /// Returns the value as an untyped stack slot. Integers are zero-extended and floats keep
+    /// their bit pattern, so NaN payloads survive the round trip. References are stored as
+    /// their address plus one, so that zeroed slots are null.
+    pub fn to_bits(&self) -> u64 {
+        match self {
+            Val::I32(x) => *x as u32 as u64,
+            Val::I64(x) => *x as u64,
+            Val::F32(x) => x.to_bits() as u64,
+            Val::F64(x) => x.to_bits(),
+            Val::FuncRef(addr) => addr.map_or(0, |addr| addr as u64 + 1),
+        }
+    }
+
+    /// Reads a stack slot back as a value of the given type.
+    pub fn from_bits(bits: u64, type_: &ValType) -> Val {
+        match type_ {
+            ValType::NumType(NumType::I32) => Val::I32(bits as i32),
+            ValType::NumType(NumType::I64) => Val::I64(bits as i64),
+            ValType::NumType(NumType::F32) => Val::F32(f32::from_bits(bits as u32)),
+            ValType::NumType(NumType::F64) => Val::F64(f64::from_bits(bits)),
+            ValType::RefType(RefType::FuncRef) => Val::FuncRef(func_ref(bits)),
+        }
+    }
+}
+
+/// Reads a stack slot back as a function reference.
+pub(crate) fn func_ref(bits: u64) -> Option<FuncAddr> {
+    bits.checked_sub(1).map(|addr| addr as FuncAddr)
+}
+
+impl fmt::Display for Val {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Val::I32(x) => write!(f, "{}", x),
+            Val::I64(x) => write!(f, "{}", x),
+            Val::F32(x) if x.is_nan() => {
+                write!(f, "{}nan", if x.is_sign_negative() { "-" } else { "" })
+            }
+            Val::F64(x) if x.is_nan() => {
+                write!(f, "{}nan", if x.is_sign_negative() { "-" } else { "" })
+            }
+            Val::F32(x) => write!(f, "{:?}", x),
+            Val::F64(x) => write!(f, "{:?}", x),
+            Val::FuncRef(None) => write!(f, "null"),
+            Val::FuncRef(Some(addr)) => write!(f, "func {}", addr),
+        }
+    }
+}
+
+/// Splits off the sign and removes `_` digit separators.
+fn split_sign(text: &str) -> (bool, String) {
+    let (negative, digits) = match text.as_bytes().first() {
+        Some(b'-') => (true, &text[1..]),
+        Some(b'+') => (false, &text[1..]),
+        _ => (false, text),
+    };
+    (negative, digits.replace('_', ""))
+}
+
+/// Parses an integer with the given bit width, accepting both the signed and the unsigned
+/// range, e.g. `-1` and `0xffffffff` for i32.
+fn parse_int(text: &str, bits: u32) -> Option<u64> {
+    let (negative, digits) = split_sign(text);
+    if digits.is_empty() || digits.starts_with(['+', '-']) {
+        return None;
+    }
+    let magnitude = match digits.strip_prefix("0x") {
+        Some(hex) => parse_hex(hex)?,
+        None => digits.parse::<u64>().ok()?,
+    };
+
+    let mask = if bits == 64 {
+        u64::MAX
+    } else {
+        (1 << bits) - 1
+    };
+    if negative {
+        if magnitude > 1 << (bits - 1) {
+            return None;
+        }
+        Some(magnitude.wrapping_neg() & mask)
+    } else {
+        if magnitude > mask {
+            return None;
+        }
+        Some(magnitude)
+    }
+}
+
+/// Parses hexadecimal digits, which unlike `u64::from_str_radix` refuses a sign.
+fn parse_hex(hex: &str) -> Option<u64> {
+    if hex.is_empty() || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    u64::from_str_radix(hex, 16).ok()
+}
+
+pub fn parse_i32(text: &str) -> Option<i32> {
+    parse_int(text, 32).map(|x| x as u32 as i32)
+}
+
+pub fn parse_i64(text: &str) -> Option<i64> {
+    parse_int(text, 64).map(|x| x as i64)
+}
+
+pub fn parse_f32(text: &str) -> Option<f32> {
+    parse_float(text, 23, 8).map(|bits| f32::from_bits(bits as u32))
+}
+
+pub fn parse_f64(text: &str) -> Option<f64> {
+    parse_float(text, 52, 11).map(f64::from_bits)
+}
+
+/// Parses a float into the bits of an IEEE 754 binary format with the given field widths.
+fn parse_float(text: &str, mantissa_bits: u32, exponent_bits: u32) -> Option<u64> {
+    let (negative, digits) = split_sign(text);
+    let sign = (negative as u64) << (mantissa_bits + exponent_bits);
+    let exponent_mask = ((1 << exponent_bits) - 1) << mantissa_bits;
+
+    let magnitude = if digits == "inf" || digits == "infinity" {
+        exponent_mask
+    } else if digits == "nan" {
+        exponent_mask | 1 << (mantissa_bits - 1)
+    } else if let Some(payload) = digits.strip_prefix("nan:0x") {
+        let payload = parse_hex(payload)?;
+        if payload == 0 || payload >= 1 << mantissa_bits {
+            return None;
+        }
+        exponent_mask | payload
+    } else if let Some(hex) = digits.strip_prefix("0x") {
+        parse_hex_float(hex, mantissa_bits, exponent_bits)?
+    } else {
+        if !digits.starts_with(|c: char| c.is_ascii_digit()) {
+            return None;
+        }
+        // Rust rounds out-of-range literals to infinity, but they are malformed in wasm.
+        let bits = if mantissa_bits == 23 {
+            let x = digits.parse::<f32>().ok()?;
+            x.is_finite().then_some(x.to_bits() as u64)?
+        } else {
+            let x = digits.parse::<f64>().ok()?;
+            x.is_finite().then_some(x.to_bits())?
+        };
+        bits & !(1 << (mantissa_bits + exponent_bits))
+    };
+    Some(sign | magnitude)
+}
+
+/// Parses the digits of a hexadecimal float such as `1.8p3` (after `0x`) with correct
+/// round-to-nearest-even.
+fn parse_hex_float(hex: &str, mantissa_bits: u32, exponent_bits: u32) -> Option<u64> {
+    let (significand, exponent) = match hex.find(['p', 'P']) {
+        Some(i) => (&hex[..i], hex[i + 1..].parse::<i64>().ok()?),
+        None => (hex, 0),
+    };
+    let (integer, fraction) = match significand.split_once('.') {
+        Some((integer, fraction)) => (integer, fraction),
+        None => (significand, ""),
+    };
+    if integer.is_empty() && fraction.is_empty() {
+        return None;
+    }
+
+    // Keep the leading 60 bits of the significand and remember whether anything was dropped.
+    // The exponent arithmetic saturates, since the written exponent can be any i64.
+    let mut m: u64 = 0;
+    let mut e: i64 = exponent;
+    let mut sticky = false;
+    for (i, c) in integer.chars().chain(fraction.chars()).enumerate() {
+        let digit = c.to_digit(16)? as u64;
+        let is_fraction = i >= integer.len();
+        if m >> 56 == 0 {
+            m = m << 4 | digit;
+            if is_fraction {
+                e = e.saturating_sub(4);
+            }
+        } else {
+            sticky |= digit != 0;
+            if !is_fraction {
+                e = e.saturating_add(4);
+            }
+        }
+    }
+    if m == 0 {
+        return Some(0);
+    }
+
+    let bias = (1i64 << (exponent_bits - 1)) - 1;
+    let msb = 63 - m.leading_zeros() as i64;
+    let mut biased_exponent = e.saturating_add(msb + bias);
+    let max_exponent = (1 << exponent_bits) - 1;
+    if biased_exponent >= max_exponent {
+        return None;
+    }
+
+    // Number of low bits of `m` to round away.
+    let mut shift = msb - mantissa_bits as i64;
+    if biased_exponent <= 0 {
+        shift = shift.saturating_add(1i64.saturating_sub(biased_exponent));
+        biased_exponent = 0;
+    }
+
+    let mut mantissa = if shift > 0 {
+        let m = m as u128;
+        let shift = shift.min(100) as u32;
+        let dropped = m & ((1 << shift) - 1);
+        let half = 1 << (shift - 1);
+        let kept = (m >> shift) as u64;
+        let round_up = dropped > half || (dropped == half && (sticky || kept & 1 == 1));
+        kept + round_up as u64
+    } else {
+        m << -shift
+    };
+
+    if biased_exponent == 0 {
+        // subnormal, possibly rounded up into the smallest normal number
+        if mantissa >> mantissa_bits != 0 {
+            biased_exponent = 1;
+        }
+    } else if mantissa >> (mantissa_bits + 1) != 0 {
+        mantissa >>= 1;
+        biased_exponent += 1;
+    }
+    if biased_exponent >= max_exponent {
+        return None;
+    }
+
+    let mantissa = mantissa & ((1 << mantissa_bits) - 1);
+    Some((biased_exponent as u64) << mantissa_bits | mantissa)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_int() {
+        assert_eq!(parse_i32("42"), Some(42));
+        assert_eq!(parse_i32("-2147483648"), Some(i32::MIN));
+        assert_eq!(parse_i32("4294967295"), Some(-1));
+        assert_eq!(parse_i32("0xffff_ffff"), Some(-1));
+        assert_eq!(parse_i32("-0x10"), Some(-16));
+        assert_eq!(parse_i32("4294967296"), None);
+        assert_eq!(parse_i32("-2147483649"), None);
+        assert_eq!(parse_i32("1.5"), None);
+        assert_eq!(parse_i32("--1"), None);
+        assert_eq!(parse_i64("0x8000000000000000"), Some(i64::MIN));
+        assert_eq!(parse_i64("-9223372036854775808"), Some(i64::MIN));
+        assert_eq!(parse_i32("0x+5"), None);
+        assert_eq!(parse_i32("0x-5"), None);
+        assert_eq!(parse_i32("0x"), None);
+    }
+
+    #[test]
+    fn test_parse_float() {
+        assert_eq!(parse_f32("1.5"), Some(1.5));
+        assert_eq!(parse_f32("-0"), Some(-0.0));
+        assert!(parse_f32("-0").unwrap().is_sign_negative());
+        assert_eq!(parse_f32("inf"), Some(f32::INFINITY));
+        assert_eq!(parse_f64("-inf"), Some(f64::NEG_INFINITY));
+        assert_eq!(parse_f32("nan").map(f32::to_bits), Some(0x7fc00000));
+        assert_eq!(parse_f32("-nan").map(f32::to_bits), Some(0xffc00000));
+        assert_eq!(
+            parse_f32("nan:0x200000").map(f32::to_bits),
+            Some(0x7fa00000)
+        );
+        assert_eq!(
+            parse_f64("nan:0x1").map(f64::to_bits),
+            Some(0x7ff0000000000001)
+        );
+        assert_eq!(parse_f32("0x1.8p3"), Some(12.0));
+        assert_eq!(parse_f64("-0x1p-2"), Some(-0.25));
+        assert_eq!(parse_f64("0x1.fffffffffffffp1023"), Some(f64::MAX));
+        assert_eq!(parse_f64("0x1p-1074"), Some(f64::from_bits(1)));
+        assert_eq!(parse_f64("0x1p1024"), None);
+        assert_eq!(parse_f32("0x1.000001p0"), Some(1.0));
+        assert_eq!(parse_f32("0x1.000003p0"), Some(1.0000002));
+        assert_eq!(parse_f32("0x1p-149"), Some(f32::from_bits(1)));
+        assert_eq!(
+            parse_f32("0x1.fffffep-127"),
+            Some(f32::from_bits(0x00800000))
+        );
+        assert_eq!(parse_f32("abc"), None);
+        assert_eq!(parse_f64("1e400"), None);
+        assert_eq!(parse_f32("1e39"), None);
+        assert_eq!(parse_f64("1e-400"), Some(0.0));
+        assert_eq!(parse_f64("0x1p9223372036854775807"), None);
+        assert_eq!(parse_f64("0x1p-9223372036854775808"), Some(0.0));
+        assert_eq!(
+            parse_f32("0xffff_ffff_ffff_ffff_ffffp9223372036854775807"),
+            None
+        );
+        assert_eq!(parse_f32("nan:0x+1"), None);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Val::I32(-1).to_string(), "-1");
+        assert_eq!(Val::F32(1.0).to_string(), "1.0");
+        assert_eq!(Val::F64(f64::NEG_INFINITY).to_string(), "-inf");
+        assert_eq!(Val::F32(f32::NAN).to_string(), "nan");
+    }
+
+    #[test]
+    fn test_bits() {
+        assert_eq!(Val::I32(-1).to_bits(), 0xffff_ffff);
+        assert_eq!(Val::I64(-1).to_bits(), u64::MAX);
+        let nan = f32::from_bits(0x7fa00000);
+        let bits = Val::F32(nan).to_bits();
+        assert_eq!(bits, 0x7fa00000);
+        let Val::F32(x) = Val::from_bits(bits, &ValType::NumType(NumType::F32)) else {
+            panic!();
+        };
+        assert_eq!(x.to_bits(), 0x7fa00000);
+        for val in [Val::I32(i32::MIN), Val::I64(i64::MIN), Val::F64(-0.5)] {
+            assert_eq!(Val::from_bits(val.to_bits(), &val.type_()), val);
+        }
+    }
+}
@@ -0,0 +1,14 @@
+#[cfg(feature = "cache")]
+pub mod cache;
+pub mod decoder;
+#[cfg(feature = "dwarf")]
+pub mod dwarf;
+pub mod encoder;
+pub mod execution;
+pub mod serialize;
+pub mod streaming;
+pub mod structure;
+pub mod text;
+pub mod validation;
+pub mod wasi;
+pub mod wast;
@@ -1,41 +1,571 @@
-use crate::decoder::Decoder;
-use crate::execution::instance::{instantiate, invoke};
-use crate::execution::structure::{Store, Val};
-use clap::Parser;
-use std::fs::File;
-use std::io::Read;
+use clap::{Args, Parser, Subcommand};
+#[cfg(feature = "cache")]
+use mini_wasm::cache::Cache;
+use mini_wasm::decoder::Decoder;
+#[cfg(feature = "dwarf")]
+use mini_wasm::dwarf::DebugInfo;
+use mini_wasm::encoder::Encoder;
+use mini_wasm::execution::engine::{Config, Engine};
+use mini_wasm::execution::instance::{invoke_func, InstantiationError, ModuleInst};
+use mini_wasm::execution::linker::Linker;
+use mini_wasm::execution::structure::{Store, Val};
+use mini_wasm::execution::trap::{Trap, TrapKind};
+use mini_wasm::streaming::{self, StreamError};
+use mini_wasm::structure::modules::export::ExportDesc;
+use mini_wasm::structure::modules::import::ImportDesc;
+use mini_wasm::structure::modules::module::Module;
+use mini_wasm::structure::types::memory::Limits;
+use mini_wasm::structure::types::value::ValType;
+use mini_wasm::text;
+use mini_wasm::validation::validate;
+use mini_wasm::wasi::{preview1, WasiCtx};
+use mini_wasm::wast::{Runner, Summary};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::rc::Rc;
 
-mod decoder;
-mod execution;
-mod structure;
+/// Exit code used when execution traps, so that traps can be told apart from other failures.
+const TRAP_EXIT_CODE: i32 = 134;
 
 #[derive(Parser)]
 #[command(version)]
 struct Cli {
-    filename: String,
+    #[command(subcommand)]
+    command: Command,
 
-    /// Run exported function by name
-    #[arg(short = 'r', long = "run-export", value_name = "FUNCTION")]
-    exported_function: String, // FIXME: Option<String>
+    /// Cache decoded modules, in `~/.cache/mini-wasm` unless `--cache-dir` is given
+    #[cfg(feature = "cache")]
+    #[arg(long, global = true, conflicts_with = "lazy")]
+    cache: bool,
 
-    /// Add argument to an exported function execution
-    #[arg(short = 'a', long = "argument", value_name = "ARGUMENT")]
-    arguments: Vec<i32>, // FIXME: Vec<String>
+    /// Directory of the module cache, which implies `--cache`
+    #[cfg(feature = "cache")]
+    #[arg(long, global = true, value_name = "DIR", conflicts_with = "lazy")]
+    cache_dir: Option<PathBuf>,
+
+    /// Decode, validate and compile each function when it is first called
+    #[arg(long, global = true)]
+    lazy: bool,
+
+    /// Validate and compile function bodies on all cores
+    #[cfg(feature = "parallel")]
+    #[arg(long, global = true)]
+    parallel: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the start function and `_start` of a WASI program
+    Run {
+        filename: PathBuf,
+
+        #[command(flatten)]
+        wasi: WasiOptions,
+
+        /// Arguments passed to the WASI program
+        #[arg(last = true, value_name = "ARGS")]
+        args: Vec<String>,
+    },
+    /// Call an exported function and print its results
+    Invoke {
+        filename: PathBuf,
+
+        /// Name of the exported function
+        function: String,
+
+        /// Arguments of the function, parsed by the parameter types
+        #[arg(value_name = "ARGS", allow_hyphen_values = true)]
+        args: Vec<String>,
+
+        #[command(flatten)]
+        wasi: WasiOptions,
+    },
+    /// Check that a module is well-formed and valid
+    Validate { filename: PathBuf },
+    /// Print the types, imports, functions, tables, memories and exports of a module
+    Inspect { filename: PathBuf },
+    /// Print the sections of a module with their offsets and sizes
+    Dump { filename: PathBuf },
+    /// Print a module in the text format
+    Print { filename: PathBuf },
+    /// Run spec test scripts, given as `.wast` files or directories containing them
+    Wast {
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+    },
+}
+
+#[derive(Args)]
+struct WasiOptions {
+    /// Pass an environment variable to the WASI program
+    #[arg(long = "env", value_name = "NAME=VALUE")]
+    envs: Vec<String>,
+
+    /// Grant the WASI program access to a host directory
+    #[arg(long = "dir", value_name = "HOST_PATH[::GUEST_PATH]")]
+    dirs: Vec<String>,
+
+    /// Grant the WASI program read-only access to a host directory
+    #[arg(long = "readonly-dir", value_name = "HOST_PATH[::GUEST_PATH]")]
+    readonly_dirs: Vec<String>,
+}
+
+enum Error {
+    Failure(String),
+    Trap(Trap),
+}
+
+impl From<Trap> for Error {
+    fn from(trap: Trap) -> Self {
+        Error::Trap(trap)
+    }
+}
+
+impl From<InstantiationError> for Error {
+    fn from(error: InstantiationError) -> Self {
+        match error {
+            InstantiationError::Trap(trap) => Error::Trap(trap),
+            error => Error::Failure(error.to_string()),
+        }
+    }
+}
+
+impl Command {
+    /// The module that is executed, whose debug information is used for trap backtraces.
+    fn executed(&self) -> Option<&Path> {
+        match self {
+            Command::Run { filename, .. } | Command::Invoke { filename, .. } => Some(filename),
+            _ => None,
+        }
+    }
 }
 
 fn main() {
     let cli = Cli::parse();
+    let executed = cli.command.executed().map(Path::to_path_buf);
+    let loader = Loader {
+        engine: Engine::with_config(Config {
+            lazy_compilation: cli.lazy,
+            #[cfg(feature = "parallel")]
+            parallel_compilation: cli.parallel,
+            ..Default::default()
+        }),
+        #[cfg(feature = "cache")]
+        cache: match cli.cache_dir {
+            Some(dir) => Some(Cache::new(dir)),
+            None if cli.cache => Cache::default_dir().map(Cache::new),
+            None => None,
+        },
+    };
+
+    let result = match cli.command {
+        Command::Run {
+            filename,
+            wasi,
+            args,
+        } => run(&filename, &wasi, args, &loader),
+        Command::Invoke {
+            filename,
+            function,
+            args,
+            wasi,
+        } => invoke(&filename, &function, &args, &wasi, &loader),
+        Command::Validate { filename } => validate_file(&filename),
+        Command::Inspect { filename } => inspect(&filename),
+        Command::Dump { filename } => dump(&filename),
+        Command::Print { filename } => {
+            load(&filename).map(|module| print!("{}", text::print(&module)))
+        }
+        Command::Wast { paths } => run_wast(&paths),
+    };
+
+    match result {
+        Ok(()) => {}
+        Err(Error::Trap(Trap {
+            kind: TrapKind::Exit(code),
+            ..
+        })) => process::exit(code),
+        Err(Error::Trap(trap)) => {
+            eprintln!("error: wasm trap: {}", trap);
+            print_backtrace(&trap, executed.as_deref());
+            process::exit(TRAP_EXIT_CODE);
+        }
+        Err(Error::Failure(message)) => {
+            eprintln!("error: {}", message);
+            process::exit(1);
+        }
+    }
+}
+
+fn print_backtrace(trap: &Trap, filename: Option<&Path>) {
+    if trap.backtrace.is_empty() {
+        return;
+    }
+
+    // The module is loaded again for its debug sections, which only matter once it has trapped.
+    #[cfg(feature = "dwarf")]
+    let debug_info = filename
+        .and_then(|filename| load(filename).ok())
+        .and_then(|module| DebugInfo::new(&module.customs).ok().flatten());
+    #[cfg(not(feature = "dwarf"))]
+    let _ = filename;
+
+    eprintln!("wasm backtrace:");
+    for (i, frame) in trap.backtrace.iter().enumerate() {
+        eprintln!("  {:>3}: {}", i, frame);
+
+        #[cfg(feature = "dwarf")]
+        if let (Some(debug_info), Some(offset)) = (&debug_info, frame.offset) {
+            // Inlined functions come first, followed by the function they were inlined into.
+            for source in debug_info.frames(offset).unwrap_or_default() {
+                if let Some(function) = &source.function {
+                    eprintln!("{:>18}{}", "- ", function);
+                }
+                eprintln!("{:>23}{}", "at ", source);
+            }
+        }
+    }
+}
+
+fn run(
+    filename: &Path,
+    options: &WasiOptions,
+    wasi_args: Vec<String>,
+    loader: &Loader,
+) -> Result<(), Error> {
+    let module = loader.load(filename)?;
+    let has_start = module.start.is_some();
+
+    let mut args = vec![filename.display().to_string()];
+    args.extend(wasi_args);
+    let (mut store, module_inst) = instantiate(module, &loader.engine, options, &args)?;
+
+    match module_inst.get_func("_start") {
+        Some(func_addr) => {
+            invoke_func(&mut store, func_addr, vec![])?;
+        }
+        None if has_start => {}
+        None => {
+            return Err(Error::Failure(format!(
+                "{} has neither a start function nor a `_start` export",
+                filename.display()
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn invoke(
+    filename: &Path,
+    func_name: &str,
+    texts: &[String],
+    options: &WasiOptions,
+    loader: &Loader,
+) -> Result<(), Error> {
+    let module = loader.load(filename)?;
+    let args = vec![filename.display().to_string()];
+    let (mut store, module_inst) = instantiate(module, &loader.engine, options, &args)?;
+
+    let Some(func_addr) = module_inst.get_func(func_name) else {
+        return Err(Error::Failure(format!(
+            "{} does not export a function `{}`",
+            filename.display(),
+            func_name
+        )));
+    };
+
+    let func_type = store.funcs[func_addr as usize].type_().clone();
+    if func_type.parameters.len() != texts.len() {
+        return Err(Error::Failure(format!(
+            "`{}` has type {} and expects {} argument(s), but {} were given",
+            func_name,
+            func_type,
+            func_type.parameters.len(),
+            texts.len()
+        )));
+    }
 
-    let mut file = File::open(cli.filename).unwrap();
-    let mut input = Vec::new();
-    file.read_to_end(&mut input).unwrap();
+    let mut arguments = Vec::new();
+    for (i, (text, type_)) in texts.iter().zip(&func_type.parameters).enumerate() {
+        let value = Val::parse(text, type_).map_err(|error| {
+            Error::Failure(format!("argument {} of `{}`: {}", i + 1, func_name, error))
+        })?;
+        arguments.push(value);
+    }
+
+    let results = invoke_func(&mut store, func_addr, arguments)?;
+    let results: Vec<String> = results
+        .iter()
+        .map(|result| format!("{}: {}", result, result.type_()))
+        .collect();
+    println!("👻 {}", results.join(", "));
+    Ok(())
+}
+
+fn validate_file(filename: &Path) -> Result<(), Error> {
+    let module = load(filename)?;
+    validate(&module)
+        .map_err(|error| Error::Failure(format!("{} is invalid: {}", filename.display(), error)))?;
+    println!("{} is valid", filename.display());
+    Ok(())
+}
+
+fn inspect(filename: &Path) -> Result<(), Error> {
+    let module = load(filename)?;
+    let type_at = |idx: u32| match module.types.get(idx as usize) {
+        Some(type_) => type_.to_string(),
+        None => format!("<unknown type {}>", idx),
+    };
+
+    println!("types:");
+    for (i, type_) in module.types.iter().enumerate() {
+        println!("  {}: {}", i, type_);
+    }
+
+    println!("imports:");
+    for import in &module.imports {
+        let desc = match &import.desc {
+            ImportDesc::Func(idx) => format!("func {}", type_at(*idx)),
+            ImportDesc::Mem(mem_type) => format!("memory {}", limits(&mem_type.0)),
+        };
+        println!("  \"{}\" \"{}\": {}", import.module, import.name, desc);
+    }
+
+    println!("functions:");
+    let imported_funcs = module
+        .imports
+        .iter()
+        .filter(|import| matches!(import.desc, ImportDesc::Func(_)))
+        .count();
+    for (i, func) in module.funcs.iter().enumerate() {
+        let idx = (imported_funcs + i) as u32;
+        match module.names.funcs.get(&idx) {
+            Some(name) => println!("  {} ${}: {}", idx, name, type_at(func.type_)),
+            None => println!("  {}: {}", idx, type_at(func.type_)),
+        }
+    }
+
+    println!("tables:");
+    for (i, table) in module.tables.iter().enumerate() {
+        let type_ = ValType::RefType(table.type_.1.clone());
+        println!("  {}: {} {}", i, limits(&table.type_.0), type_);
+    }
+
+    println!("memories:");
+    for (i, mem) in module.mems.iter().enumerate() {
+        println!("  {}: {}", i, limits(&mem.type_.0));
+    }
+
+    println!("exports:");
+    for export in &module.exports {
+        let desc = match export.desc {
+            ExportDesc::Func(idx) => format!("func {}", idx),
+            ExportDesc::Mem(idx) => format!("memory {}", idx),
+        };
+        println!("  \"{}\": {}", export.name, desc);
+    }
+
+    if let Some(start) = module.start {
+        println!("start: func {}", start);
+    }
+    Ok(())
+}
+
+fn dump(filename: &Path) -> Result<(), Error> {
+    // Text modules are dumped as the binary they encode to.
+    let input = if is_text(filename) {
+        Encoder::encode(&load(filename)?)
+    } else {
+        read(filename)?
+    };
+    let sections = Decoder::new(&input)
+        .decode_sections()
+        .map_err(|error| Error::Failure(format!("{}: {}", filename.display(), error)))?;
+
+    println!(
+        "{:>2} {:<10} {:>10} {:>10} {:>6}",
+        "id", "section", "offset", "size", "count"
+    );
+    for section in sections {
+        let kind = match &section.name {
+            Some(name) => format!("{} \"{}\"", section.kind(), name),
+            None => section.kind().to_string(),
+        };
+        let count = match section.count {
+            Some(count) => count.to_string(),
+            None => "-".to_string(),
+        };
+        println!(
+            "{:>2} {:<10} {:#010x} {:#010x} {:>6}",
+            section.id, kind, section.offset, section.size, count
+        );
+    }
+    Ok(())
+}
+
+fn run_wast(paths: &[PathBuf]) -> Result<(), Error> {
+    let mut files = Vec::new();
+    for path in paths {
+        collect_wast_files(path, &mut files)?;
+    }
+
+    let mut summary = Summary::default();
+    for file in &files {
+        let source = fs::read_to_string(file).map_err(|error| {
+            Error::Failure(format!("failed to read {}: {}", file.display(), error))
+        })?;
+        // Every script starts from a fresh store, as in the spec interpreter.
+        let reports = Runner::new()
+            .run(&source)
+            .map_err(|error| Error::Failure(format!("{}:{}", file.display(), error)))?;
+        for report in reports {
+            println!("{}:{}", file.display(), report);
+            summary.add(&report.outcome);
+        }
+    }
+
+    println!("{}", summary);
+    if summary.failed > 0 {
+        return Err(Error::Failure(format!(
+            "{} directive(s) failed",
+            summary.failed
+        )));
+    }
+    Ok(())
+}
+
+/// Collects `.wast` files, searching directories recursively in name order.
+fn collect_wast_files(path: &Path, files: &mut Vec<PathBuf>) -> Result<(), Error> {
+    if !path.is_dir() {
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+    let entries = fs::read_dir(path)
+        .map_err(|error| Error::Failure(format!("failed to read {}: {}", path.display(), error)))?;
+    let mut entries: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect();
+    entries.sort();
+    for entry in entries {
+        if entry.is_dir()
+            || entry
+                .extension()
+                .is_some_and(|extension| extension == "wast")
+        {
+            collect_wast_files(&entry, files)?;
+        }
+    }
+    Ok(())
+}
+
+fn limits(limits: &Limits) -> String {
+    match limits.max {
+        Some(max) => format!("{} {}", limits.min, max),
+        None => limits.min.to_string(),
+    }
+}
+
+fn read(filename: &Path) -> Result<Vec<u8>, Error> {
+    fs::read(filename).map_err(|error| {
+        Error::Failure(format!("failed to read {}: {}", filename.display(), error))
+    })
+}
+
+/// Loads a module from a binary, or from the text format if the file has a `.wat` extension.
+fn load(filename: &Path) -> Result<Module, Error> {
+    if is_text(filename) {
+        let source = fs::read_to_string(filename).map_err(|error| {
+            Error::Failure(format!("failed to read {}: {}", filename.display(), error))
+        })?;
+        return text::parse(&source)
+            .map_err(|error| Error::Failure(format!("{}:{}", filename.display(), error)));
+    }
+
+    // Binaries are decoded section by section while they are read.
+    let failed_to_read =
+        |error| Error::Failure(format!("failed to read {}: {}", filename.display(), error));
+    let file = fs::File::open(filename).map_err(failed_to_read)?;
+    streaming::decode(file).map_err(|error| match error {
+        StreamError::Io(error) => failed_to_read(error),
+        StreamError::Decoding(error) => {
+            Error::Failure(format!("{}: {}", filename.display(), error))
+        }
+    })
+}
+
+/// The engine that `run` and `invoke` execute modules with, and the cache they load them
+/// through.
+struct Loader {
+    engine: Engine,
+    #[cfg(feature = "cache")]
+    cache: Option<Cache>,
+}
+
+impl Loader {
+    /// Loads a module like `load`, but goes through the cache for binaries if there is one,
+    /// and leaves function bodies undecoded if the engine compiles lazily.
+    fn load(&self, filename: &Path) -> Result<Module, Error> {
+        let failure = |error: String| Error::Failure(format!("{}: {}", filename.display(), error));
+        if is_text(filename) {
+            return load(filename);
+        }
+        // The cache decodes every body, which is why `--cache` conflicts with `--lazy`.
+        #[cfg(feature = "cache")]
+        if let Some(cache) = &self.cache {
+            let input = read(filename)?;
+            return cache
+                .load(&input, &self.engine)
+                .map_err(|error| failure(error.to_string()));
+        }
+        if !self.engine.config().lazy_compilation {
+            return load(filename);
+        }
+        let input = read(filename)?;
+        Decoder::new(&input)
+            .lazy_bodies(true)
+            .decode()
+            .map_err(|error| failure(error.to_string()))
+    }
+}
+
+fn is_text(filename: &Path) -> bool {
+    filename
+        .extension()
+        .is_some_and(|extension| extension == "wat")
+}
 
-    let mut decoder = Decoder::new(&input);
-    let module = decoder.decode().unwrap();
+fn instantiate(
+    module: Module,
+    engine: &Engine,
+    options: &WasiOptions,
+    args: &[String],
+) -> Result<(Store, Rc<ModuleInst>), Error> {
+    let envs: Vec<(String, String)> = options
+        .envs
+        .iter()
+        .map(|env| match env.split_once('=') {
+            Some((key, value)) => (key.to_string(), value.to_string()),
+            None => (env.to_string(), String::new()),
+        })
+        .collect();
 
-    let mut store = Store { funcs: Vec::new() };
-    let module_inst = instantiate(&mut store, module);
+    let mut wasi = WasiCtx::builder().args(args).envs(&envs).inherit_stdio();
+    let dirs = options.dirs.iter().map(|dir| (dir, false));
+    let readonly_dirs = options.readonly_dirs.iter().map(|dir| (dir, true));
+    for (dir, read_only) in dirs.chain(readonly_dirs) {
+        let (host_path, guest_path) = dir.split_once("::").unwrap_or((dir, dir));
+        wasi = wasi
+            .preopened_dir(Path::new(host_path), guest_path, read_only)
+            .map_err(|error| {
+                Error::Failure(format!("failed to open directory {}: {}", host_path, error))
+            })?;
+    }
 
-    let arguments = cli.arguments.iter().map(|x| Val::I64(*x as i64)).collect();
-    invoke(store, &module_inst, cli.exported_function, arguments);
+    let mut store = Store::new(engine);
+    store.wasi = Some(wasi.build());
+    let mut linker = Linker::new();
+    preview1::add_to_linker(&mut linker, &mut store);
+    let module_inst = linker.instantiate(&mut store, module)?;
+    Ok((store, module_inst))
 }
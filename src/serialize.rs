@@ -0,0 +1,565 @@
+use crate::decoder::{Decoder, DecodingError};
+use crate::encoder::Encoder;
+use crate::execution::engine::{Config, Engine};
+use crate::structure::instructions::expression::Expr;
+use crate::structure::modules::custom::{CustomSection, IndirectNameMap, NameMap, Names};
+use crate::structure::modules::data::{Data, DataMode};
+use crate::structure::modules::export::{Export, ExportDesc};
+use crate::structure::modules::function::Func;
+use crate::structure::modules::import::{Import, ImportDesc};
+use crate::structure::modules::memory::Mem;
+use crate::structure::modules::module::Module;
+use crate::structure::modules::table::Table;
+use crate::structure::types::function::FuncType;
+use crate::structure::types::memory::{Limits, MemType};
+use crate::structure::types::table::TableType;
+use crate::structure::types::value::{NumType, RefType, ValType};
+use crate::validation::{validate_module, Bodies, ValidationError};
+use std::borrow::Cow;
+use std::fmt;
+
+const MAGIC: &[u8; 4] = b"\0mwm";
+/// The version of the serialized form, bumped whenever it changes.
+const FORMAT_VERSION: u32 = 2;
+
+#[derive(Debug)]
+pub enum DeserializeError {
+    /// The bytes were serialized by another version of this crate or for another engine
+    /// configuration.
+    Incompatible(String),
+    Malformed(DecodingError),
+}
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeserializeError::Incompatible(reason) => {
+                write!(f, "incompatible serialized module: {}", reason)
+            }
+            DeserializeError::Malformed(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl From<DecodingError> for DeserializeError {
+    fn from(error: DecodingError) -> Self {
+        DeserializeError::Malformed(error)
+    }
+}
+
+impl Module {
+    /// Writes the module in a form that `deserialize` reads back without decoding it again.
+    ///
+    /// The header records the crate version and a hash of the engine configuration, so that
+    /// stale entries of an on-disk cache are rejected instead of misread.
+    pub fn serialize(&self, engine: &Engine) -> Result<Vec<u8>, ValidationError> {
+        if !self.validated {
+            validate_module(self, Bodies::All, engine.config())?;
+        }
+        let funcs = self
+            .funcs
+            .iter()
+            .enumerate()
+            .map(|(i, func)| match self.lazy_bodies.get(i) {
+                Some(body) => body
+                    .decode(func.type_)
+                    .map(Cow::Owned)
+                    .map_err(|error| ValidationError(error.to_string())),
+                None => Ok(Cow::Borrowed(func)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut writer = Writer::default();
+        writer.output.extend_from_slice(MAGIC);
+        writer.u32(FORMAT_VERSION);
+        writer.str(env!("CARGO_PKG_VERSION"));
+        writer.u64(config_hash(engine.config()));
+        writer.module(self, &funcs);
+        Ok(writer.output)
+    }
+
+    /// Reads a module written by `serialize`. The bytes may have been changed since, so the
+    /// module is validated when it is instantiated, like a decoded one.
+    pub fn deserialize(bytes: &[u8], engine: &Engine) -> Result<Module, DeserializeError> {
+        let mut reader = Reader { input: bytes };
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(DeserializeError::Incompatible(
+                "not a serialized module".into(),
+            ));
+        }
+        let format_version = reader.u32()?;
+        let crate_version = reader.str()?;
+        if format_version != FORMAT_VERSION || crate_version != env!("CARGO_PKG_VERSION") {
+            return Err(DeserializeError::Incompatible(format!(
+                "written by version {} (format {})",
+                crate_version, format_version
+            )));
+        }
+        if reader.u64()? != config_hash(engine.config()) {
+            return Err(DeserializeError::Incompatible(
+                "written for another engine configuration".into(),
+            ));
+        }
+        let module = reader.module()?;
+        if !reader.input.is_empty() {
+            return Err(DecodingError::new().into());
+        }
+        Ok(module)
+    }
+}
+
+/// FNV-1a of the configuration, which is stable across builds unlike `DefaultHasher`.
+fn config_hash(config: &Config) -> u64 {
+    format!("{:?}", config)
+        .bytes()
+        .fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        })
+}
+
+#[derive(Default)]
+struct Writer {
+    output: Vec<u8>,
+}
+
+impl Writer {
+    /// Writes the module with `funcs` in place of its functions, whose bodies may be undecoded.
+    fn module(&mut self, module: &Module, funcs: &[Cow<Func>]) {
+        self.vec(&module.types, Writer::func_type);
+        self.vec(funcs, |w, func| {
+            w.u32(func.type_);
+            w.vec(&func.locals, Writer::val_type);
+            w.expr(&func.body);
+            w.vec(&func.offsets, |w, offset| w.u32(*offset));
+        });
+        self.vec(&module.tables, |w, table| {
+            w.limits(&table.type_.0);
+            w.ref_type(&table.type_.1);
+        });
+        self.vec(&module.mems, |w, mem| w.limits(&mem.type_.0));
+        self.vec(&module.datas, |w, data| {
+            w.bytes(&data.init);
+            match &data.mode {
+                DataMode::Passive => w.u8(0),
+                DataMode::Active { memory, offset } => {
+                    w.u8(1);
+                    w.u32(*memory);
+                    w.expr(offset);
+                }
+            }
+        });
+        self.vec(&module.imports, |w, import| {
+            w.str(&import.module);
+            w.str(&import.name);
+            match &import.desc {
+                ImportDesc::Func(type_idx) => {
+                    w.u8(0);
+                    w.u32(*type_idx);
+                }
+                ImportDesc::Mem(mem_type) => {
+                    w.u8(1);
+                    w.limits(&mem_type.0);
+                }
+            }
+        });
+        self.vec(&module.exports, |w, export| {
+            w.str(&export.name);
+            match export.desc {
+                ExportDesc::Func(idx) => {
+                    w.u8(0);
+                    w.u32(idx);
+                }
+                ExportDesc::Mem(idx) => {
+                    w.u8(1);
+                    w.u32(idx);
+                }
+            }
+        });
+        self.option(&module.start, |w, start| w.u32(*start));
+        self.names(&module.names);
+        self.vec(&module.customs, |w, custom| {
+            w.str(&custom.name);
+            w.bytes(&custom.data);
+        });
+    }
+
+    fn names(&mut self, names: &Names) {
+        self.option(&names.module, |w, name| w.str(name));
+        self.name_map(&names.funcs);
+        self.indirect_name_map(&names.locals);
+        self.indirect_name_map(&names.labels);
+        for map in [
+            &names.types,
+            &names.tables,
+            &names.mems,
+            &names.globals,
+            &names.elems,
+            &names.datas,
+        ] {
+            self.name_map(map);
+        }
+    }
+
+    fn name_map(&mut self, map: &NameMap) {
+        self.u32(map.len() as u32);
+        for (idx, name) in map {
+            self.u32(*idx);
+            self.str(name);
+        }
+    }
+
+    fn indirect_name_map(&mut self, map: &IndirectNameMap) {
+        self.u32(map.len() as u32);
+        for (idx, names) in map {
+            self.u32(*idx);
+            self.name_map(names);
+        }
+    }
+
+    fn func_type(&mut self, type_: &FuncType) {
+        self.vec(&type_.parameters, Writer::val_type);
+        self.vec(&type_.results, Writer::val_type);
+    }
+
+    fn val_type(&mut self, type_: &ValType) {
+        self.u8(match type_ {
+            ValType::NumType(NumType::I32) => 0,
+            ValType::NumType(NumType::I64) => 1,
+            ValType::NumType(NumType::F32) => 2,
+            ValType::NumType(NumType::F64) => 3,
+            ValType::RefType(RefType::FuncRef) => 4,
+        });
+    }
+
+    fn ref_type(&mut self, type_: &RefType) {
+        self.u8(match type_ {
+            RefType::FuncRef => 0,
+        });
+    }
+
+    fn limits(&mut self, limits: &Limits) {
+        self.u32(limits.min);
+        self.option(&limits.max, |w, max| w.u32(*max));
+    }
+
+    /// Instructions keep their binary encoding, which is already compact.
+    fn expr(&mut self, expr: &Expr) {
+        self.bytes(&Encoder::encode_expr(expr));
+    }
+
+    fn vec<T>(&mut self, items: &[T], mut item: impl FnMut(&mut Writer, &T)) {
+        self.u32(items.len() as u32);
+        for i in items {
+            item(self, i);
+        }
+    }
+
+    fn option<T>(&mut self, value: &Option<T>, some: impl FnOnce(&mut Writer, &T)) {
+        match value {
+            Some(value) => {
+                self.u8(1);
+                some(self, value);
+            }
+            None => self.u8(0),
+        }
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.u32(bytes.len() as u32);
+        self.output.extend_from_slice(bytes);
+    }
+
+    fn str(&mut self, s: &str) {
+        self.bytes(s.as_bytes());
+    }
+
+    fn u8(&mut self, value: u8) {
+        self.output.push(value);
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.output.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.output.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+struct Reader<'a> {
+    input: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn module(&mut self) -> Result<Module, DecodingError> {
+        let types = self.vec(Reader::func_type)?;
+        let funcs = self.vec(|r| {
+            Ok(Func {
+                type_: r.u32()?,
+                locals: r.vec(Reader::val_type)?,
+                body: r.expr()?,
+                offsets: r.vec(Reader::u32)?,
+            })
+        })?;
+        let tables = self.vec(|r| {
+            Ok(Table {
+                type_: TableType(r.limits()?, r.ref_type()?),
+            })
+        })?;
+        let mems = self.vec(|r| {
+            Ok(Mem {
+                type_: MemType(r.limits()?),
+            })
+        })?;
+        let datas = self.vec(|r| {
+            let init = r.bytes()?.to_vec();
+            let mode = match r.u8()? {
+                0 => DataMode::Passive,
+                1 => DataMode::Active {
+                    memory: r.u32()?,
+                    offset: r.expr()?,
+                },
+                _ => return Err(DecodingError::new()),
+            };
+            Ok(Data { init, mode })
+        })?;
+        let imports = self.vec(|r| {
+            let module = r.str()?;
+            let name = r.str()?;
+            let desc = match r.u8()? {
+                0 => ImportDesc::Func(r.u32()?),
+                1 => ImportDesc::Mem(MemType(r.limits()?)),
+                _ => return Err(DecodingError::new()),
+            };
+            Ok(Import { module, name, desc })
+        })?;
+        let exports = self.vec(|r| {
+            let name = r.str()?;
+            let desc = match r.u8()? {
+                0 => ExportDesc::Func(r.u32()?),
+                1 => ExportDesc::Mem(r.u32()?),
+                _ => return Err(DecodingError::new()),
+            };
+            Ok(Export { name, desc })
+        })?;
+        let start = self.option(Reader::u32)?;
+        let names = self.names()?;
+        let customs = self.vec(|r| {
+            Ok(CustomSection {
+                name: r.str()?,
+                data: r.bytes()?.to_vec(),
+            })
+        })?;
+        Ok(Module {
+            types,
+            funcs,
+            tables,
+            mems,
+            datas,
+            imports,
+            exports,
+            start,
+            names,
+            customs,
+            validated: false,
+            lazy_bodies: Vec::new(),
+        })
+    }
+
+    fn names(&mut self) -> Result<Names, DecodingError> {
+        Ok(Names {
+            module: self.option(Reader::str)?,
+            funcs: self.name_map()?,
+            locals: self.indirect_name_map()?,
+            labels: self.indirect_name_map()?,
+            types: self.name_map()?,
+            tables: self.name_map()?,
+            mems: self.name_map()?,
+            globals: self.name_map()?,
+            elems: self.name_map()?,
+            datas: self.name_map()?,
+        })
+    }
+
+    fn name_map(&mut self) -> Result<NameMap, DecodingError> {
+        let mut map = NameMap::new();
+        for _ in 0..self.u32()? {
+            map.insert(self.u32()?, self.str()?);
+        }
+        Ok(map)
+    }
+
+    fn indirect_name_map(&mut self) -> Result<IndirectNameMap, DecodingError> {
+        let mut map = IndirectNameMap::new();
+        for _ in 0..self.u32()? {
+            map.insert(self.u32()?, self.name_map()?);
+        }
+        Ok(map)
+    }
+
+    fn func_type(&mut self) -> Result<FuncType, DecodingError> {
+        Ok(FuncType {
+            parameters: self.vec(Reader::val_type)?,
+            results: self.vec(Reader::val_type)?,
+        })
+    }
+
+    fn val_type(&mut self) -> Result<ValType, DecodingError> {
+        Ok(match self.u8()? {
+            0 => ValType::NumType(NumType::I32),
+            1 => ValType::NumType(NumType::I64),
+            2 => ValType::NumType(NumType::F32),
+            3 => ValType::NumType(NumType::F64),
+            4 => ValType::RefType(RefType::FuncRef),
+            _ => return Err(DecodingError::new()),
+        })
+    }
+
+    fn ref_type(&mut self) -> Result<RefType, DecodingError> {
+        match self.u8()? {
+            0 => Ok(RefType::FuncRef),
+            _ => Err(DecodingError::new()),
+        }
+    }
+
+    fn limits(&mut self) -> Result<Limits, DecodingError> {
+        Ok(Limits {
+            min: self.u32()?,
+            max: self.option(Reader::u32)?,
+        })
+    }
+
+    fn expr(&mut self) -> Result<Expr, DecodingError> {
+        Decoder::new(self.bytes()?).decode_expr()
+    }
+
+    fn vec<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> Result<T, DecodingError>,
+    ) -> Result<Vec<T>, DecodingError> {
+        let len = self.u32()?;
+        // Each item takes at least a byte, which bounds the allocation for corrupt lengths.
+        let mut items = Vec::with_capacity((len as usize).min(self.input.len()));
+        for _ in 0..len {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    fn option<T>(
+        &mut self,
+        some: impl FnOnce(&mut Self) -> Result<T, DecodingError>,
+    ) -> Result<Option<T>, DecodingError> {
+        match self.u8()? {
+            0 => Ok(None),
+            1 => some(self).map(Some),
+            _ => Err(DecodingError::new()),
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&[u8], DecodingError> {
+        if self.input.len() < len {
+            return Err(DecodingError::new());
+        }
+        let (bytes, rest) = self.input.split_at(len);
+        self.input = rest;
+        Ok(bytes)
+    }
+
+    fn bytes(&mut self) -> Result<&[u8], DecodingError> {
+        let len = self.u32()?;
+        self.take(len as usize)
+    }
+
+    fn str(&mut self) -> Result<String, DecodingError> {
+        String::from_utf8(self.bytes()?.to_vec()).map_err(|_| DecodingError::new())
+    }
+
+    fn u8(&mut self) -> Result<u8, DecodingError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, DecodingError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, DecodingError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::engine::Backend;
+    use crate::execution::instance::{instantiate, InstantiationError};
+    use crate::execution::structure::Store;
+    use crate::text;
+    use std::fs;
+
+    #[test]
+    fn test_round_trip() {
+        let engine = Engine::new();
+        for entry in fs::read_dir("./tests/inputs").unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_none_or(|extension| extension != "wasm") {
+                continue;
+            }
+            let binary = fs::read(&path).unwrap();
+            let module = Decoder::new(&binary).decode().unwrap();
+            let bytes = module.serialize(&engine).unwrap();
+            let deserialized = Module::deserialize(&bytes, &engine).unwrap();
+            assert_eq!(deserialized, module, "{}", path.display());
+
+            // Bodies a lazy decoder left undecoded are decoded for serializing.
+            let lazy = Decoder::new(&binary).lazy_bodies(true).decode().unwrap();
+            assert_eq!(
+                lazy.serialize(&engine).unwrap(),
+                bytes,
+                "{}",
+                path.display()
+            );
+        }
+    }
+
+    #[test]
+    fn test_incompatible() {
+        let module = text::parse(
+            r#"(module
+                (memory 1 2)
+                (data (i32.const 8) "hi")
+                (func $f (export "f") (param i32) (result i32)
+                    (if (result i32) (local.get 0) (then (i32.const 1)) (else (i32.const 2)))))"#,
+        )
+        .unwrap();
+        let engine = Engine::new();
+        let bytes = module.serialize(&engine).unwrap();
+        let deserialized = Module::deserialize(&bytes, &engine).unwrap();
+        assert_eq!(deserialized.names, module.names);
+        assert_eq!(deserialized.datas, module.datas);
+
+        let other = Engine::with_config(Config {
+            backend: Backend::Register,
+            ..Default::default()
+        });
+        let result = Module::deserialize(&bytes, &other);
+        assert!(matches!(result, Err(DeserializeError::Incompatible(_))));
+        let result = Module::deserialize(&bytes[..bytes.len() - 1], &engine);
+        assert!(matches!(result, Err(DeserializeError::Malformed(_))));
+
+        let invalid = text::parse("(module (func (result i32)))").unwrap();
+        assert!(invalid.serialize(&engine).is_err());
+    }
+
+    #[test]
+    fn test_invalid() {
+        // A module that was changed after it was written is rejected when it is instantiated.
+        let mut invalid = text::parse("(module (func (result i32)))").unwrap();
+        invalid.validated = true;
+        let engine = Engine::new();
+        let bytes = invalid.serialize(&engine).unwrap();
+        let deserialized = Module::deserialize(&bytes, &engine).unwrap();
+        assert!(!deserialized.validated);
+        let result = instantiate(&mut Store::new(&engine), deserialized, &[]);
+        assert!(matches!(result, Err(InstantiationError::Invalid(_))));
+    }
+}
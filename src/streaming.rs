@@ -0,0 +1,348 @@
+use crate::decoder::{Decoder, DecodingError, Section};
+use crate::structure::modules::indice::TypeIdx;
+use crate::structure::modules::module::Module;
+use std::fmt;
+use std::io::{self, ErrorKind, Read};
+
+/// The magic number and version that start a binary module.
+const HEADER: [u8; 8] = [0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+/// The most bytes `read_from` reads at once.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Decodes a binary module from bytes as they arrive, instead of from a complete buffer like
+/// `Decoder`.
+///
+/// Bytes are added with `push` or `read_from`, and `next_payload` returns what they completed:
+/// a section, or a function of the code section, which is decoded as soon as its body is in.
+/// The functions can be used before the rest of the module has arrived.
+#[derive(Debug, Default)]
+pub struct StreamingDecoder {
+    buffer: Vec<u8>,
+    /// The offset in the module of `buffer[0]`, since decoded sections are dropped from the
+    /// buffer. The code section is kept until it ends, as offsets are relative to its start.
+    offset: usize,
+    /// The position in `buffer` of the first byte that hasn't been decoded.
+    pos: usize,
+    state: State,
+    module: Module,
+    type_idxs: Vec<TypeIdx>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+enum State {
+    #[default]
+    Header,
+    Sections,
+    /// Within the code section, whose contents span `start..end` of the buffer.
+    Code {
+        start: usize,
+        end: usize,
+        remaining: u32,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Payload {
+    /// A section, which has been decoded into the module, except for the functions of the code
+    /// section, which follow as `Func`s.
+    Section(Section),
+    /// The index in `funcs` of a function of the code section, which has been decoded.
+    Func(usize),
+}
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Decoding(DecodingError),
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::Io(error) => write!(f, "{}", error),
+            StreamError::Decoding(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+/// Decodes a module from a reader, a chunk at a time.
+pub fn decode(mut reader: impl Read) -> Result<Module, StreamError> {
+    let mut decoder = StreamingDecoder::new();
+    while decoder.read_from(&mut reader).map_err(StreamError::Io)? > 0 {
+        // Decoding as chunks arrive keeps the buffer to about a section.
+        while decoder
+            .next_payload()
+            .map_err(StreamError::Decoding)?
+            .is_some()
+        {}
+    }
+    decoder.finish().map_err(StreamError::Decoding)
+}
+
+impl StreamingDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Pushes the next chunk of the reader, returning its size, which is 0 at the end.
+    pub fn read_from(&mut self, reader: &mut impl Read) -> io::Result<usize> {
+        let len = self.buffer.len();
+        self.buffer.resize(len + CHUNK_SIZE, 0);
+        let result = loop {
+            match reader.read(&mut self.buffer[len..]) {
+                Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                result => break result,
+            }
+        };
+        self.buffer.truncate(len + *result.as_ref().unwrap_or(&0));
+        result
+    }
+
+    /// The module as decoded so far.
+    pub fn module(&self) -> &Module {
+        &self.module
+    }
+
+    /// Decodes the next section or function if its bytes have all been pushed, and returns
+    /// `None` otherwise.
+    pub fn next_payload(&mut self) -> Result<Option<Payload>, DecodingError> {
+        loop {
+            match self.state {
+                State::Header => {
+                    let Some(header) = self.buffer.get(self.pos..self.pos + HEADER.len()) else {
+                        return Ok(None);
+                    };
+                    if header != HEADER {
+                        return Err(DecodingError::new());
+                    }
+                    self.pos += HEADER.len();
+                    self.state = State::Sections;
+                }
+                State::Sections => return self.section(),
+                State::Code {
+                    end, remaining: 0, ..
+                } => {
+                    if self.pos != end {
+                        return Err(DecodingError::new());
+                    }
+                    self.state = State::Sections;
+                    self.consume();
+                }
+                State::Code {
+                    start,
+                    end,
+                    remaining,
+                } => {
+                    let available = &self.buffer[self.pos..end.min(self.buffer.len())];
+                    let Some((size, size_len)) = self.complete(read_u32(available)?, end)? else {
+                        return Ok(None);
+                    };
+                    let body_start = self.pos + size_len;
+                    let body_end = body_start
+                        .checked_add(size as usize)
+                        .filter(|body_end| *body_end <= end)
+                        .ok_or_else(DecodingError::new)?;
+                    if self.buffer.len() < body_end {
+                        return Ok(None);
+                    }
+
+                    let index = self.module.funcs.len();
+                    let type_ = *self.type_idxs.get(index).ok_or_else(DecodingError::new)?;
+                    let func = Decoder::new(&self.buffer[start..body_end])
+                        .decode_func_at(body_start - start, type_)?;
+                    self.module.funcs.push(func);
+                    self.pos = body_end;
+                    self.state = State::Code {
+                        start,
+                        end,
+                        remaining: remaining - 1,
+                    };
+                    return Ok(Some(Payload::Func(index)));
+                }
+            }
+        }
+    }
+
+    /// Decodes what is left and returns the module, which must be complete.
+    pub fn finish(mut self) -> Result<Module, DecodingError> {
+        while self.next_payload()?.is_some() {}
+        if self.state != State::Sections || self.pos != self.buffer.len() {
+            return Err(DecodingError::new());
+        }
+        Ok(self.module)
+    }
+
+    fn section(&mut self) -> Result<Option<Payload>, DecodingError> {
+        let Some(&id) = self.buffer.get(self.pos) else {
+            return Ok(None);
+        };
+        let Some((size, size_len)) = read_u32(&self.buffer[self.pos + 1..])? else {
+            return Ok(None);
+        };
+        let start = self.pos + 1 + size_len;
+        let end = start
+            .checked_add(size as usize)
+            .ok_or_else(DecodingError::new)?;
+
+        // The code section is decoded a function at a time, after its count.
+        if id == 10 {
+            let available = &self.buffer[start..end.min(self.buffer.len())];
+            let Some((count, count_len)) = self.complete(read_u32(available)?, end)? else {
+                return Ok(None);
+            };
+            let header = self.header(id, size, start, end)?;
+            self.pos = start + count_len;
+            self.state = State::Code {
+                start,
+                end,
+                remaining: count,
+            };
+            return Ok(Some(Payload::Section(header)));
+        }
+
+        if self.buffer.len() < end {
+            return Ok(None);
+        }
+        let header = self.header(id, size, start, end)?;
+        Decoder::new(&self.buffer[self.pos + 1..end]).decode_section(
+            id,
+            &mut self.module,
+            &mut self.type_idxs,
+        )?;
+        self.pos = end;
+        self.consume();
+        Ok(Some(Payload::Section(header)))
+    }
+
+    fn header(
+        &self,
+        id: u8,
+        size: u32,
+        start: usize,
+        end: usize,
+    ) -> Result<Section, DecodingError> {
+        let contents = &self.buffer[start..end.min(self.buffer.len())];
+        let mut header = Decoder::new(contents).decode_section_header(id, size)?;
+        header.offset = self.offset + start;
+        Ok(header)
+    }
+
+    /// Turns a value missing from a region that has fully arrived into an error.
+    fn complete<T>(&self, value: Option<T>, end: usize) -> Result<Option<T>, DecodingError> {
+        if value.is_none() && self.buffer.len() >= end {
+            return Err(DecodingError::new());
+        }
+        Ok(value)
+    }
+
+    /// Drops the decoded bytes from the buffer.
+    fn consume(&mut self) {
+        self.buffer.drain(..self.pos);
+        self.offset += self.pos;
+        self.pos = 0;
+    }
+}
+
+/// Reads an unsigned LEB128 u32 from the start of the bytes, with its length, or returns
+/// `None` if they end in the middle of it.
+fn read_u32(bytes: &[u8]) -> Result<Option<(u32, usize)>, DecodingError> {
+    let Some(len) = bytes.iter().take(5).position(|byte| byte & 0x80 == 0) else {
+        if bytes.len() < 5 {
+            return Ok(None);
+        }
+        return Err(DecodingError::new());
+    };
+    let value = bytes[..=len]
+        .iter()
+        .rev()
+        .fold(0, |value, byte| value << 7 | (byte & 0x7f) as u32);
+    Ok(Some((value, len + 1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_byte_at_a_time() {
+        for entry in fs::read_dir("./tests/inputs").unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_none_or(|extension| extension != "wasm") {
+                continue;
+            }
+            let binary = fs::read(&path).unwrap();
+            let expected = Decoder::new(&binary).decode().unwrap();
+            let sections = Decoder::new(&binary).decode_sections().unwrap();
+
+            let mut decoder = StreamingDecoder::new();
+            let mut payloads = Vec::new();
+            for byte in &binary {
+                decoder.push(&[*byte]);
+                while let Some(payload) = decoder.next_payload().unwrap() {
+                    payloads.push(payload);
+                }
+            }
+            let module = decoder.finish().unwrap();
+            assert_eq!(module, expected, "{}", path.display());
+
+            let funcs = (0..expected.funcs.len()).map(Payload::Func);
+            let code = sections.iter().position(|section| section.id == 10);
+            let mut expected_payloads: Vec<_> =
+                sections.into_iter().map(Payload::Section).collect();
+            if let Some(code) = code {
+                expected_payloads.splice(code + 1..code + 1, funcs);
+            }
+            assert_eq!(payloads, expected_payloads, "{}", path.display());
+
+            assert_eq!(decode(binary.as_slice()).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_functions_before_the_end() {
+        let binary = fs::read("./tests/inputs/factorial.wasm").unwrap();
+        let mut decoder = StreamingDecoder::new();
+        decoder.push(&binary[..binary.len() - 1]);
+        while let Some(payload) = decoder.next_payload().unwrap() {
+            assert!(!matches!(payload, Payload::Func(_)));
+        }
+        assert!(decoder.module().funcs.is_empty());
+        decoder.push(&binary[binary.len() - 1..]);
+        assert_eq!(decoder.next_payload().unwrap(), Some(Payload::Func(0)));
+        assert_eq!(decoder.module().funcs[0].type_, 0);
+        assert!(decoder.finish().is_ok());
+    }
+
+    #[test]
+    fn test_malformed() {
+        let binary = fs::read("./tests/inputs/factorial.wasm").unwrap();
+        let truncated = &binary[..binary.len() - 1];
+        assert!(matches!(decode(truncated), Err(StreamError::Decoding(_))));
+        assert!(decode(&b"\0asm\x02\0\0\0"[..]).is_err());
+        assert!(decode(&b"\0asm\x01\0\0\0\x01\xff\xff\xff\xff\xff"[..]).is_err());
+
+        // A body can't extend past the end of its section.
+        let mut long_body = binary.clone();
+        long_body[32] += 1;
+        assert!(decode(long_body.as_slice()).is_err());
+
+        // Unknown export kinds, block types and opcodes are errors rather than panics.
+        let header = b"\0asm\x01\0\0\0\x01\x04\x01\x60\0\0\x03\x02\x01\0";
+        for section in [
+            &b"\x07\x05\x01\x01f\x05\0"[..],
+            b"\x0a\x07\x01\x05\0\x04\x01\x0b\x0b",
+            b"\x0a\x05\x01\x03\0\xff\x0b",
+        ] {
+            let binary = [&header[..], section].concat();
+            assert!(matches!(
+                decode(binary.as_slice()),
+                Err(StreamError::Decoding(_))
+            ));
+        }
+    }
+}
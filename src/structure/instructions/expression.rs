@@ -1,5 +1,5 @@
-use crate::structure::modules::indice::{FuncIdx, LocalIdx};
-use crate::structure::types::value::ValType;
+use crate::structure::modules::indice::{FuncIdx, LocalIdx, TableIdx};
+use crate::structure::types::value::{RefType, ValType};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Expr(pub Vec<Instr>);
@@ -10,6 +10,12 @@ pub enum BlockType {
     ValType(ValType),
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemArg {
+    pub align: u32,
+    pub offset: u32,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Instr {
     If(BlockType, Vec<Instr>, Vec<Instr>),
@@ -17,8 +23,38 @@ pub enum Instr {
     Call(FuncIdx),
     Drop,
     LocalGet(LocalIdx),
+    RefNull(RefType),
+    TableSize(TableIdx),
+    TableGrow(TableIdx),
+    I32Load(MemArg),
+    I64Load(MemArg),
+    F32Load(MemArg),
+    F64Load(MemArg),
+    I32Load8S(MemArg),
+    I32Load8U(MemArg),
+    I32Load16S(MemArg),
+    I32Load16U(MemArg),
+    I64Load8S(MemArg),
+    I64Load8U(MemArg),
+    I64Load16S(MemArg),
+    I64Load16U(MemArg),
+    I64Load32S(MemArg),
+    I64Load32U(MemArg),
+    I32Store(MemArg),
+    I64Store(MemArg),
+    F32Store(MemArg),
+    F64Store(MemArg),
+    I32Store8(MemArg),
+    I32Store16(MemArg),
+    I64Store8(MemArg),
+    I64Store16(MemArg),
+    I64Store32(MemArg),
+    MemorySize,
+    MemoryGrow,
     I32Const(i32),
     I64Const(i64),
+    F32Const(f32),
+    F64Const(f64),
     I32Eqz,
     I32Eq,
     I32Ne,
@@ -0,0 +1,35 @@
+use std::collections::BTreeMap;
+
+/// Names by index, as in the `namemap` of the name section.
+pub type NameMap = BTreeMap<u32, String>;
+
+/// Names of entities nested in another one, such as the locals of each function.
+pub type IndirectNameMap = BTreeMap<u32, NameMap>;
+
+/// The contents of the `name` custom section.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Names {
+    pub module: Option<String>,
+    pub funcs: NameMap,
+    pub locals: IndirectNameMap,
+    pub labels: IndirectNameMap,
+    pub types: NameMap,
+    pub tables: NameMap,
+    pub mems: NameMap,
+    pub globals: NameMap,
+    pub elems: NameMap,
+    pub datas: NameMap,
+}
+
+impl Names {
+    pub fn is_empty(&self) -> bool {
+        self == &Names::default()
+    }
+}
+
+/// A custom section other than `name`, kept as is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomSection {
+    pub name: String,
+    pub data: Vec<u8>,
+}
@@ -0,0 +1,14 @@
+use crate::structure::instructions::expression::Expr;
+use crate::structure::modules::indice::MemIdx;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Data {
+    pub init: Vec<u8>,
+    pub mode: DataMode,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataMode {
+    Passive,
+    Active { memory: MemIdx, offset: Expr },
+}
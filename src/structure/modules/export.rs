@@ -1,12 +1,13 @@
-use crate::structure::modules::indice::FuncIdx;
+use crate::structure::modules::indice::{FuncIdx, MemIdx};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Export {
     pub name: String,
     pub desc: ExportDesc,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ExportDesc {
     Func(FuncIdx),
+    Mem(MemIdx),
 }
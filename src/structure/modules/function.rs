@@ -1,10 +1,31 @@
+use crate::decoder::{Decoder, DecodingError};
 use crate::structure::instructions::expression::Expr;
 use crate::structure::modules::indice::TypeIdx;
 use crate::structure::types::value::ValType;
+use std::ops::Range;
+use std::sync::Arc;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Func {
     pub type_: TypeIdx,
     pub locals: Vec<ValType>,
     pub body: Expr,
+    /// Code section offsets of the instructions in `body`, in the order they appear in the
+    /// binary. Empty unless the function was decoded from a binary.
+    pub offsets: Vec<u32>,
+}
+
+/// A function body that was left undecoded, as a range of the code section it came from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LazyBody {
+    /// The contents of the code section, shared by all bodies of the module.
+    pub(crate) code: Arc<[u8]>,
+    pub(crate) range: Range<usize>,
+}
+
+impl LazyBody {
+    pub fn decode(&self, type_: TypeIdx) -> Result<Func, DecodingError> {
+        let mut decoder = Decoder::new(&self.code[..self.range.end]);
+        decoder.decode_func_at(self.range.start, type_)
+    }
 }
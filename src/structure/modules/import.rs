@@ -0,0 +1,15 @@
+use crate::structure::modules::indice::TypeIdx;
+use crate::structure::types::memory::MemType;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Import {
+    pub module: String,
+    pub name: String,
+    pub desc: ImportDesc,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportDesc {
+    Func(TypeIdx),
+    Mem(MemType),
+}
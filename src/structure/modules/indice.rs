@@ -1,3 +1,5 @@
 pub type TypeIdx = u32;
 pub type FuncIdx = u32;
+pub type TableIdx = u32;
+pub type MemIdx = u32;
 pub type LocalIdx = u32;
@@ -0,0 +1,6 @@
+use crate::structure::types::memory::MemType;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mem {
+    pub type_: MemType,
+}
@@ -1,4 +1,9 @@
+pub mod custom;
+pub mod data;
 pub mod export;
 pub mod function;
+pub mod import;
 pub mod indice;
+pub mod memory;
 pub mod module;
+pub mod table;
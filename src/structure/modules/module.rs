@@ -1,10 +1,40 @@
+use crate::decoder::DecodingError;
+use crate::structure::modules::custom::{CustomSection, Names};
+use crate::structure::modules::data::Data;
 use crate::structure::modules::export::Export;
-use crate::structure::modules::function::Func;
+use crate::structure::modules::function::{Func, LazyBody};
+use crate::structure::modules::import::Import;
+use crate::structure::modules::indice::FuncIdx;
+use crate::structure::modules::memory::Mem;
+use crate::structure::modules::table::Table;
 use crate::structure::types::function::FuncType;
 
-#[derive(Debug)]
+#[derive(Debug, Default, PartialEq)]
 pub struct Module {
     pub types: Vec<FuncType>,
     pub funcs: Vec<Func>,
+    pub tables: Vec<Table>,
+    pub mems: Vec<Mem>,
+    pub datas: Vec<Data>,
+    pub imports: Vec<Import>,
     pub exports: Vec<Export>,
+    pub start: Option<FuncIdx>,
+    pub names: Names,
+    pub customs: Vec<CustomSection>,
+    /// Whether the module is known to be valid, so that instantiating it skips validation.
+    /// Only set by `Cache::load` for a module it has just validated.
+    pub(crate) validated: bool,
+    /// The undecoded bodies of `funcs`, if the decoder was lazy. Otherwise empty.
+    pub(crate) lazy_bodies: Vec<LazyBody>,
+}
+
+impl Module {
+    /// Decodes the function bodies that a lazy decoder left undecoded.
+    pub fn decode_bodies(&mut self) -> Result<(), DecodingError> {
+        for (func, body) in self.funcs.iter_mut().zip(&self.lazy_bodies) {
+            *func = body.decode(func.type_)?;
+        }
+        self.lazy_bodies.clear();
+        Ok(())
+    }
 }
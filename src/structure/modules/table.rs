@@ -0,0 +1,6 @@
+use crate::structure::types::table::TableType;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    pub type_: TableType,
+}
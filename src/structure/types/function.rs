@@ -1,7 +1,26 @@
 use crate::structure::types::value::ValType;
+use std::fmt;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct FuncType {
     pub parameters: Vec<ValType>,
     pub results: Vec<ValType>,
 }
+
+impl fmt::Display for FuncType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |types: &[ValType]| {
+            types
+                .iter()
+                .map(|type_| type_.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        write!(
+            f,
+            "({}) -> ({})",
+            join(&self.parameters),
+            join(&self.results)
+        )
+    }
+}
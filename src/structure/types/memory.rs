@@ -0,0 +1,8 @@
+#[derive(Debug, Clone, PartialEq)]
+pub struct Limits {
+    pub min: u32,
+    pub max: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemType(pub Limits);
@@ -1,2 +1,4 @@
 pub mod function;
+pub mod memory;
+pub mod table;
 pub mod value;
@@ -0,0 +1,5 @@
+use crate::structure::types::memory::Limits;
+use crate::structure::types::value::RefType;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableType(pub Limits, pub RefType);
@@ -1,6 +1,14 @@
+use std::fmt;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ValType {
     NumType(NumType),
+    RefType(RefType),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RefType {
+    FuncRef,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -10,3 +18,15 @@ pub enum NumType {
     F32,
     F64,
 }
+
+impl fmt::Display for ValType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValType::NumType(NumType::I32) => write!(f, "i32"),
+            ValType::NumType(NumType::I64) => write!(f, "i64"),
+            ValType::NumType(NumType::F32) => write!(f, "f32"),
+            ValType::NumType(NumType::F64) => write!(f, "f64"),
+            ValType::RefType(RefType::FuncRef) => write!(f, "funcref"),
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use crate::structure::instructions::expression::{Instr, MemArg};
+
+/// Instructions without immediates, keyed by their text format name.
+pub const PLAIN_INSTRS: &[(&str, Instr)] = &[
+    ("drop", Instr::Drop),
+    ("memory.size", Instr::MemorySize),
+    ("memory.grow", Instr::MemoryGrow),
+    ("i32.eqz", Instr::I32Eqz),
+    ("i32.eq", Instr::I32Eq),
+    ("i32.ne", Instr::I32Ne),
+    ("i32.lt_s", Instr::I32LtS),
+    ("i32.lt_u", Instr::I32LtU),
+    ("i32.gt_s", Instr::I32GtS),
+    ("i32.gt_u", Instr::I32GtU),
+    ("i32.le_s", Instr::I32LeS),
+    ("i32.le_u", Instr::I32LeU),
+    ("i32.ge_s", Instr::I32GeS),
+    ("i32.ge_u", Instr::I32GeU),
+    ("i64.eqz", Instr::I64Eqz),
+    ("i64.eq", Instr::I64Eq),
+    ("i64.ne", Instr::I64Ne),
+    ("i64.lt_s", Instr::I64LtS),
+    ("i64.lt_u", Instr::I64LtU),
+    ("i64.gt_s", Instr::I64GtS),
+    ("i64.gt_u", Instr::I64GtU),
+    ("i64.le_s", Instr::I64LeS),
+    ("i64.le_u", Instr::I64LeU),
+    ("i64.ge_s", Instr::I64GeS),
+    ("i64.ge_u", Instr::I64GeU),
+    ("f32.eq", Instr::F32Eq),
+    ("f32.ne", Instr::F32Ne),
+    ("f32.lt", Instr::F32Lt),
+    ("f32.gt", Instr::F32Gt),
+    ("f32.le", Instr::F32Le),
+    ("f32.ge", Instr::F32Ge),
+    ("f64.eq", Instr::F64Eq),
+    ("f64.ne", Instr::F64Ne),
+    ("f64.lt", Instr::F64Lt),
+    ("f64.gt", Instr::F64Gt),
+    ("f64.le", Instr::F64Le),
+    ("f64.ge", Instr::F64Ge),
+    ("i32.clz", Instr::I32Clz),
+    ("i32.ctz", Instr::I32Ctz),
+    ("i32.popcnt", Instr::I32Popcnt),
+    ("i32.add", Instr::I32Add),
+    ("i32.sub", Instr::I32Sub),
+    ("i32.mul", Instr::I32Mul),
+    ("i32.div_s", Instr::I32DivS),
+    ("i32.div_u", Instr::I32DivU),
+    ("i32.rem_s", Instr::I32RemS),
+    ("i32.rem_u", Instr::I32RemU),
+    ("i32.and", Instr::I32And),
+    ("i32.or", Instr::I32Or),
+    ("i32.xor", Instr::I32Xor),
+    ("i32.shl", Instr::I32Shl),
+    ("i32.shr_s", Instr::I32ShrS),
+    ("i32.shr_u", Instr::I32ShrU),
+    ("i32.rotl", Instr::I32Rotl),
+    ("i32.rotr", Instr::I32Rotr),
+    ("i64.clz", Instr::I64Clz),
+    ("i64.ctz", Instr::I64Ctz),
+    ("i64.popcnt", Instr::I64Popcnt),
+    ("i64.add", Instr::I64Add),
+    ("i64.sub", Instr::I64Sub),
+    ("i64.mul", Instr::I64Mul),
+    ("i64.div_s", Instr::I64DivS),
+    ("i64.div_u", Instr::I64DivU),
+    ("i64.rem_s", Instr::I64RemS),
+    ("i64.rem_u", Instr::I64RemU),
+    ("i64.and", Instr::I64And),
+    ("i64.or", Instr::I64Or),
+    ("i64.xor", Instr::I64Xor),
+    ("i64.shl", Instr::I64Shl),
+    ("i64.shr_s", Instr::I64ShrS),
+    ("i64.shr_u", Instr::I64ShrU),
+    ("i64.rotl", Instr::I64Rotl),
+    ("i64.rotr", Instr::I64Rotr),
+    ("f32.abs", Instr::F32Abs),
+    ("f32.neg", Instr::F32Neg),
+    ("f32.ceil", Instr::F32Ceil),
+    ("f32.floor", Instr::F32Floor),
+    ("f32.trunc", Instr::F32Trunc),
+    ("f32.nearest", Instr::F32Nearest),
+    ("f32.sqrt", Instr::F32Sqrt),
+    ("f32.add", Instr::F32Add),
+    ("f32.sub", Instr::F32Sub),
+    ("f32.mul", Instr::F32Mul),
+    ("f32.div", Instr::F32Div),
+    ("f32.min", Instr::F32Min),
+    ("f32.max", Instr::F32Max),
+    ("f32.copysign", Instr::F32Copysign),
+    ("f64.abs", Instr::F64Abs),
+    ("f64.neg", Instr::F64Neg),
+    ("f64.ceil", Instr::F64Ceil),
+    ("f64.floor", Instr::F64Floor),
+    ("f64.trunc", Instr::F64Trunc),
+    ("f64.nearest", Instr::F64Nearest),
+    ("f64.sqrt", Instr::F64Sqrt),
+    ("f64.add", Instr::F64Add),
+    ("f64.sub", Instr::F64Sub),
+    ("f64.mul", Instr::F64Mul),
+    ("f64.div", Instr::F64Div),
+    ("f64.min", Instr::F64Min),
+    ("f64.max", Instr::F64Max),
+    ("f64.copysign", Instr::F64Copysign),
+    ("i32.extend8_s", Instr::I32Extend8S),
+    ("i32.extend16_s", Instr::I32Extend16S),
+];
+
+pub type MakeMemoryInstr = fn(MemArg) -> Instr;
+
+/// Load and store instructions with their natural alignment in bytes.
+pub const MEMORY_INSTRS: &[(&str, MakeMemoryInstr, u32)] = &[
+    ("i32.load", Instr::I32Load, 4),
+    ("i64.load", Instr::I64Load, 8),
+    ("f32.load", Instr::F32Load, 4),
+    ("f64.load", Instr::F64Load, 8),
+    ("i32.load8_s", Instr::I32Load8S, 1),
+    ("i32.load8_u", Instr::I32Load8U, 1),
+    ("i32.load16_s", Instr::I32Load16S, 2),
+    ("i32.load16_u", Instr::I32Load16U, 2),
+    ("i64.load8_s", Instr::I64Load8S, 1),
+    ("i64.load8_u", Instr::I64Load8U, 1),
+    ("i64.load16_s", Instr::I64Load16S, 2),
+    ("i64.load16_u", Instr::I64Load16U, 2),
+    ("i64.load32_s", Instr::I64Load32S, 4),
+    ("i64.load32_u", Instr::I64Load32U, 4),
+    ("i32.store", Instr::I32Store, 4),
+    ("i64.store", Instr::I64Store, 8),
+    ("f32.store", Instr::F32Store, 4),
+    ("f64.store", Instr::F64Store, 8),
+    ("i32.store8", Instr::I32Store8, 1),
+    ("i32.store16", Instr::I32Store16, 2),
+    ("i64.store8", Instr::I64Store8, 1),
+    ("i64.store16", Instr::I64Store16, 2),
+    ("i64.store32", Instr::I64Store32, 4),
+];
+
+pub fn plain_instr(name: &str) -> Option<Instr> {
+    PLAIN_INSTRS
+        .iter()
+        .find(|(candidate, _)| *candidate == name)
+        .map(|(_, instr)| instr.clone())
+}
+
+pub fn memory_instr(name: &str) -> Option<(MakeMemoryInstr, u32)> {
+    MEMORY_INSTRS
+        .iter()
+        .find(|(candidate, _, _)| *candidate == name)
+        .map(|(_, make, natural_alignment)| (*make, *natural_alignment))
+}
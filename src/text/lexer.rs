@@ -0,0 +1,262 @@
+use crate::text::ParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    LParen,
+    RParen,
+    /// A keyword such as `module` or `i32.add`, or a number and other reserved words.
+    Atom(String),
+    /// A symbolic identifier with the leading `$` removed.
+    Id(String),
+    String(Vec<u8>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+}
+
+pub struct Lexer<'a> {
+    input: &'a [u8],
+    pos: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input: input.as_bytes(),
+            pos: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    pub fn tokenize(mut self) -> Result<Vec<Token>, ParseError> {
+        let mut tokens = Vec::new();
+        while let Some(token) = self.next_token()? {
+            tokens.push(token);
+        }
+        Ok(tokens)
+    }
+
+    fn span(&self) -> Span {
+        Span {
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<u8> {
+        self.input.get(self.pos + offset).copied()
+    }
+
+    fn bump(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.pos += 1;
+        if byte == b'\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(byte)
+    }
+
+    fn skip_whitespace_and_comments(&mut self) -> Result<(), ParseError> {
+        loop {
+            match (self.peek(), self.peek_at(1)) {
+                (Some(b' ' | b'\t' | b'\n' | b'\r'), _) => {
+                    self.bump();
+                }
+                (Some(b';'), Some(b';')) => {
+                    while !matches!(self.peek(), None | Some(b'\n')) {
+                        self.bump();
+                    }
+                }
+                (Some(b'('), Some(b';')) => self.skip_block_comment()?,
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Skips a `(; ... ;)` comment, which may be nested.
+    fn skip_block_comment(&mut self) -> Result<(), ParseError> {
+        let span = self.span();
+        let mut depth = 0;
+        loop {
+            match (self.peek(), self.peek_at(1)) {
+                (Some(b'('), Some(b';')) => {
+                    self.bump();
+                    self.bump();
+                    depth += 1;
+                }
+                (Some(b';'), Some(b')')) => {
+                    self.bump();
+                    self.bump();
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(());
+                    }
+                }
+                (Some(_), _) => {
+                    self.bump();
+                }
+                (None, _) => return Err(ParseError::new(span, "unclosed block comment")),
+            }
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, ParseError> {
+        self.skip_whitespace_and_comments()?;
+        let span = self.span();
+        let kind = match self.peek() {
+            None => return Ok(None),
+            Some(b'(') => {
+                self.bump();
+                TokenKind::LParen
+            }
+            Some(b')') => {
+                self.bump();
+                TokenKind::RParen
+            }
+            Some(b'"') => TokenKind::String(self.string()?),
+            Some(_) => {
+                let start = self.pos;
+                while self.peek().is_some_and(is_idchar) {
+                    self.bump();
+                }
+                if start == self.pos {
+                    return Err(ParseError::new(span, "unexpected character"));
+                }
+                let text = String::from_utf8(self.input[start..self.pos].to_vec()).unwrap();
+                match text.strip_prefix('$') {
+                    Some("") => return Err(ParseError::new(span, "empty identifier")),
+                    Some(id) => TokenKind::Id(id.to_string()),
+                    None => TokenKind::Atom(text),
+                }
+            }
+        };
+        Ok(Some(Token { kind, span }))
+    }
+
+    fn string(&mut self) -> Result<Vec<u8>, ParseError> {
+        let span = self.span();
+        self.bump();
+        let mut bytes = Vec::new();
+        loop {
+            let Some(byte) = self.bump() else {
+                return Err(ParseError::new(span, "unclosed string"));
+            };
+            match byte {
+                b'"' => return Ok(bytes),
+                b'\\' => self.escape(&mut bytes)?,
+                b'\n' => return Err(ParseError::new(span, "newline in string")),
+                _ => bytes.push(byte),
+            }
+        }
+    }
+
+    fn escape(&mut self, bytes: &mut Vec<u8>) -> Result<(), ParseError> {
+        let span = self.span();
+        let invalid = || ParseError::new(span, "invalid escape sequence");
+        match self.bump().ok_or_else(invalid)? {
+            b'n' => bytes.push(b'\n'),
+            b't' => bytes.push(b'\t'),
+            b'r' => bytes.push(b'\r'),
+            b'"' => bytes.push(b'"'),
+            b'\'' => bytes.push(b'\''),
+            b'\\' => bytes.push(b'\\'),
+            b'u' => {
+                if self.bump() != Some(b'{') {
+                    return Err(invalid());
+                }
+                let mut code = 0u32;
+                loop {
+                    let byte = self.bump().ok_or_else(invalid)?;
+                    if byte == b'}' {
+                        break;
+                    }
+                    let digit = (byte as char).to_digit(16).ok_or_else(invalid)?;
+                    code = code
+                        .checked_mul(16)
+                        .and_then(|code| code.checked_add(digit))
+                        .ok_or_else(invalid)?;
+                }
+                let c = char::from_u32(code).ok_or_else(invalid)?;
+                let mut buf = [0; 4];
+                bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            }
+            high => {
+                let low = self.bump().ok_or_else(invalid)?;
+                let high = (high as char).to_digit(16).ok_or_else(invalid)?;
+                let low = (low as char).to_digit(16).ok_or_else(invalid)?;
+                bytes.push((high * 16 + low) as u8);
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn is_idchar(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b"!#$%&'*+-./:<=>?@\\^_`|~".contains(&byte)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(input: &str) -> Vec<TokenKind> {
+        Lexer::new(input)
+            .tokenize()
+            .unwrap()
+            .into_iter()
+            .map(|token| token.kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_tokens() {
+        assert_eq!(
+            kinds("(func $f ;; comment\n (; nested (; block ;) ;) i32.const -0x1)"),
+            vec![
+                TokenKind::LParen,
+                TokenKind::Atom("func".to_string()),
+                TokenKind::Id("f".to_string()),
+                TokenKind::Atom("i32.const".to_string()),
+                TokenKind::Atom("-0x1".to_string()),
+                TokenKind::RParen,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_string_escapes() {
+        assert_eq!(
+            kinds(r#""a\n\t\"\\\00\ff\u{1F47B}""#),
+            vec![TokenKind::String(
+                [b"a\n\t\"\\\x00\xff".as_slice(), "👻".as_bytes()].concat()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = Lexer::new("(module\n  \"abc").tokenize().unwrap_err();
+        assert_eq!(error.span, Span { line: 2, column: 3 });
+        assert!(Lexer::new("(; never closed").tokenize().is_err());
+        assert!(Lexer::new("\"\\q\"").tokenize().is_err());
+    }
+}
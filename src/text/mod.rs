@@ -0,0 +1,254 @@
+pub mod instructions;
+pub mod lexer;
+pub mod parser;
+pub mod printer;
+
+use crate::structure::modules::module::Module;
+use crate::text::lexer::{Lexer, Span};
+use crate::text::parser::{parse_module, read_sexprs};
+use std::fmt;
+
+pub use crate::text::printer::print;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub span: Span,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(span: Span, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}",
+            self.span.line, self.span.column, self.message
+        )
+    }
+}
+
+/// Parses a module written in the WebAssembly text format.
+pub fn parse(source: &str) -> Result<Module, ParseError> {
+    let tokens = Lexer::new(source).tokenize()?;
+    let sexprs = read_sexprs(tokens)?;
+    parse_module(&sexprs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::structure::instructions::expression::{BlockType, Expr, Instr, MemArg};
+    use crate::structure::modules::custom::Names;
+    use crate::structure::modules::data::{Data, DataMode};
+    use crate::structure::modules::export::{Export, ExportDesc};
+    use crate::structure::modules::import::{Import, ImportDesc};
+    use crate::structure::types::function::FuncType;
+    use crate::structure::types::memory::{Limits, MemType};
+    use crate::structure::types::value::{NumType, ValType};
+    use std::fs;
+
+    const I32: ValType = ValType::NumType(NumType::I32);
+
+    #[test]
+    fn test_inputs_match_binaries() {
+        for entry in fs::read_dir("./tests/inputs").unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_none_or(|extension| extension != "wat") {
+                continue;
+            }
+            let source = fs::read_to_string(&path).unwrap();
+            let binary = fs::read(path.with_extension("wasm")).unwrap();
+            // The binaries were built without a name section, and only decoded functions
+            // know the offsets of their instructions.
+            let mut parsed = parse(&source).unwrap();
+            parsed.names = Names::default();
+            let mut decoded = Decoder::new(&binary).decode().unwrap();
+            for func in &mut decoded.funcs {
+                func.offsets.clear();
+            }
+            assert_eq!(parsed, decoded, "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn test_flat_and_folded_if() {
+        let flat = parse(
+            "(module (func (param i32) (result i32)
+                local.get 0
+                if (result i32) i32.const 1 else i32.const 2 end))",
+        )
+        .unwrap();
+        let folded = parse(
+            "(module (func (param i32) (result i32)
+                (if (result i32) (local.get 0) (then (i32.const 1)) (else (i32.const 2)))))",
+        )
+        .unwrap();
+        assert_eq!(flat.funcs, folded.funcs);
+        assert_eq!(
+            flat.funcs[0].body,
+            Expr(vec![
+                Instr::LocalGet(0),
+                Instr::If(
+                    BlockType::ValType(I32),
+                    vec![Instr::I32Const(1)],
+                    vec![Instr::I32Const(2)]
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_identifiers_and_type_uses() {
+        let module = parse(
+            r#"(module
+                (type $binary (func (param i32 i32) (result i32)))
+                (import "env" "log" (func $log (param i32)))
+                (func $main (export "main") (type $binary) (local $tmp i32)
+                    (call $log (local.get $tmp))
+                    (call $helper (local.get 1)))
+                (func $helper (param $x i32) (result i32) local.get $x)
+                (start $start)
+                (func $start))"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            module.types,
+            vec![
+                FuncType {
+                    parameters: vec![I32, I32],
+                    results: vec![I32],
+                },
+                FuncType {
+                    parameters: vec![I32],
+                    results: vec![],
+                },
+                FuncType {
+                    parameters: vec![I32],
+                    results: vec![I32],
+                },
+                FuncType {
+                    parameters: vec![],
+                    results: vec![],
+                },
+            ]
+        );
+        assert_eq!(
+            module.imports,
+            vec![Import {
+                module: "env".to_string(),
+                name: "log".to_string(),
+                desc: ImportDesc::Func(1),
+            }]
+        );
+        assert_eq!(
+            module.funcs[0].body,
+            Expr(vec![
+                Instr::LocalGet(2),
+                Instr::Call(0),
+                Instr::LocalGet(1),
+                Instr::Call(2),
+            ])
+        );
+        assert_eq!(
+            module.exports,
+            vec![Export {
+                name: "main".to_string(),
+                desc: ExportDesc::Func(1),
+            }]
+        );
+        assert_eq!(module.start, Some(3));
+    }
+
+    #[test]
+    fn test_memory_and_data() {
+        let module = parse(
+            r#"(module
+                (import "env" "memory" (memory $imported 1))
+                (memory $mem (export "memory") 1 2)
+                (data (memory $mem) (i32.const 8) "hi\00" "!")
+                (data $passive "\ff")
+                (func (result i64)
+                    (i64.load8_u offset=4 align=1 (i32.const 0))))"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            module.imports[0].desc,
+            ImportDesc::Mem(MemType(Limits { min: 1, max: None }))
+        );
+        assert_eq!(
+            module.mems[0].type_,
+            MemType(Limits {
+                min: 1,
+                max: Some(2)
+            })
+        );
+        assert_eq!(module.exports[0].desc, ExportDesc::Mem(1));
+        assert_eq!(
+            module.datas,
+            vec![
+                Data {
+                    init: b"hi\0!".to_vec(),
+                    mode: DataMode::Active {
+                        memory: 1,
+                        offset: Expr(vec![Instr::I32Const(8)]),
+                    },
+                },
+                Data {
+                    init: vec![0xff],
+                    mode: DataMode::Passive,
+                },
+            ]
+        );
+        assert_eq!(
+            module.funcs[0].body,
+            Expr(vec![
+                Instr::I32Const(0),
+                Instr::I64Load8U(MemArg {
+                    align: 0,
+                    offset: 4
+                }),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_inline_memory_data() {
+        let module = parse(r#"(module (memory (data "abc")))"#).unwrap();
+        assert_eq!(
+            module.mems[0].type_,
+            MemType(Limits {
+                min: 1,
+                max: Some(1)
+            })
+        );
+        assert_eq!(module.datas[0].init, b"abc".to_vec());
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = parse("(module\n  (func (call $missing)))").unwrap_err();
+        assert_eq!(error.to_string(), "2:15: unknown function $missing");
+
+        let error = parse("(module (func i32.frobnicate))").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "1:15: unknown instruction `i32.frobnicate`"
+        );
+
+        assert!(parse("(module (func) (import \"a\" \"b\" (func)))").is_err());
+        assert!(parse("(module (func $f) (func $f))").is_err());
+        assert!(parse("(module (func (result i32) (i32.const 0x1_0000_0000)))").is_err());
+        assert!(parse("(module (func)").is_err());
+    }
+}
@@ -0,0 +1,978 @@
+use crate::execution::structure::PAGE_SIZE;
+use crate::execution::value::{parse_f32, parse_f64, parse_i32, parse_i64};
+use crate::structure::instructions::expression::{BlockType, Expr, Instr, MemArg};
+use crate::structure::modules::custom::{NameMap, Names};
+use crate::structure::modules::data::{Data, DataMode};
+use crate::structure::modules::export::{Export, ExportDesc};
+use crate::structure::modules::function::Func;
+use crate::structure::modules::import::{Import, ImportDesc};
+use crate::structure::modules::indice::{FuncIdx, MemIdx, TableIdx, TypeIdx};
+use crate::structure::modules::memory::Mem;
+use crate::structure::modules::module::Module;
+use crate::structure::modules::table::Table;
+use crate::structure::types::function::FuncType;
+use crate::structure::types::memory::{Limits, MemType};
+use crate::structure::types::table::TableType;
+use crate::structure::types::value::{NumType, RefType, ValType};
+use crate::text::instructions::{memory_instr, plain_instr};
+use crate::text::lexer::{Span, Token, TokenKind};
+use crate::text::ParseError;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Sexpr {
+    Atom(String, Span),
+    Id(String, Span),
+    String(Vec<u8>, Span),
+    List(Vec<Sexpr>, Span),
+}
+
+impl Sexpr {
+    pub fn span(&self) -> Span {
+        match self {
+            Sexpr::Atom(_, span)
+            | Sexpr::Id(_, span)
+            | Sexpr::String(_, span)
+            | Sexpr::List(_, span) => *span,
+        }
+    }
+
+    /// Returns the keyword a list starts with, e.g. `func` for `(func ...)`.
+    pub fn keyword(&self) -> Option<&str> {
+        match self {
+            Sexpr::List(items, _) => match items.first() {
+                Some(Sexpr::Atom(keyword, _)) => Some(keyword),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Groups tokens into nested lists.
+pub fn read_sexprs(tokens: Vec<Token>) -> Result<Vec<Sexpr>, ParseError> {
+    let mut stack: Vec<(Vec<Sexpr>, Span)> = Vec::new();
+    let mut top = Vec::new();
+    for token in tokens {
+        let sexpr = match token.kind {
+            TokenKind::LParen => {
+                stack.push((std::mem::take(&mut top), token.span));
+                continue;
+            }
+            TokenKind::RParen => {
+                let Some((parent, span)) = stack.pop() else {
+                    return Err(ParseError::new(token.span, "unexpected `)`"));
+                };
+                let items = std::mem::replace(&mut top, parent);
+                Sexpr::List(items, span)
+            }
+            TokenKind::Atom(atom) => Sexpr::Atom(atom, token.span),
+            TokenKind::Id(id) => Sexpr::Id(id, token.span),
+            TokenKind::String(bytes) => Sexpr::String(bytes, token.span),
+        };
+        top.push(sexpr);
+    }
+    if let Some((_, span)) = stack.pop() {
+        return Err(ParseError::new(span, "unclosed `(`"));
+    }
+    Ok(top)
+}
+
+struct Cursor<'a> {
+    items: &'a [Sexpr],
+    pos: usize,
+    /// Where the enclosing list starts, for errors about missing items.
+    span: Span,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(items: &'a [Sexpr], span: Span) -> Self {
+        Self {
+            items,
+            pos: 0,
+            span,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.items.len()
+    }
+
+    fn peek(&self) -> Option<&'a Sexpr> {
+        self.items.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<&'a Sexpr> {
+        let item = self.items.get(self.pos)?;
+        self.pos += 1;
+        Some(item)
+    }
+
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        let span = self.peek().map_or(self.span, Sexpr::span);
+        ParseError::new(span, message)
+    }
+
+    fn peek_atom(&self) -> Option<&'a str> {
+        match self.peek() {
+            Some(Sexpr::Atom(atom, _)) => Some(atom),
+            _ => None,
+        }
+    }
+
+    fn peek_keyword(&self) -> Option<&'a str> {
+        self.peek().and_then(Sexpr::keyword)
+    }
+
+    fn atom(&mut self) -> Result<&'a str, ParseError> {
+        match self.peek() {
+            Some(Sexpr::Atom(atom, _)) => {
+                self.pos += 1;
+                Ok(atom)
+            }
+            _ => Err(self.error("expected a keyword or number")),
+        }
+    }
+
+    fn id(&mut self) -> Option<(&'a str, Span)> {
+        match self.peek() {
+            Some(Sexpr::Id(id, span)) => {
+                self.pos += 1;
+                Some((id, *span))
+            }
+            _ => None,
+        }
+    }
+
+    fn string(&mut self) -> Result<&'a [u8], ParseError> {
+        match self.peek() {
+            Some(Sexpr::String(bytes, _)) => {
+                self.pos += 1;
+                Ok(bytes)
+            }
+            _ => Err(self.error("expected a string")),
+        }
+    }
+
+    fn name(&mut self) -> Result<String, ParseError> {
+        let error = self.error("malformed UTF-8 encoding");
+        let bytes = self.string()?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| error)
+    }
+
+    fn u32(&mut self) -> Result<u32, ParseError> {
+        let error = self.error("expected an unsigned 32-bit integer");
+        parse_u32(self.atom()?).ok_or(error)
+    }
+
+    /// Consumes a list starting with `keyword` and returns a cursor over the rest of it.
+    fn try_list(&mut self, keyword: &str) -> Option<Cursor<'a>> {
+        match self.peek() {
+            Some(Sexpr::List(items, span)) if self.peek_keyword() == Some(keyword) => {
+                self.pos += 1;
+                Some(Cursor {
+                    items,
+                    pos: 1,
+                    span: *span,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn list(&mut self, keyword: &str) -> Result<Cursor<'a>, ParseError> {
+        self.try_list(keyword)
+            .ok_or_else(|| self.error(format!("expected `({} ...)`", keyword)))
+    }
+
+    fn finish(&self) -> Result<(), ParseError> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("unexpected token"))
+        }
+    }
+}
+
+fn parse_u32(text: &str) -> Option<u32> {
+    let digits = text.replace('_', "");
+    if digits.is_empty() || digits.starts_with(['+', '-']) {
+        return None;
+    }
+    match digits.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => digits.parse().ok(),
+    }
+}
+
+fn val_type(cursor: &mut Cursor) -> Result<ValType, ParseError> {
+    let error = cursor.error("expected a value type");
+    let type_ = match cursor.atom()? {
+        "i32" => ValType::NumType(NumType::I32),
+        "i64" => ValType::NumType(NumType::I64),
+        "f32" => ValType::NumType(NumType::F32),
+        "f64" => ValType::NumType(NumType::F64),
+        "funcref" => ValType::RefType(RefType::FuncRef),
+        _ => return Err(error),
+    };
+    Ok(type_)
+}
+
+fn ref_type(cursor: &mut Cursor) -> Result<RefType, ParseError> {
+    let error = cursor.error("expected a reference type");
+    match cursor.atom()? {
+        "funcref" => Ok(RefType::FuncRef),
+        _ => Err(error),
+    }
+}
+
+/// The identifier of a parameter or local and where it is defined.
+type LocalName = Option<(String, Span)>;
+
+#[derive(Default)]
+struct Identifiers {
+    indices: HashMap<String, u32>,
+}
+
+impl Identifiers {
+    fn define(&mut self, id: Option<(&str, Span)>, idx: u32, kind: &str) -> Result<(), ParseError> {
+        if let Some((id, span)) = id {
+            if self.indices.insert(id.to_string(), idx).is_some() {
+                return Err(ParseError::new(span, format!("duplicate {} ${}", kind, id)));
+            }
+        }
+        Ok(())
+    }
+
+    fn resolve(&self, cursor: &mut Cursor, kind: &str) -> Result<u32, ParseError> {
+        if let Some((id, span)) = cursor.id() {
+            return self
+                .indices
+                .get(id)
+                .copied()
+                .ok_or_else(|| ParseError::new(span, format!("unknown {} ${}", kind, id)));
+        }
+        cursor.u32()
+    }
+}
+
+struct Parser {
+    module: Module,
+    type_names: Identifiers,
+    func_names: Identifiers,
+    table_names: Identifiers,
+    mem_names: Identifiers,
+    declared_funcs: u32,
+    declared_tables: u32,
+    declared_mems: u32,
+    defined: bool,
+}
+
+/// Parses a module, either `(module ...)` or just its fields.
+pub fn parse_module(sexprs: &[Sexpr]) -> Result<Module, ParseError> {
+    let start = Span { line: 1, column: 1 };
+    match sexprs {
+        [Sexpr::List(items, span)] if sexprs[0].keyword() == Some("module") => {
+            let mut cursor = Cursor::new(items, *span);
+            cursor.next();
+            let id = cursor.id();
+            let mut module = module_fields(cursor)?;
+            module.names.module = id.map(|(id, _)| id.to_string());
+            Ok(module)
+        }
+        _ => module_fields(Cursor::new(sexprs, start)),
+    }
+}
+
+fn module_fields(mut cursor: Cursor) -> Result<Module, ParseError> {
+    let mut parser = Parser {
+        module: Module::default(),
+        type_names: Identifiers::default(),
+        func_names: Identifiers::default(),
+        table_names: Identifiers::default(),
+        mem_names: Identifiers::default(),
+        declared_funcs: 0,
+        declared_tables: 0,
+        declared_mems: 0,
+        defined: false,
+    };
+
+    // Explicit types come first in the type index space, followed by the ones introduced by
+    // inline type uses, so they are collected along with all the names before anything else.
+    for field in &cursor.items[cursor.pos..] {
+        let Sexpr::List(items, span) = field else {
+            return Err(ParseError::new(field.span(), "expected a module field"));
+        };
+        let mut field = Cursor::new(items, *span);
+        match field.atom()? {
+            "type" => parser.type_definition(&mut field)?,
+            "import" => {
+                field.string()?;
+                field.string()?;
+                let Some(kind) = field.peek_keyword() else {
+                    return Err(field.error("expected an import description"));
+                };
+                let mut desc = field.list(kind)?;
+                parser.declare(kind, desc.id(), true, *span)?;
+            }
+            keyword @ ("func" | "table" | "memory") => {
+                let id = field.id();
+                while field.try_list("export").is_some() {}
+                let is_import = field.peek_keyword() == Some("import");
+                parser.declare(keyword, id, is_import, *span)?;
+            }
+            _ => {}
+        }
+    }
+
+    while let Some(Sexpr::List(items, span)) = cursor.next() {
+        let mut field = Cursor::new(items, *span);
+        match field.atom()? {
+            "type" => {}
+            "import" => parser.import(&mut field)?,
+            "func" => parser.func(&mut field)?,
+            "table" => parser.table(&mut field)?,
+            "memory" => parser.memory(&mut field)?,
+            "export" => parser.export(&mut field)?,
+            "start" => {
+                if parser.module.start.is_some() {
+                    return Err(ParseError::new(*span, "multiple start sections"));
+                }
+                parser.module.start = Some(parser.func_names.resolve(&mut field, "function")?);
+                field.finish()?;
+            }
+            "data" => parser.data(&mut field)?,
+            keyword => {
+                return Err(ParseError::new(
+                    *span,
+                    format!("unknown module field `{}`", keyword),
+                ))
+            }
+        }
+    }
+
+    Ok(parser.module)
+}
+
+impl Parser {
+    /// Assigns the next index of the function, table or memory index space to `id`.
+    fn declare(
+        &mut self,
+        kind: &str,
+        id: Option<(&str, Span)>,
+        is_import: bool,
+        span: Span,
+    ) -> Result<(), ParseError> {
+        if is_import && self.defined {
+            return Err(ParseError::new(span, "import after definition"));
+        }
+        self.defined |= !is_import;
+
+        match kind {
+            "func" => {
+                self.func_names
+                    .define(id, self.declared_funcs, "function")?;
+                self.record_name(id, |names| &mut names.funcs, self.declared_funcs);
+                self.declared_funcs += 1;
+            }
+            // Tables can't be imported.
+            "table" if !is_import => {
+                self.table_names.define(id, self.declared_tables, "table")?;
+                self.record_name(id, |names| &mut names.tables, self.declared_tables);
+                self.declared_tables += 1;
+            }
+            "memory" => {
+                self.mem_names.define(id, self.declared_mems, "memory")?;
+                self.record_name(id, |names| &mut names.mems, self.declared_mems);
+                self.declared_mems += 1;
+            }
+            _ => {
+                return Err(ParseError::new(
+                    span,
+                    format!("unsupported import kind `{}`", kind),
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    /// Keeps an identifier in the name section, so that it survives encoding.
+    fn record_name(
+        &mut self,
+        id: Option<(&str, Span)>,
+        map: impl FnOnce(&mut Names) -> &mut NameMap,
+        idx: u32,
+    ) {
+        if let Some((id, _)) = id {
+            map(&mut self.module.names).insert(idx, id.to_string());
+        }
+    }
+
+    fn type_definition(&mut self, cursor: &mut Cursor) -> Result<(), ParseError> {
+        let id = cursor.id();
+        let mut func = cursor.list("func")?;
+        let (type_, _) = func_type(&mut func)?;
+        func.finish()?;
+        cursor.finish()?;
+
+        let idx = self.module.types.len() as TypeIdx;
+        self.type_names.define(id, idx, "type")?;
+        self.record_name(id, |names| &mut names.types, idx);
+        self.module.types.push(type_);
+        Ok(())
+    }
+
+    /// Parses a type use, `(type x)` followed by optional parameters and results, and
+    /// returns its type index and the names of the parameters.
+    fn type_use(&mut self, cursor: &mut Cursor) -> Result<(TypeIdx, Vec<LocalName>), ParseError> {
+        let explicit = match cursor.try_list("type") {
+            Some(mut type_cursor) => {
+                let idx = self.type_names.resolve(&mut type_cursor, "type")?;
+                type_cursor.finish()?;
+                if idx as usize >= self.module.types.len() {
+                    return Err(type_cursor.error(format!("unknown type {}", idx)));
+                }
+                Some(idx)
+            }
+            None => None,
+        };
+
+        let span = cursor.peek().map_or(cursor.span, Sexpr::span);
+        let (type_, names) = func_type(cursor)?;
+        match explicit {
+            Some(idx) => {
+                let has_inline = !type_.parameters.is_empty() || !type_.results.is_empty();
+                let expected = &self.module.types[idx as usize];
+                if has_inline && &type_ != expected {
+                    return Err(ParseError::new(span, "inline function type does not match"));
+                }
+                let names = if has_inline {
+                    names
+                } else {
+                    vec![None; expected.parameters.len()]
+                };
+                Ok((idx, names))
+            }
+            None => {
+                let idx = match self.module.types.iter().position(|t| t == &type_) {
+                    Some(idx) => idx,
+                    None => {
+                        self.module.types.push(type_);
+                        self.module.types.len() - 1
+                    }
+                };
+                Ok((idx as TypeIdx, names))
+            }
+        }
+    }
+
+    fn inline_exports(&mut self, cursor: &mut Cursor, desc: ExportDesc) -> Result<(), ParseError> {
+        while let Some(mut export) = cursor.try_list("export") {
+            let name = export.name()?;
+            export.finish()?;
+            self.module.exports.push(Export {
+                name,
+                desc: desc.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    fn inline_import(
+        &mut self,
+        cursor: &mut Cursor,
+    ) -> Result<Option<(String, String)>, ParseError> {
+        match cursor.try_list("import") {
+            Some(mut import) => {
+                let module = import.name()?;
+                let name = import.name()?;
+                import.finish()?;
+                Ok(Some((module, name)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn import(&mut self, cursor: &mut Cursor) -> Result<(), ParseError> {
+        let module = cursor.name()?;
+        let name = cursor.name()?;
+        let desc = if let Some(mut func) = cursor.try_list("func") {
+            func.id();
+            let (type_idx, _) = self.type_use(&mut func)?;
+            func.finish()?;
+            ImportDesc::Func(type_idx)
+        } else if let Some(mut memory) = cursor.try_list("memory") {
+            memory.id();
+            let limits = limits(&mut memory)?;
+            memory.finish()?;
+            ImportDesc::Mem(MemType(limits))
+        } else {
+            return Err(cursor.error("unsupported import description"));
+        };
+        cursor.finish()?;
+        self.module.imports.push(Import { module, name, desc });
+        Ok(())
+    }
+
+    fn func(&mut self, cursor: &mut Cursor) -> Result<(), ParseError> {
+        cursor.id();
+        let idx = self.func_count();
+        self.inline_exports(cursor, ExportDesc::Func(idx))?;
+
+        if let Some((module, name)) = self.inline_import(cursor)? {
+            let (type_idx, _) = self.type_use(cursor)?;
+            cursor.finish()?;
+            self.module.imports.push(Import {
+                module,
+                name,
+                desc: ImportDesc::Func(type_idx),
+            });
+            return Ok(());
+        }
+
+        let (type_idx, mut local_names) = self.type_use(cursor)?;
+        let mut locals = Vec::new();
+        while let Some(mut local) = cursor.try_list("local") {
+            if let Some((id, span)) = local.id() {
+                locals.push(val_type(&mut local)?);
+                local_names.push(Some((id.to_string(), span)));
+                local.finish()?;
+                continue;
+            }
+            while !local.is_empty() {
+                locals.push(val_type(&mut local)?);
+                local_names.push(None);
+            }
+        }
+
+        let mut local_indices = Identifiers::default();
+        for (i, name) in local_names.iter().enumerate() {
+            let id = name.as_ref().map(|(name, span)| (name.as_str(), *span));
+            local_indices.define(id, i as u32, "local")?;
+            if let Some((name, _)) = name {
+                let locals = self.module.names.locals.entry(idx).or_default();
+                locals.insert(i as u32, name.clone());
+            }
+        }
+
+        let body = FuncBody {
+            parser: self,
+            locals: &local_indices,
+        }
+        .instrs(cursor)?;
+        self.module.funcs.push(Func {
+            type_: type_idx,
+            locals,
+            body: Expr(body),
+            offsets: Vec::new(),
+        });
+        Ok(())
+    }
+
+    fn table(&mut self, cursor: &mut Cursor) -> Result<(), ParseError> {
+        cursor.id();
+        if cursor.peek_keyword() == Some("export") {
+            return Err(cursor.error("table exports are not supported"));
+        }
+        let limits = limits(cursor)?;
+        let ref_type = ref_type(cursor)?;
+        cursor.finish()?;
+        self.module.tables.push(Table {
+            type_: TableType(limits, ref_type),
+        });
+        Ok(())
+    }
+
+    fn memory(&mut self, cursor: &mut Cursor) -> Result<(), ParseError> {
+        cursor.id();
+        let idx = self.mem_count();
+        self.inline_exports(cursor, ExportDesc::Mem(idx))?;
+
+        if let Some((module, name)) = self.inline_import(cursor)? {
+            let limits = limits(cursor)?;
+            cursor.finish()?;
+            self.module.imports.push(Import {
+                module,
+                name,
+                desc: ImportDesc::Mem(MemType(limits)),
+            });
+            return Ok(());
+        }
+
+        if let Some(mut data) = cursor.try_list("data") {
+            let mut init = Vec::new();
+            while !data.is_empty() {
+                init.extend_from_slice(data.string()?);
+            }
+            cursor.finish()?;
+            let pages = init.len().div_ceil(PAGE_SIZE) as u32;
+            self.module.mems.push(Mem {
+                type_: MemType(Limits {
+                    min: pages,
+                    max: Some(pages),
+                }),
+            });
+            self.module.datas.push(Data {
+                init,
+                mode: DataMode::Active {
+                    memory: idx,
+                    offset: Expr(vec![Instr::I32Const(0)]),
+                },
+            });
+            return Ok(());
+        }
+
+        let limits = limits(cursor)?;
+        cursor.finish()?;
+        self.module.mems.push(Mem {
+            type_: MemType(limits),
+        });
+        Ok(())
+    }
+
+    fn export(&mut self, cursor: &mut Cursor) -> Result<(), ParseError> {
+        let name = cursor.name()?;
+        let desc = if let Some(mut func) = cursor.try_list("func") {
+            let idx = self.func_names.resolve(&mut func, "function")?;
+            func.finish()?;
+            ExportDesc::Func(idx as FuncIdx)
+        } else if let Some(mut memory) = cursor.try_list("memory") {
+            let idx = self.mem_names.resolve(&mut memory, "memory")?;
+            memory.finish()?;
+            ExportDesc::Mem(idx as MemIdx)
+        } else {
+            return Err(cursor.error("unsupported export description"));
+        };
+        cursor.finish()?;
+        self.module.exports.push(Export { name, desc });
+        Ok(())
+    }
+
+    fn data(&mut self, cursor: &mut Cursor) -> Result<(), ParseError> {
+        let id = cursor.id();
+        let idx = self.module.datas.len() as u32;
+        self.record_name(id, |names| &mut names.datas, idx);
+        let memory = if let Some(mut memory) = cursor.try_list("memory") {
+            let idx = self.mem_names.resolve(&mut memory, "memory")?;
+            memory.finish()?;
+            Some(idx)
+        } else if matches!(cursor.peek(), Some(Sexpr::Id(..) | Sexpr::Atom(..))) {
+            Some(self.mem_names.resolve(cursor, "memory")?)
+        } else {
+            None
+        };
+
+        let no_locals = Identifiers::default();
+        let offset = if let Some(mut offset) = cursor.try_list("offset") {
+            Some(
+                FuncBody {
+                    parser: self,
+                    locals: &no_locals,
+                }
+                .instrs(&mut offset)?,
+            )
+        } else if matches!(cursor.peek(), Some(Sexpr::List(..))) {
+            let mut instrs = Vec::new();
+            let mut body = FuncBody {
+                parser: self,
+                locals: &no_locals,
+            };
+            body.folded_instr(cursor, &mut instrs)?;
+            Some(instrs)
+        } else {
+            None
+        };
+
+        let mut init = Vec::new();
+        while !cursor.is_empty() {
+            init.extend_from_slice(cursor.string()?);
+        }
+
+        let mode = match offset {
+            Some(offset) => DataMode::Active {
+                memory: memory.unwrap_or(0),
+                offset: Expr(offset),
+            },
+            None if memory.is_some() => return Err(cursor.error("expected an offset expression")),
+            None => DataMode::Passive,
+        };
+        self.module.datas.push(Data { init, mode });
+        Ok(())
+    }
+
+    fn func_count(&self) -> u32 {
+        let imported = self
+            .module
+            .imports
+            .iter()
+            .filter(|import| matches!(import.desc, ImportDesc::Func(_)))
+            .count();
+        (imported + self.module.funcs.len()) as u32
+    }
+
+    fn mem_count(&self) -> u32 {
+        let imported = self
+            .module
+            .imports
+            .iter()
+            .filter(|import| matches!(import.desc, ImportDesc::Mem(_)))
+            .count();
+        (imported + self.module.mems.len()) as u32
+    }
+}
+
+/// Parses `(param ...)*` and `(result ...)*`, returning the names of the parameters.
+fn func_type(cursor: &mut Cursor) -> Result<(FuncType, Vec<LocalName>), ParseError> {
+    let mut parameters = Vec::new();
+    let mut names = Vec::new();
+    while let Some(mut param) = cursor.try_list("param") {
+        if let Some((id, span)) = param.id() {
+            parameters.push(val_type(&mut param)?);
+            names.push(Some((id.to_string(), span)));
+            param.finish()?;
+            continue;
+        }
+        while !param.is_empty() {
+            parameters.push(val_type(&mut param)?);
+            names.push(None);
+        }
+    }
+
+    let mut results = Vec::new();
+    while let Some(mut result) = cursor.try_list("result") {
+        while !result.is_empty() {
+            results.push(val_type(&mut result)?);
+        }
+    }
+
+    Ok((
+        FuncType {
+            parameters,
+            results,
+        },
+        names,
+    ))
+}
+
+fn limits(cursor: &mut Cursor) -> Result<Limits, ParseError> {
+    let min = cursor.u32()?;
+    let max = match cursor.peek_atom() {
+        Some(atom) if parse_u32(atom).is_some() => Some(cursor.u32()?),
+        _ => None,
+    };
+    Ok(Limits { min, max })
+}
+
+struct FuncBody<'a> {
+    parser: &'a Parser,
+    locals: &'a Identifiers,
+}
+
+impl FuncBody<'_> {
+    /// Parses instructions until the end of the list.
+    fn instrs(&mut self, cursor: &mut Cursor) -> Result<Vec<Instr>, ParseError> {
+        let mut instrs = Vec::new();
+        while !cursor.is_empty() {
+            self.instr(cursor, &mut instrs)?;
+        }
+        Ok(instrs)
+    }
+
+    /// Parses flat instructions until one of `terminators`, which is left unconsumed.
+    fn instrs_until(
+        &mut self,
+        cursor: &mut Cursor,
+        terminators: &[&str],
+    ) -> Result<Vec<Instr>, ParseError> {
+        let mut instrs = Vec::new();
+        loop {
+            if cursor.is_empty() {
+                return Err(cursor.error(format!("expected `{}`", terminators.join("` or `"))));
+            }
+            if cursor
+                .peek_atom()
+                .is_some_and(|atom| terminators.contains(&atom))
+            {
+                return Ok(instrs);
+            }
+            self.instr(cursor, &mut instrs)?;
+        }
+    }
+
+    fn instr(&mut self, cursor: &mut Cursor, instrs: &mut Vec<Instr>) -> Result<(), ParseError> {
+        match cursor.peek() {
+            Some(Sexpr::List(..)) => self.folded_instr(cursor, instrs),
+            _ => {
+                if cursor.peek_atom() == Some("if") {
+                    cursor.next();
+                    let block_type = self.block_type(cursor)?;
+                    let then = self.instrs_until(cursor, &["else", "end"])?;
+                    let mut else_ = Vec::new();
+                    if cursor.atom()? == "else" {
+                        label(cursor);
+                        else_ = self.instrs_until(cursor, &["end"])?;
+                        cursor.next();
+                    }
+                    label(cursor);
+                    instrs.push(Instr::If(block_type, then, else_));
+                    return Ok(());
+                }
+                let instr = self.plain_instr(cursor)?;
+                instrs.push(instr);
+                Ok(())
+            }
+        }
+    }
+
+    fn folded_instr(
+        &mut self,
+        cursor: &mut Cursor,
+        instrs: &mut Vec<Instr>,
+    ) -> Result<(), ParseError> {
+        let Some(Sexpr::List(items, span)) = cursor.next() else {
+            return Err(cursor.error("expected a folded instruction"));
+        };
+        let mut list = Cursor::new(items, *span);
+
+        if list.peek_atom() == Some("if") {
+            list.next();
+            let block_type = self.block_type(&mut list)?;
+            while !list.is_empty() && list.peek_keyword() != Some("then") {
+                self.folded_instr(&mut list, instrs)?;
+            }
+            let mut then = list.list("then")?;
+            let then = self.instrs(&mut then)?;
+            let else_ = match list.try_list("else") {
+                Some(mut else_) => self.instrs(&mut else_)?,
+                None => Vec::new(),
+            };
+            list.finish()?;
+            instrs.push(Instr::If(block_type, then, else_));
+            return Ok(());
+        }
+
+        let instr = self.plain_instr(&mut list)?;
+        while !list.is_empty() {
+            self.folded_instr(&mut list, instrs)?;
+        }
+        instrs.push(instr);
+        Ok(())
+    }
+
+    fn block_type(&mut self, cursor: &mut Cursor) -> Result<BlockType, ParseError> {
+        label(cursor);
+        match cursor.try_list("result") {
+            Some(mut result) => {
+                let type_ = val_type(&mut result)?;
+                result.finish()?;
+                if cursor.peek_keyword() == Some("result") {
+                    return Err(cursor.error("multiple block results are not supported"));
+                }
+                Ok(BlockType::ValType(type_))
+            }
+            None => Ok(BlockType::Empty),
+        }
+    }
+
+    fn plain_instr(&mut self, cursor: &mut Cursor) -> Result<Instr, ParseError> {
+        let span = cursor.peek().map_or(cursor.span, Sexpr::span);
+        let name = cursor.atom()?;
+        let instr = match name {
+            "call" => Instr::Call(self.parser.func_names.resolve(cursor, "function")?),
+            "local.get" => Instr::LocalGet(self.locals.resolve(cursor, "local")?),
+            "ref.null" => {
+                let error = cursor.error("expected a heap type");
+                match cursor.atom()? {
+                    "func" => Instr::RefNull(RefType::FuncRef),
+                    _ => return Err(error),
+                }
+            }
+            "table.size" => Instr::TableSize(self.table_idx(cursor)?),
+            "table.grow" => Instr::TableGrow(self.table_idx(cursor)?),
+            "i32.const" => {
+                let error = cursor.error("invalid i32 constant");
+                Instr::I32Const(parse_i32(cursor.atom()?).ok_or(error)?)
+            }
+            "i64.const" => {
+                let error = cursor.error("invalid i64 constant");
+                Instr::I64Const(parse_i64(cursor.atom()?).ok_or(error)?)
+            }
+            "f32.const" => {
+                let error = cursor.error("invalid f32 constant");
+                Instr::F32Const(parse_f32(cursor.atom()?).ok_or(error)?)
+            }
+            "f64.const" => {
+                let error = cursor.error("invalid f64 constant");
+                Instr::F64Const(parse_f64(cursor.atom()?).ok_or(error)?)
+            }
+            "memory.size" | "memory.grow" => {
+                if matches!(cursor.peek(), Some(Sexpr::Id(..)))
+                    || cursor
+                        .peek_atom()
+                        .is_some_and(|atom| parse_u32(atom).is_some())
+                {
+                    let idx = self.parser.mem_names.resolve(cursor, "memory")?;
+                    if idx != 0 {
+                        return Err(ParseError::new(span, "multiple memories are not supported"));
+                    }
+                }
+                plain_instr(name).unwrap()
+            }
+            _ => {
+                if let Some(instr) = plain_instr(name) {
+                    instr
+                } else if let Some((make, natural_alignment)) = memory_instr(name) {
+                    make(memarg(cursor, natural_alignment)?)
+                } else {
+                    return Err(ParseError::new(
+                        span,
+                        format!("unknown instruction `{}`", name),
+                    ));
+                }
+            }
+        };
+        Ok(instr)
+    }
+
+    /// Parses the optional table operand of a table instruction, which defaults to 0.
+    fn table_idx(&mut self, cursor: &mut Cursor) -> Result<TableIdx, ParseError> {
+        if matches!(cursor.peek(), Some(Sexpr::Id(..)))
+            || cursor
+                .peek_atom()
+                .is_some_and(|atom| parse_u32(atom).is_some())
+        {
+            return self.parser.table_names.resolve(cursor, "table");
+        }
+        Ok(0)
+    }
+}
+
+/// Skips an optional block label, which is not needed without branch instructions.
+fn label(cursor: &mut Cursor) {
+    cursor.id();
+}
+
+fn memarg(cursor: &mut Cursor, natural_alignment: u32) -> Result<MemArg, ParseError> {
+    let mut offset = 0;
+    if let Some(text) = cursor
+        .peek_atom()
+        .and_then(|atom| atom.strip_prefix("offset="))
+    {
+        offset = parse_u32(text).ok_or_else(|| cursor.error("invalid offset"))?;
+        cursor.next();
+    }
+    let mut align = natural_alignment;
+    if let Some(text) = cursor
+        .peek_atom()
+        .and_then(|atom| atom.strip_prefix("align="))
+    {
+        align = parse_u32(text)
+            .filter(|align| align.is_power_of_two())
+            .ok_or_else(|| cursor.error("alignment must be a power of two"))?;
+        cursor.next();
+    }
+    Ok(MemArg {
+        align: align.trailing_zeros(),
+        offset,
+    })
+}
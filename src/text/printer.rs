@@ -0,0 +1,536 @@
+use crate::structure::instructions::expression::{BlockType, Expr, Instr, MemArg};
+use crate::structure::modules::custom::NameMap;
+use crate::structure::modules::data::DataMode;
+use crate::structure::modules::export::ExportDesc;
+use crate::structure::modules::import::ImportDesc;
+use crate::structure::modules::module::Module;
+use crate::structure::types::function::FuncType;
+use crate::structure::types::memory::Limits;
+use crate::structure::types::value::{RefType, ValType};
+use crate::text::instructions::{MEMORY_INSTRS, PLAIN_INSTRS};
+use crate::text::lexer::is_idchar;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+const INDENT: &str = "  ";
+
+/// Prints a module in the WebAssembly text format, with instructions in the flat form.
+/// Entities that have a name in the name section are given it as their identifier.
+pub fn print(module: &Module) -> String {
+    let names = &module.names;
+    let mut printer = Printer {
+        module,
+        out: String::new(),
+        depth: 0,
+        types: Ids::new(&names.types),
+        funcs: Ids::new(&names.funcs),
+        tables: Ids::new(&names.tables),
+        mems: Ids::new(&names.mems),
+        datas: Ids::new(&names.datas),
+        locals: Ids::default(),
+    };
+    printer.module();
+    printer.out
+}
+
+/// Identifiers for an index space, leaving out names that can't be written as one.
+#[derive(Default)]
+struct Ids(HashMap<u32, String>);
+
+impl Ids {
+    fn new(names: &NameMap) -> Self {
+        let mut seen = HashSet::new();
+        let ids = names
+            .iter()
+            .filter(|(_, name)| !name.is_empty() && name.bytes().all(is_idchar))
+            .filter(|(_, name)| seen.insert(name.as_str()))
+            .map(|(idx, name)| (*idx, format!("${}", name)))
+            .collect();
+        Self(ids)
+    }
+
+    /// How an entity is introduced: its identifier, if any, and its index as a comment.
+    fn definition(&self, idx: u32) -> String {
+        match self.0.get(&idx) {
+            Some(id) => format!("{} (;{};)", id, idx),
+            None => format!("(;{};)", idx),
+        }
+    }
+
+    /// How an entity is referred to: its identifier or else its index.
+    fn reference(&self, idx: u32) -> String {
+        match self.0.get(&idx) {
+            Some(id) => id.clone(),
+            None => idx.to_string(),
+        }
+    }
+
+    fn get(&self, idx: u32) -> Option<&str> {
+        self.0.get(&idx).map(String::as_str)
+    }
+}
+
+struct Printer<'a> {
+    module: &'a Module,
+    out: String,
+    depth: usize,
+    types: Ids,
+    funcs: Ids,
+    tables: Ids,
+    mems: Ids,
+    datas: Ids,
+    /// The locals of the function being printed.
+    locals: Ids,
+}
+
+impl Printer<'_> {
+    fn line(&mut self, text: &str) {
+        for _ in 0..self.depth {
+            self.out.push_str(INDENT);
+        }
+        self.out.push_str(text);
+        self.out.push('\n');
+    }
+
+    fn module(&mut self) {
+        match &self.module.names.module {
+            Some(name) if !name.is_empty() && name.bytes().all(is_idchar) => {
+                self.line(&format!("(module ${}", name))
+            }
+            _ => self.line("(module"),
+        }
+        self.depth += 1;
+
+        for (i, type_) in self.module.types.iter().enumerate() {
+            self.line(&format!(
+                "(type {} (func{}))",
+                self.types.definition(i as u32),
+                signature(type_)
+            ));
+        }
+
+        let mut funcs = 0;
+        let mut mems = 0;
+        for import in &self.module.imports {
+            let desc = match &import.desc {
+                ImportDesc::Func(type_idx) => {
+                    funcs += 1;
+                    format!(
+                        "(func {} {})",
+                        self.funcs.definition(funcs - 1),
+                        self.type_use(*type_idx)
+                    )
+                }
+                ImportDesc::Mem(mem_type) => {
+                    mems += 1;
+                    format!(
+                        "(memory {} {})",
+                        self.mems.definition(mems - 1),
+                        limits(&mem_type.0)
+                    )
+                }
+            };
+            self.line(&format!(
+                "(import {} {} {})",
+                string(import.module.as_bytes()),
+                string(import.name.as_bytes()),
+                desc
+            ));
+        }
+
+        for (i, func) in self.module.funcs.iter().enumerate() {
+            let idx = funcs + i as u32;
+            let empty = NameMap::new();
+            self.locals = Ids::new(self.module.names.locals.get(&idx).unwrap_or(&empty));
+
+            let parameters = match self.module.types.get(func.type_ as usize) {
+                Some(type_) => &type_.parameters[..],
+                None => &[],
+            };
+            if self.locals.0.is_empty() {
+                self.line(&format!(
+                    "(func {} {}",
+                    self.funcs.definition(idx),
+                    self.type_use(func.type_)
+                ));
+                self.depth += 1;
+                if !func.locals.is_empty() {
+                    self.line(&format!("(local{})", val_types(&func.locals)));
+                }
+            } else {
+                // Named parameters and locals are declared one by one.
+                let mut text = format!(
+                    "(func {} (type {})",
+                    self.funcs.definition(idx),
+                    self.types.reference(func.type_)
+                );
+                for (i, type_) in parameters.iter().enumerate() {
+                    write!(text, " {}", self.local("param", i as u32, type_)).unwrap();
+                }
+                if let Some(type_) = self.module.types.get(func.type_ as usize) {
+                    if !type_.results.is_empty() {
+                        write!(text, " (result{})", val_types(&type_.results)).unwrap();
+                    }
+                }
+                self.line(&text);
+                self.depth += 1;
+                for (i, type_) in func.locals.iter().enumerate() {
+                    let local = self.local("local", (parameters.len() + i) as u32, type_);
+                    self.line(&local);
+                }
+            }
+            self.instrs(&func.body.0);
+            self.depth -= 1;
+            self.line(")");
+        }
+        self.locals = Ids::default();
+
+        for (i, table) in self.module.tables.iter().enumerate() {
+            self.line(&format!(
+                "(table {} {} {})",
+                self.tables.definition(i as u32),
+                limits(&table.type_.0),
+                ValType::RefType(table.type_.1.clone())
+            ));
+        }
+
+        for (i, mem) in self.module.mems.iter().enumerate() {
+            self.line(&format!(
+                "(memory {} {})",
+                self.mems.definition(mems + i as u32),
+                limits(&mem.type_.0)
+            ));
+        }
+
+        for export in &self.module.exports {
+            let desc = match export.desc {
+                ExportDesc::Func(idx) => format!("(func {})", self.funcs.reference(idx)),
+                ExportDesc::Mem(idx) => format!("(memory {})", self.mems.reference(idx)),
+            };
+            self.line(&format!(
+                "(export {} {})",
+                string(export.name.as_bytes()),
+                desc
+            ));
+        }
+
+        if let Some(start) = self.module.start {
+            self.line(&format!("(start {})", self.funcs.reference(start)));
+        }
+
+        for (i, data) in self.module.datas.iter().enumerate() {
+            let mut text = format!("(data {}", self.datas.definition(i as u32));
+            if let DataMode::Active { memory, offset } = &data.mode {
+                if *memory != 0 {
+                    write!(text, " (memory {})", self.mems.reference(*memory)).unwrap();
+                }
+                write!(text, " ({})", const_expr(offset)).unwrap();
+            }
+            write!(text, " {})", string(&data.init)).unwrap();
+            self.line(&text);
+        }
+
+        self.depth -= 1;
+        self.line(")");
+    }
+
+    fn local(&self, keyword: &str, idx: u32, type_: &ValType) -> String {
+        match self.locals.get(idx) {
+            Some(id) => format!("({} {} {})", keyword, id, type_),
+            None => format!("({} {})", keyword, type_),
+        }
+    }
+
+    fn type_use(&self, type_idx: u32) -> String {
+        let reference = self.types.reference(type_idx);
+        match self.module.types.get(type_idx as usize) {
+            Some(type_) => format!("(type {}){}", reference, signature(type_)),
+            None => format!("(type {})", reference),
+        }
+    }
+
+    fn instrs(&mut self, instrs: &[Instr]) {
+        for instr in instrs {
+            match instr {
+                Instr::If(block_type, then, else_) => {
+                    match block_type {
+                        BlockType::Empty => self.line("if"),
+                        BlockType::ValType(type_) => self.line(&format!("if (result {})", type_)),
+                    }
+                    self.depth += 1;
+                    self.instrs(then);
+                    self.depth -= 1;
+                    if !else_.is_empty() {
+                        self.line("else");
+                        self.depth += 1;
+                        self.instrs(else_);
+                        self.depth -= 1;
+                    }
+                    self.line("end");
+                }
+                Instr::Call(idx) => {
+                    let text = format!("call {}", self.funcs.reference(*idx));
+                    self.line(&text);
+                }
+                Instr::LocalGet(idx) => {
+                    let text = format!("local.get {}", self.locals.reference(*idx));
+                    self.line(&text);
+                }
+                Instr::TableSize(idx) => {
+                    let text = format!("table.size {}", self.tables.reference(*idx));
+                    self.line(&text);
+                }
+                Instr::TableGrow(idx) => {
+                    let text = format!("table.grow {}", self.tables.reference(*idx));
+                    self.line(&text);
+                }
+                _ => self.line(&plain_instr(instr)),
+            }
+        }
+    }
+}
+
+fn plain_instr(instr: &Instr) -> String {
+    match instr {
+        Instr::Call(idx) => format!("call {}", idx),
+        Instr::LocalGet(idx) => format!("local.get {}", idx),
+        Instr::RefNull(RefType::FuncRef) => "ref.null func".to_string(),
+        Instr::TableSize(idx) => format!("table.size {}", idx),
+        Instr::TableGrow(idx) => format!("table.grow {}", idx),
+        Instr::I32Const(x) => format!("i32.const {}", x),
+        Instr::I64Const(x) => format!("i64.const {}", x),
+        Instr::F32Const(x) => format!("f32.const {}", f32_text(*x)),
+        Instr::F64Const(x) => format!("f64.const {}", f64_text(*x)),
+        Instr::If(..) | Instr::Else | Instr::End => unreachable!("block instructions are nested"),
+        _ => {
+            if let Some((name, _)) = PLAIN_INSTRS.iter().find(|(_, plain)| plain == instr) {
+                return name.to_string();
+            }
+            let memarg = memarg(instr);
+            let (name, _, natural_alignment) = MEMORY_INSTRS
+                .iter()
+                .find(|(_, make, _)| make(memarg.clone()) == *instr)
+                .unwrap();
+            let mut text = name.to_string();
+            if memarg.offset != 0 {
+                write!(text, " offset={}", memarg.offset).unwrap();
+            }
+            if 1 << memarg.align != *natural_alignment {
+                write!(text, " align={}", 1u64 << memarg.align).unwrap();
+            }
+            text
+        }
+    }
+}
+
+fn memarg(instr: &Instr) -> MemArg {
+    match instr {
+        Instr::I32Load(memarg)
+        | Instr::I64Load(memarg)
+        | Instr::F32Load(memarg)
+        | Instr::F64Load(memarg)
+        | Instr::I32Load8S(memarg)
+        | Instr::I32Load8U(memarg)
+        | Instr::I32Load16S(memarg)
+        | Instr::I32Load16U(memarg)
+        | Instr::I64Load8S(memarg)
+        | Instr::I64Load8U(memarg)
+        | Instr::I64Load16S(memarg)
+        | Instr::I64Load16U(memarg)
+        | Instr::I64Load32S(memarg)
+        | Instr::I64Load32U(memarg)
+        | Instr::I32Store(memarg)
+        | Instr::I64Store(memarg)
+        | Instr::F32Store(memarg)
+        | Instr::F64Store(memarg)
+        | Instr::I32Store8(memarg)
+        | Instr::I32Store16(memarg)
+        | Instr::I64Store8(memarg)
+        | Instr::I64Store16(memarg)
+        | Instr::I64Store32(memarg) => memarg.clone(),
+        _ => unreachable!("{:?} has no memory argument", instr),
+    }
+}
+
+fn const_expr(expr: &Expr) -> String {
+    expr.0.iter().map(plain_instr).collect::<Vec<_>>().join(" ")
+}
+
+fn signature(type_: &FuncType) -> String {
+    let mut text = String::new();
+    if !type_.parameters.is_empty() {
+        write!(text, " (param{})", val_types(&type_.parameters)).unwrap();
+    }
+    if !type_.results.is_empty() {
+        write!(text, " (result{})", val_types(&type_.results)).unwrap();
+    }
+    text
+}
+
+fn val_types(types: &[ValType]) -> String {
+    types.iter().map(|type_| format!(" {}", type_)).collect()
+}
+
+fn limits(limits: &Limits) -> String {
+    match limits.max {
+        Some(max) => format!("{} {}", limits.min, max),
+        None => limits.min.to_string(),
+    }
+}
+
+fn string(bytes: &[u8]) -> String {
+    let mut text = String::from("\"");
+    for &byte in bytes {
+        match byte {
+            b'"' => text.push_str("\\\""),
+            b'\\' => text.push_str("\\\\"),
+            0x20..=0x7e => text.push(byte as char),
+            _ => write!(text, "\\{:02x}", byte).unwrap(),
+        }
+    }
+    text.push('"');
+    text
+}
+
+/// Formats a float so that parsing it back yields the same bits, including NaN payloads.
+fn f32_text(x: f32) -> String {
+    float_text(x.to_bits() as u64, 23, 8, || format!("{:?}", x.abs()))
+}
+
+fn f64_text(x: f64) -> String {
+    float_text(x.to_bits(), 52, 11, || format!("{:?}", x.abs()))
+}
+
+fn float_text(
+    bits: u64,
+    mantissa_bits: u32,
+    exponent_bits: u32,
+    decimal: impl Fn() -> String,
+) -> String {
+    let sign = if bits >> (mantissa_bits + exponent_bits) & 1 == 1 {
+        "-"
+    } else {
+        ""
+    };
+    let exponent = bits >> mantissa_bits & ((1 << exponent_bits) - 1);
+    let mantissa = bits & ((1 << mantissa_bits) - 1);
+
+    let magnitude = if exponent != (1 << exponent_bits) - 1 {
+        decimal()
+    } else if mantissa == 0 {
+        "inf".to_string()
+    } else if mantissa == 1 << (mantissa_bits - 1) {
+        "nan".to_string()
+    } else {
+        format!("nan:{:#x}", mantissa)
+    };
+    format!("{}{}", sign, magnitude)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::text::parse;
+
+    #[test]
+    fn test_print() {
+        let source = r#"(module
+            (import "env" "log" (func $log (param i32)))
+            (memory (export "memory") 1 2)
+            (func $abs (export "abs") (param $x i64) (result i64) (local i32 f64)
+                (if (result i64) (i64.lt_s (local.get $x) (i64.const 0))
+                    (then (i64.sub (i64.const 0) (local.get $x)))
+                    (else (local.get $x))))
+            (func (i32.store16 offset=2 align=1 (i32.const 0) (i32.const 7)))
+            (data (i32.const 8) "a\"b\\c\n"))"#;
+        let expected = r#"(module
+  (type (;0;) (func (param i32)))
+  (type (;1;) (func (param i64) (result i64)))
+  (type (;2;) (func))
+  (import "env" "log" (func $log (;0;) (type 0) (param i32)))
+  (func $abs (;1;) (type 1) (param $x i64) (result i64)
+    (local i32)
+    (local f64)
+    local.get $x
+    i64.const 0
+    i64.lt_s
+    if (result i64)
+      i64.const 0
+      local.get $x
+      i64.sub
+    else
+      local.get $x
+    end
+  )
+  (func (;2;) (type 2)
+    i32.const 0
+    i32.const 7
+    i32.store16 offset=2 align=1
+  )
+  (memory (;0;) 1 2)
+  (export "memory" (memory 0))
+  (export "abs" (func $abs))
+  (data (;0;) (i32.const 8) "a\"b\\c\0a")
+)
+"#;
+        let module = parse(source).unwrap();
+        assert_eq!(print(&module), expected);
+        assert_eq!(parse(&print(&module)).unwrap(), module);
+    }
+
+    #[test]
+    fn test_inputs_round_trip() {
+        for entry in std::fs::read_dir("./tests/inputs").unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_none_or(|extension| extension != "wasm") {
+                continue;
+            }
+            let binary = std::fs::read(&path).unwrap();
+            let mut module = Decoder::new(&binary).decode().unwrap();
+            for func in &mut module.funcs {
+                func.offsets.clear();
+            }
+            assert_eq!(
+                parse(&print(&module)).unwrap(),
+                module,
+                "{}",
+                path.display()
+            );
+        }
+    }
+
+    #[test]
+    fn test_float_constants_round_trip() {
+        let values = [
+            0.0,
+            -0.0,
+            0.1,
+            1e-45,
+            f32::MAX,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            f32::NAN,
+            -f32::NAN,
+            f32::from_bits(0x7fa0_0001),
+        ];
+        for value in values {
+            let text = f32_text(value);
+            let parsed = crate::execution::value::parse_f32(&text).unwrap();
+            assert_eq!(parsed.to_bits(), value.to_bits(), "{}", text);
+        }
+        assert_eq!(f32_text(f32::from_bits(0xffa0_0001)), "-nan:0x200001");
+
+        let values = [
+            0.1,
+            -2.5e-308,
+            f64::MAX,
+            f64::from_bits(0x7ff0_0000_0000_0001),
+        ];
+        for value in values {
+            let text = f64_text(value);
+            let parsed = crate::execution::value::parse_f64(&text).unwrap();
+            assert_eq!(parsed.to_bits(), value.to_bits(), "{}", text);
+        }
+    }
+}
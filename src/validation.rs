@@ -0,0 +1,736 @@
+use crate::execution::engine::Config;
+use crate::structure::instructions::expression::{BlockType, Expr, Instr, MemArg};
+use crate::structure::modules::data::DataMode;
+use crate::structure::modules::export::ExportDesc;
+use crate::structure::modules::function::Func;
+use crate::structure::modules::import::ImportDesc;
+use crate::structure::modules::module::Module;
+use crate::structure::types::function::FuncType;
+use crate::structure::types::memory::Limits;
+use crate::structure::types::value::{NumType, RefType, ValType};
+use std::collections::HashSet;
+use std::fmt;
+
+const MAX_PAGES: u32 = 65536;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError(pub String);
+
+impl ValidationError {
+    fn new(message: impl Into<String>) -> Self {
+        Self(message.into())
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Checks that a module is valid as defined by the specification. Function bodies left
+/// undecoded by a lazy decoder are skipped, so they are only checked by `validate_all` or
+/// when they are first called.
+pub fn validate(module: &Module) -> Result<(), ValidationError> {
+    validate_module(module, Bodies::Decoded, &Config::default())
+}
+
+/// Checks that a module is valid like `validate`, including the bodies a lazy decoder left
+/// undecoded.
+pub fn validate_all(module: &Module) -> Result<(), ValidationError> {
+    validate_module(module, Bodies::All, &Config::default())
+}
+
+/// The function bodies `validate_module` checks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Bodies {
+    None,
+    Decoded,
+    All,
+}
+
+/// Validates a module, with the function bodies validated as `config` says.
+pub(crate) fn validate_module(
+    module: &Module,
+    bodies: Bodies,
+    config: &Config,
+) -> Result<(), ValidationError> {
+    let context = Context::new(module)?;
+
+    for (i, table) in module.tables.iter().enumerate() {
+        validate_limits(&table.type_.0).map_err(|e| at(e, "table", i))?;
+    }
+    for (i, mem) in module.mems.iter().enumerate() {
+        validate_mem_limits(&mem.type_.0).map_err(|e| at(e, "memory", i))?;
+    }
+    if context.mems > 1 {
+        return Err(ValidationError::new("multiple memories"));
+    }
+
+    let imported_funcs = context.funcs.len() - module.funcs.len();
+    let results = config.map_funcs(&module.funcs, |i, func| {
+        let func_idx = imported_funcs + i;
+        match (bodies, module.lazy_bodies.get(i)) {
+            (Bodies::None, _) | (Bodies::Decoded, Some(_)) => Ok(()),
+            (Bodies::All, Some(body)) => {
+                let func = body
+                    .decode(func.type_)
+                    .map_err(|error| at(ValidationError(error.to_string()), "func", func_idx))?;
+                validate_func(&context, &func).map_err(|e| at(e, "func", func_idx))
+            }
+            (_, None) => validate_func(&context, func).map_err(|e| at(e, "func", func_idx)),
+        }
+    });
+    // The first invalid function is reported, whichever order the bodies were validated in.
+    results.into_iter().collect::<Result<(), _>>()?;
+
+    let mut names = HashSet::new();
+    for export in &module.exports {
+        if !names.insert(&export.name) {
+            return Err(ValidationError::new(format!(
+                "duplicate export name \"{}\"",
+                export.name
+            )));
+        }
+        match export.desc {
+            ExportDesc::Func(idx) => {
+                context.func_type(idx)?;
+            }
+            ExportDesc::Mem(idx) => context.check_mem(idx)?,
+        }
+    }
+
+    for (i, data) in module.datas.iter().enumerate() {
+        if let DataMode::Active { memory, offset } = &data.mode {
+            context.check_mem(*memory).map_err(|e| at(e, "data", i))?;
+            validate_const_expr(offset, &ValType::NumType(NumType::I32))
+                .map_err(|e| at(e, "data", i))?;
+        }
+    }
+
+    if let Some(start) = module.start {
+        let type_ = context.func_type(start)?;
+        if !type_.parameters.is_empty() || !type_.results.is_empty() {
+            return Err(ValidationError::new(
+                "start function must have type [] -> []",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn at(error: ValidationError, kind: &str, idx: usize) -> ValidationError {
+    ValidationError(format!("{} {}: {}", kind, idx, error.0))
+}
+
+fn validate_mem_limits(limits: &Limits) -> Result<(), ValidationError> {
+    if limits.min > MAX_PAGES || limits.max.is_some_and(|max| max > MAX_PAGES) {
+        return Err(ValidationError::new(
+            "memory size must be at most 65536 pages (4GiB)",
+        ));
+    }
+    validate_limits(limits)
+}
+
+fn validate_limits(limits: &Limits) -> Result<(), ValidationError> {
+    if limits.max.is_some_and(|max| max < limits.min) {
+        return Err(ValidationError::new(
+            "size minimum must not be greater than maximum",
+        ));
+    }
+    Ok(())
+}
+
+fn validate_const_expr(expr: &Expr, expected: &ValType) -> Result<(), ValidationError> {
+    let type_ = match expr.0.as_slice() {
+        [Instr::I32Const(_)] => ValType::NumType(NumType::I32),
+        [Instr::I64Const(_)] => ValType::NumType(NumType::I64),
+        [Instr::F32Const(_)] => ValType::NumType(NumType::F32),
+        [Instr::F64Const(_)] => ValType::NumType(NumType::F64),
+        _ => return Err(ValidationError::new("constant expression required")),
+    };
+    if &type_ != expected {
+        return Err(ValidationError::new("type mismatch in constant expression"));
+    }
+    Ok(())
+}
+
+struct Context<'a> {
+    types: &'a [FuncType],
+    funcs: Vec<&'a FuncType>,
+    /// The element types of the tables.
+    tables: Vec<RefType>,
+    mems: usize,
+}
+
+impl<'a> Context<'a> {
+    fn new(module: &'a Module) -> Result<Self, ValidationError> {
+        let type_at = |idx: u32| {
+            module
+                .types
+                .get(idx as usize)
+                .ok_or_else(|| ValidationError::new(format!("unknown type {}", idx)))
+        };
+
+        let mut funcs = Vec::new();
+        let mut mems = 0;
+        for import in &module.imports {
+            match &import.desc {
+                ImportDesc::Func(idx) => funcs.push(type_at(*idx)?),
+                ImportDesc::Mem(mem_type) => {
+                    validate_mem_limits(&mem_type.0)?;
+                    mems += 1;
+                }
+            }
+        }
+        for func in &module.funcs {
+            funcs.push(type_at(func.type_)?);
+        }
+        mems += module.mems.len();
+
+        Ok(Self {
+            types: &module.types,
+            funcs,
+            tables: module
+                .tables
+                .iter()
+                .map(|table| table.type_.1.clone())
+                .collect(),
+            mems,
+        })
+    }
+
+    fn func_type(&self, idx: u32) -> Result<&'a FuncType, ValidationError> {
+        self.funcs
+            .get(idx as usize)
+            .copied()
+            .ok_or_else(|| ValidationError::new(format!("unknown function {}", idx)))
+    }
+
+    fn table_type(&self, idx: u32) -> Result<&RefType, ValidationError> {
+        self.tables
+            .get(idx as usize)
+            .ok_or_else(|| ValidationError::new(format!("unknown table {}", idx)))
+    }
+
+    fn check_mem(&self, idx: u32) -> Result<(), ValidationError> {
+        if idx as usize >= self.mems {
+            return Err(ValidationError::new(format!("unknown memory {}", idx)));
+        }
+        Ok(())
+    }
+}
+
+/// Checks a function body on its own, given the types of the function index space, the
+/// element types of the tables and the number of memories of its module.
+pub(crate) fn validate_body(
+    types: &[FuncType],
+    funcs: Vec<&FuncType>,
+    tables: Vec<RefType>,
+    mems: usize,
+    func: &Func,
+) -> Result<(), ValidationError> {
+    let context = Context {
+        types,
+        funcs,
+        tables,
+        mems,
+    };
+    validate_func(&context, func)
+}
+
+fn validate_func(context: &Context, func: &Func) -> Result<(), ValidationError> {
+    let type_ = context
+        .types
+        .get(func.type_ as usize)
+        .ok_or_else(|| ValidationError::new(format!("unknown type {}", func.type_)))?;
+
+    let mut locals = type_.parameters.clone();
+    locals.extend(func.locals.iter().cloned());
+
+    let mut validator = FuncValidator {
+        context,
+        locals,
+        stack: Vec::new(),
+    };
+    validator.validate_block(&func.body.0, &type_.results)
+}
+
+struct FuncValidator<'a> {
+    context: &'a Context<'a>,
+    locals: Vec<ValType>,
+    stack: Vec<ValType>,
+}
+
+impl FuncValidator<'_> {
+    /// Validates a block that starts with an empty operand stack and must leave `results`.
+    fn validate_block(
+        &mut self,
+        instrs: &[Instr],
+        results: &[ValType],
+    ) -> Result<(), ValidationError> {
+        let outer = std::mem::take(&mut self.stack);
+        for instr in instrs {
+            self.validate_instr(instr)?;
+        }
+        self.pop_values(results)?;
+        if !self.stack.is_empty() {
+            return Err(ValidationError::new(
+                "type mismatch: values remaining on the stack at end of block",
+            ));
+        }
+        self.stack = outer;
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Result<ValType, ValidationError> {
+        self.stack
+            .pop()
+            .ok_or_else(|| ValidationError::new("type mismatch: operand stack underflow"))
+    }
+
+    fn pop_expect(&mut self, expected: &ValType) -> Result<(), ValidationError> {
+        let actual = self.pop()?;
+        if &actual != expected {
+            return Err(ValidationError::new(format!(
+                "type mismatch: expected {}, found {}",
+                expected, actual
+            )));
+        }
+        Ok(())
+    }
+
+    fn pop_values(&mut self, types: &[ValType]) -> Result<(), ValidationError> {
+        for type_ in types.iter().rev() {
+            self.pop_expect(type_)?;
+        }
+        Ok(())
+    }
+
+    fn check_memarg(&self, memarg: &MemArg, natural_alignment: u32) -> Result<(), ValidationError> {
+        self.context.check_mem(0)?;
+        if 1u64 << memarg.align > natural_alignment as u64 {
+            return Err(ValidationError::new(
+                "alignment must not be larger than natural",
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_instr(&mut self, instr: &Instr) -> Result<(), ValidationError> {
+        match instr {
+            Instr::If(block_type, instructions1, instructions2) => {
+                self.pop_expect(&I32)?;
+                let results = match block_type {
+                    BlockType::Empty => Vec::new(),
+                    BlockType::ValType(val_type) => vec![val_type.clone()],
+                };
+                self.validate_block(instructions1, &results)?;
+                self.validate_block(instructions2, &results)?;
+                self.stack.extend(results);
+            }
+            Instr::Else | Instr::End => {
+                return Err(ValidationError::new("unexpected block delimiter"));
+            }
+            Instr::Call(idx) => {
+                let type_ = self.context.func_type(*idx)?;
+                self.pop_values(&type_.parameters)?;
+                self.stack.extend(type_.results.iter().cloned());
+            }
+            Instr::Drop => {
+                self.pop()?;
+            }
+            Instr::LocalGet(idx) => {
+                let type_ = self
+                    .locals
+                    .get(*idx as usize)
+                    .cloned()
+                    .ok_or_else(|| ValidationError::new(format!("unknown local {}", idx)))?;
+                self.stack.push(type_);
+            }
+            Instr::RefNull(type_) => self.stack.push(ValType::RefType(type_.clone())),
+            Instr::TableSize(idx) => {
+                self.context.table_type(*idx)?;
+                self.stack.push(I32);
+            }
+            Instr::TableGrow(idx) => {
+                let type_ = ValType::RefType(self.context.table_type(*idx)?.clone());
+                self.pop_expect(&I32)?;
+                self.pop_expect(&type_)?;
+                self.stack.push(I32);
+            }
+            Instr::MemorySize => {
+                self.context.check_mem(0)?;
+                self.stack.push(I32);
+            }
+            Instr::MemoryGrow => {
+                self.context.check_mem(0)?;
+                self.pop_expect(&I32)?;
+                self.stack.push(I32);
+            }
+            Instr::I32Const(_) => self.stack.push(I32),
+            Instr::I64Const(_) => self.stack.push(I64),
+            Instr::F32Const(_) => self.stack.push(F32),
+            Instr::F64Const(_) => self.stack.push(F64),
+            _ => {
+                if let Some((memarg, natural_alignment, value)) = load_signature(instr) {
+                    self.check_memarg(memarg, natural_alignment)?;
+                    self.pop_expect(&I32)?;
+                    self.stack.push(value);
+                } else if let Some((memarg, natural_alignment, value)) = store_signature(instr) {
+                    self.check_memarg(memarg, natural_alignment)?;
+                    self.pop_expect(&value)?;
+                    self.pop_expect(&I32)?;
+                } else {
+                    let (parameters, result) = numeric_signature(instr);
+                    self.pop_values(parameters)?;
+                    self.stack.push(result);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+const I32: ValType = ValType::NumType(NumType::I32);
+const I64: ValType = ValType::NumType(NumType::I64);
+const F32: ValType = ValType::NumType(NumType::F32);
+const F64: ValType = ValType::NumType(NumType::F64);
+
+/// Returns the memory argument, natural alignment in bytes and loaded type.
+pub(crate) fn load_signature(instr: &Instr) -> Option<(&MemArg, u32, ValType)> {
+    let signature = match instr {
+        Instr::I32Load(memarg) => (memarg, 4, I32),
+        Instr::I64Load(memarg) => (memarg, 8, I64),
+        Instr::F32Load(memarg) => (memarg, 4, F32),
+        Instr::F64Load(memarg) => (memarg, 8, F64),
+        Instr::I32Load8S(memarg) | Instr::I32Load8U(memarg) => (memarg, 1, I32),
+        Instr::I32Load16S(memarg) | Instr::I32Load16U(memarg) => (memarg, 2, I32),
+        Instr::I64Load8S(memarg) | Instr::I64Load8U(memarg) => (memarg, 1, I64),
+        Instr::I64Load16S(memarg) | Instr::I64Load16U(memarg) => (memarg, 2, I64),
+        Instr::I64Load32S(memarg) | Instr::I64Load32U(memarg) => (memarg, 4, I64),
+        _ => return None,
+    };
+    Some(signature)
+}
+
+/// Returns the memory argument, natural alignment in bytes and stored type.
+pub(crate) fn store_signature(instr: &Instr) -> Option<(&MemArg, u32, ValType)> {
+    let signature = match instr {
+        Instr::I32Store(memarg) => (memarg, 4, I32),
+        Instr::I64Store(memarg) => (memarg, 8, I64),
+        Instr::F32Store(memarg) => (memarg, 4, F32),
+        Instr::F64Store(memarg) => (memarg, 8, F64),
+        Instr::I32Store8(memarg) => (memarg, 1, I32),
+        Instr::I32Store16(memarg) => (memarg, 2, I32),
+        Instr::I64Store8(memarg) => (memarg, 1, I64),
+        Instr::I64Store16(memarg) => (memarg, 2, I64),
+        Instr::I64Store32(memarg) => (memarg, 4, I64),
+        _ => return None,
+    };
+    Some(signature)
+}
+
+pub(crate) fn numeric_signature(instr: &Instr) -> (&'static [ValType], ValType) {
+    match instr {
+        Instr::I32Eqz => (&[I32], I32),
+        Instr::I32Eq
+        | Instr::I32Ne
+        | Instr::I32LtS
+        | Instr::I32LtU
+        | Instr::I32GtS
+        | Instr::I32GtU
+        | Instr::I32LeS
+        | Instr::I32LeU
+        | Instr::I32GeS
+        | Instr::I32GeU => (&[I32, I32], I32),
+        Instr::I64Eqz => (&[I64], I32),
+        Instr::I64Eq
+        | Instr::I64Ne
+        | Instr::I64LtS
+        | Instr::I64LtU
+        | Instr::I64GtS
+        | Instr::I64GtU
+        | Instr::I64LeS
+        | Instr::I64LeU
+        | Instr::I64GeS
+        | Instr::I64GeU => (&[I64, I64], I32),
+        Instr::F32Eq | Instr::F32Ne | Instr::F32Lt | Instr::F32Gt | Instr::F32Le | Instr::F32Ge => {
+            (&[F32, F32], I32)
+        }
+        Instr::F64Eq | Instr::F64Ne | Instr::F64Lt | Instr::F64Gt | Instr::F64Le | Instr::F64Ge => {
+            (&[F64, F64], I32)
+        }
+        Instr::I32Clz
+        | Instr::I32Ctz
+        | Instr::I32Popcnt
+        | Instr::I32Extend8S
+        | Instr::I32Extend16S => (&[I32], I32),
+        Instr::I32Add
+        | Instr::I32Sub
+        | Instr::I32Mul
+        | Instr::I32DivS
+        | Instr::I32DivU
+        | Instr::I32RemS
+        | Instr::I32RemU
+        | Instr::I32And
+        | Instr::I32Or
+        | Instr::I32Xor
+        | Instr::I32Shl
+        | Instr::I32ShrS
+        | Instr::I32ShrU
+        | Instr::I32Rotl
+        | Instr::I32Rotr => (&[I32, I32], I32),
+        Instr::I64Clz | Instr::I64Ctz | Instr::I64Popcnt => (&[I64], I64),
+        Instr::I64Add
+        | Instr::I64Sub
+        | Instr::I64Mul
+        | Instr::I64DivS
+        | Instr::I64DivU
+        | Instr::I64RemS
+        | Instr::I64RemU
+        | Instr::I64And
+        | Instr::I64Or
+        | Instr::I64Xor
+        | Instr::I64Shl
+        | Instr::I64ShrS
+        | Instr::I64ShrU
+        | Instr::I64Rotl
+        | Instr::I64Rotr => (&[I64, I64], I64),
+        Instr::F32Abs
+        | Instr::F32Neg
+        | Instr::F32Ceil
+        | Instr::F32Floor
+        | Instr::F32Trunc
+        | Instr::F32Nearest
+        | Instr::F32Sqrt => (&[F32], F32),
+        Instr::F32Add
+        | Instr::F32Sub
+        | Instr::F32Mul
+        | Instr::F32Div
+        | Instr::F32Min
+        | Instr::F32Max
+        | Instr::F32Copysign => (&[F32, F32], F32),
+        Instr::F64Abs
+        | Instr::F64Neg
+        | Instr::F64Ceil
+        | Instr::F64Floor
+        | Instr::F64Trunc
+        | Instr::F64Nearest
+        | Instr::F64Sqrt => (&[F64], F64),
+        Instr::F64Add
+        | Instr::F64Sub
+        | Instr::F64Mul
+        | Instr::F64Div
+        | Instr::F64Min
+        | Instr::F64Max
+        | Instr::F64Copysign => (&[F64, F64], F64),
+        _ => unreachable!("{:?} is not a numeric instruction", instr),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structure::modules::export::Export;
+    use crate::structure::modules::memory::Mem;
+    use crate::structure::types::memory::MemType;
+    use crate::text;
+    use std::fs;
+
+    fn module_with_body(
+        parameters: Vec<ValType>,
+        results: Vec<ValType>,
+        body: Vec<Instr>,
+    ) -> Module {
+        Module {
+            types: vec![FuncType {
+                parameters,
+                results,
+            }],
+            funcs: vec![Func {
+                type_: 0,
+                locals: Vec::new(),
+                body: Expr(body),
+                offsets: Vec::new(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_inputs_are_valid() {
+        for name in [
+            "factorial",
+            "i32.add",
+            "i32.div_s",
+            "i32.extend8_s",
+            "local_get.1",
+        ] {
+            let source = fs::read_to_string(format!("./tests/inputs/{}.wat", name)).unwrap();
+            let module = text::parse(&source).unwrap();
+            assert_eq!(validate(&module), Ok(()), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_type_mismatch() {
+        let module = module_with_body(
+            vec![I64],
+            vec![I32],
+            vec![Instr::LocalGet(0), Instr::I32Const(1), Instr::I32Add],
+        );
+        assert_eq!(
+            validate(&module),
+            Err(ValidationError::new(
+                "func 0: type mismatch: expected i32, found i64"
+            ))
+        );
+
+        let module = module_with_body(vec![], vec![I32], vec![]);
+        assert!(validate(&module).is_err());
+
+        let module = module_with_body(vec![], vec![], vec![Instr::I32Const(1)]);
+        assert!(validate(&module).is_err());
+    }
+
+    #[test]
+    fn test_if_without_else_must_not_produce_values() {
+        let module = module_with_body(
+            vec![],
+            vec![I32],
+            vec![
+                Instr::I32Const(1),
+                Instr::If(BlockType::ValType(I32), vec![Instr::I32Const(2)], vec![]),
+            ],
+        );
+        assert!(validate(&module).is_err());
+    }
+
+    #[test]
+    fn test_unknown_indices() {
+        let module = module_with_body(vec![], vec![], vec![Instr::Call(1)]);
+        assert_eq!(
+            validate(&module),
+            Err(ValidationError::new("func 0: unknown function 1"))
+        );
+
+        let module = module_with_body(vec![], vec![], vec![Instr::LocalGet(0), Instr::Drop]);
+        assert_eq!(
+            validate(&module),
+            Err(ValidationError::new("func 0: unknown local 0"))
+        );
+
+        let mut module = module_with_body(vec![], vec![], vec![]);
+        module.exports.push(Export {
+            name: "memory".to_string(),
+            desc: ExportDesc::Mem(0),
+        });
+        assert_eq!(
+            validate(&module),
+            Err(ValidationError::new("unknown memory 0"))
+        );
+    }
+
+    #[test]
+    fn test_memory() {
+        let load = vec![
+            Instr::I32Const(0),
+            Instr::I64Load(MemArg {
+                align: 3,
+                offset: 0,
+            }),
+            Instr::Drop,
+        ];
+        let module = module_with_body(vec![], vec![], load.clone());
+        assert_eq!(
+            validate(&module),
+            Err(ValidationError::new("func 0: unknown memory 0"))
+        );
+
+        let mut module = module_with_body(vec![], vec![], load);
+        module.mems.push(Mem {
+            type_: MemType(Limits { min: 1, max: None }),
+        });
+        assert_eq!(validate(&module), Ok(()));
+
+        let mut module = module_with_body(
+            vec![],
+            vec![],
+            vec![
+                Instr::I32Const(0),
+                Instr::I32Load(MemArg {
+                    align: 3,
+                    offset: 0,
+                }),
+                Instr::Drop,
+            ],
+        );
+        module.mems.push(Mem {
+            type_: MemType(Limits { min: 1, max: None }),
+        });
+        assert!(validate(&module).is_err());
+
+        let mut module = Module::default();
+        module.mems.push(Mem {
+            type_: MemType(Limits {
+                min: 2,
+                max: Some(1),
+            }),
+        });
+        assert!(validate(&module).is_err());
+    }
+
+    #[test]
+    fn test_start_function_type() {
+        let mut module = module_with_body(vec![], vec![I32], vec![Instr::I32Const(1)]);
+        module.start = Some(0);
+        assert_eq!(
+            validate(&module),
+            Err(ValidationError::new(
+                "start function must have type [] -> []"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_duplicate_export() {
+        let mut module = module_with_body(vec![], vec![], vec![]);
+        for _ in 0..2 {
+            module.exports.push(Export {
+                name: "f".to_string(),
+                desc: ExportDesc::Func(0),
+            });
+        }
+        assert!(validate(&module).is_err());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel() {
+        let mut module = module_with_body(vec![], vec![I32], vec![Instr::I32Const(0)]);
+        let valid = module.funcs[0].clone();
+        module.funcs = vec![valid; 1000];
+        for i in [357, 600, 999] {
+            module.funcs[i].body = Expr(vec![Instr::I64Const(0)]);
+        }
+
+        let config = Config {
+            parallel_compilation: true,
+            ..Default::default()
+        };
+        for _ in 0..20 {
+            assert_eq!(
+                validate_module(&module, Bodies::All, &config),
+                Err(ValidationError::new(
+                    "func 357: type mismatch: expected i32, found i64"
+                ))
+            );
+        }
+        assert_eq!(
+            validate_module(&module, Bodies::All, &config),
+            validate(&module)
+        );
+    }
+}
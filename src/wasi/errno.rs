@@ -0,0 +1,46 @@
+use crate::execution::memory::MemoryAccessError;
+use std::io;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Errno(pub u16);
+
+impl Errno {
+    pub const SUCCESS: Errno = Errno(0);
+    pub const ACCES: Errno = Errno(2);
+    pub const BADF: Errno = Errno(8);
+    pub const EXIST: Errno = Errno(20);
+    pub const FAULT: Errno = Errno(21);
+    pub const FBIG: Errno = Errno(22);
+    pub const INVAL: Errno = Errno(28);
+    pub const IO: Errno = Errno(29);
+    pub const ISDIR: Errno = Errno(31);
+    pub const LOOP: Errno = Errno(32);
+    pub const NOENT: Errno = Errno(44);
+    pub const NOSPC: Errno = Errno(51);
+    pub const NOTDIR: Errno = Errno(54);
+    pub const ROFS: Errno = Errno(69);
+    pub const SPIPE: Errno = Errno(70);
+    pub const NOTCAPABLE: Errno = Errno(76);
+}
+
+impl From<MemoryAccessError> for Errno {
+    fn from(_: MemoryAccessError) -> Self {
+        Errno::FAULT
+    }
+}
+
+impl From<io::Error> for Errno {
+    fn from(error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::NotFound => Errno::NOENT,
+            io::ErrorKind::PermissionDenied => Errno::ACCES,
+            io::ErrorKind::AlreadyExists => Errno::EXIST,
+            io::ErrorKind::InvalidInput => Errno::INVAL,
+            io::ErrorKind::NotADirectory => Errno::NOTDIR,
+            io::ErrorKind::IsADirectory => Errno::ISDIR,
+            io::ErrorKind::FileTooLarge => Errno::FBIG,
+            io::ErrorKind::StorageFull => Errno::NOSPC,
+            _ => Errno::IO,
+        }
+    }
+}
@@ -0,0 +1,559 @@
+use crate::wasi::errno::Errno;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::ffi::CString;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::{AsRawFd, FromRawFd};
+use std::path::{Component, Path, PathBuf};
+use std::rc::Rc;
+
+const MAX_SYMLINK_EXPANSIONS: usize = 40;
+/// The largest an in-memory file may grow, since its contents are held in host memory.
+const MAX_VIRTUAL_FILE_SIZE: usize = 1 << 30;
+
+#[derive(Debug, Clone, Default)]
+pub struct OpenFlags {
+    pub read: bool,
+    pub write: bool,
+    pub create: bool,
+    pub exclusive: bool,
+    pub truncate: bool,
+    pub append: bool,
+    pub directory: bool,
+}
+
+/// A directory capability. Paths opened through it can never resolve outside of it.
+#[derive(Debug, Clone)]
+pub enum Dir {
+    Host(HostDir),
+    Virtual(VirtualDir),
+}
+
+/// A host directory, kept as a path below a preopened root that is opened again from the
+/// root for every lookup, so that replacing a directory on the way with a symlink can't
+/// redirect it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HostDir {
+    root: PathBuf,
+    /// Relative to `root`, without symlinks or `..`.
+    path: PathBuf,
+}
+
+impl HostDir {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            path: PathBuf::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Opened {
+    Dir(Dir),
+    File(OpenFile),
+}
+
+#[derive(Debug)]
+pub enum OpenFile {
+    Host(File),
+    Virtual(VirtualFile),
+}
+
+impl Dir {
+    pub fn open(&self, path: &str, flags: &OpenFlags) -> Result<Opened, Errno> {
+        match self {
+            Dir::Host(dir) => open_host(dir, path, flags),
+            Dir::Virtual(root) => root.open(path, flags),
+        }
+    }
+}
+
+impl Read for OpenFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            OpenFile::Host(file) => file.read(buf),
+            OpenFile::Virtual(file) => file.read(buf),
+        }
+    }
+}
+
+impl Write for OpenFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            OpenFile::Host(file) => file.write(buf),
+            OpenFile::Virtual(file) => file.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            OpenFile::Host(file) => file.flush(),
+            OpenFile::Virtual(file) => file.flush(),
+        }
+    }
+}
+
+impl OpenFile {
+    pub fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match self {
+            OpenFile::Host(file) => file.seek(pos),
+            OpenFile::Virtual(file) => file.seek(pos),
+        }
+    }
+}
+
+/// Splits a guest path into its components. Absolute paths are refused, since every lookup
+/// is relative to a directory capability.
+fn guest_components(path: &str) -> Result<Vec<&str>, Errno> {
+    if path.starts_with('/') {
+        return Err(Errno::NOTCAPABLE);
+    }
+    Ok(path
+        .split('/')
+        .filter(|component| !component.is_empty() && *component != ".")
+        .collect())
+}
+
+/// Resolves `path` below `root`, expanding symlinks one component at a time so that neither
+/// `..` nor a symlink target can leave `root`.
+fn resolve_host_path(root: &Path, path: &str) -> Result<PathBuf, Errno> {
+    let mut resolved: Vec<String> = Vec::new();
+    let mut pending: Vec<String> = guest_components(path)?
+        .into_iter()
+        .rev()
+        .map(String::from)
+        .collect();
+    let mut expansions = 0;
+
+    while let Some(component) = pending.pop() {
+        if component == ".." {
+            if resolved.pop().is_none() {
+                return Err(Errno::NOTCAPABLE);
+            }
+            continue;
+        }
+
+        let candidate = resolved.iter().fold(root.to_path_buf(), |p, c| p.join(c));
+        let candidate = candidate.join(&component);
+        match fs::symlink_metadata(&candidate) {
+            Ok(metadata) if metadata.file_type().is_symlink() => {
+                expansions += 1;
+                if expansions > MAX_SYMLINK_EXPANSIONS {
+                    return Err(Errno::LOOP);
+                }
+                let target = fs::read_link(&candidate)?;
+                let target = target.to_str().ok_or(Errno::NOTCAPABLE)?;
+                pending.extend(
+                    guest_components(target)?
+                        .into_iter()
+                        .rev()
+                        .map(String::from),
+                );
+            }
+            _ => resolved.push(component),
+        }
+    }
+
+    Ok(resolved.iter().fold(root.to_path_buf(), |p, c| p.join(c)))
+}
+
+fn open_host(dir: &HostDir, path: &str, flags: &OpenFlags) -> Result<Opened, Errno> {
+    let path = resolve_host_path(&dir.root.join(&dir.path), path)?;
+    let path = path
+        .strip_prefix(&dir.root)
+        .map_err(|_| Errno::NOTCAPABLE)?
+        .to_path_buf();
+
+    let is_dir = fs::symlink_metadata(dir.root.join(&path)).is_ok_and(|m| m.is_dir());
+    if flags.directory || is_dir {
+        // Opening it checks that it's still a directory below the root.
+        open_beneath(&dir.root, &path, libc::O_RDONLY | libc::O_DIRECTORY)?;
+        let root = dir.root.clone();
+        return Ok(Opened::Dir(Dir::Host(HostDir { root, path })));
+    }
+
+    // The same combinations are refused as by `OpenOptions`, where appending implies writing.
+    let write = flags.write || flags.append;
+    if ((flags.create || flags.truncate) && !write) || (flags.truncate && flags.append) {
+        return Err(Errno::INVAL);
+    }
+    let mut oflags = match (flags.read || !flags.write, write) {
+        (true, true) => libc::O_RDWR,
+        (false, true) => libc::O_WRONLY,
+        _ => libc::O_RDONLY,
+    };
+    for (set, flag) in [
+        (flags.append, libc::O_APPEND),
+        (flags.create, libc::O_CREAT),
+        (flags.create && flags.exclusive, libc::O_EXCL),
+        (flags.truncate, libc::O_TRUNC),
+    ] {
+        if set {
+            oflags |= flag;
+        }
+    }
+    let file = open_beneath(&dir.root, &path, oflags)?;
+    Ok(Opened::File(OpenFile::Host(file)))
+}
+
+/// Opens a path that `resolve_host_path` returned, relative to `root`, one component at a time
+/// from the directory before it and without following symlinks. A symlink that was swapped in
+/// after the path was resolved fails the open instead of leading outside of `root`.
+fn open_beneath(root: &Path, path: &Path, oflags: libc::c_int) -> Result<File, Errno> {
+    let mut dir = OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_DIRECTORY)
+        .open(root)?;
+    let components: Vec<_> = path.components().collect();
+    let Some((last, parents)) = components.split_last() else {
+        return Ok(dir);
+    };
+    for parent in parents {
+        dir = open_at(&dir, parent, libc::O_RDONLY | libc::O_DIRECTORY)?;
+    }
+    open_at(&dir, last, oflags)
+}
+
+fn open_at(dir: &File, component: &Component, oflags: libc::c_int) -> Result<File, Errno> {
+    let Component::Normal(name) = component else {
+        return Err(Errno::NOTCAPABLE);
+    };
+    let name = CString::new(name.as_bytes()).map_err(|_| Errno::INVAL)?;
+    let oflags = oflags | libc::O_NOFOLLOW | libc::O_CLOEXEC;
+    // SAFETY: `name` is a NUL-terminated string and the returned descriptor is owned by the file.
+    let fd = unsafe { libc::openat(dir.as_raw_fd(), name.as_ptr(), oflags, 0o666) };
+    if fd < 0 {
+        let error = io::Error::last_os_error();
+        return Err(match error.raw_os_error() {
+            Some(libc::ELOOP) => Errno::LOOP,
+            _ => error.into(),
+        });
+    }
+    Ok(unsafe { File::from_raw_fd(fd) })
+}
+
+#[derive(Debug, Clone)]
+enum VirtualNode {
+    File(Rc<RefCell<Vec<u8>>>),
+    Dir(VirtualDir),
+}
+
+/// An in-memory directory tree, mainly for tests that should not touch the host filesystem.
+#[derive(Debug, Clone, Default)]
+pub struct VirtualDir(Rc<RefCell<BTreeMap<String, VirtualNode>>>);
+
+impl VirtualDir {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates or replaces a file, creating missing parent directories.
+    pub fn insert_file(&self, path: &str, contents: &[u8]) {
+        let components: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+        let Some((name, parents)) = components.split_last() else {
+            return;
+        };
+
+        let mut dir = self.clone();
+        for parent in parents {
+            let child = match dir
+                .0
+                .borrow_mut()
+                .entry(parent.to_string())
+                .or_insert_with(|| VirtualNode::Dir(VirtualDir::new()))
+            {
+                VirtualNode::Dir(child) => child.clone(),
+                VirtualNode::File(_) => return,
+            };
+            dir = child;
+        }
+        let file = VirtualNode::File(Rc::new(RefCell::new(contents.to_vec())));
+        dir.0.borrow_mut().insert(name.to_string(), file);
+    }
+
+    pub fn read_file(&self, path: &str) -> Option<Vec<u8>> {
+        match self.open(path, &OpenFlags::default()) {
+            Ok(Opened::File(OpenFile::Virtual(file))) => Some(file.data.borrow().clone()),
+            _ => None,
+        }
+    }
+
+    fn open(&self, path: &str, flags: &OpenFlags) -> Result<Opened, Errno> {
+        let components = guest_components(path)?;
+
+        let mut dirs = vec![self.clone()];
+        let mut last = None;
+        for (i, component) in components.iter().enumerate() {
+            if *component == ".." {
+                if dirs.len() == 1 {
+                    return Err(Errno::NOTCAPABLE);
+                }
+                dirs.pop();
+                continue;
+            }
+
+            let node = dirs.last().unwrap().0.borrow().get(*component).cloned();
+            if i == components.len() - 1 {
+                last = Some((*component, node));
+                break;
+            }
+            match node {
+                Some(VirtualNode::Dir(dir)) => dirs.push(dir),
+                Some(VirtualNode::File(_)) => return Err(Errno::NOTDIR),
+                None => return Err(Errno::NOENT),
+            }
+        }
+
+        let parent = dirs.pop().unwrap();
+        let node = match last {
+            None => return Ok(Opened::Dir(Dir::Virtual(parent))),
+            Some((_, Some(_))) if flags.create && flags.exclusive => return Err(Errno::EXIST),
+            Some((_, Some(node))) => node,
+            Some((name, None)) => {
+                if !flags.create || flags.directory {
+                    return Err(Errno::NOENT);
+                }
+                let node = VirtualNode::File(Rc::new(RefCell::new(Vec::new())));
+                parent.0.borrow_mut().insert(name.to_string(), node.clone());
+                node
+            }
+        };
+
+        match node {
+            VirtualNode::Dir(dir) => Ok(Opened::Dir(Dir::Virtual(dir))),
+            VirtualNode::File(_) if flags.directory => Err(Errno::NOTDIR),
+            VirtualNode::File(data) => {
+                if flags.truncate {
+                    data.borrow_mut().clear();
+                }
+                Ok(Opened::File(OpenFile::Virtual(VirtualFile {
+                    data,
+                    pos: 0,
+                    append: flags.append,
+                })))
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct VirtualFile {
+    data: Rc<RefCell<Vec<u8>>>,
+    pos: u64,
+    append: bool,
+}
+
+impl Read for VirtualFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let data = self.data.borrow();
+        let start = (self.pos as usize).min(data.len());
+        let n = buf.len().min(data.len() - start);
+        buf[..n].copy_from_slice(&data[start..start + n]);
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl Write for VirtualFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut data = self.data.borrow_mut();
+        if self.append {
+            self.pos = data.len() as u64;
+        }
+        let start = usize::try_from(self.pos).unwrap_or(usize::MAX);
+        let end = start
+            .checked_add(buf.len())
+            .filter(|end| *end <= MAX_VIRTUAL_FILE_SIZE)
+            .ok_or_else(|| io::Error::from(io::ErrorKind::FileTooLarge))?;
+        if data.len() < end {
+            let additional = end - data.len();
+            data.try_reserve(additional)
+                .map_err(|_| io::Error::from(io::ErrorKind::StorageFull))?;
+            data.resize(end, 0);
+        }
+        data[start..end].copy_from_slice(buf);
+        self.pos += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for VirtualFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+            SeekFrom::End(offset) => (self.data.borrow().len() as u64).checked_add_signed(offset),
+        };
+        match new_pos {
+            Some(new_pos) => {
+                self.pos = new_pos;
+                Ok(new_pos)
+            }
+            None => Err(io::Error::from(io::ErrorKind::InvalidInput)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mini-wasm-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.canonicalize().unwrap()
+    }
+
+    #[test]
+    fn test_resolve_host_path() {
+        let root = temp_dir("resolve");
+        fs::create_dir(root.join("a")).unwrap();
+        symlink("a", root.join("inner")).unwrap();
+        symlink("../..", root.join("a/outer")).unwrap();
+        symlink("/etc", root.join("absolute")).unwrap();
+        symlink("loop", root.join("loop")).unwrap();
+
+        assert_eq!(resolve_host_path(&root, "a/./b"), Ok(root.join("a/b")));
+        assert_eq!(resolve_host_path(&root, "a/../b"), Ok(root.join("b")));
+        assert_eq!(resolve_host_path(&root, "inner/b"), Ok(root.join("a/b")));
+        assert_eq!(
+            resolve_host_path(&root, "../etc/passwd"),
+            Err(Errno::NOTCAPABLE)
+        );
+        assert_eq!(
+            resolve_host_path(&root, "/etc/passwd"),
+            Err(Errno::NOTCAPABLE)
+        );
+        assert_eq!(
+            resolve_host_path(&root, "a/outer/etc"),
+            Err(Errno::NOTCAPABLE)
+        );
+        assert_eq!(
+            resolve_host_path(&root, "absolute/passwd"),
+            Err(Errno::NOTCAPABLE)
+        );
+        assert_eq!(resolve_host_path(&root, "loop"), Err(Errno::LOOP));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_open_host() {
+        let root = temp_dir("open");
+        let dir = Dir::Host(HostDir::new(&root));
+
+        let flags = OpenFlags {
+            write: true,
+            create: true,
+            ..Default::default()
+        };
+        let Ok(Opened::File(mut file)) = dir.open("out.txt", &flags) else {
+            panic!();
+        };
+        file.write_all(b"hello").unwrap();
+        assert_eq!(fs::read(root.join("out.txt")).unwrap(), b"hello");
+
+        let flags = OpenFlags {
+            create: true,
+            exclusive: true,
+            ..flags
+        };
+        assert!(matches!(dir.open("out.txt", &flags), Err(Errno::EXIST)));
+        assert!(matches!(
+            dir.open("missing", &OpenFlags::default()),
+            Err(Errno::NOENT)
+        ));
+
+        // A directory replaced by a symlink after it was resolved or opened isn't followed.
+        let outside = temp_dir("open-outside");
+        fs::write(outside.join("secret"), b"secret").unwrap();
+        fs::create_dir(root.join("a")).unwrap();
+        fs::write(root.join("a/secret"), b"inside").unwrap();
+        let Ok(Opened::Dir(a)) = dir.open("a", &OpenFlags::default()) else {
+            panic!();
+        };
+        fs::rename(root.join("a"), root.join("b")).unwrap();
+        symlink(&outside, root.join("a")).unwrap();
+        let read = OpenFlags::default();
+        assert!(matches!(a.open("secret", &read), Err(Errno::NOTDIR)));
+        let result = open_beneath(&root, Path::new("a/secret"), libc::O_RDONLY);
+        assert!(matches!(result, Err(Errno::NOTDIR)));
+        fs::remove_file(root.join("b/secret")).unwrap();
+        symlink(outside.join("secret"), root.join("b/secret")).unwrap();
+        let result = open_beneath(&root, Path::new("b/secret"), libc::O_RDONLY);
+        assert!(matches!(result, Err(Errno::LOOP)));
+
+        fs::remove_dir_all(&outside).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_virtual_dir() {
+        let root = VirtualDir::new();
+        root.insert_file("etc/motd", b"hi");
+        let dir = Dir::Virtual(root.clone());
+
+        let Ok(Opened::File(mut file)) = dir.open("etc/../etc/motd", &OpenFlags::default()) else {
+            panic!();
+        };
+        let mut buf = [0; 8];
+        assert_eq!(file.read(&mut buf).unwrap(), 2);
+        assert_eq!(&buf[..2], b"hi");
+
+        let Ok(Opened::Dir(etc)) = dir.open("etc", &OpenFlags::default()) else {
+            panic!();
+        };
+        assert!(matches!(
+            etc.open("../etc/motd", &OpenFlags::default()),
+            Err(Errno::NOTCAPABLE)
+        ));
+        assert!(matches!(
+            dir.open("../x", &OpenFlags::default()),
+            Err(Errno::NOTCAPABLE)
+        ));
+        assert!(matches!(
+            dir.open("etc/motd/x", &OpenFlags::default()),
+            Err(Errno::NOTDIR)
+        ));
+
+        let flags = OpenFlags {
+            write: true,
+            create: true,
+            append: true,
+            ..Default::default()
+        };
+        let Ok(Opened::File(mut file)) = etc.open("motd", &flags) else {
+            panic!();
+        };
+        file.write_all(b"!").unwrap();
+        assert_eq!(root.read_file("etc/motd"), Some(b"hi!".to_vec()));
+
+        let Ok(Opened::File(mut file)) = dir.open("new", &flags) else {
+            panic!();
+        };
+        file.write_all(b"created").unwrap();
+        assert_eq!(file.seek(SeekFrom::Start(2)).unwrap(), 2);
+        assert_eq!(root.read_file("new"), Some(b"created".to_vec()));
+
+        // Writing far past the end fails instead of allocating the gap.
+        for offset in [MAX_VIRTUAL_FILE_SIZE as u64, u64::MAX - 1] {
+            let Ok(Opened::File(mut file)) = dir.open("new", &OpenFlags::default()) else {
+                panic!();
+            };
+            file.seek(SeekFrom::Start(offset)).unwrap();
+            let error = file.write(b"xy").unwrap_err();
+            assert_eq!(Errno::from(error), Errno::FBIG);
+        }
+        assert_eq!(root.read_file("new"), Some(b"created".to_vec()));
+    }
+}
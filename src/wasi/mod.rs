@@ -0,0 +1,204 @@
+pub mod errno;
+pub mod fs;
+pub mod preview1;
+pub mod stdio;
+
+use crate::wasi::fs::{Dir, HostDir, OpenFile, VirtualDir};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, Cursor, Read, Write};
+use std::path::Path;
+use std::time::Instant;
+
+pub type Fd = u32;
+
+pub enum Descriptor {
+    Stdin(Box<dyn Read>),
+    Stdout(Box<dyn Write>),
+    Stderr(Box<dyn Write>),
+    Dir {
+        dir: Dir,
+        preopen: Option<String>,
+        read_only: bool,
+    },
+    File {
+        file: OpenFile,
+        read_only: bool,
+    },
+}
+
+impl fmt::Debug for Descriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Descriptor::Stdin(_) => write!(f, "Stdin"),
+            Descriptor::Stdout(_) => write!(f, "Stdout"),
+            Descriptor::Stderr(_) => write!(f, "Stderr"),
+            Descriptor::Dir {
+                dir,
+                preopen,
+                read_only,
+            } => f
+                .debug_struct("Dir")
+                .field("dir", dir)
+                .field("preopen", preopen)
+                .field("read_only", read_only)
+                .finish(),
+            Descriptor::File { file, read_only } => f
+                .debug_struct("File")
+                .field("file", file)
+                .field("read_only", read_only)
+                .finish(),
+        }
+    }
+}
+
+/// The state behind the `wasi_snapshot_preview1` host functions.
+#[derive(Debug)]
+pub struct WasiCtx {
+    pub args: Vec<String>,
+    pub envs: Vec<(String, String)>,
+    pub fds: BTreeMap<Fd, Descriptor>,
+    pub start: Instant,
+}
+
+impl WasiCtx {
+    pub fn builder() -> WasiCtxBuilder {
+        WasiCtxBuilder::new()
+    }
+
+    fn push_fd(&mut self, descriptor: Descriptor) -> Fd {
+        let mut fd = 0;
+        while self.fds.contains_key(&fd) {
+            fd += 1;
+        }
+        self.fds.insert(fd, descriptor);
+        fd
+    }
+}
+
+/// Configures a `WasiCtx`. By default the guest gets no arguments, no environment, an empty
+/// stdin, discarded stdout and stderr, and no directories.
+pub struct WasiCtxBuilder {
+    args: Vec<String>,
+    envs: Vec<(String, String)>,
+    stdin: Box<dyn Read>,
+    stdout: Box<dyn Write>,
+    stderr: Box<dyn Write>,
+    preopens: Vec<(Dir, String, bool)>,
+}
+
+impl Default for WasiCtxBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WasiCtxBuilder {
+    pub fn new() -> Self {
+        Self {
+            args: Vec::new(),
+            envs: Vec::new(),
+            stdin: Box::new(io::empty()),
+            stdout: Box::new(io::sink()),
+            stderr: Box::new(io::sink()),
+            preopens: Vec::new(),
+        }
+    }
+
+    pub fn arg(mut self, arg: &str) -> Self {
+        self.args.push(arg.to_string());
+        self
+    }
+
+    pub fn args(mut self, args: &[String]) -> Self {
+        self.args.extend_from_slice(args);
+        self
+    }
+
+    pub fn env(mut self, key: &str, value: &str) -> Self {
+        self.envs.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn envs(mut self, envs: &[(String, String)]) -> Self {
+        self.envs.extend_from_slice(envs);
+        self
+    }
+
+    pub fn stdin(mut self, stdin: impl Read + 'static) -> Self {
+        self.stdin = Box::new(stdin);
+        self
+    }
+
+    pub fn stdin_bytes(self, bytes: &[u8]) -> Self {
+        self.stdin(Cursor::new(bytes.to_vec()))
+    }
+
+    pub fn stdout(mut self, stdout: impl Write + 'static) -> Self {
+        self.stdout = Box::new(stdout);
+        self
+    }
+
+    pub fn stderr(mut self, stderr: impl Write + 'static) -> Self {
+        self.stderr = Box::new(stderr);
+        self
+    }
+
+    pub fn inherit_stdio(self) -> Self {
+        self.stdin(io::stdin())
+            .stdout(io::stdout())
+            .stderr(io::stderr())
+    }
+
+    /// Grants the guest access to `host_path` and everything below it as `guest_path`.
+    pub fn preopened_dir(
+        mut self,
+        host_path: &Path,
+        guest_path: &str,
+        read_only: bool,
+    ) -> io::Result<Self> {
+        let host_path = host_path.canonicalize()?;
+        if !host_path.is_dir() {
+            return Err(io::Error::from(io::ErrorKind::NotADirectory));
+        }
+        self.preopens.push((
+            Dir::Host(HostDir::new(host_path)),
+            guest_path.to_string(),
+            read_only,
+        ));
+        Ok(self)
+    }
+
+    pub fn preopened_virtual_dir(
+        mut self,
+        dir: VirtualDir,
+        guest_path: &str,
+        read_only: bool,
+    ) -> Self {
+        self.preopens
+            .push((Dir::Virtual(dir), guest_path.to_string(), read_only));
+        self
+    }
+
+    pub fn build(self) -> WasiCtx {
+        let mut fds = BTreeMap::new();
+        fds.insert(0, Descriptor::Stdin(self.stdin));
+        fds.insert(1, Descriptor::Stdout(self.stdout));
+        fds.insert(2, Descriptor::Stderr(self.stderr));
+
+        let mut ctx = WasiCtx {
+            args: self.args,
+            envs: self.envs,
+            fds,
+            start: Instant::now(),
+        };
+        for (dir, guest_path, read_only) in self.preopens {
+            ctx.push_fd(Descriptor::Dir {
+                dir,
+                preopen: Some(guest_path),
+                read_only,
+            });
+        }
+        ctx
+    }
+}
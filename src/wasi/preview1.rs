@@ -0,0 +1,904 @@
+use crate::execution::host::{Caller, HostFunc};
+use crate::execution::instance::ExternVal;
+use crate::execution::linker::Linker;
+use crate::execution::memory::Memory;
+use crate::execution::structure::{Store, Val};
+use crate::execution::trap::{Trap, TrapKind};
+use crate::structure::types::function::FuncType;
+use crate::structure::types::value::{NumType, ValType};
+use crate::wasi::errno::Errno;
+use crate::wasi::fs::{OpenFlags, Opened};
+use crate::wasi::{Descriptor, Fd, WasiCtx};
+use std::io::{Read, SeekFrom, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const MODULE: &str = "wasi_snapshot_preview1";
+
+const FILETYPE_CHARACTER_DEVICE: u8 = 2;
+const FILETYPE_DIRECTORY: u8 = 3;
+const FILETYPE_REGULAR_FILE: u8 = 4;
+
+const OFLAGS_CREAT: u32 = 1 << 0;
+const OFLAGS_DIRECTORY: u32 = 1 << 1;
+const OFLAGS_EXCL: u32 = 1 << 2;
+const OFLAGS_TRUNC: u32 = 1 << 3;
+
+const FDFLAGS_APPEND: u32 = 1 << 0;
+
+const RIGHTS_FD_READ: u64 = 1 << 1;
+const RIGHTS_FD_WRITE: u64 = 1 << 6;
+const RIGHTS_ALL: u64 = (1 << 30) - 1;
+const RIGHTS_READ_ONLY: u64 = RIGHTS_ALL & !RIGHTS_FD_WRITE;
+
+const CLOCKID_REALTIME: u32 = 0;
+const CLOCKID_MONOTONIC: u32 = 1;
+const CLOCKID_PROCESS_CPUTIME_ID: u32 = 2;
+const CLOCKID_THREAD_CPUTIME_ID: u32 = 3;
+
+type WasiFn = fn(&mut Memory, &mut WasiCtx, &[Val]) -> Result<(), Errno>;
+
+/// Registers every supported `wasi_snapshot_preview1` function. The calling module must
+/// export its memory as `memory`, and the store must carry a `WasiCtx`.
+pub fn add_to_linker(linker: &mut Linker, store: &mut Store) {
+    let i32_ = ValType::NumType(NumType::I32);
+    let i64_ = ValType::NumType(NumType::I64);
+
+    define(linker, store, "args_get", vec![i32_.clone(); 2], args_get);
+    define(
+        linker,
+        store,
+        "args_sizes_get",
+        vec![i32_.clone(); 2],
+        args_sizes_get,
+    );
+    define(
+        linker,
+        store,
+        "environ_get",
+        vec![i32_.clone(); 2],
+        environ_get,
+    );
+    define(
+        linker,
+        store,
+        "environ_sizes_get",
+        vec![i32_.clone(); 2],
+        environ_sizes_get,
+    );
+    define(
+        linker,
+        store,
+        "clock_time_get",
+        vec![i32_.clone(), i64_.clone(), i32_.clone()],
+        clock_time_get,
+    );
+    define(linker, store, "fd_close", vec![i32_.clone()], fd_close);
+    define(
+        linker,
+        store,
+        "fd_fdstat_get",
+        vec![i32_.clone(); 2],
+        fd_fdstat_get,
+    );
+    define(
+        linker,
+        store,
+        "fd_prestat_get",
+        vec![i32_.clone(); 2],
+        fd_prestat_get,
+    );
+    define(
+        linker,
+        store,
+        "fd_prestat_dir_name",
+        vec![i32_.clone(); 3],
+        fd_prestat_dir_name,
+    );
+    define(linker, store, "fd_read", vec![i32_.clone(); 4], fd_read);
+    define(
+        linker,
+        store,
+        "fd_seek",
+        vec![i32_.clone(), i64_.clone(), i32_.clone(), i32_.clone()],
+        fd_seek,
+    );
+    define(linker, store, "fd_write", vec![i32_.clone(); 4], fd_write);
+    define(
+        linker,
+        store,
+        "path_open",
+        vec![
+            i32_.clone(),
+            i32_.clone(),
+            i32_.clone(),
+            i32_.clone(),
+            i32_.clone(),
+            i64_.clone(),
+            i64_,
+            i32_.clone(),
+            i32_.clone(),
+        ],
+        path_open,
+    );
+    define(
+        linker,
+        store,
+        "random_get",
+        vec![i32_.clone(); 2],
+        random_get,
+    );
+    define(linker, store, "sched_yield", vec![], sched_yield);
+
+    let proc_exit_type = FuncType {
+        parameters: vec![i32_],
+        results: vec![],
+    };
+    let proc_exit = HostFunc::new(|_, arguments| {
+        let code = i32_arg(arguments, 0)
+            .map_err(|_| TrapKind::Host("wasi: proc_exit expects an i32".to_string()))?;
+        Err(TrapKind::Exit(code as i32).into())
+    });
+    linker.func(store, MODULE, "proc_exit", proc_exit_type, proc_exit);
+}
+
+fn define(linker: &mut Linker, store: &mut Store, name: &str, parameters: Vec<ValType>, f: WasiFn) {
+    let type_ = FuncType {
+        parameters,
+        results: vec![ValType::NumType(NumType::I32)],
+    };
+    let code = HostFunc::new(move |caller, arguments| {
+        let (mut memory, ctx) = memory_and_ctx(caller)?;
+        let errno = match f(&mut memory, ctx, arguments) {
+            Ok(()) => Errno::SUCCESS,
+            Err(errno) => errno,
+        };
+        Ok(vec![Val::I32(errno.0 as i32)])
+    });
+    linker.func(store, MODULE, name, type_, code);
+}
+
+fn memory_and_ctx<'a>(caller: &'a mut Caller) -> Result<(Memory<'a>, &'a mut WasiCtx), Trap> {
+    let module = caller
+        .module
+        .as_ref()
+        .ok_or_else(|| TrapKind::Host("wasi: called without a module instance".to_string()))?;
+    let mem_addr = match module.exports.iter().find(|e| e.name == "memory") {
+        Some(export_inst) => match export_inst.value {
+            ExternVal::Mem(mem_addr) => mem_addr,
+            _ => return Err(TrapKind::Host("wasi: `memory` is not a memory".to_string()).into()),
+        },
+        None => {
+            return Err(TrapKind::Host("wasi: module does not export `memory`".to_string()).into())
+        }
+    };
+
+    let store = &mut *caller.store;
+    let memory = Memory::new(&mut store.mems[mem_addr as usize]);
+    let ctx = store
+        .wasi
+        .as_mut()
+        .ok_or_else(|| TrapKind::Host("wasi: store has no WasiCtx".to_string()))?;
+    Ok((memory, ctx))
+}
+
+fn i32_arg(arguments: &[Val], i: usize) -> Result<u32, Errno> {
+    match arguments.get(i) {
+        Some(Val::I32(x)) => Ok(*x as u32),
+        _ => Err(Errno::INVAL),
+    }
+}
+
+fn i64_arg(arguments: &[Val], i: usize) -> Result<u64, Errno> {
+    match arguments.get(i) {
+        Some(Val::I64(x)) => Ok(*x as u64),
+        _ => Err(Errno::INVAL),
+    }
+}
+
+fn write_strings(
+    memory: &mut Memory,
+    strings: &[String],
+    ptrs: u32,
+    buf: u32,
+) -> Result<(), Errno> {
+    let mut buf = buf as usize;
+    for (i, string) in strings.iter().enumerate() {
+        memory.write_u32(ptrs as usize + i * 4, buf as u32)?;
+        memory.write(buf, string.as_bytes())?;
+        memory.write_u8(buf + string.len(), 0)?;
+        buf += string.len() + 1;
+    }
+    Ok(())
+}
+
+fn write_sizes(
+    memory: &mut Memory,
+    strings: &[String],
+    count: u32,
+    size: u32,
+) -> Result<(), Errno> {
+    let buf_size: usize = strings.iter().map(|s| s.len() + 1).sum();
+    memory.write_u32(count as usize, strings.len() as u32)?;
+    memory.write_u32(size as usize, buf_size as u32)?;
+    Ok(())
+}
+
+fn environ(ctx: &WasiCtx) -> Vec<String> {
+    ctx.envs
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect()
+}
+
+fn args_get(memory: &mut Memory, ctx: &mut WasiCtx, arguments: &[Val]) -> Result<(), Errno> {
+    write_strings(
+        memory,
+        &ctx.args,
+        i32_arg(arguments, 0)?,
+        i32_arg(arguments, 1)?,
+    )
+}
+
+fn args_sizes_get(memory: &mut Memory, ctx: &mut WasiCtx, arguments: &[Val]) -> Result<(), Errno> {
+    write_sizes(
+        memory,
+        &ctx.args,
+        i32_arg(arguments, 0)?,
+        i32_arg(arguments, 1)?,
+    )
+}
+
+fn environ_get(memory: &mut Memory, ctx: &mut WasiCtx, arguments: &[Val]) -> Result<(), Errno> {
+    write_strings(
+        memory,
+        &environ(ctx),
+        i32_arg(arguments, 0)?,
+        i32_arg(arguments, 1)?,
+    )
+}
+
+fn environ_sizes_get(
+    memory: &mut Memory,
+    ctx: &mut WasiCtx,
+    arguments: &[Val],
+) -> Result<(), Errno> {
+    write_sizes(
+        memory,
+        &environ(ctx),
+        i32_arg(arguments, 0)?,
+        i32_arg(arguments, 1)?,
+    )
+}
+
+fn clock_time_get(memory: &mut Memory, ctx: &mut WasiCtx, arguments: &[Val]) -> Result<(), Errno> {
+    let time = match i32_arg(arguments, 0)? {
+        CLOCKID_REALTIME => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| Errno::IO)?,
+        CLOCKID_MONOTONIC => ctx.start.elapsed(),
+        CLOCKID_PROCESS_CPUTIME_ID => cpu_time(libc::CLOCK_PROCESS_CPUTIME_ID)?,
+        CLOCKID_THREAD_CPUTIME_ID => cpu_time(libc::CLOCK_THREAD_CPUTIME_ID)?,
+        _ => return Err(Errno::INVAL),
+    };
+    memory.write_u64(i32_arg(arguments, 2)? as usize, time.as_nanos() as u64)?;
+    Ok(())
+}
+
+/// Reads one of the host's CPU-time clocks.
+fn cpu_time(clock: libc::clockid_t) -> Result<Duration, Errno> {
+    let mut time = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // SAFETY: `time` is a valid timespec for the call to fill in.
+    if unsafe { libc::clock_gettime(clock, &mut time) } != 0 {
+        return Err(Errno::INVAL);
+    }
+    Ok(Duration::new(time.tv_sec as u64, time.tv_nsec as u32))
+}
+
+fn fd_close(_: &mut Memory, ctx: &mut WasiCtx, arguments: &[Val]) -> Result<(), Errno> {
+    match ctx.fds.remove(&i32_arg(arguments, 0)?) {
+        Some(_) => Ok(()),
+        None => Err(Errno::BADF),
+    }
+}
+
+fn fd_fdstat_get(memory: &mut Memory, ctx: &mut WasiCtx, arguments: &[Val]) -> Result<(), Errno> {
+    let (filetype, read_only) = match ctx.fds.get(&i32_arg(arguments, 0)?) {
+        Some(Descriptor::Stdin(_) | Descriptor::Stdout(_) | Descriptor::Stderr(_)) => {
+            (FILETYPE_CHARACTER_DEVICE, false)
+        }
+        Some(Descriptor::Dir { read_only, .. }) => (FILETYPE_DIRECTORY, *read_only),
+        Some(Descriptor::File { read_only, .. }) => (FILETYPE_REGULAR_FILE, *read_only),
+        None => return Err(Errno::BADF),
+    };
+    let rights = if read_only {
+        RIGHTS_READ_ONLY
+    } else {
+        RIGHTS_ALL
+    };
+
+    let ptr = i32_arg(arguments, 1)? as usize;
+    memory.write(ptr, &[0; 24])?;
+    memory.write_u8(ptr, filetype)?;
+    memory.write_u64(ptr + 8, rights)?;
+    memory.write_u64(ptr + 16, rights)?;
+    Ok(())
+}
+
+fn fd_prestat_get(memory: &mut Memory, ctx: &mut WasiCtx, arguments: &[Val]) -> Result<(), Errno> {
+    let Some(Descriptor::Dir {
+        preopen: Some(guest_path),
+        ..
+    }) = ctx.fds.get(&i32_arg(arguments, 0)?)
+    else {
+        return Err(Errno::BADF);
+    };
+
+    let ptr = i32_arg(arguments, 1)? as usize;
+    memory.write_u32(ptr, 0)?;
+    memory.write_u32(ptr + 4, guest_path.len() as u32)?;
+    Ok(())
+}
+
+fn fd_prestat_dir_name(
+    memory: &mut Memory,
+    ctx: &mut WasiCtx,
+    arguments: &[Val],
+) -> Result<(), Errno> {
+    let Some(Descriptor::Dir {
+        preopen: Some(guest_path),
+        ..
+    }) = ctx.fds.get(&i32_arg(arguments, 0)?)
+    else {
+        return Err(Errno::BADF);
+    };
+
+    let len = i32_arg(arguments, 2)? as usize;
+    if len < guest_path.len() {
+        return Err(Errno::INVAL);
+    }
+    memory.write(i32_arg(arguments, 1)? as usize, guest_path.as_bytes())?;
+    Ok(())
+}
+
+fn fd_read(memory: &mut Memory, ctx: &mut WasiCtx, arguments: &[Val]) -> Result<(), Errno> {
+    let iovs = read_iovecs(memory, i32_arg(arguments, 1)?, i32_arg(arguments, 2)?)?;
+    let reader: &mut dyn Read = match ctx.fds.get_mut(&i32_arg(arguments, 0)?) {
+        Some(Descriptor::Stdin(stdin)) => stdin,
+        Some(Descriptor::File { file, .. }) => file,
+        Some(Descriptor::Dir { .. }) => return Err(Errno::ISDIR),
+        Some(_) | None => return Err(Errno::BADF),
+    };
+
+    // Reads go straight into memory, so a guest can't make the host allocate for them.
+    let mut n = 0u32;
+    for (ptr, len) in iovs {
+        let buf = memory.slice_mut(ptr, len)?;
+        let read = reader.read(buf)?;
+        n = n.checked_add(read as u32).ok_or(Errno::INVAL)?;
+        if read < len {
+            break;
+        }
+    }
+    memory.write_u32(i32_arg(arguments, 3)? as usize, n)?;
+    Ok(())
+}
+
+fn fd_seek(memory: &mut Memory, ctx: &mut WasiCtx, arguments: &[Val]) -> Result<(), Errno> {
+    let offset = i64_arg(arguments, 1)? as i64;
+    let pos = match i32_arg(arguments, 2)? {
+        0 => SeekFrom::Start(u64::try_from(offset).map_err(|_| Errno::INVAL)?),
+        1 => SeekFrom::Current(offset),
+        2 => SeekFrom::End(offset),
+        _ => return Err(Errno::INVAL),
+    };
+
+    let new_offset = match ctx.fds.get_mut(&i32_arg(arguments, 0)?) {
+        Some(Descriptor::File { file, .. }) => file.seek(pos)?,
+        Some(Descriptor::Stdin(_) | Descriptor::Stdout(_) | Descriptor::Stderr(_)) => {
+            return Err(Errno::SPIPE)
+        }
+        Some(_) => return Err(Errno::ISDIR),
+        None => return Err(Errno::BADF),
+    };
+    memory.write_u64(i32_arg(arguments, 3)? as usize, new_offset)?;
+    Ok(())
+}
+
+fn fd_write(memory: &mut Memory, ctx: &mut WasiCtx, arguments: &[Val]) -> Result<(), Errno> {
+    let iovs = read_iovecs(memory, i32_arg(arguments, 1)?, i32_arg(arguments, 2)?)?;
+    let mut n = 0u32;
+    for (_, len) in &iovs {
+        n = n.checked_add(*len as u32).ok_or(Errno::INVAL)?;
+    }
+    let writer: &mut dyn Write = match ctx.fds.get_mut(&i32_arg(arguments, 0)?) {
+        Some(Descriptor::Stdout(stream) | Descriptor::Stderr(stream)) => stream,
+        Some(Descriptor::File {
+            file,
+            read_only: false,
+        }) => file,
+        Some(Descriptor::Dir { .. }) => return Err(Errno::ISDIR),
+        Some(_) | None => return Err(Errno::BADF),
+    };
+
+    for (ptr, len) in iovs {
+        writer.write_all(memory.slice(ptr, len)?)?;
+    }
+    writer.flush()?;
+    memory.write_u32(i32_arg(arguments, 3)? as usize, n)?;
+    Ok(())
+}
+
+fn read_iovecs(memory: &Memory, iovs: u32, iovs_len: u32) -> Result<Vec<(usize, usize)>, Errno> {
+    let mut result = Vec::new();
+    for i in 0..iovs_len as usize {
+        let ptr = memory.read_u32(iovs as usize + i * 8)? as usize;
+        let len = memory.read_u32(iovs as usize + i * 8 + 4)? as usize;
+        memory.slice(ptr, len)?;
+        result.push((ptr, len));
+    }
+    Ok(result)
+}
+
+fn path_open(memory: &mut Memory, ctx: &mut WasiCtx, arguments: &[Val]) -> Result<(), Errno> {
+    let (dir, read_only) = match ctx.fds.get(&i32_arg(arguments, 0)?) {
+        Some(Descriptor::Dir { dir, read_only, .. }) => (dir.clone(), *read_only),
+        Some(_) => return Err(Errno::NOTDIR),
+        None => return Err(Errno::BADF),
+    };
+    let path = memory
+        .read_str(
+            i32_arg(arguments, 2)? as usize,
+            i32_arg(arguments, 3)? as usize,
+        )
+        .map_err(|_| Errno::INVAL)?;
+
+    let oflags = i32_arg(arguments, 4)?;
+    let rights = i64_arg(arguments, 5)?;
+    let fdflags = i32_arg(arguments, 7)?;
+    let flags = OpenFlags {
+        read: rights & RIGHTS_FD_READ != 0,
+        write: rights & RIGHTS_FD_WRITE != 0 || oflags & (OFLAGS_CREAT | OFLAGS_TRUNC) != 0,
+        create: oflags & OFLAGS_CREAT != 0,
+        exclusive: oflags & OFLAGS_EXCL != 0,
+        truncate: oflags & OFLAGS_TRUNC != 0,
+        append: fdflags & FDFLAGS_APPEND != 0,
+        directory: oflags & OFLAGS_DIRECTORY != 0,
+    };
+    if read_only && (flags.write || flags.append) {
+        return Err(Errno::ROFS);
+    }
+
+    let descriptor = match dir.open(&path, &flags)? {
+        Opened::Dir(dir) => Descriptor::Dir {
+            dir,
+            preopen: None,
+            read_only,
+        },
+        Opened::File(file) => Descriptor::File { file, read_only },
+    };
+
+    let fd: Fd = ctx.push_fd(descriptor);
+    memory.write_u32(i32_arg(arguments, 8)? as usize, fd)?;
+    Ok(())
+}
+
+fn random_get(memory: &mut Memory, _: &mut WasiCtx, arguments: &[Val]) -> Result<(), Errno> {
+    let buf = memory.slice_mut(
+        i32_arg(arguments, 0)? as usize,
+        i32_arg(arguments, 1)? as usize,
+    )?;
+    getrandom::getrandom(buf).map_err(|_| Errno::IO)
+}
+
+fn sched_yield(_: &mut Memory, _: &mut WasiCtx, _: &[Val]) -> Result<(), Errno> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::instance::{invoke, InvokeError};
+    use crate::structure::instructions::expression::{Expr, Instr, MemArg};
+    use crate::structure::modules::data::{Data, DataMode};
+    use crate::structure::modules::export::{Export, ExportDesc};
+    use crate::structure::modules::function::Func;
+    use crate::structure::modules::import::{Import, ImportDesc};
+    use crate::structure::modules::memory::Mem;
+    use crate::structure::modules::module::Module;
+    use crate::structure::types::memory::{Limits, MemType};
+    use crate::wasi::fs::VirtualDir;
+    use crate::wasi::stdio::OutputBuffer;
+    use std::fs;
+
+    fn run(
+        ctx: WasiCtx,
+        imports: &[(&str, FuncType)],
+        data: &[u8],
+        body: Vec<Instr>,
+    ) -> (Store, Result<Vec<Val>, Trap>) {
+        let mut types = Vec::new();
+        let mut module_imports = Vec::new();
+        for (name, type_) in imports {
+            module_imports.push(Import {
+                module: MODULE.to_string(),
+                name: name.to_string(),
+                desc: ImportDesc::Func(types.len() as u32),
+            });
+            types.push(type_.clone());
+        }
+        types.push(FuncType {
+            parameters: vec![],
+            results: vec![],
+        });
+
+        let module = Module {
+            funcs: vec![Func {
+                type_: imports.len() as u32,
+                locals: Vec::new(),
+                body: Expr(body),
+                offsets: Vec::new(),
+            }],
+            mems: vec![Mem {
+                type_: MemType(Limits { min: 1, max: None }),
+            }],
+            datas: vec![Data {
+                init: data.to_vec(),
+                mode: DataMode::Active {
+                    memory: 0,
+                    offset: Expr(vec![Instr::I32Const(1024)]),
+                },
+            }],
+            exports: vec![
+                Export {
+                    name: "_start".to_string(),
+                    desc: ExportDesc::Func(imports.len() as u32),
+                },
+                Export {
+                    name: "memory".to_string(),
+                    desc: ExportDesc::Mem(0),
+                },
+            ],
+            types,
+            imports: module_imports,
+            ..Default::default()
+        };
+
+        let mut store = Store {
+            wasi: Some(ctx),
+            ..Default::default()
+        };
+        let mut linker = Linker::new();
+        add_to_linker(&mut linker, &mut store);
+        let module_inst = linker.instantiate(&mut store, module).unwrap();
+        let result = invoke(&mut store, &module_inst, "_start".to_string(), Vec::new())
+            .map_err(InvokeError::unwrap_trap);
+        (store, result)
+    }
+
+    fn errno_type(n: usize) -> FuncType {
+        FuncType {
+            parameters: vec![ValType::NumType(NumType::I32); n],
+            results: vec![ValType::NumType(NumType::I32)],
+        }
+    }
+
+    fn path_open_type() -> FuncType {
+        let i32_ = ValType::NumType(NumType::I32);
+        let i64_ = ValType::NumType(NumType::I64);
+        FuncType {
+            parameters: vec![
+                i32_.clone(),
+                i32_.clone(),
+                i32_.clone(),
+                i32_.clone(),
+                i32_.clone(),
+                i64_.clone(),
+                i64_,
+                i32_.clone(),
+                i32_.clone(),
+            ],
+            results: vec![i32_],
+        }
+    }
+
+    #[test]
+    fn test_args() {
+        let ctx = WasiCtx::builder().arg("prog").arg("-v").build();
+        let body = vec![
+            Instr::I32Const(0),
+            Instr::I32Const(4),
+            Instr::Call(0),
+            Instr::Drop,
+            Instr::I32Const(16),
+            Instr::I32Const(64),
+            Instr::Call(1),
+            Instr::Drop,
+        ];
+        let imports = [
+            ("args_sizes_get", errno_type(2)),
+            ("args_get", errno_type(2)),
+        ];
+        let (store, result) = run(ctx, &imports, &[], body);
+        assert_eq!(result, Ok(Vec::new()));
+
+        let data = &store.mems[0].data;
+        assert_eq!(&data[0..8], &[2, 0, 0, 0, 8, 0, 0, 0]);
+        assert_eq!(&data[16..24], &[64, 0, 0, 0, 69, 0, 0, 0]);
+        assert_eq!(&data[64..72], b"prog\0-v\0");
+    }
+
+    #[test]
+    fn test_proc_exit() {
+        let ctx = WasiCtx::builder().build();
+        let proc_exit_type = FuncType {
+            parameters: vec![ValType::NumType(NumType::I32)],
+            results: vec![],
+        };
+        let body = vec![Instr::I32Const(3), Instr::Call(0)];
+        let (_, result) = run(ctx, &[("proc_exit", proc_exit_type)], &[], body);
+        assert_eq!(result.unwrap_err().kind, TrapKind::Exit(3));
+    }
+
+    #[test]
+    fn test_path_open_and_fd_write() {
+        let dir = std::env::temp_dir().join(format!("mini-wasm-wasi-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let ctx = WasiCtx::builder()
+            .preopened_dir(&dir, "/sandbox", false)
+            .unwrap()
+            .build();
+
+        let path_open_type = path_open_type();
+        let load = |offset| Instr::I32Load(MemArg { align: 2, offset });
+        let store_i32 = |offset| Instr::I32Store(MemArg { align: 2, offset });
+        // data: "out.txt" at 1024, "hello" at 1031
+        let body = vec![
+            // errno at 200 = path_open(3, 0, 1024, 7, CREAT | TRUNC, FD_WRITE, 0, 0, 100)
+            Instr::I32Const(0),
+            Instr::I32Const(3),
+            Instr::I32Const(0),
+            Instr::I32Const(1024),
+            Instr::I32Const(7),
+            Instr::I32Const((OFLAGS_CREAT | OFLAGS_TRUNC) as i32),
+            Instr::I64Const(RIGHTS_FD_WRITE as i64),
+            Instr::I64Const(0),
+            Instr::I32Const(0),
+            Instr::I32Const(100),
+            Instr::Call(0),
+            store_i32(200),
+            // iovec { 1031, 5 } at 8
+            Instr::I32Const(0),
+            Instr::I32Const(1031),
+            store_i32(8),
+            Instr::I32Const(0),
+            Instr::I32Const(5),
+            store_i32(12),
+            // errno at 204 = fd_write(fd, 8, 1, 104)
+            Instr::I32Const(0),
+            Instr::I32Const(0),
+            load(100),
+            Instr::I32Const(8),
+            Instr::I32Const(1),
+            Instr::I32Const(104),
+            Instr::Call(1),
+            store_i32(204),
+        ];
+        let imports = [("path_open", path_open_type), ("fd_write", errno_type(4))];
+        let (store, result) = run(ctx, &imports, b"out.txthello", body);
+        assert_eq!(result, Ok(Vec::new()));
+
+        let data = &store.mems[0].data;
+        assert_eq!(&data[200..204], &[0, 0, 0, 0]);
+        assert_eq!(&data[204..208], &[0, 0, 0, 0]);
+        assert_eq!(&data[100..104], &[4, 0, 0, 0]);
+        assert_eq!(&data[104..108], &[5, 0, 0, 0]);
+        drop(store);
+        assert_eq!(fs::read_to_string(dir.join("out.txt")).unwrap(), "hello");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_only_preopen() {
+        let dir = VirtualDir::new();
+        dir.insert_file("config.txt", b"key=value");
+        let ctx = WasiCtx::builder()
+            .preopened_virtual_dir(dir.clone(), "/data", true)
+            .build();
+
+        let path_open = |oflags: u32, rights: u64, errno_ptr: i32| {
+            vec![
+                Instr::I32Const(errno_ptr),
+                Instr::I32Const(3),
+                Instr::I32Const(0),
+                Instr::I32Const(1024),
+                Instr::I32Const(10),
+                Instr::I32Const(oflags as i32),
+                Instr::I64Const(rights as i64),
+                Instr::I64Const(0),
+                Instr::I32Const(0),
+                Instr::I32Const(100),
+                Instr::Call(0),
+                Instr::I32Store(MemArg {
+                    align: 2,
+                    offset: 0,
+                }),
+            ]
+        };
+        let mut body = path_open(0, RIGHTS_FD_READ, 200);
+        body.extend(path_open(OFLAGS_TRUNC, RIGHTS_FD_WRITE, 204));
+        let (store, result) = run(ctx, &[("path_open", path_open_type())], b"config.txt", body);
+        assert_eq!(result, Ok(Vec::new()));
+
+        let data = &store.mems[0].data;
+        assert_eq!(&data[200..204], &[0, 0, 0, 0]);
+        assert_eq!(&data[204..208], &[Errno::ROFS.0 as u8, 0, 0, 0]);
+        assert_eq!(dir.read_file("config.txt"), Some(b"key=value".to_vec()));
+    }
+
+    #[test]
+    fn test_stdio() {
+        let stdout = OutputBuffer::new();
+        let stderr = OutputBuffer::new();
+        let ctx = WasiCtx::builder()
+            .stdin_bytes(b"ping")
+            .stdout(stdout.clone())
+            .stderr(stderr.clone())
+            .build();
+
+        let store_i32 = |offset| Instr::I32Store(MemArg { align: 2, offset });
+        let iovec = |ptr: i32, len: Vec<Instr>| {
+            let mut instrs = vec![
+                Instr::I32Const(0),
+                Instr::I32Const(ptr),
+                store_i32(8),
+                Instr::I32Const(0),
+            ];
+            instrs.extend(len);
+            instrs.push(store_i32(12));
+            instrs
+        };
+        let nread = vec![
+            Instr::I32Const(0),
+            Instr::I32Load(MemArg {
+                align: 2,
+                offset: 104,
+            }),
+        ];
+        let call = |func: u32, fd: i32| {
+            vec![
+                Instr::I32Const(fd),
+                Instr::I32Const(8),
+                Instr::I32Const(1),
+                Instr::I32Const(104),
+                Instr::Call(func),
+                Instr::Drop,
+            ]
+        };
+
+        // echo stdin to stdout, then write the data segment to stderr
+        let mut body = iovec(2048, vec![Instr::I32Const(16)]);
+        body.extend(call(0, 0));
+        body.extend(iovec(2048, nread));
+        body.extend(call(1, 1));
+        body.extend(iovec(1024, vec![Instr::I32Const(4)]));
+        body.extend(call(1, 2));
+
+        let imports = [("fd_read", errno_type(4)), ("fd_write", errno_type(4))];
+        let (_, result) = run(ctx, &imports, b"oops", body);
+        assert_eq!(result, Ok(Vec::new()));
+        assert_eq!(stdout.contents(), b"ping");
+        assert_eq!(stderr.contents(), b"oops");
+    }
+
+    #[test]
+    fn test_out_of_bounds_buffers() {
+        let stdout = OutputBuffer::new();
+        let ctx = WasiCtx::builder()
+            .stdin_bytes(b"ping")
+            .stdout(stdout.clone())
+            .build();
+
+        let store_i32 = |offset| Instr::I32Store(MemArg { align: 2, offset });
+        // An iovec { 1024, 0x7fff0000 } at 8, which is far larger than the memory.
+        let mut body = vec![
+            Instr::I32Const(0),
+            Instr::I32Const(1024),
+            store_i32(8),
+            Instr::I32Const(0),
+            Instr::I32Const(0x7fff_0000),
+            store_i32(12),
+        ];
+        for (func, fd, errno_ptr) in [(0, 0, 200), (1, 1, 204)] {
+            body.extend([
+                Instr::I32Const(0),
+                Instr::I32Const(fd),
+                Instr::I32Const(8),
+                Instr::I32Const(1),
+                Instr::I32Const(104),
+                Instr::Call(func),
+                store_i32(errno_ptr),
+            ]);
+        }
+        body.extend([
+            Instr::I32Const(0),
+            Instr::I32Const(1024),
+            Instr::I32Const(0x7fff_0000),
+            Instr::Call(2),
+            store_i32(208),
+        ]);
+
+        let imports = [
+            ("fd_read", errno_type(4)),
+            ("fd_write", errno_type(4)),
+            ("random_get", errno_type(2)),
+        ];
+        let (store, result) = run(ctx, &imports, &[], body);
+        assert_eq!(result, Ok(Vec::new()));
+
+        let data = &store.mems[0].data;
+        let fault = [Errno::FAULT.0 as u8, 0, 0, 0];
+        assert_eq!(&data[200..204], &fault);
+        assert_eq!(&data[204..208], &fault);
+        assert_eq!(&data[208..212], &fault);
+        assert_eq!(stdout.contents(), b"");
+    }
+
+    #[test]
+    fn test_clocks_and_random() {
+        let store_i32 = |offset| Instr::I32Store(MemArg { align: 2, offset });
+        let mut body = Vec::new();
+        // errno at 100 + 4 * clock = clock_time_get(clock, 1, 8 * clock)
+        for clock in 0..4 {
+            body.extend([
+                Instr::I32Const(0),
+                Instr::I32Const(clock),
+                Instr::I64Const(1),
+                Instr::I32Const(8 * clock),
+                Instr::Call(0),
+                store_i32(100 + 4 * clock as u32),
+            ]);
+        }
+        // errno at 116 = random_get(1024, 64)
+        body.extend([
+            Instr::I32Const(0),
+            Instr::I32Const(1024),
+            Instr::I32Const(64),
+            Instr::Call(1),
+            store_i32(116),
+        ]);
+        let i32_ = ValType::NumType(NumType::I32);
+        let clock_time_get_type = FuncType {
+            parameters: vec![i32_.clone(), ValType::NumType(NumType::I64), i32_.clone()],
+            results: vec![i32_],
+        };
+        let imports = [
+            ("clock_time_get", clock_time_get_type),
+            ("random_get", errno_type(2)),
+        ];
+        let (store, result) = run(WasiCtx::builder().build(), &imports, &[], body);
+        assert_eq!(result, Ok(Vec::new()));
+        let process_time = cpu_time(libc::CLOCK_PROCESS_CPUTIME_ID).unwrap();
+
+        let data = &store.mems[0].data;
+        assert_eq!(&data[100..120], &[0; 20]);
+        let time =
+            |clock: usize| u64::from_le_bytes(data[clock * 8..clock * 8 + 8].try_into().unwrap());
+        // The CPU clocks measure the time the process and thread have run, not the wall clock.
+        assert!(time(2) > 0 && time(2) <= process_time.as_nanos() as u64);
+        assert!(time(3) > 0 && time(3) <= process_time.as_nanos() as u64);
+        assert_ne!(&data[1024..1088], &[0; 64]);
+    }
+
+    #[test]
+    fn test_arguments() {
+        assert_eq!(i32_arg(&[Val::I32(-1)], 0), Ok(u32::MAX));
+        assert_eq!(i32_arg(&[Val::I64(1)], 0), Err(Errno::INVAL));
+        assert_eq!(i32_arg(&[], 0), Err(Errno::INVAL));
+        assert_eq!(i64_arg(&[Val::I64(-1)], 0), Ok(u64::MAX));
+        assert_eq!(i64_arg(&[Val::I32(1)], 0), Err(Errno::INVAL));
+    }
+}
@@ -0,0 +1,616 @@
+use crate::decoder::Decoder;
+use crate::execution::engine::Engine;
+use crate::execution::host::HostFunc;
+use crate::execution::instance::{invoke_func, ExternVal, InstantiationError, ModuleInst};
+use crate::execution::linker::Linker;
+use crate::execution::structure::{MemAddr, MemInst, Store, Val};
+use crate::execution::trap::{Trap, TrapKind};
+use crate::structure::modules::module::Module;
+use crate::structure::types::function::FuncType;
+use crate::structure::types::memory::{Limits, MemType};
+use crate::structure::types::value::{NumType, ValType};
+use crate::text::lexer::{Lexer, Span};
+use crate::text::parser::{parse_module, read_sexprs, Sexpr};
+use crate::text::{self, ParseError};
+use crate::validation::validate;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Passed,
+    Failed(String),
+    /// The directive uses something mini-wasm doesn't support, so it wasn't checked.
+    Skipped(String),
+}
+
+/// The outcome of one directive of a script.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub span: Span,
+    /// The directive keyword, e.g. `assert_return`.
+    pub kind: String,
+    pub outcome: Outcome,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {} ", self.span.line, self.span.column, self.kind)?;
+        match &self.outcome {
+            Outcome::Passed => write!(f, "ok"),
+            Outcome::Failed(message) => write!(f, "FAILED: {}", message),
+            Outcome::Skipped(message) => write!(f, "skipped: {}", message),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+}
+
+impl Summary {
+    pub fn add(&mut self, outcome: &Outcome) {
+        match outcome {
+            Outcome::Passed => self.passed += 1,
+            Outcome::Failed(_) => self.failed += 1,
+            Outcome::Skipped(_) => self.skipped += 1,
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} passed, {} failed, {} skipped",
+            self.passed, self.failed, self.skipped
+        )
+    }
+}
+
+/// Runs the directives of spec `.wast` scripts against a store that holds the `spectest`
+/// module and the modules registered so far.
+pub struct Runner {
+    store: Store,
+    linker: Linker,
+    instances: HashMap<String, Rc<ModuleInst>>,
+    current: Option<Rc<ModuleInst>>,
+}
+
+/// Why a directive didn't pass.
+enum Failure {
+    Failed(String),
+    Skipped(String),
+}
+
+impl From<String> for Failure {
+    fn from(message: String) -> Self {
+        Failure::Failed(message)
+    }
+}
+
+enum Expected {
+    Val(Val),
+    CanonicalNan(NumType),
+    ArithmeticNan(NumType),
+}
+
+impl Default for Runner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Runner {
+    pub fn new() -> Self {
+        Self::with_engine(&Engine::default())
+    }
+
+    pub fn with_engine(engine: &Engine) -> Self {
+        let mut store = Store::new(engine);
+        let mut linker = Linker::new();
+        define_spectest(&mut linker, &mut store);
+        Self {
+            store,
+            linker,
+            instances: HashMap::new(),
+            current: None,
+        }
+    }
+
+    /// Runs every directive of a script. Only a script that can't be read as s-expressions is
+    /// an error; a directive that can't be run is reported as failed.
+    pub fn run(&mut self, source: &str) -> Result<Vec<Report>, ParseError> {
+        let tokens = Lexer::new(source).tokenize()?;
+        let sexprs = read_sexprs(tokens)?;
+        Ok(sexprs.iter().map(|sexpr| self.directive(sexpr)).collect())
+    }
+
+    fn directive(&mut self, sexpr: &Sexpr) -> Report {
+        let kind = sexpr.keyword().unwrap_or_default().to_string();
+        let outcome = match self.execute(&kind, sexpr) {
+            Ok(()) => Outcome::Passed,
+            Err(Failure::Failed(message)) => Outcome::Failed(message),
+            Err(Failure::Skipped(message)) => Outcome::Skipped(message),
+        };
+        Report {
+            span: sexpr.span(),
+            kind,
+            outcome,
+        }
+    }
+
+    fn execute(&mut self, kind: &str, sexpr: &Sexpr) -> Result<(), Failure> {
+        let Sexpr::List(items, _) = sexpr else {
+            return Err(Failure::Failed("expected a directive".to_string()));
+        };
+        let args = &items[1..];
+        match kind {
+            "module" => {
+                let (id, module) = module_def(sexpr)?;
+                let module_inst = self
+                    .linker
+                    .instantiate(&mut self.store, module)
+                    .map_err(|error| error.to_string())?;
+                self.define(id, module_inst);
+                Ok(())
+            }
+            "register" => {
+                let (name, id) = match args {
+                    [Sexpr::String(name, _)] => (name, None),
+                    [Sexpr::String(name, _), Sexpr::Id(id, _)] => (name, Some(id.as_str())),
+                    _ => return Err(malformed("register")),
+                };
+                let name = String::from_utf8_lossy(name);
+                let module_inst = self.instance(id)?;
+                for export in &module_inst.exports {
+                    self.linker
+                        .define(&name, &export.name, export.value.clone());
+                }
+                Ok(())
+            }
+            "invoke" | "get" => self.action(sexpr).map(|_| ()),
+            "assert_return" => {
+                let [action, results @ ..] = args else {
+                    return Err(malformed(kind));
+                };
+                let expected = results
+                    .iter()
+                    .map(expected)
+                    .collect::<Result<Vec<_>, _>>()?;
+                let actual = self
+                    .action(action)?
+                    .map_err(|trap| format!("unexpected trap: {}", trap))?;
+                let matched = actual.len() == expected.len()
+                    && actual.iter().zip(&expected).all(|(a, e)| matches(a, e));
+                if !matched {
+                    return Err(Failure::Failed(format!(
+                        "expected {}, got {}",
+                        display_expected(&expected),
+                        display_vals(&actual)
+                    )));
+                }
+                Ok(())
+            }
+            "assert_trap" => {
+                let [target, Sexpr::String(message, _)] = args else {
+                    return Err(malformed(kind));
+                };
+                let message = String::from_utf8_lossy(message);
+                let trap = if target.keyword() == Some("module") {
+                    let (_, module) = module_def(target)?;
+                    match self.linker.instantiate(&mut self.store, module) {
+                        Err(InstantiationError::Trap(trap)) => trap,
+                        Err(error) => return Err(Failure::Failed(error.to_string())),
+                        Ok(_) => return Err(Failure::Failed("expected a trap".to_string())),
+                    }
+                } else {
+                    match self.action(target)? {
+                        Err(trap) => trap,
+                        Ok(vals) => {
+                            return Err(Failure::Failed(format!(
+                                "expected a trap, got {}",
+                                display_vals(&vals)
+                            )))
+                        }
+                    }
+                };
+                expect_message(&trap.to_string(), &message)
+            }
+            "assert_exhaustion" => {
+                let [action, Sexpr::String(message, _)] = args else {
+                    return Err(malformed(kind));
+                };
+                match self.action(action)? {
+                    Err(trap) if trap.kind == TrapKind::StackExhausted => {
+                        expect_message(&trap.to_string(), &String::from_utf8_lossy(message))
+                    }
+                    Err(trap) => Err(Failure::Failed(format!("unexpected trap: {}", trap))),
+                    Ok(vals) => Err(Failure::Failed(format!(
+                        "expected stack exhaustion, got {}",
+                        display_vals(&vals)
+                    ))),
+                }
+            }
+            "assert_invalid" => {
+                let [target, Sexpr::String(message, _)] = args else {
+                    return Err(malformed(kind));
+                };
+                let (_, module) = module_def(target)?;
+                match validate(&module) {
+                    Ok(()) => Err(Failure::Failed(format!(
+                        "expected invalid module: {}",
+                        String::from_utf8_lossy(message)
+                    ))),
+                    Err(_) => Ok(()),
+                }
+            }
+            "assert_malformed" => {
+                let [target, Sexpr::String(message, _)] = args else {
+                    return Err(malformed(kind));
+                };
+                match module_def(target) {
+                    Err(_) => Ok(()),
+                    Ok(_) => Err(Failure::Failed(format!(
+                        "expected malformed module: {}",
+                        String::from_utf8_lossy(message)
+                    ))),
+                }
+            }
+            "assert_unlinkable" => {
+                let [target, Sexpr::String(message, _)] = args else {
+                    return Err(malformed(kind));
+                };
+                let (_, module) = module_def(target)?;
+                match self.linker.instantiate(&mut self.store, module) {
+                    Err(
+                        InstantiationError::UnknownImport(..)
+                        | InstantiationError::IncompatibleImportType(..),
+                    ) => Ok(()),
+                    _ => Err(Failure::Failed(format!(
+                        "expected unlinkable module: {}",
+                        String::from_utf8_lossy(message)
+                    ))),
+                }
+            }
+            _ => Err(Failure::Skipped("unsupported directive".to_string())),
+        }
+    }
+
+    fn define(&mut self, id: Option<String>, module_inst: Rc<ModuleInst>) {
+        if let Some(id) = id {
+            self.instances.insert(id, Rc::clone(&module_inst));
+        }
+        self.current = Some(module_inst);
+    }
+
+    fn instance(&self, id: Option<&str>) -> Result<Rc<ModuleInst>, Failure> {
+        let module_inst = match id {
+            Some(id) => self.instances.get(id),
+            None => self.current.as_ref(),
+        };
+        match module_inst {
+            Some(module_inst) => Ok(Rc::clone(module_inst)),
+            None => Err(Failure::Failed(match id {
+                Some(id) => format!("unknown module ${}", id),
+                None => "no module has been defined".to_string(),
+            })),
+        }
+    }
+
+    /// Performs an `invoke` action. The outer error is a directive that can't be run, the inner
+    /// one a trap of the invoked function.
+    fn action(&mut self, sexpr: &Sexpr) -> Result<Result<Vec<Val>, Trap>, Failure> {
+        let Sexpr::List(items, _) = sexpr else {
+            return Err(malformed("action"));
+        };
+        match sexpr.keyword() {
+            Some("invoke") => {}
+            Some("get") => return Err(Failure::Skipped("globals are not supported".to_string())),
+            _ => return Err(malformed("action")),
+        }
+
+        let mut items = &items[1..];
+        let mut id = None;
+        if let [Sexpr::Id(name, _), rest @ ..] = items {
+            id = Some(name.as_str());
+            items = rest;
+        }
+        let [Sexpr::String(name, _), args @ ..] = items else {
+            return Err(malformed("invoke"));
+        };
+        let name = String::from_utf8_lossy(name);
+
+        let module_inst = self.instance(id)?;
+        let Some(func_addr) = module_inst.get_func(&name) else {
+            return Err(Failure::Failed(format!("unknown function \"{}\"", name)));
+        };
+        let args = args.iter().map(constant).collect::<Result<Vec<_>, _>>()?;
+        let func_type = self.store.funcs[func_addr as usize].type_();
+        let arg_types: Vec<ValType> = args.iter().map(Val::type_).collect();
+        if func_type.parameters != arg_types {
+            return Err(Failure::Failed(format!(
+                "\"{}\" has type {}, but was given ({})",
+                name,
+                func_type,
+                display_vals(&args)
+            )));
+        }
+        Ok(invoke_func(&mut self.store, func_addr, args))
+    }
+}
+
+/// Defines the `spectest` module that the spec tests import from.
+fn define_spectest(linker: &mut Linker, store: &mut Store) {
+    let i32_ = ValType::NumType(NumType::I32);
+    let i64_ = ValType::NumType(NumType::I64);
+    let f32_ = ValType::NumType(NumType::F32);
+    let f64_ = ValType::NumType(NumType::F64);
+    let prints = [
+        ("print", vec![]),
+        ("print_i32", vec![i32_.clone()]),
+        ("print_i64", vec![i64_]),
+        ("print_f32", vec![f32_.clone()]),
+        ("print_f64", vec![f64_.clone()]),
+        ("print_i32_f32", vec![i32_, f32_]),
+        ("print_f64_f64", vec![f64_.clone(), f64_]),
+    ];
+    for (name, parameters) in prints {
+        let type_ = FuncType {
+            parameters,
+            results: vec![],
+        };
+        linker.func(
+            store,
+            "spectest",
+            name,
+            type_,
+            HostFunc::new(|_, _| Ok(vec![])),
+        );
+    }
+
+    let mem_addr = store.mems.len() as MemAddr;
+    store.mems.push(MemInst::new(MemType(Limits {
+        min: 1,
+        max: Some(2),
+    })));
+    linker.define("spectest", "memory", ExternVal::Mem(mem_addr));
+}
+
+/// Reads a `(module ...)` in the text format, or given as `binary` or `quote` strings, along
+/// with its identifier. The error is why the module is malformed.
+fn module_def(sexpr: &Sexpr) -> Result<(Option<String>, Module), String> {
+    let Sexpr::List(items, _) = sexpr else {
+        return Err("expected a module".to_string());
+    };
+    let mut rest = &items[1..];
+    let mut id = None;
+    if let [Sexpr::Id(name, _), tail @ ..] = rest {
+        id = Some(name.clone());
+        rest = tail;
+    }
+
+    let strings = |items: &[Sexpr]| -> Vec<u8> {
+        items
+            .iter()
+            .filter_map(|item| match item {
+                Sexpr::String(bytes, _) => Some(bytes.as_slice()),
+                _ => None,
+            })
+            .flatten()
+            .copied()
+            .collect()
+    };
+    let result = match rest {
+        [Sexpr::Atom(keyword, _), strings_ @ ..] if keyword == "binary" => {
+            let binary = strings(strings_);
+            Decoder::new(&binary)
+                .decode()
+                .map_err(|error| error.to_string())
+        }
+        [Sexpr::Atom(keyword, _), strings_ @ ..] if keyword == "quote" => {
+            match String::from_utf8(strings(strings_)) {
+                Ok(source) => text::parse(&source).map_err(|error| error.to_string()),
+                Err(_) => Err("malformed UTF-8 encoding".to_string()),
+            }
+        }
+        _ => parse_module(std::slice::from_ref(sexpr)).map_err(|error| error.to_string()),
+    };
+    result.map(|module| (id, module))
+}
+
+/// Reads a constant such as `(i32.const 1)`.
+fn constant(sexpr: &Sexpr) -> Result<Val, Failure> {
+    match expected(sexpr)? {
+        Expected::Val(val) => Ok(val),
+        _ => Err(malformed("constant")),
+    }
+}
+
+/// Reads an expected result, which may also be a NaN pattern such as `(f32.const nan:canonical)`
+/// or a null reference.
+fn expected(sexpr: &Sexpr) -> Result<Expected, Failure> {
+    let Sexpr::List(items, _) = sexpr else {
+        return Err(malformed("constant"));
+    };
+    let [Sexpr::Atom(op, _), Sexpr::Atom(value, _)] = items.as_slice() else {
+        return match sexpr.keyword() {
+            Some(keyword) if keyword.ends_with(".const") => Err(malformed("constant")),
+            Some(keyword) => Err(Failure::Skipped(format!("`{}` is not supported", keyword))),
+            None => Err(malformed("constant")),
+        };
+    };
+    if op == "ref.null" && value == "func" {
+        return Ok(Expected::Val(Val::FuncRef(None)));
+    }
+    let type_ = match op.as_str() {
+        "i32.const" => NumType::I32,
+        "i64.const" => NumType::I64,
+        "f32.const" => NumType::F32,
+        "f64.const" => NumType::F64,
+        _ => return Err(Failure::Skipped(format!("`{}` is not supported", op))),
+    };
+    match value.as_str() {
+        "nan:canonical" => return Ok(Expected::CanonicalNan(type_)),
+        "nan:arithmetic" => return Ok(Expected::ArithmeticNan(type_)),
+        _ => {}
+    }
+    Val::parse(value, &ValType::NumType(type_))
+        .map(Expected::Val)
+        .map_err(|error| Failure::Failed(error.to_string()))
+}
+
+fn matches(actual: &Val, expected: &Expected) -> bool {
+    match (actual, expected) {
+        (Val::F32(a), Expected::Val(Val::F32(e))) => a.to_bits() == e.to_bits(),
+        (Val::F64(a), Expected::Val(Val::F64(e))) => a.to_bits() == e.to_bits(),
+        (a, Expected::Val(e)) => a == e,
+        (Val::F32(a), Expected::CanonicalNan(NumType::F32)) => {
+            a.to_bits() & 0x7fff_ffff == 0x7fc0_0000
+        }
+        (Val::F64(a), Expected::CanonicalNan(NumType::F64)) => {
+            a.to_bits() & 0x7fff_ffff_ffff_ffff == 0x7ff8_0000_0000_0000
+        }
+        (Val::F32(a), Expected::ArithmeticNan(NumType::F32)) => {
+            a.to_bits() & 0x7fc0_0000 == 0x7fc0_0000
+        }
+        (Val::F64(a), Expected::ArithmeticNan(NumType::F64)) => {
+            a.to_bits() & 0x7ff8_0000_0000_0000 == 0x7ff8_0000_0000_0000
+        }
+        _ => false,
+    }
+}
+
+/// Spec scripts only give the start of the expected message.
+fn expect_message(actual: &str, expected: &str) -> Result<(), Failure> {
+    if actual.starts_with(expected) {
+        Ok(())
+    } else {
+        Err(Failure::Failed(format!(
+            "expected \"{}\", got \"{}\"",
+            expected, actual
+        )))
+    }
+}
+
+fn malformed(kind: &str) -> Failure {
+    Failure::Failed(format!("malformed `{}`", kind))
+}
+
+fn display_vals(vals: &[Val]) -> String {
+    let vals: Vec<String> = vals
+        .iter()
+        .map(|val| format!("{}: {}", val, val.type_()))
+        .collect();
+    vals.join(", ")
+}
+
+fn display_expected(expected: &[Expected]) -> String {
+    let expected: Vec<String> = expected
+        .iter()
+        .map(|expected| match expected {
+            Expected::Val(val) => format!("{}: {}", val, val.type_()),
+            Expected::CanonicalNan(type_) => {
+                format!("nan:canonical: {}", ValType::NumType(type_.clone()))
+            }
+            Expected::ArithmeticNan(type_) => {
+                format!("nan:arithmetic: {}", ValType::NumType(type_.clone()))
+            }
+        })
+        .collect();
+    expected.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::engine::{Backend, Config};
+    use std::fs;
+
+    fn outcomes(source: &str) -> Vec<Outcome> {
+        Runner::new()
+            .run(source)
+            .unwrap()
+            .into_iter()
+            .map(|report| report.outcome)
+            .collect()
+    }
+
+    #[test]
+    fn test_scripts() {
+        let backends = [
+            Backend::Stack,
+            Backend::Register,
+            #[cfg(feature = "jit")]
+            Backend::Jit,
+        ];
+        for backend in backends {
+            let engine = Engine::with_config(Config {
+                backend,
+                ..Default::default()
+            });
+            for dir in ["./tests/wast", "./tests/spec"] {
+                for entry in fs::read_dir(dir).unwrap() {
+                    let path = entry.unwrap().path();
+                    if path.extension().is_none_or(|extension| extension != "wast") {
+                        continue;
+                    }
+                    let source = fs::read_to_string(&path).unwrap();
+                    for report in Runner::with_engine(&engine).run(&source).unwrap() {
+                        assert_eq!(
+                            report.outcome,
+                            Outcome::Passed,
+                            "{:?} {}:{}",
+                            backend,
+                            path.display(),
+                            report
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_failures() {
+        let outcomes = outcomes(
+            r#"(module (func (export "one") (result i32) (i32.const 1)))
+            (assert_return (invoke "one") (i32.const 2))
+            (assert_return (invoke "one") (f32.const nan:canonical))
+            (assert_trap (invoke "one") "unreachable")
+            (assert_invalid (module (func)) "type mismatch")
+            (assert_malformed (module quote "(func)") "unexpected token")
+            (assert_return (invoke "two"))
+            (assert_return (get "global") (i32.const 1))
+            (assert_exhaustion (invoke "one") "call stack exhausted")"#,
+        );
+        assert_eq!(outcomes[0], Outcome::Passed);
+        assert_eq!(
+            outcomes[1],
+            Outcome::Failed("expected 2: i32, got 1: i32".to_string())
+        );
+        for outcome in &outcomes[2..7] {
+            assert!(matches!(outcome, Outcome::Failed(_)), "{:?}", outcome);
+        }
+        assert!(matches!(outcomes[7], Outcome::Skipped(_)));
+        assert!(matches!(outcomes[8], Outcome::Failed(_)));
+    }
+
+    #[test]
+    fn test_traps() {
+        let outcomes = outcomes(
+            r#"(module (func (export "div") (result i32) (i32.div_s (i32.const 1) (i32.const 0))))
+            (assert_trap (invoke "div") "integer divide by zero")
+            (assert_return (invoke "div") (i32.const 0))"#,
+        );
+        assert_eq!(outcomes[1], Outcome::Passed);
+        assert_eq!(
+            outcomes[2],
+            Outcome::Failed("unexpected trap: integer divide by zero".to_string())
+        );
+    }
+}
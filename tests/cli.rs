@@ -0,0 +1,126 @@
+use std::process::{Command, Output};
+
+/// Runs the built binary from the crate root, so that fixtures are found by relative paths.
+fn mini_wasm(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_mini-wasm"))
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+#[test]
+fn test_invoke() {
+    let output = mini_wasm(&["invoke", "tests/cli/math.wat", "add", "2", "3"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "👻 5: i32\n");
+    assert_eq!(stderr(&output), "");
+}
+
+#[test]
+fn test_negative_arguments() {
+    let output = mini_wasm(&["invoke", "tests/cli/math.wat", "add", "-2", "-3"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "👻 -5: i32\n");
+
+    let output = mini_wasm(&["invoke", "tests/cli/math.wat", "negate", "-9"]);
+    assert_eq!(stdout(&output), "👻 9: i64\n");
+
+    let output = mini_wasm(&["invoke", "tests/cli/math.wat", "half", "-1.5"]);
+    assert_eq!(stdout(&output), "👻 -0.75: f64\n");
+}
+
+#[test]
+fn test_trap() {
+    let output = mini_wasm(&["invoke", "tests/cli/math.wat", "fail"]);
+    assert_eq!(output.status.code(), Some(134));
+    assert_eq!(stdout(&output), "");
+    let stderr = stderr(&output);
+    assert!(
+        stderr.starts_with("error: wasm trap: integer divide by zero\nwasm backtrace:\n"),
+        "{}",
+        stderr
+    );
+    assert!(stderr.contains("$divide"), "{}", stderr);
+}
+
+#[test]
+fn test_bad_arguments() {
+    let cases: [(&[&str], &str); 3] = [
+        (
+            &["add", "1"],
+            "error: `add` has type (i32, i32) -> (i32) and expects 2 argument(s), but 1 were given\n",
+        ),
+        (
+            &["add", "1", "x"],
+            "error: argument 2 of `add`: `x` is not a valid i32 value\n",
+        ),
+        (
+            &["missing"],
+            "error: tests/cli/math.wat does not export a function `missing`\n",
+        ),
+    ];
+    for (args, message) in cases {
+        let output = mini_wasm(&[&["invoke", "tests/cli/math.wat"], args].concat());
+        assert_eq!(output.status.code(), Some(1), "{:?}", args);
+        assert_eq!(stdout(&output), "");
+        assert_eq!(stderr(&output), message);
+    }
+}
+
+#[test]
+fn test_run() {
+    let output = mini_wasm(&["run", "tests/cli/hello.wat"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "hello\n");
+    assert_eq!(stderr(&output), "");
+
+    // `proc_exit` sets the exit code.
+    let output = mini_wasm(&["invoke", "tests/cli/hello.wat", "exit", "3"]);
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(stderr(&output), "");
+}
+
+#[test]
+fn test_validate() {
+    let output = mini_wasm(&["validate", "tests/cli/math.wat"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "tests/cli/math.wat is valid\n");
+
+    let output = mini_wasm(&["validate", "tests/cli/invalid.wat"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stderr(&output),
+        "error: tests/cli/invalid.wat is invalid: func 0: type mismatch: expected i32, found i64\n"
+    );
+
+    let output = mini_wasm(&["run", "tests/cli/invalid.wat"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stderr(&output),
+        "error: invalid module: func 0: type mismatch: expected i32, found i64\n"
+    );
+}
+
+#[test]
+fn test_usage() {
+    let output = mini_wasm(&["run", "tests/cli/missing.wat"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stderr(&output).starts_with("error: failed to read tests/cli/missing.wat: "),
+        "{}",
+        stderr(&output)
+    );
+
+    let output = mini_wasm(&["frobnicate"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("unrecognized subcommand 'frobnicate'"));
+}
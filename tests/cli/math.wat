@@ -0,0 +1,11 @@
+(module
+  (func (export "add") (param i32 i32) (result i32)
+    (i32.add (local.get 0) (local.get 1)))
+  (func (export "negate") (param i64) (result i64)
+    (i64.sub (i64.const 0) (local.get 0)))
+  (func (export "half") (param f64) (result f64)
+    (f64.div (local.get 0) (f64.const 2)))
+  (func $divide (export "divide") (param i32 i32) (result i32)
+    (i32.div_s (local.get 0) (local.get 1)))
+  (func (export "fail") (result i32)
+    (call $divide (i32.const 1) (i32.const 0))))
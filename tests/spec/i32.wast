@@ -0,0 +1,1089 @@
+;; i32 operations
+
+;; A subset of i32.wast from the WebAssembly spec testsuite
+;; (https://github.com/WebAssembly/testsuite), limited to the directives mini-wasm supports.
+
+(module
+  (func (export "add") (param $x i32) (param $y i32) (result i32) (i32.add (local.get $x) (local.get $y)))
+  (func (export "sub") (param $x i32) (param $y i32) (result i32) (i32.sub (local.get $x) (local.get $y)))
+  (func (export "mul") (param $x i32) (param $y i32) (result i32) (i32.mul (local.get $x) (local.get $y)))
+  (func (export "div_s") (param $x i32) (param $y i32) (result i32) (i32.div_s (local.get $x) (local.get $y)))
+  (func (export "div_u") (param $x i32) (param $y i32) (result i32) (i32.div_u (local.get $x) (local.get $y)))
+  (func (export "rem_s") (param $x i32) (param $y i32) (result i32) (i32.rem_s (local.get $x) (local.get $y)))
+  (func (export "rem_u") (param $x i32) (param $y i32) (result i32) (i32.rem_u (local.get $x) (local.get $y)))
+  (func (export "and") (param $x i32) (param $y i32) (result i32) (i32.and (local.get $x) (local.get $y)))
+  (func (export "or") (param $x i32) (param $y i32) (result i32) (i32.or (local.get $x) (local.get $y)))
+  (func (export "xor") (param $x i32) (param $y i32) (result i32) (i32.xor (local.get $x) (local.get $y)))
+  (func (export "shl") (param $x i32) (param $y i32) (result i32) (i32.shl (local.get $x) (local.get $y)))
+  (func (export "shr_s") (param $x i32) (param $y i32) (result i32) (i32.shr_s (local.get $x) (local.get $y)))
+  (func (export "shr_u") (param $x i32) (param $y i32) (result i32) (i32.shr_u (local.get $x) (local.get $y)))
+  (func (export "rotl") (param $x i32) (param $y i32) (result i32) (i32.rotl (local.get $x) (local.get $y)))
+  (func (export "rotr") (param $x i32) (param $y i32) (result i32) (i32.rotr (local.get $x) (local.get $y)))
+  (func (export "clz") (param $x i32) (result i32) (i32.clz (local.get $x)))
+  (func (export "ctz") (param $x i32) (result i32) (i32.ctz (local.get $x)))
+  (func (export "popcnt") (param $x i32) (result i32) (i32.popcnt (local.get $x)))
+  (func (export "eqz") (param $x i32) (result i32) (i32.eqz (local.get $x)))
+  (func (export "extend8_s") (param $x i32) (result i32) (i32.extend8_s (local.get $x)))
+  (func (export "extend16_s") (param $x i32) (result i32) (i32.extend16_s (local.get $x)))
+  (func (export "eq") (param $x i32) (param $y i32) (result i32) (i32.eq (local.get $x) (local.get $y)))
+  (func (export "ne") (param $x i32) (param $y i32) (result i32) (i32.ne (local.get $x) (local.get $y)))
+  (func (export "lt_s") (param $x i32) (param $y i32) (result i32) (i32.lt_s (local.get $x) (local.get $y)))
+  (func (export "lt_u") (param $x i32) (param $y i32) (result i32) (i32.lt_u (local.get $x) (local.get $y)))
+  (func (export "le_s") (param $x i32) (param $y i32) (result i32) (i32.le_s (local.get $x) (local.get $y)))
+  (func (export "le_u") (param $x i32) (param $y i32) (result i32) (i32.le_u (local.get $x) (local.get $y)))
+  (func (export "gt_s") (param $x i32) (param $y i32) (result i32) (i32.gt_s (local.get $x) (local.get $y)))
+  (func (export "gt_u") (param $x i32) (param $y i32) (result i32) (i32.gt_u (local.get $x) (local.get $y)))
+  (func (export "ge_s") (param $x i32) (param $y i32) (result i32) (i32.ge_s (local.get $x) (local.get $y)))
+  (func (export "ge_u") (param $x i32) (param $y i32) (result i32) (i32.ge_u (local.get $x) (local.get $y)))
+)
+
+(assert_return (invoke "add" (i32.const 1) (i32.const 1)) (i32.const 2))
+(assert_return (invoke "add" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "add" (i32.const 0) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "add" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "add" (i32.const -1) (i32.const -1)) (i32.const -2))
+(assert_return (invoke "add" (i32.const -1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "add" (i32.const 1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "add" (i32.const 0x7fffffff) (i32.const 1)) (i32.const 0x80000000))
+(assert_return (invoke "add" (i32.const 0x80000000) (i32.const -1)) (i32.const 0x7fffffff))
+(assert_return (invoke "add" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "add" (i32.const 0x7fffffff) (i32.const -1)) (i32.const 0x7ffffffe))
+(assert_return (invoke "add" (i32.const 0x80000000) (i32.const 1)) (i32.const 0x80000001))
+(assert_return (invoke "add" (i32.const 0x80000000) (i32.const 2)) (i32.const 0x80000002))
+(assert_return (invoke "add" (i32.const 0x80000000) (i32.const 0)) (i32.const 0x80000000))
+(assert_return (invoke "add" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const -2))
+(assert_return (invoke "add" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const -1))
+(assert_return (invoke "add" (i32.const 5) (i32.const 2)) (i32.const 7))
+(assert_return (invoke "add" (i32.const -5) (i32.const 2)) (i32.const -3))
+(assert_return (invoke "add" (i32.const 5) (i32.const -2)) (i32.const 3))
+(assert_return (invoke "add" (i32.const -5) (i32.const -2)) (i32.const -7))
+(assert_return (invoke "add" (i32.const 7) (i32.const 3)) (i32.const 10))
+(assert_return (invoke "add" (i32.const -7) (i32.const 3)) (i32.const -4))
+(assert_return (invoke "add" (i32.const 7) (i32.const -3)) (i32.const 4))
+(assert_return (invoke "add" (i32.const -7) (i32.const -3)) (i32.const -10))
+(assert_return (invoke "add" (i32.const 11) (i32.const 5)) (i32.const 16))
+(assert_return (invoke "add" (i32.const 17) (i32.const 7)) (i32.const 24))
+(assert_return (invoke "add" (i32.const 1) (i32.const 31)) (i32.const 32))
+(assert_return (invoke "add" (i32.const 1) (i32.const 32)) (i32.const 33))
+(assert_return (invoke "add" (i32.const 1) (i32.const 33)) (i32.const 34))
+(assert_return (invoke "add" (i32.const -1) (i32.const 32)) (i32.const 31))
+(assert_return (invoke "add" (i32.const -1) (i32.const 33)) (i32.const 32))
+(assert_return (invoke "add" (i32.const 1) (i32.const 0x7fffffff)) (i32.const 0x80000000))
+(assert_return (invoke "add" (i32.const 1) (i32.const 0x80000000)) (i32.const 0x80000001))
+(assert_return (invoke "add" (i32.const -1) (i32.const 0x80000000)) (i32.const 0x7fffffff))
+(assert_return (invoke "add" (i32.const 0x80000000) (i32.const 31)) (i32.const 0x8000001f))
+(assert_return (invoke "add" (i32.const 0x1234567) (i32.const 0x76543210)) (i32.const 0x77777777))
+(assert_return (invoke "add" (i32.const 0x8ff00ff0) (i32.const 0x10001)) (i32.const 0x8ff10ff1))
+(assert_return (invoke "add" (i32.const 0x80000001) (i32.const 1000)) (i32.const 0x800003e9))
+(assert_return (invoke "add" (i32.const 0xf0f0ffff) (i32.const 0xfffff0f0)) (i32.const 0xf0f0f0ef))
+(assert_return (invoke "add" (i32.const 0xabcd9876) (i32.const 1)) (i32.const 0xabcd9877))
+(assert_return (invoke "add" (i32.const 0xfe00dc00) (i32.const 4)) (i32.const 0xfe00dc04))
+(assert_return (invoke "add" (i32.const 0xb0c1d2e3) (i32.const 5)) (i32.const 0xb0c1d2e8))
+(assert_return (invoke "add" (i32.const 0x8000) (i32.const 37)) (i32.const 0x8025))
+(assert_return (invoke "add" (i32.const 0xb0c1d2e3) (i32.const 0xff05)) (i32.const 0xb0c2d1e8))
+(assert_return (invoke "add" (i32.const 0x769abcdf) (i32.const -19)) (i32.const 0x769abccc))
+(assert_return (invoke "add" (i32.const 0x769abcdf) (i32.const 0x8000000d)) (i32.const 0xf69abcec))
+(assert_return (invoke "add" (i32.const 0xff00cc00) (i32.const 1)) (i32.const 0xff00cc01))
+(assert_return (invoke "add" (i32.const 0x80000) (i32.const 4)) (i32.const 0x80004))
+
+(assert_return (invoke "sub" (i32.const 1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "sub" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "sub" (i32.const 0) (i32.const 1)) (i32.const -1))
+(assert_return (invoke "sub" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "sub" (i32.const -1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "sub" (i32.const -1) (i32.const 1)) (i32.const -2))
+(assert_return (invoke "sub" (i32.const 1) (i32.const -1)) (i32.const 2))
+(assert_return (invoke "sub" (i32.const 0x7fffffff) (i32.const 1)) (i32.const 0x7ffffffe))
+(assert_return (invoke "sub" (i32.const 0x80000000) (i32.const -1)) (i32.const 0x80000001))
+(assert_return (invoke "sub" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "sub" (i32.const 0x7fffffff) (i32.const -1)) (i32.const 0x80000000))
+(assert_return (invoke "sub" (i32.const 0x80000000) (i32.const 1)) (i32.const 0x7fffffff))
+(assert_return (invoke "sub" (i32.const 0x80000000) (i32.const 2)) (i32.const 0x7ffffffe))
+(assert_return (invoke "sub" (i32.const 0x80000000) (i32.const 0)) (i32.const 0x80000000))
+(assert_return (invoke "sub" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "sub" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const -1))
+(assert_return (invoke "sub" (i32.const 5) (i32.const 2)) (i32.const 3))
+(assert_return (invoke "sub" (i32.const -5) (i32.const 2)) (i32.const -7))
+(assert_return (invoke "sub" (i32.const 5) (i32.const -2)) (i32.const 7))
+(assert_return (invoke "sub" (i32.const -5) (i32.const -2)) (i32.const -3))
+(assert_return (invoke "sub" (i32.const 7) (i32.const 3)) (i32.const 4))
+(assert_return (invoke "sub" (i32.const -7) (i32.const 3)) (i32.const -10))
+(assert_return (invoke "sub" (i32.const 7) (i32.const -3)) (i32.const 10))
+(assert_return (invoke "sub" (i32.const -7) (i32.const -3)) (i32.const -4))
+(assert_return (invoke "sub" (i32.const 11) (i32.const 5)) (i32.const 6))
+(assert_return (invoke "sub" (i32.const 17) (i32.const 7)) (i32.const 10))
+(assert_return (invoke "sub" (i32.const 1) (i32.const 31)) (i32.const -30))
+(assert_return (invoke "sub" (i32.const 1) (i32.const 32)) (i32.const -31))
+(assert_return (invoke "sub" (i32.const 1) (i32.const 33)) (i32.const -32))
+(assert_return (invoke "sub" (i32.const -1) (i32.const 32)) (i32.const -33))
+(assert_return (invoke "sub" (i32.const -1) (i32.const 33)) (i32.const -34))
+(assert_return (invoke "sub" (i32.const 1) (i32.const 0x7fffffff)) (i32.const 0x80000002))
+(assert_return (invoke "sub" (i32.const 1) (i32.const 0x80000000)) (i32.const 0x80000001))
+(assert_return (invoke "sub" (i32.const -1) (i32.const 0x80000000)) (i32.const 0x7fffffff))
+(assert_return (invoke "sub" (i32.const 0x80000000) (i32.const 31)) (i32.const 0x7fffffe1))
+(assert_return (invoke "sub" (i32.const 0x1234567) (i32.const 0x76543210)) (i32.const 0x8acf1357))
+(assert_return (invoke "sub" (i32.const 0x8ff00ff0) (i32.const 0x10001)) (i32.const 0x8fef0fef))
+(assert_return (invoke "sub" (i32.const 0x80000001) (i32.const 1000)) (i32.const 0x7ffffc19))
+(assert_return (invoke "sub" (i32.const 0xf0f0ffff) (i32.const 0xfffff0f0)) (i32.const 0xf0f10f0f))
+(assert_return (invoke "sub" (i32.const 0xabcd9876) (i32.const 1)) (i32.const 0xabcd9875))
+(assert_return (invoke "sub" (i32.const 0xfe00dc00) (i32.const 4)) (i32.const 0xfe00dbfc))
+(assert_return (invoke "sub" (i32.const 0xb0c1d2e3) (i32.const 5)) (i32.const 0xb0c1d2de))
+(assert_return (invoke "sub" (i32.const 0x8000) (i32.const 37)) (i32.const 0x7fdb))
+(assert_return (invoke "sub" (i32.const 0xb0c1d2e3) (i32.const 0xff05)) (i32.const 0xb0c0d3de))
+(assert_return (invoke "sub" (i32.const 0x769abcdf) (i32.const -19)) (i32.const 0x769abcf2))
+(assert_return (invoke "sub" (i32.const 0x769abcdf) (i32.const 0x8000000d)) (i32.const 0xf69abcd2))
+(assert_return (invoke "sub" (i32.const 0xff00cc00) (i32.const 1)) (i32.const 0xff00cbff))
+(assert_return (invoke "sub" (i32.const 0x80000) (i32.const 4)) (i32.const 0x7fffc))
+
+(assert_return (invoke "mul" (i32.const 1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "mul" (i32.const 1) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "mul" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "mul" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "mul" (i32.const -1) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "mul" (i32.const -1) (i32.const 1)) (i32.const -1))
+(assert_return (invoke "mul" (i32.const 1) (i32.const -1)) (i32.const -1))
+(assert_return (invoke "mul" (i32.const 0x7fffffff) (i32.const 1)) (i32.const 0x7fffffff))
+(assert_return (invoke "mul" (i32.const 0x80000000) (i32.const -1)) (i32.const 0x80000000))
+(assert_return (invoke "mul" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "mul" (i32.const 0x7fffffff) (i32.const -1)) (i32.const 0x80000001))
+(assert_return (invoke "mul" (i32.const 0x80000000) (i32.const 1)) (i32.const 0x80000000))
+(assert_return (invoke "mul" (i32.const 0x80000000) (i32.const 2)) (i32.const 0))
+(assert_return (invoke "mul" (i32.const 0x80000000) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "mul" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 1))
+(assert_return (invoke "mul" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 0x80000000))
+(assert_return (invoke "mul" (i32.const 5) (i32.const 2)) (i32.const 10))
+(assert_return (invoke "mul" (i32.const -5) (i32.const 2)) (i32.const -10))
+(assert_return (invoke "mul" (i32.const 5) (i32.const -2)) (i32.const -10))
+(assert_return (invoke "mul" (i32.const -5) (i32.const -2)) (i32.const 10))
+(assert_return (invoke "mul" (i32.const 7) (i32.const 3)) (i32.const 21))
+(assert_return (invoke "mul" (i32.const -7) (i32.const 3)) (i32.const -21))
+(assert_return (invoke "mul" (i32.const 7) (i32.const -3)) (i32.const -21))
+(assert_return (invoke "mul" (i32.const -7) (i32.const -3)) (i32.const 21))
+(assert_return (invoke "mul" (i32.const 11) (i32.const 5)) (i32.const 55))
+(assert_return (invoke "mul" (i32.const 17) (i32.const 7)) (i32.const 119))
+(assert_return (invoke "mul" (i32.const 1) (i32.const 31)) (i32.const 31))
+(assert_return (invoke "mul" (i32.const 1) (i32.const 32)) (i32.const 32))
+(assert_return (invoke "mul" (i32.const 1) (i32.const 33)) (i32.const 33))
+(assert_return (invoke "mul" (i32.const -1) (i32.const 32)) (i32.const -32))
+(assert_return (invoke "mul" (i32.const -1) (i32.const 33)) (i32.const -33))
+(assert_return (invoke "mul" (i32.const 1) (i32.const 0x7fffffff)) (i32.const 0x7fffffff))
+(assert_return (invoke "mul" (i32.const 1) (i32.const 0x80000000)) (i32.const 0x80000000))
+(assert_return (invoke "mul" (i32.const -1) (i32.const 0x80000000)) (i32.const 0x80000000))
+(assert_return (invoke "mul" (i32.const 0x80000000) (i32.const 31)) (i32.const 0x80000000))
+(assert_return (invoke "mul" (i32.const 0x1234567) (i32.const 0x76543210)) (i32.const 0x358e7470))
+(assert_return (invoke "mul" (i32.const 0x8ff00ff0) (i32.const 0x10001)) (i32.const 0x9fe00ff0))
+(assert_return (invoke "mul" (i32.const 0x80000001) (i32.const 1000)) (i32.const 1000))
+(assert_return (invoke "mul" (i32.const 0xf0f0ffff) (i32.const 0xfffff0f0)) (i32.const 0xd1f00f10))
+(assert_return (invoke "mul" (i32.const 0xabcd9876) (i32.const 1)) (i32.const 0xabcd9876))
+(assert_return (invoke "mul" (i32.const 0xfe00dc00) (i32.const 4)) (i32.const 0xf8037000))
+(assert_return (invoke "mul" (i32.const 0xb0c1d2e3) (i32.const 5)) (i32.const 0x73c91e6f))
+(assert_return (invoke "mul" (i32.const 0x8000) (i32.const 37)) (i32.const 0x128000))
+(assert_return (invoke "mul" (i32.const 0xb0c1d2e3) (i32.const 0xff05)) (i32.const 0x84d93b6f))
+(assert_return (invoke "mul" (i32.const 0x769abcdf) (i32.const -19)) (i32.const 0x3283fb73))
+(assert_return (invoke "mul" (i32.const 0x769abcdf) (i32.const 0x8000000d)) (i32.const 0x85db9753))
+(assert_return (invoke "mul" (i32.const 0xff00cc00) (i32.const 1)) (i32.const 0xff00cc00))
+(assert_return (invoke "mul" (i32.const 0x80000) (i32.const 4)) (i32.const 0x200000))
+
+(assert_return (invoke "div_s" (i32.const 1) (i32.const 1)) (i32.const 1))
+(assert_trap (invoke "div_s" (i32.const 1) (i32.const 0)) "integer divide by zero")
+(assert_return (invoke "div_s" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_trap (invoke "div_s" (i32.const 0) (i32.const 0)) "integer divide by zero")
+(assert_return (invoke "div_s" (i32.const -1) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "div_s" (i32.const -1) (i32.const 1)) (i32.const -1))
+(assert_return (invoke "div_s" (i32.const 1) (i32.const -1)) (i32.const -1))
+(assert_return (invoke "div_s" (i32.const 0x7fffffff) (i32.const 1)) (i32.const 0x7fffffff))
+(assert_trap (invoke "div_s" (i32.const 0x80000000) (i32.const -1)) "integer overflow")
+(assert_return (invoke "div_s" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "div_s" (i32.const 0x7fffffff) (i32.const -1)) (i32.const 0x80000001))
+(assert_return (invoke "div_s" (i32.const 0x80000000) (i32.const 1)) (i32.const 0x80000000))
+(assert_return (invoke "div_s" (i32.const 0x80000000) (i32.const 2)) (i32.const 0xc0000000))
+(assert_trap (invoke "div_s" (i32.const 0x80000000) (i32.const 0)) "integer divide by zero")
+(assert_return (invoke "div_s" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 1))
+(assert_return (invoke "div_s" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "div_s" (i32.const 5) (i32.const 2)) (i32.const 2))
+(assert_return (invoke "div_s" (i32.const -5) (i32.const 2)) (i32.const -2))
+(assert_return (invoke "div_s" (i32.const 5) (i32.const -2)) (i32.const -2))
+(assert_return (invoke "div_s" (i32.const -5) (i32.const -2)) (i32.const 2))
+(assert_return (invoke "div_s" (i32.const 7) (i32.const 3)) (i32.const 2))
+(assert_return (invoke "div_s" (i32.const -7) (i32.const 3)) (i32.const -2))
+(assert_return (invoke "div_s" (i32.const 7) (i32.const -3)) (i32.const -2))
+(assert_return (invoke "div_s" (i32.const -7) (i32.const -3)) (i32.const 2))
+(assert_return (invoke "div_s" (i32.const 11) (i32.const 5)) (i32.const 2))
+(assert_return (invoke "div_s" (i32.const 17) (i32.const 7)) (i32.const 2))
+(assert_return (invoke "div_s" (i32.const 1) (i32.const 31)) (i32.const 0))
+(assert_return (invoke "div_s" (i32.const 1) (i32.const 32)) (i32.const 0))
+(assert_return (invoke "div_s" (i32.const 1) (i32.const 33)) (i32.const 0))
+(assert_return (invoke "div_s" (i32.const -1) (i32.const 32)) (i32.const 0))
+(assert_return (invoke "div_s" (i32.const -1) (i32.const 33)) (i32.const 0))
+(assert_return (invoke "div_s" (i32.const 1) (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "div_s" (i32.const 1) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "div_s" (i32.const -1) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "div_s" (i32.const 0x80000000) (i32.const 31)) (i32.const 0xfbdef7be))
+(assert_return (invoke "div_s" (i32.const 0x1234567) (i32.const 0x76543210)) (i32.const 0))
+(assert_return (invoke "div_s" (i32.const 0x8ff00ff0) (i32.const 0x10001)) (i32.const 0xffff8ff1))
+(assert_return (invoke "div_s" (i32.const 0x80000001) (i32.const 1000)) (i32.const 0xffdf3b65))
+(assert_return (invoke "div_s" (i32.const 0xf0f0ffff) (i32.const 0xfffff0f0)) (i32.const 0xffef))
+(assert_return (invoke "div_s" (i32.const 0xabcd9876) (i32.const 1)) (i32.const 0xabcd9876))
+(assert_return (invoke "div_s" (i32.const 0xfe00dc00) (i32.const 4)) (i32.const 0xff803700))
+(assert_return (invoke "div_s" (i32.const 0xb0c1d2e3) (i32.const 5)) (i32.const 0xf026c3c7))
+(assert_return (invoke "div_s" (i32.const 0x8000) (i32.const 37)) (i32.const 885))
+(assert_return (invoke "div_s" (i32.const 0xb0c1d2e3) (i32.const 0xff05)) (i32.const 0xffffb074))
+(assert_return (invoke "div_s" (i32.const 0x769abcdf) (i32.const -19)) (i32.const 0xf9c1f610))
+(assert_return (invoke "div_s" (i32.const 0x769abcdf) (i32.const 0x8000000d)) (i32.const 0))
+(assert_return (invoke "div_s" (i32.const 0xff00cc00) (i32.const 1)) (i32.const 0xff00cc00))
+(assert_return (invoke "div_s" (i32.const 0x80000) (i32.const 4)) (i32.const 0x20000))
+
+(assert_return (invoke "div_u" (i32.const 1) (i32.const 1)) (i32.const 1))
+(assert_trap (invoke "div_u" (i32.const 1) (i32.const 0)) "integer divide by zero")
+(assert_return (invoke "div_u" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_trap (invoke "div_u" (i32.const 0) (i32.const 0)) "integer divide by zero")
+(assert_return (invoke "div_u" (i32.const -1) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "div_u" (i32.const -1) (i32.const 1)) (i32.const -1))
+(assert_return (invoke "div_u" (i32.const 1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const 0x7fffffff) (i32.const 1)) (i32.const 0x7fffffff))
+(assert_return (invoke "div_u" (i32.const 0x80000000) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "div_u" (i32.const 0x7fffffff) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const 0x80000000) (i32.const 1)) (i32.const 0x80000000))
+(assert_return (invoke "div_u" (i32.const 0x80000000) (i32.const 2)) (i32.const 0x40000000))
+(assert_trap (invoke "div_u" (i32.const 0x80000000) (i32.const 0)) "integer divide by zero")
+(assert_return (invoke "div_u" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 1))
+(assert_return (invoke "div_u" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const 5) (i32.const 2)) (i32.const 2))
+(assert_return (invoke "div_u" (i32.const -5) (i32.const 2)) (i32.const 0x7ffffffd))
+(assert_return (invoke "div_u" (i32.const 5) (i32.const -2)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const -5) (i32.const -2)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const 7) (i32.const 3)) (i32.const 2))
+(assert_return (invoke "div_u" (i32.const -7) (i32.const 3)) (i32.const 0x55555553))
+(assert_return (invoke "div_u" (i32.const 7) (i32.const -3)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const -7) (i32.const -3)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const 11) (i32.const 5)) (i32.const 2))
+(assert_return (invoke "div_u" (i32.const 17) (i32.const 7)) (i32.const 2))
+(assert_return (invoke "div_u" (i32.const 1) (i32.const 31)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const 1) (i32.const 32)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const 1) (i32.const 33)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const -1) (i32.const 32)) (i32.const 0x7ffffff))
+(assert_return (invoke "div_u" (i32.const -1) (i32.const 33)) (i32.const 0x7c1f07c))
+(assert_return (invoke "div_u" (i32.const 1) (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const 1) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const -1) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "div_u" (i32.const 0x80000000) (i32.const 31)) (i32.const 0x4210842))
+(assert_return (invoke "div_u" (i32.const 0x1234567) (i32.const 0x76543210)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const 0x8ff00ff0) (i32.const 0x10001)) (i32.const 0x8fef))
+(assert_return (invoke "div_u" (i32.const 0x80000001) (i32.const 1000)) (i32.const 0x20c49b))
+(assert_return (invoke "div_u" (i32.const 0xf0f0ffff) (i32.const 0xfffff0f0)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const 0xabcd9876) (i32.const 1)) (i32.const 0xabcd9876))
+(assert_return (invoke "div_u" (i32.const 0xfe00dc00) (i32.const 4)) (i32.const 0x3f803700))
+(assert_return (invoke "div_u" (i32.const 0xb0c1d2e3) (i32.const 5)) (i32.const 0x2359f6fa))
+(assert_return (invoke "div_u" (i32.const 0x8000) (i32.const 37)) (i32.const 885))
+(assert_return (invoke "div_u" (i32.const 0xb0c1d2e3) (i32.const 0xff05)) (i32.const 0xb16f))
+(assert_return (invoke "div_u" (i32.const 0x769abcdf) (i32.const -19)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const 0x769abcdf) (i32.const 0x8000000d)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const 0xff00cc00) (i32.const 1)) (i32.const 0xff00cc00))
+(assert_return (invoke "div_u" (i32.const 0x80000) (i32.const 4)) (i32.const 0x20000))
+
+(assert_return (invoke "rem_s" (i32.const 1) (i32.const 1)) (i32.const 0))
+(assert_trap (invoke "rem_s" (i32.const 1) (i32.const 0)) "integer divide by zero")
+(assert_return (invoke "rem_s" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_trap (invoke "rem_s" (i32.const 0) (i32.const 0)) "integer divide by zero")
+(assert_return (invoke "rem_s" (i32.const -1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "rem_s" (i32.const -1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "rem_s" (i32.const 1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "rem_s" (i32.const 0x7fffffff) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "rem_s" (i32.const 0x80000000) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "rem_s" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "rem_s" (i32.const 0x7fffffff) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "rem_s" (i32.const 0x80000000) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "rem_s" (i32.const 0x80000000) (i32.const 2)) (i32.const 0))
+(assert_trap (invoke "rem_s" (i32.const 0x80000000) (i32.const 0)) "integer divide by zero")
+(assert_return (invoke "rem_s" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "rem_s" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 0x7fffffff))
+(assert_return (invoke "rem_s" (i32.const 5) (i32.const 2)) (i32.const 1))
+(assert_return (invoke "rem_s" (i32.const -5) (i32.const 2)) (i32.const -1))
+(assert_return (invoke "rem_s" (i32.const 5) (i32.const -2)) (i32.const 1))
+(assert_return (invoke "rem_s" (i32.const -5) (i32.const -2)) (i32.const -1))
+(assert_return (invoke "rem_s" (i32.const 7) (i32.const 3)) (i32.const 1))
+(assert_return (invoke "rem_s" (i32.const -7) (i32.const 3)) (i32.const -1))
+(assert_return (invoke "rem_s" (i32.const 7) (i32.const -3)) (i32.const 1))
+(assert_return (invoke "rem_s" (i32.const -7) (i32.const -3)) (i32.const -1))
+(assert_return (invoke "rem_s" (i32.const 11) (i32.const 5)) (i32.const 1))
+(assert_return (invoke "rem_s" (i32.const 17) (i32.const 7)) (i32.const 3))
+(assert_return (invoke "rem_s" (i32.const 1) (i32.const 31)) (i32.const 1))
+(assert_return (invoke "rem_s" (i32.const 1) (i32.const 32)) (i32.const 1))
+(assert_return (invoke "rem_s" (i32.const 1) (i32.const 33)) (i32.const 1))
+(assert_return (invoke "rem_s" (i32.const -1) (i32.const 32)) (i32.const -1))
+(assert_return (invoke "rem_s" (i32.const -1) (i32.const 33)) (i32.const -1))
+(assert_return (invoke "rem_s" (i32.const 1) (i32.const 0x7fffffff)) (i32.const 1))
+(assert_return (invoke "rem_s" (i32.const 1) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "rem_s" (i32.const -1) (i32.const 0x80000000)) (i32.const -1))
+(assert_return (invoke "rem_s" (i32.const 0x80000000) (i32.const 31)) (i32.const -2))
+(assert_return (invoke "rem_s" (i32.const 0x1234567) (i32.const 0x76543210)) (i32.const 0x1234567))
+(assert_return (invoke "rem_s" (i32.const 0x8ff00ff0) (i32.const 0x10001)) (i32.const 0xffff7fff))
+(assert_return (invoke "rem_s" (i32.const 0x80000001) (i32.const 1000)) (i32.const -647))
+(assert_return (invoke "rem_s" (i32.const 0xf0f0ffff) (i32.const 0xfffff0f0)) (i32.const -17))
+(assert_return (invoke "rem_s" (i32.const 0xabcd9876) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "rem_s" (i32.const 0xfe00dc00) (i32.const 4)) (i32.const 0))
+(assert_return (invoke "rem_s" (i32.const 0xb0c1d2e3) (i32.const 5)) (i32.const 0))
+(assert_return (invoke "rem_s" (i32.const 0x8000) (i32.const 37)) (i32.const 23))
+(assert_return (invoke "rem_s" (i32.const 0xb0c1d2e3) (i32.const 0xff05)) (i32.const 0xffffd49f))
+(assert_return (invoke "rem_s" (i32.const 0x769abcdf) (i32.const -19)) (i32.const 15))
+(assert_return (invoke "rem_s" (i32.const 0x769abcdf) (i32.const 0x8000000d)) (i32.const 0x769abcdf))
+(assert_return (invoke "rem_s" (i32.const 0xff00cc00) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "rem_s" (i32.const 0x80000) (i32.const 4)) (i32.const 0))
+
+(assert_return (invoke "rem_u" (i32.const 1) (i32.const 1)) (i32.const 0))
+(assert_trap (invoke "rem_u" (i32.const 1) (i32.const 0)) "integer divide by zero")
+(assert_return (invoke "rem_u" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_trap (invoke "rem_u" (i32.const 0) (i32.const 0)) "integer divide by zero")
+(assert_return (invoke "rem_u" (i32.const -1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "rem_u" (i32.const -1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "rem_u" (i32.const 1) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "rem_u" (i32.const 0x7fffffff) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "rem_u" (i32.const 0x80000000) (i32.const -1)) (i32.const 0x80000000))
+(assert_return (invoke "rem_u" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "rem_u" (i32.const 0x7fffffff) (i32.const -1)) (i32.const 0x7fffffff))
+(assert_return (invoke "rem_u" (i32.const 0x80000000) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "rem_u" (i32.const 0x80000000) (i32.const 2)) (i32.const 0))
+(assert_trap (invoke "rem_u" (i32.const 0x80000000) (i32.const 0)) "integer divide by zero")
+(assert_return (invoke "rem_u" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "rem_u" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 0x7fffffff))
+(assert_return (invoke "rem_u" (i32.const 5) (i32.const 2)) (i32.const 1))
+(assert_return (invoke "rem_u" (i32.const -5) (i32.const 2)) (i32.const 1))
+(assert_return (invoke "rem_u" (i32.const 5) (i32.const -2)) (i32.const 5))
+(assert_return (invoke "rem_u" (i32.const -5) (i32.const -2)) (i32.const -5))
+(assert_return (invoke "rem_u" (i32.const 7) (i32.const 3)) (i32.const 1))
+(assert_return (invoke "rem_u" (i32.const -7) (i32.const 3)) (i32.const 0))
+(assert_return (invoke "rem_u" (i32.const 7) (i32.const -3)) (i32.const 7))
+(assert_return (invoke "rem_u" (i32.const -7) (i32.const -3)) (i32.const -7))
+(assert_return (invoke "rem_u" (i32.const 11) (i32.const 5)) (i32.const 1))
+(assert_return (invoke "rem_u" (i32.const 17) (i32.const 7)) (i32.const 3))
+(assert_return (invoke "rem_u" (i32.const 1) (i32.const 31)) (i32.const 1))
+(assert_return (invoke "rem_u" (i32.const 1) (i32.const 32)) (i32.const 1))
+(assert_return (invoke "rem_u" (i32.const 1) (i32.const 33)) (i32.const 1))
+(assert_return (invoke "rem_u" (i32.const -1) (i32.const 32)) (i32.const 31))
+(assert_return (invoke "rem_u" (i32.const -1) (i32.const 33)) (i32.const 3))
+(assert_return (invoke "rem_u" (i32.const 1) (i32.const 0x7fffffff)) (i32.const 1))
+(assert_return (invoke "rem_u" (i32.const 1) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "rem_u" (i32.const -1) (i32.const 0x80000000)) (i32.const 0x7fffffff))
+(assert_return (invoke "rem_u" (i32.const 0x80000000) (i32.const 31)) (i32.const 2))
+(assert_return (invoke "rem_u" (i32.const 0x1234567) (i32.const 0x76543210)) (i32.const 0x1234567))
+(assert_return (invoke "rem_u" (i32.const 0x8ff00ff0) (i32.const 0x10001)) (i32.const 0x8001))
+(assert_return (invoke "rem_u" (i32.const 0x80000001) (i32.const 1000)) (i32.const 649))
+(assert_return (invoke "rem_u" (i32.const 0xf0f0ffff) (i32.const 0xfffff0f0)) (i32.const 0xf0f0ffff))
+(assert_return (invoke "rem_u" (i32.const 0xabcd9876) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "rem_u" (i32.const 0xfe00dc00) (i32.const 4)) (i32.const 0))
+(assert_return (invoke "rem_u" (i32.const 0xb0c1d2e3) (i32.const 5)) (i32.const 1))
+(assert_return (invoke "rem_u" (i32.const 0x8000) (i32.const 37)) (i32.const 23))
+(assert_return (invoke "rem_u" (i32.const 0xb0c1d2e3) (i32.const 0xff05)) (i32.const 0xcab8))
+(assert_return (invoke "rem_u" (i32.const 0x769abcdf) (i32.const -19)) (i32.const 0x769abcdf))
+(assert_return (invoke "rem_u" (i32.const 0x769abcdf) (i32.const 0x8000000d)) (i32.const 0x769abcdf))
+(assert_return (invoke "rem_u" (i32.const 0xff00cc00) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "rem_u" (i32.const 0x80000) (i32.const 4)) (i32.const 0))
+
+(assert_return (invoke "and" (i32.const 1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "and" (i32.const 1) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "and" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "and" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "and" (i32.const -1) (i32.const -1)) (i32.const -1))
+(assert_return (invoke "and" (i32.const -1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "and" (i32.const 1) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "and" (i32.const 0x7fffffff) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "and" (i32.const 0x80000000) (i32.const -1)) (i32.const 0x80000000))
+(assert_return (invoke "and" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 0x80000000))
+(assert_return (invoke "and" (i32.const 0x7fffffff) (i32.const -1)) (i32.const 0x7fffffff))
+(assert_return (invoke "and" (i32.const 0x80000000) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "and" (i32.const 0x80000000) (i32.const 2)) (i32.const 0))
+(assert_return (invoke "and" (i32.const 0x80000000) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "and" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 0x7fffffff))
+(assert_return (invoke "and" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "and" (i32.const 5) (i32.const 2)) (i32.const 0))
+(assert_return (invoke "and" (i32.const -5) (i32.const 2)) (i32.const 2))
+(assert_return (invoke "and" (i32.const 5) (i32.const -2)) (i32.const 4))
+(assert_return (invoke "and" (i32.const -5) (i32.const -2)) (i32.const -6))
+(assert_return (invoke "and" (i32.const 7) (i32.const 3)) (i32.const 3))
+(assert_return (invoke "and" (i32.const -7) (i32.const 3)) (i32.const 1))
+(assert_return (invoke "and" (i32.const 7) (i32.const -3)) (i32.const 5))
+(assert_return (invoke "and" (i32.const -7) (i32.const -3)) (i32.const -7))
+(assert_return (invoke "and" (i32.const 11) (i32.const 5)) (i32.const 1))
+(assert_return (invoke "and" (i32.const 17) (i32.const 7)) (i32.const 1))
+(assert_return (invoke "and" (i32.const 1) (i32.const 31)) (i32.const 1))
+(assert_return (invoke "and" (i32.const 1) (i32.const 32)) (i32.const 0))
+(assert_return (invoke "and" (i32.const 1) (i32.const 33)) (i32.const 1))
+(assert_return (invoke "and" (i32.const -1) (i32.const 32)) (i32.const 32))
+(assert_return (invoke "and" (i32.const -1) (i32.const 33)) (i32.const 33))
+(assert_return (invoke "and" (i32.const 1) (i32.const 0x7fffffff)) (i32.const 1))
+(assert_return (invoke "and" (i32.const 1) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "and" (i32.const -1) (i32.const 0x80000000)) (i32.const 0x80000000))
+(assert_return (invoke "and" (i32.const 0x80000000) (i32.const 31)) (i32.const 0))
+(assert_return (invoke "and" (i32.const 0x1234567) (i32.const 0x76543210)) (i32.const 0))
+(assert_return (invoke "and" (i32.const 0x8ff00ff0) (i32.const 0x10001)) (i32.const 0))
+(assert_return (invoke "and" (i32.const 0x80000001) (i32.const 1000)) (i32.const 0))
+(assert_return (invoke "and" (i32.const 0xf0f0ffff) (i32.const 0xfffff0f0)) (i32.const 0xf0f0f0f0))
+(assert_return (invoke "and" (i32.const 0xabcd9876) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "and" (i32.const 0xfe00dc00) (i32.const 4)) (i32.const 0))
+(assert_return (invoke "and" (i32.const 0xb0c1d2e3) (i32.const 5)) (i32.const 1))
+(assert_return (invoke "and" (i32.const 0x8000) (i32.const 37)) (i32.const 0))
+(assert_return (invoke "and" (i32.const 0xb0c1d2e3) (i32.const 0xff05)) (i32.const 0xd201))
+(assert_return (invoke "and" (i32.const 0x769abcdf) (i32.const -19)) (i32.const 0x769abccd))
+(assert_return (invoke "and" (i32.const 0x769abcdf) (i32.const 0x8000000d)) (i32.const 13))
+(assert_return (invoke "and" (i32.const 0xff00cc00) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "and" (i32.const 0x80000) (i32.const 4)) (i32.const 0))
+
+(assert_return (invoke "or" (i32.const 1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "or" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "or" (i32.const 0) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "or" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "or" (i32.const -1) (i32.const -1)) (i32.const -1))
+(assert_return (invoke "or" (i32.const -1) (i32.const 1)) (i32.const -1))
+(assert_return (invoke "or" (i32.const 1) (i32.const -1)) (i32.const -1))
+(assert_return (invoke "or" (i32.const 0x7fffffff) (i32.const 1)) (i32.const 0x7fffffff))
+(assert_return (invoke "or" (i32.const 0x80000000) (i32.const -1)) (i32.const -1))
+(assert_return (invoke "or" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 0x80000000))
+(assert_return (invoke "or" (i32.const 0x7fffffff) (i32.const -1)) (i32.const -1))
+(assert_return (invoke "or" (i32.const 0x80000000) (i32.const 1)) (i32.const 0x80000001))
+(assert_return (invoke "or" (i32.const 0x80000000) (i32.const 2)) (i32.const 0x80000002))
+(assert_return (invoke "or" (i32.const 0x80000000) (i32.const 0)) (i32.const 0x80000000))
+(assert_return (invoke "or" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 0x7fffffff))
+(assert_return (invoke "or" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const -1))
+(assert_return (invoke "or" (i32.const 5) (i32.const 2)) (i32.const 7))
+(assert_return (invoke "or" (i32.const -5) (i32.const 2)) (i32.const -5))
+(assert_return (invoke "or" (i32.const 5) (i32.const -2)) (i32.const -1))
+(assert_return (invoke "or" (i32.const -5) (i32.const -2)) (i32.const -1))
+(assert_return (invoke "or" (i32.const 7) (i32.const 3)) (i32.const 7))
+(assert_return (invoke "or" (i32.const -7) (i32.const 3)) (i32.const -5))
+(assert_return (invoke "or" (i32.const 7) (i32.const -3)) (i32.const -1))
+(assert_return (invoke "or" (i32.const -7) (i32.const -3)) (i32.const -3))
+(assert_return (invoke "or" (i32.const 11) (i32.const 5)) (i32.const 15))
+(assert_return (invoke "or" (i32.const 17) (i32.const 7)) (i32.const 23))
+(assert_return (invoke "or" (i32.const 1) (i32.const 31)) (i32.const 31))
+(assert_return (invoke "or" (i32.const 1) (i32.const 32)) (i32.const 33))
+(assert_return (invoke "or" (i32.const 1) (i32.const 33)) (i32.const 33))
+(assert_return (invoke "or" (i32.const -1) (i32.const 32)) (i32.const -1))
+(assert_return (invoke "or" (i32.const -1) (i32.const 33)) (i32.const -1))
+(assert_return (invoke "or" (i32.const 1) (i32.const 0x7fffffff)) (i32.const 0x7fffffff))
+(assert_return (invoke "or" (i32.const 1) (i32.const 0x80000000)) (i32.const 0x80000001))
+(assert_return (invoke "or" (i32.const -1) (i32.const 0x80000000)) (i32.const -1))
+(assert_return (invoke "or" (i32.const 0x80000000) (i32.const 31)) (i32.const 0x8000001f))
+(assert_return (invoke "or" (i32.const 0x1234567) (i32.const 0x76543210)) (i32.const 0x77777777))
+(assert_return (invoke "or" (i32.const 0x8ff00ff0) (i32.const 0x10001)) (i32.const 0x8ff10ff1))
+(assert_return (invoke "or" (i32.const 0x80000001) (i32.const 1000)) (i32.const 0x800003e9))
+(assert_return (invoke "or" (i32.const 0xf0f0ffff) (i32.const 0xfffff0f0)) (i32.const -1))
+(assert_return (invoke "or" (i32.const 0xabcd9876) (i32.const 1)) (i32.const 0xabcd9877))
+(assert_return (invoke "or" (i32.const 0xfe00dc00) (i32.const 4)) (i32.const 0xfe00dc04))
+(assert_return (invoke "or" (i32.const 0xb0c1d2e3) (i32.const 5)) (i32.const 0xb0c1d2e7))
+(assert_return (invoke "or" (i32.const 0x8000) (i32.const 37)) (i32.const 0x8025))
+(assert_return (invoke "or" (i32.const 0xb0c1d2e3) (i32.const 0xff05)) (i32.const 0xb0c1ffe7))
+(assert_return (invoke "or" (i32.const 0x769abcdf) (i32.const -19)) (i32.const -1))
+(assert_return (invoke "or" (i32.const 0x769abcdf) (i32.const 0x8000000d)) (i32.const 0xf69abcdf))
+(assert_return (invoke "or" (i32.const 0xff00cc00) (i32.const 1)) (i32.const 0xff00cc01))
+(assert_return (invoke "or" (i32.const 0x80000) (i32.const 4)) (i32.const 0x80004))
+
+(assert_return (invoke "xor" (i32.const 1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "xor" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "xor" (i32.const 0) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "xor" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "xor" (i32.const -1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "xor" (i32.const -1) (i32.const 1)) (i32.const -2))
+(assert_return (invoke "xor" (i32.const 1) (i32.const -1)) (i32.const -2))
+(assert_return (invoke "xor" (i32.const 0x7fffffff) (i32.const 1)) (i32.const 0x7ffffffe))
+(assert_return (invoke "xor" (i32.const 0x80000000) (i32.const -1)) (i32.const 0x7fffffff))
+(assert_return (invoke "xor" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "xor" (i32.const 0x7fffffff) (i32.const -1)) (i32.const 0x80000000))
+(assert_return (invoke "xor" (i32.const 0x80000000) (i32.const 1)) (i32.const 0x80000001))
+(assert_return (invoke "xor" (i32.const 0x80000000) (i32.const 2)) (i32.const 0x80000002))
+(assert_return (invoke "xor" (i32.const 0x80000000) (i32.const 0)) (i32.const 0x80000000))
+(assert_return (invoke "xor" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "xor" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const -1))
+(assert_return (invoke "xor" (i32.const 5) (i32.const 2)) (i32.const 7))
+(assert_return (invoke "xor" (i32.const -5) (i32.const 2)) (i32.const -7))
+(assert_return (invoke "xor" (i32.const 5) (i32.const -2)) (i32.const -5))
+(assert_return (invoke "xor" (i32.const -5) (i32.const -2)) (i32.const 5))
+(assert_return (invoke "xor" (i32.const 7) (i32.const 3)) (i32.const 4))
+(assert_return (invoke "xor" (i32.const -7) (i32.const 3)) (i32.const -6))
+(assert_return (invoke "xor" (i32.const 7) (i32.const -3)) (i32.const -6))
+(assert_return (invoke "xor" (i32.const -7) (i32.const -3)) (i32.const 4))
+(assert_return (invoke "xor" (i32.const 11) (i32.const 5)) (i32.const 14))
+(assert_return (invoke "xor" (i32.const 17) (i32.const 7)) (i32.const 22))
+(assert_return (invoke "xor" (i32.const 1) (i32.const 31)) (i32.const 30))
+(assert_return (invoke "xor" (i32.const 1) (i32.const 32)) (i32.const 33))
+(assert_return (invoke "xor" (i32.const 1) (i32.const 33)) (i32.const 32))
+(assert_return (invoke "xor" (i32.const -1) (i32.const 32)) (i32.const -33))
+(assert_return (invoke "xor" (i32.const -1) (i32.const 33)) (i32.const -34))
+(assert_return (invoke "xor" (i32.const 1) (i32.const 0x7fffffff)) (i32.const 0x7ffffffe))
+(assert_return (invoke "xor" (i32.const 1) (i32.const 0x80000000)) (i32.const 0x80000001))
+(assert_return (invoke "xor" (i32.const -1) (i32.const 0x80000000)) (i32.const 0x7fffffff))
+(assert_return (invoke "xor" (i32.const 0x80000000) (i32.const 31)) (i32.const 0x8000001f))
+(assert_return (invoke "xor" (i32.const 0x1234567) (i32.const 0x76543210)) (i32.const 0x77777777))
+(assert_return (invoke "xor" (i32.const 0x8ff00ff0) (i32.const 0x10001)) (i32.const 0x8ff10ff1))
+(assert_return (invoke "xor" (i32.const 0x80000001) (i32.const 1000)) (i32.const 0x800003e9))
+(assert_return (invoke "xor" (i32.const 0xf0f0ffff) (i32.const 0xfffff0f0)) (i32.const 0xf0f0f0f))
+(assert_return (invoke "xor" (i32.const 0xabcd9876) (i32.const 1)) (i32.const 0xabcd9877))
+(assert_return (invoke "xor" (i32.const 0xfe00dc00) (i32.const 4)) (i32.const 0xfe00dc04))
+(assert_return (invoke "xor" (i32.const 0xb0c1d2e3) (i32.const 5)) (i32.const 0xb0c1d2e6))
+(assert_return (invoke "xor" (i32.const 0x8000) (i32.const 37)) (i32.const 0x8025))
+(assert_return (invoke "xor" (i32.const 0xb0c1d2e3) (i32.const 0xff05)) (i32.const 0xb0c12de6))
+(assert_return (invoke "xor" (i32.const 0x769abcdf) (i32.const -19)) (i32.const 0x89654332))
+(assert_return (invoke "xor" (i32.const 0x769abcdf) (i32.const 0x8000000d)) (i32.const 0xf69abcd2))
+(assert_return (invoke "xor" (i32.const 0xff00cc00) (i32.const 1)) (i32.const 0xff00cc01))
+(assert_return (invoke "xor" (i32.const 0x80000) (i32.const 4)) (i32.const 0x80004))
+
+(assert_return (invoke "shl" (i32.const 1) (i32.const 1)) (i32.const 2))
+(assert_return (invoke "shl" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "shl" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "shl" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "shl" (i32.const -1) (i32.const -1)) (i32.const 0x80000000))
+(assert_return (invoke "shl" (i32.const -1) (i32.const 1)) (i32.const -2))
+(assert_return (invoke "shl" (i32.const 1) (i32.const -1)) (i32.const 0x80000000))
+(assert_return (invoke "shl" (i32.const 0x7fffffff) (i32.const 1)) (i32.const -2))
+(assert_return (invoke "shl" (i32.const 0x80000000) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "shl" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 0x80000000))
+(assert_return (invoke "shl" (i32.const 0x7fffffff) (i32.const -1)) (i32.const 0x80000000))
+(assert_return (invoke "shl" (i32.const 0x80000000) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "shl" (i32.const 0x80000000) (i32.const 2)) (i32.const 0))
+(assert_return (invoke "shl" (i32.const 0x80000000) (i32.const 0)) (i32.const 0x80000000))
+(assert_return (invoke "shl" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 0x80000000))
+(assert_return (invoke "shl" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 0x7fffffff))
+(assert_return (invoke "shl" (i32.const 5) (i32.const 2)) (i32.const 20))
+(assert_return (invoke "shl" (i32.const -5) (i32.const 2)) (i32.const -20))
+(assert_return (invoke "shl" (i32.const 5) (i32.const -2)) (i32.const 0x40000000))
+(assert_return (invoke "shl" (i32.const -5) (i32.const -2)) (i32.const 0xc0000000))
+(assert_return (invoke "shl" (i32.const 7) (i32.const 3)) (i32.const 56))
+(assert_return (invoke "shl" (i32.const -7) (i32.const 3)) (i32.const -56))
+(assert_return (invoke "shl" (i32.const 7) (i32.const -3)) (i32.const 0xe0000000))
+(assert_return (invoke "shl" (i32.const -7) (i32.const -3)) (i32.const 0x20000000))
+(assert_return (invoke "shl" (i32.const 11) (i32.const 5)) (i32.const 352))
+(assert_return (invoke "shl" (i32.const 17) (i32.const 7)) (i32.const 0x880))
+(assert_return (invoke "shl" (i32.const 1) (i32.const 31)) (i32.const 0x80000000))
+(assert_return (invoke "shl" (i32.const 1) (i32.const 32)) (i32.const 1))
+(assert_return (invoke "shl" (i32.const 1) (i32.const 33)) (i32.const 2))
+(assert_return (invoke "shl" (i32.const -1) (i32.const 32)) (i32.const -1))
+(assert_return (invoke "shl" (i32.const -1) (i32.const 33)) (i32.const -2))
+(assert_return (invoke "shl" (i32.const 1) (i32.const 0x7fffffff)) (i32.const 0x80000000))
+(assert_return (invoke "shl" (i32.const 1) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "shl" (i32.const -1) (i32.const 0x80000000)) (i32.const -1))
+(assert_return (invoke "shl" (i32.const 0x80000000) (i32.const 31)) (i32.const 0))
+(assert_return (invoke "shl" (i32.const 0x1234567) (i32.const 0x76543210)) (i32.const 0x45670000))
+(assert_return (invoke "shl" (i32.const 0x8ff00ff0) (i32.const 0x10001)) (i32.const 0x1fe01fe0))
+(assert_return (invoke "shl" (i32.const 0x80000001) (i32.const 1000)) (i32.const 256))
+(assert_return (invoke "shl" (i32.const 0xf0f0ffff) (i32.const 0xfffff0f0)) (i32.const 0xffff0000))
+(assert_return (invoke "shl" (i32.const 0xabcd9876) (i32.const 1)) (i32.const 0x579b30ec))
+(assert_return (invoke "shl" (i32.const 0xfe00dc00) (i32.const 4)) (i32.const 0xe00dc000))
+(assert_return (invoke "shl" (i32.const 0xb0c1d2e3) (i32.const 5)) (i32.const 0x183a5c60))
+(assert_return (invoke "shl" (i32.const 0x8000) (i32.const 37)) (i32.const 0x100000))
+(assert_return (invoke "shl" (i32.const 0xb0c1d2e3) (i32.const 0xff05)) (i32.const 0x183a5c60))
+(assert_return (invoke "shl" (i32.const 0x769abcdf) (i32.const -19)) (i32.const 0x579be000))
+(assert_return (invoke "shl" (i32.const 0x769abcdf) (i32.const 0x8000000d)) (i32.const 0x579be000))
+(assert_return (invoke "shl" (i32.const 0xff00cc00) (i32.const 1)) (i32.const 0xfe019800))
+(assert_return (invoke "shl" (i32.const 0x80000) (i32.const 4)) (i32.const 0x800000))
+
+(assert_return (invoke "shr_s" (i32.const 1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "shr_s" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "shr_s" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "shr_s" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "shr_s" (i32.const -1) (i32.const -1)) (i32.const -1))
+(assert_return (invoke "shr_s" (i32.const -1) (i32.const 1)) (i32.const -1))
+(assert_return (invoke "shr_s" (i32.const 1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "shr_s" (i32.const 0x7fffffff) (i32.const 1)) (i32.const 0x3fffffff))
+(assert_return (invoke "shr_s" (i32.const 0x80000000) (i32.const -1)) (i32.const -1))
+(assert_return (invoke "shr_s" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 0x80000000))
+(assert_return (invoke "shr_s" (i32.const 0x7fffffff) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "shr_s" (i32.const 0x80000000) (i32.const 1)) (i32.const 0xc0000000))
+(assert_return (invoke "shr_s" (i32.const 0x80000000) (i32.const 2)) (i32.const 0xe0000000))
+(assert_return (invoke "shr_s" (i32.const 0x80000000) (i32.const 0)) (i32.const 0x80000000))
+(assert_return (invoke "shr_s" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "shr_s" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 0x7fffffff))
+(assert_return (invoke "shr_s" (i32.const 5) (i32.const 2)) (i32.const 1))
+(assert_return (invoke "shr_s" (i32.const -5) (i32.const 2)) (i32.const -2))
+(assert_return (invoke "shr_s" (i32.const 5) (i32.const -2)) (i32.const 0))
+(assert_return (invoke "shr_s" (i32.const -5) (i32.const -2)) (i32.const -1))
+(assert_return (invoke "shr_s" (i32.const 7) (i32.const 3)) (i32.const 0))
+(assert_return (invoke "shr_s" (i32.const -7) (i32.const 3)) (i32.const -1))
+(assert_return (invoke "shr_s" (i32.const 7) (i32.const -3)) (i32.const 0))
+(assert_return (invoke "shr_s" (i32.const -7) (i32.const -3)) (i32.const -1))
+(assert_return (invoke "shr_s" (i32.const 11) (i32.const 5)) (i32.const 0))
+(assert_return (invoke "shr_s" (i32.const 17) (i32.const 7)) (i32.const 0))
+(assert_return (invoke "shr_s" (i32.const 1) (i32.const 31)) (i32.const 0))
+(assert_return (invoke "shr_s" (i32.const 1) (i32.const 32)) (i32.const 1))
+(assert_return (invoke "shr_s" (i32.const 1) (i32.const 33)) (i32.const 0))
+(assert_return (invoke "shr_s" (i32.const -1) (i32.const 32)) (i32.const -1))
+(assert_return (invoke "shr_s" (i32.const -1) (i32.const 33)) (i32.const -1))
+(assert_return (invoke "shr_s" (i32.const 1) (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "shr_s" (i32.const 1) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "shr_s" (i32.const -1) (i32.const 0x80000000)) (i32.const -1))
+(assert_return (invoke "shr_s" (i32.const 0x80000000) (i32.const 31)) (i32.const -1))
+(assert_return (invoke "shr_s" (i32.const 0x1234567) (i32.const 0x76543210)) (i32.const 291))
+(assert_return (invoke "shr_s" (i32.const 0x8ff00ff0) (i32.const 0x10001)) (i32.const 0xc7f807f8))
+(assert_return (invoke "shr_s" (i32.const 0x80000001) (i32.const 1000)) (i32.const 0xff800000))
+(assert_return (invoke "shr_s" (i32.const 0xf0f0ffff) (i32.const 0xfffff0f0)) (i32.const 0xfffff0f0))
+(assert_return (invoke "shr_s" (i32.const 0xabcd9876) (i32.const 1)) (i32.const 0xd5e6cc3b))
+(assert_return (invoke "shr_s" (i32.const 0xfe00dc00) (i32.const 4)) (i32.const 0xffe00dc0))
+(assert_return (invoke "shr_s" (i32.const 0xb0c1d2e3) (i32.const 5)) (i32.const 0xfd860e97))
+(assert_return (invoke "shr_s" (i32.const 0x8000) (i32.const 37)) (i32.const 0x400))
+(assert_return (invoke "shr_s" (i32.const 0xb0c1d2e3) (i32.const 0xff05)) (i32.const 0xfd860e97))
+(assert_return (invoke "shr_s" (i32.const 0x769abcdf) (i32.const -19)) (i32.const 0x3b4d5))
+(assert_return (invoke "shr_s" (i32.const 0x769abcdf) (i32.const 0x8000000d)) (i32.const 0x3b4d5))
+(assert_return (invoke "shr_s" (i32.const 0xff00cc00) (i32.const 1)) (i32.const 0xff806600))
+(assert_return (invoke "shr_s" (i32.const 0x80000) (i32.const 4)) (i32.const 0x8000))
+
+(assert_return (invoke "shr_u" (i32.const 1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "shr_u" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "shr_u" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "shr_u" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "shr_u" (i32.const -1) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "shr_u" (i32.const -1) (i32.const 1)) (i32.const 0x7fffffff))
+(assert_return (invoke "shr_u" (i32.const 1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "shr_u" (i32.const 0x7fffffff) (i32.const 1)) (i32.const 0x3fffffff))
+(assert_return (invoke "shr_u" (i32.const 0x80000000) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "shr_u" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 0x80000000))
+(assert_return (invoke "shr_u" (i32.const 0x7fffffff) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "shr_u" (i32.const 0x80000000) (i32.const 1)) (i32.const 0x40000000))
+(assert_return (invoke "shr_u" (i32.const 0x80000000) (i32.const 2)) (i32.const 0x20000000))
+(assert_return (invoke "shr_u" (i32.const 0x80000000) (i32.const 0)) (i32.const 0x80000000))
+(assert_return (invoke "shr_u" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "shr_u" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 0x7fffffff))
+(assert_return (invoke "shr_u" (i32.const 5) (i32.const 2)) (i32.const 1))
+(assert_return (invoke "shr_u" (i32.const -5) (i32.const 2)) (i32.const 0x3ffffffe))
+(assert_return (invoke "shr_u" (i32.const 5) (i32.const -2)) (i32.const 0))
+(assert_return (invoke "shr_u" (i32.const -5) (i32.const -2)) (i32.const 3))
+(assert_return (invoke "shr_u" (i32.const 7) (i32.const 3)) (i32.const 0))
+(assert_return (invoke "shr_u" (i32.const -7) (i32.const 3)) (i32.const 0x1fffffff))
+(assert_return (invoke "shr_u" (i32.const 7) (i32.const -3)) (i32.const 0))
+(assert_return (invoke "shr_u" (i32.const -7) (i32.const -3)) (i32.const 7))
+(assert_return (invoke "shr_u" (i32.const 11) (i32.const 5)) (i32.const 0))
+(assert_return (invoke "shr_u" (i32.const 17) (i32.const 7)) (i32.const 0))
+(assert_return (invoke "shr_u" (i32.const 1) (i32.const 31)) (i32.const 0))
+(assert_return (invoke "shr_u" (i32.const 1) (i32.const 32)) (i32.const 1))
+(assert_return (invoke "shr_u" (i32.const 1) (i32.const 33)) (i32.const 0))
+(assert_return (invoke "shr_u" (i32.const -1) (i32.const 32)) (i32.const -1))
+(assert_return (invoke "shr_u" (i32.const -1) (i32.const 33)) (i32.const 0x7fffffff))
+(assert_return (invoke "shr_u" (i32.const 1) (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "shr_u" (i32.const 1) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "shr_u" (i32.const -1) (i32.const 0x80000000)) (i32.const -1))
+(assert_return (invoke "shr_u" (i32.const 0x80000000) (i32.const 31)) (i32.const 1))
+(assert_return (invoke "shr_u" (i32.const 0x1234567) (i32.const 0x76543210)) (i32.const 291))
+(assert_return (invoke "shr_u" (i32.const 0x8ff00ff0) (i32.const 0x10001)) (i32.const 0x47f807f8))
+(assert_return (invoke "shr_u" (i32.const 0x80000001) (i32.const 1000)) (i32.const 0x800000))
+(assert_return (invoke "shr_u" (i32.const 0xf0f0ffff) (i32.const 0xfffff0f0)) (i32.const 0xf0f0))
+(assert_return (invoke "shr_u" (i32.const 0xabcd9876) (i32.const 1)) (i32.const 0x55e6cc3b))
+(assert_return (invoke "shr_u" (i32.const 0xfe00dc00) (i32.const 4)) (i32.const 0xfe00dc0))
+(assert_return (invoke "shr_u" (i32.const 0xb0c1d2e3) (i32.const 5)) (i32.const 0x5860e97))
+(assert_return (invoke "shr_u" (i32.const 0x8000) (i32.const 37)) (i32.const 0x400))
+(assert_return (invoke "shr_u" (i32.const 0xb0c1d2e3) (i32.const 0xff05)) (i32.const 0x5860e97))
+(assert_return (invoke "shr_u" (i32.const 0x769abcdf) (i32.const -19)) (i32.const 0x3b4d5))
+(assert_return (invoke "shr_u" (i32.const 0x769abcdf) (i32.const 0x8000000d)) (i32.const 0x3b4d5))
+(assert_return (invoke "shr_u" (i32.const 0xff00cc00) (i32.const 1)) (i32.const 0x7f806600))
+(assert_return (invoke "shr_u" (i32.const 0x80000) (i32.const 4)) (i32.const 0x8000))
+
+(assert_return (invoke "rotl" (i32.const 1) (i32.const 1)) (i32.const 2))
+(assert_return (invoke "rotl" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "rotl" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "rotl" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "rotl" (i32.const -1) (i32.const -1)) (i32.const -1))
+(assert_return (invoke "rotl" (i32.const -1) (i32.const 1)) (i32.const -1))
+(assert_return (invoke "rotl" (i32.const 1) (i32.const -1)) (i32.const 0x80000000))
+(assert_return (invoke "rotl" (i32.const 0x7fffffff) (i32.const 1)) (i32.const -2))
+(assert_return (invoke "rotl" (i32.const 0x80000000) (i32.const -1)) (i32.const 0x40000000))
+(assert_return (invoke "rotl" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 0x80000000))
+(assert_return (invoke "rotl" (i32.const 0x7fffffff) (i32.const -1)) (i32.const 0xbfffffff))
+(assert_return (invoke "rotl" (i32.const 0x80000000) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "rotl" (i32.const 0x80000000) (i32.const 2)) (i32.const 2))
+(assert_return (invoke "rotl" (i32.const 0x80000000) (i32.const 0)) (i32.const 0x80000000))
+(assert_return (invoke "rotl" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 0xbfffffff))
+(assert_return (invoke "rotl" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 0x7fffffff))
+(assert_return (invoke "rotl" (i32.const 5) (i32.const 2)) (i32.const 20))
+(assert_return (invoke "rotl" (i32.const -5) (i32.const 2)) (i32.const -17))
+(assert_return (invoke "rotl" (i32.const 5) (i32.const -2)) (i32.const 0x40000001))
+(assert_return (invoke "rotl" (i32.const -5) (i32.const -2)) (i32.const -2))
+(assert_return (invoke "rotl" (i32.const 7) (i32.const 3)) (i32.const 56))
+(assert_return (invoke "rotl" (i32.const -7) (i32.const 3)) (i32.const -49))
+(assert_return (invoke "rotl" (i32.const 7) (i32.const -3)) (i32.const 0xe0000000))
+(assert_return (invoke "rotl" (i32.const -7) (i32.const -3)) (i32.const 0x3fffffff))
+(assert_return (invoke "rotl" (i32.const 11) (i32.const 5)) (i32.const 352))
+(assert_return (invoke "rotl" (i32.const 17) (i32.const 7)) (i32.const 0x880))
+(assert_return (invoke "rotl" (i32.const 1) (i32.const 31)) (i32.const 0x80000000))
+(assert_return (invoke "rotl" (i32.const 1) (i32.const 32)) (i32.const 1))
+(assert_return (invoke "rotl" (i32.const 1) (i32.const 33)) (i32.const 2))
+(assert_return (invoke "rotl" (i32.const -1) (i32.const 32)) (i32.const -1))
+(assert_return (invoke "rotl" (i32.const -1) (i32.const 33)) (i32.const -1))
+(assert_return (invoke "rotl" (i32.const 1) (i32.const 0x7fffffff)) (i32.const 0x80000000))
+(assert_return (invoke "rotl" (i32.const 1) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "rotl" (i32.const -1) (i32.const 0x80000000)) (i32.const -1))
+(assert_return (invoke "rotl" (i32.const 0x80000000) (i32.const 31)) (i32.const 0x40000000))
+(assert_return (invoke "rotl" (i32.const 0x1234567) (i32.const 0x76543210)) (i32.const 0x45670123))
+(assert_return (invoke "rotl" (i32.const 0x8ff00ff0) (i32.const 0x10001)) (i32.const 0x1fe01fe1))
+(assert_return (invoke "rotl" (i32.const 0x80000001) (i32.const 1000)) (i32.const 384))
+(assert_return (invoke "rotl" (i32.const 0xf0f0ffff) (i32.const 0xfffff0f0)) (i32.const 0xfffff0f0))
+(assert_return (invoke "rotl" (i32.const 0xabcd9876) (i32.const 1)) (i32.const 0x579b30ed))
+(assert_return (invoke "rotl" (i32.const 0xfe00dc00) (i32.const 4)) (i32.const 0xe00dc00f))
+(assert_return (invoke "rotl" (i32.const 0xb0c1d2e3) (i32.const 5)) (i32.const 0x183a5c76))
+(assert_return (invoke "rotl" (i32.const 0x8000) (i32.const 37)) (i32.const 0x100000))
+(assert_return (invoke "rotl" (i32.const 0xb0c1d2e3) (i32.const 0xff05)) (i32.const 0x183a5c76))
+(assert_return (invoke "rotl" (i32.const 0x769abcdf) (i32.const -19)) (i32.const 0x579beed3))
+(assert_return (invoke "rotl" (i32.const 0x769abcdf) (i32.const 0x8000000d)) (i32.const 0x579beed3))
+(assert_return (invoke "rotl" (i32.const 0xff00cc00) (i32.const 1)) (i32.const 0xfe019801))
+(assert_return (invoke "rotl" (i32.const 0x80000) (i32.const 4)) (i32.const 0x800000))
+
+(assert_return (invoke "rotr" (i32.const 1) (i32.const 1)) (i32.const 0x80000000))
+(assert_return (invoke "rotr" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "rotr" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "rotr" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "rotr" (i32.const -1) (i32.const -1)) (i32.const -1))
+(assert_return (invoke "rotr" (i32.const -1) (i32.const 1)) (i32.const -1))
+(assert_return (invoke "rotr" (i32.const 1) (i32.const -1)) (i32.const 2))
+(assert_return (invoke "rotr" (i32.const 0x7fffffff) (i32.const 1)) (i32.const 0xbfffffff))
+(assert_return (invoke "rotr" (i32.const 0x80000000) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "rotr" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 0x80000000))
+(assert_return (invoke "rotr" (i32.const 0x7fffffff) (i32.const -1)) (i32.const -2))
+(assert_return (invoke "rotr" (i32.const 0x80000000) (i32.const 1)) (i32.const 0x40000000))
+(assert_return (invoke "rotr" (i32.const 0x80000000) (i32.const 2)) (i32.const 0x20000000))
+(assert_return (invoke "rotr" (i32.const 0x80000000) (i32.const 0)) (i32.const 0x80000000))
+(assert_return (invoke "rotr" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const -2))
+(assert_return (invoke "rotr" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 0x7fffffff))
+(assert_return (invoke "rotr" (i32.const 5) (i32.const 2)) (i32.const 0x40000001))
+(assert_return (invoke "rotr" (i32.const -5) (i32.const 2)) (i32.const -2))
+(assert_return (invoke "rotr" (i32.const 5) (i32.const -2)) (i32.const 20))
+(assert_return (invoke "rotr" (i32.const -5) (i32.const -2)) (i32.const -17))
+(assert_return (invoke "rotr" (i32.const 7) (i32.const 3)) (i32.const 0xe0000000))
+(assert_return (invoke "rotr" (i32.const -7) (i32.const 3)) (i32.const 0x3fffffff))
+(assert_return (invoke "rotr" (i32.const 7) (i32.const -3)) (i32.const 56))
+(assert_return (invoke "rotr" (i32.const -7) (i32.const -3)) (i32.const -49))
+(assert_return (invoke "rotr" (i32.const 11) (i32.const 5)) (i32.const 0x58000000))
+(assert_return (invoke "rotr" (i32.const 17) (i32.const 7)) (i32.const 0x22000000))
+(assert_return (invoke "rotr" (i32.const 1) (i32.const 31)) (i32.const 2))
+(assert_return (invoke "rotr" (i32.const 1) (i32.const 32)) (i32.const 1))
+(assert_return (invoke "rotr" (i32.const 1) (i32.const 33)) (i32.const 0x80000000))
+(assert_return (invoke "rotr" (i32.const -1) (i32.const 32)) (i32.const -1))
+(assert_return (invoke "rotr" (i32.const -1) (i32.const 33)) (i32.const -1))
+(assert_return (invoke "rotr" (i32.const 1) (i32.const 0x7fffffff)) (i32.const 2))
+(assert_return (invoke "rotr" (i32.const 1) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "rotr" (i32.const -1) (i32.const 0x80000000)) (i32.const -1))
+(assert_return (invoke "rotr" (i32.const 0x80000000) (i32.const 31)) (i32.const 1))
+(assert_return (invoke "rotr" (i32.const 0x1234567) (i32.const 0x76543210)) (i32.const 0x45670123))
+(assert_return (invoke "rotr" (i32.const 0x8ff00ff0) (i32.const 0x10001)) (i32.const 0x47f807f8))
+(assert_return (invoke "rotr" (i32.const 0x80000001) (i32.const 1000)) (i32.const 0x1800000))
+(assert_return (invoke "rotr" (i32.const 0xf0f0ffff) (i32.const 0xfffff0f0)) (i32.const 0xfffff0f0))
+(assert_return (invoke "rotr" (i32.const 0xabcd9876) (i32.const 1)) (i32.const 0x55e6cc3b))
+(assert_return (invoke "rotr" (i32.const 0xfe00dc00) (i32.const 4)) (i32.const 0xfe00dc0))
+(assert_return (invoke "rotr" (i32.const 0xb0c1d2e3) (i32.const 5)) (i32.const 0x1d860e97))
+(assert_return (invoke "rotr" (i32.const 0x8000) (i32.const 37)) (i32.const 0x400))
+(assert_return (invoke "rotr" (i32.const 0xb0c1d2e3) (i32.const 0xff05)) (i32.const 0x1d860e97))
+(assert_return (invoke "rotr" (i32.const 0x769abcdf) (i32.const -19)) (i32.const 0xe6fbb4d5))
+(assert_return (invoke "rotr" (i32.const 0x769abcdf) (i32.const 0x8000000d)) (i32.const 0xe6fbb4d5))
+(assert_return (invoke "rotr" (i32.const 0xff00cc00) (i32.const 1)) (i32.const 0x7f806600))
+(assert_return (invoke "rotr" (i32.const 0x80000) (i32.const 4)) (i32.const 0x8000))
+
+(assert_return (invoke "clz" (i32.const 0)) (i32.const 32))
+(assert_return (invoke "clz" (i32.const 1)) (i32.const 31))
+(assert_return (invoke "clz" (i32.const 2)) (i32.const 30))
+(assert_return (invoke "clz" (i32.const -1)) (i32.const 0))
+(assert_return (invoke "clz" (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "clz" (i32.const 0x7fffffff)) (i32.const 1))
+(assert_return (invoke "clz" (i32.const 0x8000)) (i32.const 16))
+(assert_return (invoke "clz" (i32.const 255)) (i32.const 24))
+(assert_return (invoke "clz" (i32.const 0x10000)) (i32.const 15))
+(assert_return (invoke "clz" (i32.const 0xaaaaaaaa)) (i32.const 0))
+(assert_return (invoke "clz" (i32.const 0x55555555)) (i32.const 1))
+(assert_return (invoke "clz" (i32.const 0xdeadbeef)) (i32.const 0))
+(assert_return (invoke "clz" (i32.const 127)) (i32.const 25))
+(assert_return (invoke "clz" (i32.const 128)) (i32.const 24))
+(assert_return (invoke "clz" (i32.const 0x1234500)) (i32.const 7))
+(assert_return (invoke "clz" (i32.const 0xfedcba80)) (i32.const 0))
+(assert_return (invoke "clz" (i32.const 0x7fff)) (i32.const 17))
+(assert_return (invoke "clz" (i32.const 0x8000)) (i32.const 16))
+(assert_return (invoke "clz" (i32.const 0xffff)) (i32.const 16))
+(assert_return (invoke "clz" (i32.const 0x1230000)) (i32.const 7))
+(assert_return (invoke "clz" (i32.const 0xfedc8000)) (i32.const 0))
+
+(assert_return (invoke "ctz" (i32.const 0)) (i32.const 32))
+(assert_return (invoke "ctz" (i32.const 1)) (i32.const 0))
+(assert_return (invoke "ctz" (i32.const 2)) (i32.const 1))
+(assert_return (invoke "ctz" (i32.const -1)) (i32.const 0))
+(assert_return (invoke "ctz" (i32.const 0x80000000)) (i32.const 31))
+(assert_return (invoke "ctz" (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "ctz" (i32.const 0x8000)) (i32.const 15))
+(assert_return (invoke "ctz" (i32.const 255)) (i32.const 0))
+(assert_return (invoke "ctz" (i32.const 0x10000)) (i32.const 16))
+(assert_return (invoke "ctz" (i32.const 0xaaaaaaaa)) (i32.const 1))
+(assert_return (invoke "ctz" (i32.const 0x55555555)) (i32.const 0))
+(assert_return (invoke "ctz" (i32.const 0xdeadbeef)) (i32.const 0))
+(assert_return (invoke "ctz" (i32.const 127)) (i32.const 0))
+(assert_return (invoke "ctz" (i32.const 128)) (i32.const 7))
+(assert_return (invoke "ctz" (i32.const 0x1234500)) (i32.const 8))
+(assert_return (invoke "ctz" (i32.const 0xfedcba80)) (i32.const 7))
+(assert_return (invoke "ctz" (i32.const 0x7fff)) (i32.const 0))
+(assert_return (invoke "ctz" (i32.const 0x8000)) (i32.const 15))
+(assert_return (invoke "ctz" (i32.const 0xffff)) (i32.const 0))
+(assert_return (invoke "ctz" (i32.const 0x1230000)) (i32.const 16))
+(assert_return (invoke "ctz" (i32.const 0xfedc8000)) (i32.const 15))
+
+(assert_return (invoke "popcnt" (i32.const 0)) (i32.const 0))
+(assert_return (invoke "popcnt" (i32.const 1)) (i32.const 1))
+(assert_return (invoke "popcnt" (i32.const 2)) (i32.const 1))
+(assert_return (invoke "popcnt" (i32.const -1)) (i32.const 32))
+(assert_return (invoke "popcnt" (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "popcnt" (i32.const 0x7fffffff)) (i32.const 31))
+(assert_return (invoke "popcnt" (i32.const 0x8000)) (i32.const 1))
+(assert_return (invoke "popcnt" (i32.const 255)) (i32.const 8))
+(assert_return (invoke "popcnt" (i32.const 0x10000)) (i32.const 1))
+(assert_return (invoke "popcnt" (i32.const 0xaaaaaaaa)) (i32.const 16))
+(assert_return (invoke "popcnt" (i32.const 0x55555555)) (i32.const 16))
+(assert_return (invoke "popcnt" (i32.const 0xdeadbeef)) (i32.const 24))
+(assert_return (invoke "popcnt" (i32.const 127)) (i32.const 7))
+(assert_return (invoke "popcnt" (i32.const 128)) (i32.const 1))
+(assert_return (invoke "popcnt" (i32.const 0x1234500)) (i32.const 7))
+(assert_return (invoke "popcnt" (i32.const 0xfedcba80)) (i32.const 18))
+(assert_return (invoke "popcnt" (i32.const 0x7fff)) (i32.const 15))
+(assert_return (invoke "popcnt" (i32.const 0x8000)) (i32.const 1))
+(assert_return (invoke "popcnt" (i32.const 0xffff)) (i32.const 16))
+(assert_return (invoke "popcnt" (i32.const 0x1230000)) (i32.const 4))
+(assert_return (invoke "popcnt" (i32.const 0xfedc8000)) (i32.const 13))
+
+(assert_return (invoke "eqz" (i32.const 0)) (i32.const 1))
+(assert_return (invoke "eqz" (i32.const 1)) (i32.const 0))
+(assert_return (invoke "eqz" (i32.const 2)) (i32.const 0))
+(assert_return (invoke "eqz" (i32.const -1)) (i32.const 0))
+(assert_return (invoke "eqz" (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "eqz" (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "eqz" (i32.const 0x8000)) (i32.const 0))
+(assert_return (invoke "eqz" (i32.const 255)) (i32.const 0))
+(assert_return (invoke "eqz" (i32.const 0x10000)) (i32.const 0))
+(assert_return (invoke "eqz" (i32.const 0xaaaaaaaa)) (i32.const 0))
+(assert_return (invoke "eqz" (i32.const 0x55555555)) (i32.const 0))
+(assert_return (invoke "eqz" (i32.const 0xdeadbeef)) (i32.const 0))
+(assert_return (invoke "eqz" (i32.const 127)) (i32.const 0))
+(assert_return (invoke "eqz" (i32.const 128)) (i32.const 0))
+(assert_return (invoke "eqz" (i32.const 0x1234500)) (i32.const 0))
+(assert_return (invoke "eqz" (i32.const 0xfedcba80)) (i32.const 0))
+(assert_return (invoke "eqz" (i32.const 0x7fff)) (i32.const 0))
+(assert_return (invoke "eqz" (i32.const 0x8000)) (i32.const 0))
+(assert_return (invoke "eqz" (i32.const 0xffff)) (i32.const 0))
+(assert_return (invoke "eqz" (i32.const 0x1230000)) (i32.const 0))
+(assert_return (invoke "eqz" (i32.const 0xfedc8000)) (i32.const 0))
+
+(assert_return (invoke "extend8_s" (i32.const 0)) (i32.const 0))
+(assert_return (invoke "extend8_s" (i32.const 1)) (i32.const 1))
+(assert_return (invoke "extend8_s" (i32.const 2)) (i32.const 2))
+(assert_return (invoke "extend8_s" (i32.const -1)) (i32.const -1))
+(assert_return (invoke "extend8_s" (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "extend8_s" (i32.const 0x7fffffff)) (i32.const -1))
+(assert_return (invoke "extend8_s" (i32.const 0x8000)) (i32.const 0))
+(assert_return (invoke "extend8_s" (i32.const 255)) (i32.const -1))
+(assert_return (invoke "extend8_s" (i32.const 0x10000)) (i32.const 0))
+(assert_return (invoke "extend8_s" (i32.const 0xaaaaaaaa)) (i32.const -86))
+(assert_return (invoke "extend8_s" (i32.const 0x55555555)) (i32.const 85))
+(assert_return (invoke "extend8_s" (i32.const 0xdeadbeef)) (i32.const -17))
+(assert_return (invoke "extend8_s" (i32.const 127)) (i32.const 127))
+(assert_return (invoke "extend8_s" (i32.const 128)) (i32.const -128))
+(assert_return (invoke "extend8_s" (i32.const 0x1234500)) (i32.const 0))
+(assert_return (invoke "extend8_s" (i32.const 0xfedcba80)) (i32.const -128))
+(assert_return (invoke "extend8_s" (i32.const 0x7fff)) (i32.const -1))
+(assert_return (invoke "extend8_s" (i32.const 0x8000)) (i32.const 0))
+(assert_return (invoke "extend8_s" (i32.const 0xffff)) (i32.const -1))
+(assert_return (invoke "extend8_s" (i32.const 0x1230000)) (i32.const 0))
+(assert_return (invoke "extend8_s" (i32.const 0xfedc8000)) (i32.const 0))
+
+(assert_return (invoke "extend16_s" (i32.const 0)) (i32.const 0))
+(assert_return (invoke "extend16_s" (i32.const 1)) (i32.const 1))
+(assert_return (invoke "extend16_s" (i32.const 2)) (i32.const 2))
+(assert_return (invoke "extend16_s" (i32.const -1)) (i32.const -1))
+(assert_return (invoke "extend16_s" (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "extend16_s" (i32.const 0x7fffffff)) (i32.const -1))
+(assert_return (invoke "extend16_s" (i32.const 0x8000)) (i32.const 0xffff8000))
+(assert_return (invoke "extend16_s" (i32.const 255)) (i32.const 255))
+(assert_return (invoke "extend16_s" (i32.const 0x10000)) (i32.const 0))
+(assert_return (invoke "extend16_s" (i32.const 0xaaaaaaaa)) (i32.const 0xffffaaaa))
+(assert_return (invoke "extend16_s" (i32.const 0x55555555)) (i32.const 0x5555))
+(assert_return (invoke "extend16_s" (i32.const 0xdeadbeef)) (i32.const 0xffffbeef))
+(assert_return (invoke "extend16_s" (i32.const 127)) (i32.const 127))
+(assert_return (invoke "extend16_s" (i32.const 128)) (i32.const 128))
+(assert_return (invoke "extend16_s" (i32.const 0x1234500)) (i32.const 0x4500))
+(assert_return (invoke "extend16_s" (i32.const 0xfedcba80)) (i32.const 0xffffba80))
+(assert_return (invoke "extend16_s" (i32.const 0x7fff)) (i32.const 0x7fff))
+(assert_return (invoke "extend16_s" (i32.const 0x8000)) (i32.const 0xffff8000))
+(assert_return (invoke "extend16_s" (i32.const 0xffff)) (i32.const -1))
+(assert_return (invoke "extend16_s" (i32.const 0x1230000)) (i32.const 0))
+(assert_return (invoke "extend16_s" (i32.const 0xfedc8000)) (i32.const 0xffff8000))
+
+(assert_return (invoke "eq" (i32.const 0) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "eq" (i32.const 1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "eq" (i32.const -1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "eq" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "eq" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 1))
+(assert_return (invoke "eq" (i32.const -1) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "eq" (i32.const 1) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "eq" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "eq" (i32.const 0x80000000) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "eq" (i32.const 0) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "eq" (i32.const 0x80000000) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "eq" (i32.const -1) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "eq" (i32.const 0x80000000) (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "eq" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 0))
+
+(assert_return (invoke "ne" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "ne" (i32.const 1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "ne" (i32.const -1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "ne" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "ne" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "ne" (i32.const -1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "ne" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "ne" (i32.const 0) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "ne" (i32.const 0x80000000) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "ne" (i32.const 0) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "ne" (i32.const 0x80000000) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "ne" (i32.const -1) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "ne" (i32.const 0x80000000) (i32.const 0x7fffffff)) (i32.const 1))
+(assert_return (invoke "ne" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 1))
+
+(assert_return (invoke "lt_s" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "lt_s" (i32.const 1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "lt_s" (i32.const -1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "lt_s" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "lt_s" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "lt_s" (i32.const -1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "lt_s" (i32.const 1) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "lt_s" (i32.const 0) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "lt_s" (i32.const 0x80000000) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "lt_s" (i32.const 0) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "lt_s" (i32.const 0x80000000) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "lt_s" (i32.const -1) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "lt_s" (i32.const 0x80000000) (i32.const 0x7fffffff)) (i32.const 1))
+(assert_return (invoke "lt_s" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 0))
+
+(assert_return (invoke "lt_u" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "lt_u" (i32.const 1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "lt_u" (i32.const -1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "lt_u" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "lt_u" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "lt_u" (i32.const -1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "lt_u" (i32.const 1) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "lt_u" (i32.const 0) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "lt_u" (i32.const 0x80000000) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "lt_u" (i32.const 0) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "lt_u" (i32.const 0x80000000) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "lt_u" (i32.const -1) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "lt_u" (i32.const 0x80000000) (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "lt_u" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 1))
+
+(assert_return (invoke "le_s" (i32.const 0) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "le_s" (i32.const 1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "le_s" (i32.const -1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "le_s" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "le_s" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 1))
+(assert_return (invoke "le_s" (i32.const -1) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "le_s" (i32.const 1) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "le_s" (i32.const 0) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "le_s" (i32.const 0x80000000) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "le_s" (i32.const 0) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "le_s" (i32.const 0x80000000) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "le_s" (i32.const -1) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "le_s" (i32.const 0x80000000) (i32.const 0x7fffffff)) (i32.const 1))
+(assert_return (invoke "le_s" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 0))
+
+(assert_return (invoke "le_u" (i32.const 0) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "le_u" (i32.const 1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "le_u" (i32.const -1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "le_u" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "le_u" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 1))
+(assert_return (invoke "le_u" (i32.const -1) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "le_u" (i32.const 1) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "le_u" (i32.const 0) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "le_u" (i32.const 0x80000000) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "le_u" (i32.const 0) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "le_u" (i32.const 0x80000000) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "le_u" (i32.const -1) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "le_u" (i32.const 0x80000000) (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "le_u" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 1))
+
+(assert_return (invoke "gt_s" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "gt_s" (i32.const 1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "gt_s" (i32.const -1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "gt_s" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "gt_s" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "gt_s" (i32.const -1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "gt_s" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "gt_s" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "gt_s" (i32.const 0x80000000) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "gt_s" (i32.const 0) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "gt_s" (i32.const 0x80000000) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "gt_s" (i32.const -1) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "gt_s" (i32.const 0x80000000) (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "gt_s" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 1))
+
+(assert_return (invoke "gt_u" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "gt_u" (i32.const 1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "gt_u" (i32.const -1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "gt_u" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "gt_u" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "gt_u" (i32.const -1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "gt_u" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "gt_u" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "gt_u" (i32.const 0x80000000) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "gt_u" (i32.const 0) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "gt_u" (i32.const 0x80000000) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "gt_u" (i32.const -1) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "gt_u" (i32.const 0x80000000) (i32.const 0x7fffffff)) (i32.const 1))
+(assert_return (invoke "gt_u" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 0))
+
+(assert_return (invoke "ge_s" (i32.const 0) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "ge_s" (i32.const 1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "ge_s" (i32.const -1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "ge_s" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "ge_s" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 1))
+(assert_return (invoke "ge_s" (i32.const -1) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "ge_s" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "ge_s" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "ge_s" (i32.const 0x80000000) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "ge_s" (i32.const 0) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "ge_s" (i32.const 0x80000000) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "ge_s" (i32.const -1) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "ge_s" (i32.const 0x80000000) (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "ge_s" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 1))
+
+(assert_return (invoke "ge_u" (i32.const 0) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "ge_u" (i32.const 1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "ge_u" (i32.const -1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "ge_u" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "ge_u" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 1))
+(assert_return (invoke "ge_u" (i32.const -1) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "ge_u" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "ge_u" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "ge_u" (i32.const 0x80000000) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "ge_u" (i32.const 0) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "ge_u" (i32.const 0x80000000) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "ge_u" (i32.const -1) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "ge_u" (i32.const 0x80000000) (i32.const 0x7fffffff)) (i32.const 1))
+(assert_return (invoke "ge_u" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 0))
+
+;; Type check
+
+(assert_invalid (module (func (result i32) (i32.add (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.sub (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.mul (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.div_s (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.div_u (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.rem_s (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.rem_u (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.and (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.or (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.xor (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.shl (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.shr_s (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.shr_u (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.rotl (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.rotr (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.clz (i64.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.ctz (i64.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.popcnt (i64.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.eqz (i64.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.extend8_s (i64.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.extend16_s (i64.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.eq (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.ne (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.lt_s (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.lt_u (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.le_s (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.le_u (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.gt_s (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.gt_u (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.ge_s (i64.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i32.ge_u (i64.const 0) (f32.const 0)))) "type mismatch")
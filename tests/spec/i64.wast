@@ -0,0 +1,1013 @@
+;; i64 operations
+
+;; A subset of i64.wast from the WebAssembly spec testsuite
+;; (https://github.com/WebAssembly/testsuite), limited to the directives mini-wasm supports.
+
+(module
+  (func (export "add") (param $x i64) (param $y i64) (result i64) (i64.add (local.get $x) (local.get $y)))
+  (func (export "sub") (param $x i64) (param $y i64) (result i64) (i64.sub (local.get $x) (local.get $y)))
+  (func (export "mul") (param $x i64) (param $y i64) (result i64) (i64.mul (local.get $x) (local.get $y)))
+  (func (export "div_s") (param $x i64) (param $y i64) (result i64) (i64.div_s (local.get $x) (local.get $y)))
+  (func (export "div_u") (param $x i64) (param $y i64) (result i64) (i64.div_u (local.get $x) (local.get $y)))
+  (func (export "rem_s") (param $x i64) (param $y i64) (result i64) (i64.rem_s (local.get $x) (local.get $y)))
+  (func (export "rem_u") (param $x i64) (param $y i64) (result i64) (i64.rem_u (local.get $x) (local.get $y)))
+  (func (export "and") (param $x i64) (param $y i64) (result i64) (i64.and (local.get $x) (local.get $y)))
+  (func (export "or") (param $x i64) (param $y i64) (result i64) (i64.or (local.get $x) (local.get $y)))
+  (func (export "xor") (param $x i64) (param $y i64) (result i64) (i64.xor (local.get $x) (local.get $y)))
+  (func (export "shl") (param $x i64) (param $y i64) (result i64) (i64.shl (local.get $x) (local.get $y)))
+  (func (export "shr_s") (param $x i64) (param $y i64) (result i64) (i64.shr_s (local.get $x) (local.get $y)))
+  (func (export "shr_u") (param $x i64) (param $y i64) (result i64) (i64.shr_u (local.get $x) (local.get $y)))
+  (func (export "rotl") (param $x i64) (param $y i64) (result i64) (i64.rotl (local.get $x) (local.get $y)))
+  (func (export "rotr") (param $x i64) (param $y i64) (result i64) (i64.rotr (local.get $x) (local.get $y)))
+  (func (export "clz") (param $x i64) (result i64) (i64.clz (local.get $x)))
+  (func (export "ctz") (param $x i64) (result i64) (i64.ctz (local.get $x)))
+  (func (export "popcnt") (param $x i64) (result i64) (i64.popcnt (local.get $x)))
+  (func (export "eqz") (param $x i64) (result i32) (i64.eqz (local.get $x)))
+  (func (export "eq") (param $x i64) (param $y i64) (result i32) (i64.eq (local.get $x) (local.get $y)))
+  (func (export "ne") (param $x i64) (param $y i64) (result i32) (i64.ne (local.get $x) (local.get $y)))
+  (func (export "lt_s") (param $x i64) (param $y i64) (result i32) (i64.lt_s (local.get $x) (local.get $y)))
+  (func (export "lt_u") (param $x i64) (param $y i64) (result i32) (i64.lt_u (local.get $x) (local.get $y)))
+  (func (export "le_s") (param $x i64) (param $y i64) (result i32) (i64.le_s (local.get $x) (local.get $y)))
+  (func (export "le_u") (param $x i64) (param $y i64) (result i32) (i64.le_u (local.get $x) (local.get $y)))
+  (func (export "gt_s") (param $x i64) (param $y i64) (result i32) (i64.gt_s (local.get $x) (local.get $y)))
+  (func (export "gt_u") (param $x i64) (param $y i64) (result i32) (i64.gt_u (local.get $x) (local.get $y)))
+  (func (export "ge_s") (param $x i64) (param $y i64) (result i32) (i64.ge_s (local.get $x) (local.get $y)))
+  (func (export "ge_u") (param $x i64) (param $y i64) (result i32) (i64.ge_u (local.get $x) (local.get $y)))
+)
+
+(assert_return (invoke "add" (i64.const 1) (i64.const 1)) (i64.const 2))
+(assert_return (invoke "add" (i64.const 1) (i64.const 0)) (i64.const 1))
+(assert_return (invoke "add" (i64.const 0) (i64.const 1)) (i64.const 1))
+(assert_return (invoke "add" (i64.const 0) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "add" (i64.const -1) (i64.const -1)) (i64.const -2))
+(assert_return (invoke "add" (i64.const -1) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "add" (i64.const 1) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "add" (i64.const 0x7fffffffffffffff) (i64.const 1)) (i64.const 0x8000000000000000))
+(assert_return (invoke "add" (i64.const 0x8000000000000000) (i64.const -1)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "add" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i64.const 0))
+(assert_return (invoke "add" (i64.const 0x7fffffffffffffff) (i64.const -1)) (i64.const 0x7ffffffffffffffe))
+(assert_return (invoke "add" (i64.const 0x8000000000000000) (i64.const 1)) (i64.const 0x8000000000000001))
+(assert_return (invoke "add" (i64.const 0x8000000000000000) (i64.const 2)) (i64.const 0x8000000000000002))
+(assert_return (invoke "add" (i64.const 0x8000000000000000) (i64.const 0)) (i64.const 0x8000000000000000))
+(assert_return (invoke "add" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i64.const -2))
+(assert_return (invoke "add" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i64.const -1))
+(assert_return (invoke "add" (i64.const 5) (i64.const 2)) (i64.const 7))
+(assert_return (invoke "add" (i64.const -5) (i64.const 2)) (i64.const -3))
+(assert_return (invoke "add" (i64.const 5) (i64.const -2)) (i64.const 3))
+(assert_return (invoke "add" (i64.const -5) (i64.const -2)) (i64.const -7))
+(assert_return (invoke "add" (i64.const 7) (i64.const 3)) (i64.const 10))
+(assert_return (invoke "add" (i64.const -7) (i64.const 3)) (i64.const -4))
+(assert_return (invoke "add" (i64.const 7) (i64.const -3)) (i64.const 4))
+(assert_return (invoke "add" (i64.const -7) (i64.const -3)) (i64.const -10))
+(assert_return (invoke "add" (i64.const 11) (i64.const 5)) (i64.const 16))
+(assert_return (invoke "add" (i64.const 17) (i64.const 7)) (i64.const 24))
+(assert_return (invoke "add" (i64.const 1) (i64.const 63)) (i64.const 64))
+(assert_return (invoke "add" (i64.const 1) (i64.const 64)) (i64.const 65))
+(assert_return (invoke "add" (i64.const 1) (i64.const 65)) (i64.const 66))
+(assert_return (invoke "add" (i64.const -1) (i64.const 64)) (i64.const 63))
+(assert_return (invoke "add" (i64.const -1) (i64.const 65)) (i64.const 64))
+(assert_return (invoke "add" (i64.const 1) (i64.const 0x7fffffffffffffff)) (i64.const 0x8000000000000000))
+(assert_return (invoke "add" (i64.const 1) (i64.const 0x8000000000000000)) (i64.const 0x8000000000000001))
+(assert_return (invoke "add" (i64.const -1) (i64.const 0x8000000000000000)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "add" (i64.const 0x8000000000000000) (i64.const 63)) (i64.const 0x800000000000003f))
+(assert_return (invoke "add" (i64.const 0x123456789abcdef) (i64.const 0xfedcba9876543210)) (i64.const -1))
+(assert_return (invoke "add" (i64.const 0x8ff00ff00ff00ff0) (i64.const 0x100000001)) (i64.const 0x8ff00ff10ff00ff1))
+(assert_return (invoke "add" (i64.const 0x8000000000000001) (i64.const 1000)) (i64.const 0x80000000000003e9))
+(assert_return (invoke "add" (i64.const 0xf0f0ffff0f0fffff) (i64.const 0xfffff0f0fffff0f0)) (i64.const 0xf0f0f0f00f0ff0ef))
+(assert_return (invoke "add" (i64.const 0xabcd987602468ace) (i64.const 1)) (i64.const 0xabcd987602468acf))
+(assert_return (invoke "add" (i64.const 0xfe000000dc000000) (i64.const 4)) (i64.const 0xfe000000dc000004))
+(assert_return (invoke "add" (i64.const 0xabcd1234ef567809) (i64.const 53)) (i64.const 0xabcd1234ef56783e))
+(assert_return (invoke "add" (i64.const 0xabd1234ef567809c) (i64.const 63)) (i64.const 0xabd1234ef56780db))
+(assert_return (invoke "add" (i64.const 0xabcd1234ef567809) (i64.const 245)) (i64.const 0xabcd1234ef5678fe))
+(assert_return (invoke "add" (i64.const 0xabcd7294ef567809) (i64.const -19)) (i64.const 0xabcd7294ef5677f6))
+(assert_return (invoke "add" (i64.const 0xabcd1234ef567809) (i64.const 0x8000000000000035)) (i64.const 0x2bcd1234ef56783e))
+(assert_return (invoke "add" (i64.const 0xfe00dc00) (i64.const 4)) (i64.const 0xfe00dc04))
+(assert_return (invoke "add" (i64.const 0x8000) (i64.const 37)) (i64.const 0x8025))
+
+(assert_return (invoke "sub" (i64.const 1) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "sub" (i64.const 1) (i64.const 0)) (i64.const 1))
+(assert_return (invoke "sub" (i64.const 0) (i64.const 1)) (i64.const -1))
+(assert_return (invoke "sub" (i64.const 0) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "sub" (i64.const -1) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "sub" (i64.const -1) (i64.const 1)) (i64.const -2))
+(assert_return (invoke "sub" (i64.const 1) (i64.const -1)) (i64.const 2))
+(assert_return (invoke "sub" (i64.const 0x7fffffffffffffff) (i64.const 1)) (i64.const 0x7ffffffffffffffe))
+(assert_return (invoke "sub" (i64.const 0x8000000000000000) (i64.const -1)) (i64.const 0x8000000000000001))
+(assert_return (invoke "sub" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i64.const 0))
+(assert_return (invoke "sub" (i64.const 0x7fffffffffffffff) (i64.const -1)) (i64.const 0x8000000000000000))
+(assert_return (invoke "sub" (i64.const 0x8000000000000000) (i64.const 1)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "sub" (i64.const 0x8000000000000000) (i64.const 2)) (i64.const 0x7ffffffffffffffe))
+(assert_return (invoke "sub" (i64.const 0x8000000000000000) (i64.const 0)) (i64.const 0x8000000000000000))
+(assert_return (invoke "sub" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i64.const 0))
+(assert_return (invoke "sub" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i64.const -1))
+(assert_return (invoke "sub" (i64.const 5) (i64.const 2)) (i64.const 3))
+(assert_return (invoke "sub" (i64.const -5) (i64.const 2)) (i64.const -7))
+(assert_return (invoke "sub" (i64.const 5) (i64.const -2)) (i64.const 7))
+(assert_return (invoke "sub" (i64.const -5) (i64.const -2)) (i64.const -3))
+(assert_return (invoke "sub" (i64.const 7) (i64.const 3)) (i64.const 4))
+(assert_return (invoke "sub" (i64.const -7) (i64.const 3)) (i64.const -10))
+(assert_return (invoke "sub" (i64.const 7) (i64.const -3)) (i64.const 10))
+(assert_return (invoke "sub" (i64.const -7) (i64.const -3)) (i64.const -4))
+(assert_return (invoke "sub" (i64.const 11) (i64.const 5)) (i64.const 6))
+(assert_return (invoke "sub" (i64.const 17) (i64.const 7)) (i64.const 10))
+(assert_return (invoke "sub" (i64.const 1) (i64.const 63)) (i64.const -62))
+(assert_return (invoke "sub" (i64.const 1) (i64.const 64)) (i64.const -63))
+(assert_return (invoke "sub" (i64.const 1) (i64.const 65)) (i64.const -64))
+(assert_return (invoke "sub" (i64.const -1) (i64.const 64)) (i64.const -65))
+(assert_return (invoke "sub" (i64.const -1) (i64.const 65)) (i64.const -66))
+(assert_return (invoke "sub" (i64.const 1) (i64.const 0x7fffffffffffffff)) (i64.const 0x8000000000000002))
+(assert_return (invoke "sub" (i64.const 1) (i64.const 0x8000000000000000)) (i64.const 0x8000000000000001))
+(assert_return (invoke "sub" (i64.const -1) (i64.const 0x8000000000000000)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "sub" (i64.const 0x8000000000000000) (i64.const 63)) (i64.const 0x7fffffffffffffc1))
+(assert_return (invoke "sub" (i64.const 0x123456789abcdef) (i64.const 0xfedcba9876543210)) (i64.const 0x2468acf13579bdf))
+(assert_return (invoke "sub" (i64.const 0x8ff00ff00ff00ff0) (i64.const 0x100000001)) (i64.const 0x8ff00fef0ff00fef))
+(assert_return (invoke "sub" (i64.const 0x8000000000000001) (i64.const 1000)) (i64.const 0x7ffffffffffffc19))
+(assert_return (invoke "sub" (i64.const 0xf0f0ffff0f0fffff) (i64.const 0xfffff0f0fffff0f0)) (i64.const 0xf0f10f0e0f100f0f))
+(assert_return (invoke "sub" (i64.const 0xabcd987602468ace) (i64.const 1)) (i64.const 0xabcd987602468acd))
+(assert_return (invoke "sub" (i64.const 0xfe000000dc000000) (i64.const 4)) (i64.const 0xfe000000dbfffffc))
+(assert_return (invoke "sub" (i64.const 0xabcd1234ef567809) (i64.const 53)) (i64.const 0xabcd1234ef5677d4))
+(assert_return (invoke "sub" (i64.const 0xabd1234ef567809c) (i64.const 63)) (i64.const 0xabd1234ef567805d))
+(assert_return (invoke "sub" (i64.const 0xabcd1234ef567809) (i64.const 245)) (i64.const 0xabcd1234ef567714))
+(assert_return (invoke "sub" (i64.const 0xabcd7294ef567809) (i64.const -19)) (i64.const 0xabcd7294ef56781c))
+(assert_return (invoke "sub" (i64.const 0xabcd1234ef567809) (i64.const 0x8000000000000035)) (i64.const 0x2bcd1234ef5677d4))
+(assert_return (invoke "sub" (i64.const 0xfe00dc00) (i64.const 4)) (i64.const 0xfe00dbfc))
+(assert_return (invoke "sub" (i64.const 0x8000) (i64.const 37)) (i64.const 0x7fdb))
+
+(assert_return (invoke "mul" (i64.const 1) (i64.const 1)) (i64.const 1))
+(assert_return (invoke "mul" (i64.const 1) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "mul" (i64.const 0) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "mul" (i64.const 0) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "mul" (i64.const -1) (i64.const -1)) (i64.const 1))
+(assert_return (invoke "mul" (i64.const -1) (i64.const 1)) (i64.const -1))
+(assert_return (invoke "mul" (i64.const 1) (i64.const -1)) (i64.const -1))
+(assert_return (invoke "mul" (i64.const 0x7fffffffffffffff) (i64.const 1)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "mul" (i64.const 0x8000000000000000) (i64.const -1)) (i64.const 0x8000000000000000))
+(assert_return (invoke "mul" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i64.const 0))
+(assert_return (invoke "mul" (i64.const 0x7fffffffffffffff) (i64.const -1)) (i64.const 0x8000000000000001))
+(assert_return (invoke "mul" (i64.const 0x8000000000000000) (i64.const 1)) (i64.const 0x8000000000000000))
+(assert_return (invoke "mul" (i64.const 0x8000000000000000) (i64.const 2)) (i64.const 0))
+(assert_return (invoke "mul" (i64.const 0x8000000000000000) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "mul" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i64.const 1))
+(assert_return (invoke "mul" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i64.const 0x8000000000000000))
+(assert_return (invoke "mul" (i64.const 5) (i64.const 2)) (i64.const 10))
+(assert_return (invoke "mul" (i64.const -5) (i64.const 2)) (i64.const -10))
+(assert_return (invoke "mul" (i64.const 5) (i64.const -2)) (i64.const -10))
+(assert_return (invoke "mul" (i64.const -5) (i64.const -2)) (i64.const 10))
+(assert_return (invoke "mul" (i64.const 7) (i64.const 3)) (i64.const 21))
+(assert_return (invoke "mul" (i64.const -7) (i64.const 3)) (i64.const -21))
+(assert_return (invoke "mul" (i64.const 7) (i64.const -3)) (i64.const -21))
+(assert_return (invoke "mul" (i64.const -7) (i64.const -3)) (i64.const 21))
+(assert_return (invoke "mul" (i64.const 11) (i64.const 5)) (i64.const 55))
+(assert_return (invoke "mul" (i64.const 17) (i64.const 7)) (i64.const 119))
+(assert_return (invoke "mul" (i64.const 1) (i64.const 63)) (i64.const 63))
+(assert_return (invoke "mul" (i64.const 1) (i64.const 64)) (i64.const 64))
+(assert_return (invoke "mul" (i64.const 1) (i64.const 65)) (i64.const 65))
+(assert_return (invoke "mul" (i64.const -1) (i64.const 64)) (i64.const -64))
+(assert_return (invoke "mul" (i64.const -1) (i64.const 65)) (i64.const -65))
+(assert_return (invoke "mul" (i64.const 1) (i64.const 0x7fffffffffffffff)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "mul" (i64.const 1) (i64.const 0x8000000000000000)) (i64.const 0x8000000000000000))
+(assert_return (invoke "mul" (i64.const -1) (i64.const 0x8000000000000000)) (i64.const 0x8000000000000000))
+(assert_return (invoke "mul" (i64.const 0x8000000000000000) (i64.const 63)) (i64.const 0x8000000000000000))
+(assert_return (invoke "mul" (i64.const 0x123456789abcdef) (i64.const 0xfedcba9876543210)) (i64.const 0x2236d88fe5618cf0))
+(assert_return (invoke "mul" (i64.const 0x8ff00ff00ff00ff0) (i64.const 0x100000001)) (i64.const 0x9fe01fe00ff00ff0))
+(assert_return (invoke "mul" (i64.const 0x8000000000000001) (i64.const 1000)) (i64.const 1000))
+(assert_return (invoke "mul" (i64.const 0xf0f0ffff0f0fffff) (i64.const 0xfffff0f0fffff0f0)) (i64.const 0x1d3c1f000f10))
+(assert_return (invoke "mul" (i64.const 0xabcd987602468ace) (i64.const 1)) (i64.const 0xabcd987602468ace))
+(assert_return (invoke "mul" (i64.const 0xfe000000dc000000) (i64.const 4)) (i64.const 0xf800000370000000))
+(assert_return (invoke "mul" (i64.const 0xabcd1234ef567809) (i64.const 53)) (i64.const 0x9174c4f58ce6d9dd))
+(assert_return (invoke "mul" (i64.const 0xabd1234ef567809c) (i64.const 63)) (i64.const 0x4877b06e6478a664))
+(assert_return (invoke "mul" (i64.const 0xabcd1234ef567809) (i64.const 245)) (i64.const 0x6b426ca90dc0e09d))
+(assert_return (invoke "mul" (i64.const 0xabcd7294ef567809) (i64.const -19)) (i64.const 0x3fc07ef23c951755))
+(assert_return (invoke "mul" (i64.const 0xabcd1234ef567809) (i64.const 0x8000000000000035)) (i64.const 0x1174c4f58ce6d9dd))
+(assert_return (invoke "mul" (i64.const 0xfe00dc00) (i64.const 4)) (i64.const 0x3f8037000))
+(assert_return (invoke "mul" (i64.const 0x8000) (i64.const 37)) (i64.const 0x128000))
+
+(assert_return (invoke "div_s" (i64.const 1) (i64.const 1)) (i64.const 1))
+(assert_trap (invoke "div_s" (i64.const 1) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "div_s" (i64.const 0) (i64.const 1)) (i64.const 0))
+(assert_trap (invoke "div_s" (i64.const 0) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "div_s" (i64.const -1) (i64.const -1)) (i64.const 1))
+(assert_return (invoke "div_s" (i64.const -1) (i64.const 1)) (i64.const -1))
+(assert_return (invoke "div_s" (i64.const 1) (i64.const -1)) (i64.const -1))
+(assert_return (invoke "div_s" (i64.const 0x7fffffffffffffff) (i64.const 1)) (i64.const 0x7fffffffffffffff))
+(assert_trap (invoke "div_s" (i64.const 0x8000000000000000) (i64.const -1)) "integer overflow")
+(assert_return (invoke "div_s" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i64.const 1))
+(assert_return (invoke "div_s" (i64.const 0x7fffffffffffffff) (i64.const -1)) (i64.const 0x8000000000000001))
+(assert_return (invoke "div_s" (i64.const 0x8000000000000000) (i64.const 1)) (i64.const 0x8000000000000000))
+(assert_return (invoke "div_s" (i64.const 0x8000000000000000) (i64.const 2)) (i64.const 0xc000000000000000))
+(assert_trap (invoke "div_s" (i64.const 0x8000000000000000) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "div_s" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i64.const 1))
+(assert_return (invoke "div_s" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i64.const 0))
+(assert_return (invoke "div_s" (i64.const 5) (i64.const 2)) (i64.const 2))
+(assert_return (invoke "div_s" (i64.const -5) (i64.const 2)) (i64.const -2))
+(assert_return (invoke "div_s" (i64.const 5) (i64.const -2)) (i64.const -2))
+(assert_return (invoke "div_s" (i64.const -5) (i64.const -2)) (i64.const 2))
+(assert_return (invoke "div_s" (i64.const 7) (i64.const 3)) (i64.const 2))
+(assert_return (invoke "div_s" (i64.const -7) (i64.const 3)) (i64.const -2))
+(assert_return (invoke "div_s" (i64.const 7) (i64.const -3)) (i64.const -2))
+(assert_return (invoke "div_s" (i64.const -7) (i64.const -3)) (i64.const 2))
+(assert_return (invoke "div_s" (i64.const 11) (i64.const 5)) (i64.const 2))
+(assert_return (invoke "div_s" (i64.const 17) (i64.const 7)) (i64.const 2))
+(assert_return (invoke "div_s" (i64.const 1) (i64.const 63)) (i64.const 0))
+(assert_return (invoke "div_s" (i64.const 1) (i64.const 64)) (i64.const 0))
+(assert_return (invoke "div_s" (i64.const 1) (i64.const 65)) (i64.const 0))
+(assert_return (invoke "div_s" (i64.const -1) (i64.const 64)) (i64.const 0))
+(assert_return (invoke "div_s" (i64.const -1) (i64.const 65)) (i64.const 0))
+(assert_return (invoke "div_s" (i64.const 1) (i64.const 0x7fffffffffffffff)) (i64.const 0))
+(assert_return (invoke "div_s" (i64.const 1) (i64.const 0x8000000000000000)) (i64.const 0))
+(assert_return (invoke "div_s" (i64.const -1) (i64.const 0x8000000000000000)) (i64.const 0))
+(assert_return (invoke "div_s" (i64.const 0x8000000000000000) (i64.const 63)) (i64.const 0xfdf7df7df7df7df8))
+(assert_return (invoke "div_s" (i64.const 0x123456789abcdef) (i64.const 0xfedcba9876543210)) (i64.const 0))
+(assert_return (invoke "div_s" (i64.const 0x8ff00ff00ff00ff0) (i64.const 0x100000001)) (i64.const 0xffffffff8ff00ff1))
+(assert_return (invoke "div_s" (i64.const 0x8000000000000001) (i64.const 1000)) (i64.const 0xffdf3b645a1cac09))
+(assert_return (invoke "div_s" (i64.const 0xf0f0ffff0f0fffff) (i64.const 0xfffff0f0fffff0f0)) (i64.const 0x10000))
+(assert_return (invoke "div_s" (i64.const 0xabcd987602468ace) (i64.const 1)) (i64.const 0xabcd987602468ace))
+(assert_return (invoke "div_s" (i64.const 0xfe000000dc000000) (i64.const 4)) (i64.const 0xff80000037000000))
+(assert_return (invoke "div_s" (i64.const 0xabcd1234ef567809) (i64.const 53)) (i64.const 0xfe694da0651e9cd5))
+(assert_return (invoke "div_s" (i64.const 0xabd1234ef567809c) (i64.const 63)) (i64.const 0xfea9ec3e34a83eff))
+(assert_return (invoke "div_s" (i64.const 0xabcd1234ef567809) (i64.const 245)) (i64.const 0xffa8054c7e5d594f))
+(assert_return (invoke "div_s" (i64.const 0xabcd7294ef567809) (i64.const -19)) (i64.const 0x46e733b879d221a))
+(assert_return (invoke "div_s" (i64.const 0xabcd1234ef567809) (i64.const 0x8000000000000035)) (i64.const 0))
+(assert_return (invoke "div_s" (i64.const 0xfe00dc00) (i64.const 4)) (i64.const 0x3f803700))
+(assert_return (invoke "div_s" (i64.const 0x8000) (i64.const 37)) (i64.const 885))
+
+(assert_return (invoke "div_u" (i64.const 1) (i64.const 1)) (i64.const 1))
+(assert_trap (invoke "div_u" (i64.const 1) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "div_u" (i64.const 0) (i64.const 1)) (i64.const 0))
+(assert_trap (invoke "div_u" (i64.const 0) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "div_u" (i64.const -1) (i64.const -1)) (i64.const 1))
+(assert_return (invoke "div_u" (i64.const -1) (i64.const 1)) (i64.const -1))
+(assert_return (invoke "div_u" (i64.const 1) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const 0x7fffffffffffffff) (i64.const 1)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "div_u" (i64.const 0x8000000000000000) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i64.const 1))
+(assert_return (invoke "div_u" (i64.const 0x7fffffffffffffff) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const 0x8000000000000000) (i64.const 1)) (i64.const 0x8000000000000000))
+(assert_return (invoke "div_u" (i64.const 0x8000000000000000) (i64.const 2)) (i64.const 0x4000000000000000))
+(assert_trap (invoke "div_u" (i64.const 0x8000000000000000) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "div_u" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i64.const 1))
+(assert_return (invoke "div_u" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const 5) (i64.const 2)) (i64.const 2))
+(assert_return (invoke "div_u" (i64.const -5) (i64.const 2)) (i64.const 0x7ffffffffffffffd))
+(assert_return (invoke "div_u" (i64.const 5) (i64.const -2)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const -5) (i64.const -2)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const 7) (i64.const 3)) (i64.const 2))
+(assert_return (invoke "div_u" (i64.const -7) (i64.const 3)) (i64.const 0x5555555555555553))
+(assert_return (invoke "div_u" (i64.const 7) (i64.const -3)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const -7) (i64.const -3)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const 11) (i64.const 5)) (i64.const 2))
+(assert_return (invoke "div_u" (i64.const 17) (i64.const 7)) (i64.const 2))
+(assert_return (invoke "div_u" (i64.const 1) (i64.const 63)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const 1) (i64.const 64)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const 1) (i64.const 65)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const -1) (i64.const 64)) (i64.const 0x3ffffffffffffff))
+(assert_return (invoke "div_u" (i64.const -1) (i64.const 65)) (i64.const 0x3f03f03f03f03f0))
+(assert_return (invoke "div_u" (i64.const 1) (i64.const 0x7fffffffffffffff)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const 1) (i64.const 0x8000000000000000)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const -1) (i64.const 0x8000000000000000)) (i64.const 1))
+(assert_return (invoke "div_u" (i64.const 0x8000000000000000) (i64.const 63)) (i64.const 0x208208208208208))
+(assert_return (invoke "div_u" (i64.const 0x123456789abcdef) (i64.const 0xfedcba9876543210)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const 0x8ff00ff00ff00ff0) (i64.const 0x100000001)) (i64.const 0x8ff00fef))
+(assert_return (invoke "div_u" (i64.const 0x8000000000000001) (i64.const 1000)) (i64.const 0x20c49ba5e353f7))
+(assert_return (invoke "div_u" (i64.const 0xf0f0ffff0f0fffff) (i64.const 0xfffff0f0fffff0f0)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const 0xabcd987602468ace) (i64.const 1)) (i64.const 0xabcd987602468ace))
+(assert_return (invoke "div_u" (i64.const 0xfe000000dc000000) (i64.const 4)) (i64.const 0x3f80000037000000))
+(assert_return (invoke "div_u" (i64.const 0xabcd1234ef567809) (i64.const 53)) (i64.const 0x33dd4df2ffccd21))
+(assert_return (invoke "div_u" (i64.const 0xabd1234ef567809c) (i64.const 63)) (i64.const 0x2ba2d4244e9430e))
+(assert_return (invoke "div_u" (i64.const 0xabcd1234ef567809) (i64.const 245)) (i64.const 0xb383bb40b735c7))
+(assert_return (invoke "div_u" (i64.const 0xabcd7294ef567809) (i64.const -19)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const 0xabcd1234ef567809) (i64.const 0x8000000000000035)) (i64.const 1))
+(assert_return (invoke "div_u" (i64.const 0xfe00dc00) (i64.const 4)) (i64.const 0x3f803700))
+(assert_return (invoke "div_u" (i64.const 0x8000) (i64.const 37)) (i64.const 885))
+
+(assert_return (invoke "rem_s" (i64.const 1) (i64.const 1)) (i64.const 0))
+(assert_trap (invoke "rem_s" (i64.const 1) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "rem_s" (i64.const 0) (i64.const 1)) (i64.const 0))
+(assert_trap (invoke "rem_s" (i64.const 0) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "rem_s" (i64.const -1) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "rem_s" (i64.const -1) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "rem_s" (i64.const 1) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "rem_s" (i64.const 0x7fffffffffffffff) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "rem_s" (i64.const 0x8000000000000000) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "rem_s" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i64.const 0))
+(assert_return (invoke "rem_s" (i64.const 0x7fffffffffffffff) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "rem_s" (i64.const 0x8000000000000000) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "rem_s" (i64.const 0x8000000000000000) (i64.const 2)) (i64.const 0))
+(assert_trap (invoke "rem_s" (i64.const 0x8000000000000000) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "rem_s" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i64.const 0))
+(assert_return (invoke "rem_s" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "rem_s" (i64.const 5) (i64.const 2)) (i64.const 1))
+(assert_return (invoke "rem_s" (i64.const -5) (i64.const 2)) (i64.const -1))
+(assert_return (invoke "rem_s" (i64.const 5) (i64.const -2)) (i64.const 1))
+(assert_return (invoke "rem_s" (i64.const -5) (i64.const -2)) (i64.const -1))
+(assert_return (invoke "rem_s" (i64.const 7) (i64.const 3)) (i64.const 1))
+(assert_return (invoke "rem_s" (i64.const -7) (i64.const 3)) (i64.const -1))
+(assert_return (invoke "rem_s" (i64.const 7) (i64.const -3)) (i64.const 1))
+(assert_return (invoke "rem_s" (i64.const -7) (i64.const -3)) (i64.const -1))
+(assert_return (invoke "rem_s" (i64.const 11) (i64.const 5)) (i64.const 1))
+(assert_return (invoke "rem_s" (i64.const 17) (i64.const 7)) (i64.const 3))
+(assert_return (invoke "rem_s" (i64.const 1) (i64.const 63)) (i64.const 1))
+(assert_return (invoke "rem_s" (i64.const 1) (i64.const 64)) (i64.const 1))
+(assert_return (invoke "rem_s" (i64.const 1) (i64.const 65)) (i64.const 1))
+(assert_return (invoke "rem_s" (i64.const -1) (i64.const 64)) (i64.const -1))
+(assert_return (invoke "rem_s" (i64.const -1) (i64.const 65)) (i64.const -1))
+(assert_return (invoke "rem_s" (i64.const 1) (i64.const 0x7fffffffffffffff)) (i64.const 1))
+(assert_return (invoke "rem_s" (i64.const 1) (i64.const 0x8000000000000000)) (i64.const 1))
+(assert_return (invoke "rem_s" (i64.const -1) (i64.const 0x8000000000000000)) (i64.const -1))
+(assert_return (invoke "rem_s" (i64.const 0x8000000000000000) (i64.const 63)) (i64.const -8))
+(assert_return (invoke "rem_s" (i64.const 0x123456789abcdef) (i64.const 0xfedcba9876543210)) (i64.const 0x123456789abcdef))
+(assert_return (invoke "rem_s" (i64.const 0x8ff00ff00ff00ff0) (i64.const 0x100000001)) (i64.const 0xffffffff7fffffff))
+(assert_return (invoke "rem_s" (i64.const 0x8000000000000001) (i64.const 1000)) (i64.const -807))
+(assert_return (invoke "rem_s" (i64.const 0xf0f0ffff0f0fffff) (i64.const 0xfffff0f0fffff0f0)) (i64.const 0xffffffff1e1fffff))
+(assert_return (invoke "rem_s" (i64.const 0xabcd987602468ace) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "rem_s" (i64.const 0xfe000000dc000000) (i64.const 4)) (i64.const 0))
+(assert_return (invoke "rem_s" (i64.const 0xabcd1234ef567809) (i64.const 53)) (i64.const -16))
+(assert_return (invoke "rem_s" (i64.const 0xabd1234ef567809c) (i64.const 63)) (i64.const -37))
+(assert_return (invoke "rem_s" (i64.const 0xabcd1234ef567809) (i64.const 245)) (i64.const -146))
+(assert_return (invoke "rem_s" (i64.const 0xabcd7294ef567809) (i64.const -19)) (i64.const -9))
+(assert_return (invoke "rem_s" (i64.const 0xabcd1234ef567809) (i64.const 0x8000000000000035)) (i64.const 0xabcd1234ef567809))
+(assert_return (invoke "rem_s" (i64.const 0xfe00dc00) (i64.const 4)) (i64.const 0))
+(assert_return (invoke "rem_s" (i64.const 0x8000) (i64.const 37)) (i64.const 23))
+
+(assert_return (invoke "rem_u" (i64.const 1) (i64.const 1)) (i64.const 0))
+(assert_trap (invoke "rem_u" (i64.const 1) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "rem_u" (i64.const 0) (i64.const 1)) (i64.const 0))
+(assert_trap (invoke "rem_u" (i64.const 0) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "rem_u" (i64.const -1) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "rem_u" (i64.const -1) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "rem_u" (i64.const 1) (i64.const -1)) (i64.const 1))
+(assert_return (invoke "rem_u" (i64.const 0x7fffffffffffffff) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "rem_u" (i64.const 0x8000000000000000) (i64.const -1)) (i64.const 0x8000000000000000))
+(assert_return (invoke "rem_u" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i64.const 0))
+(assert_return (invoke "rem_u" (i64.const 0x7fffffffffffffff) (i64.const -1)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "rem_u" (i64.const 0x8000000000000000) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "rem_u" (i64.const 0x8000000000000000) (i64.const 2)) (i64.const 0))
+(assert_trap (invoke "rem_u" (i64.const 0x8000000000000000) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "rem_u" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i64.const 0))
+(assert_return (invoke "rem_u" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "rem_u" (i64.const 5) (i64.const 2)) (i64.const 1))
+(assert_return (invoke "rem_u" (i64.const -5) (i64.const 2)) (i64.const 1))
+(assert_return (invoke "rem_u" (i64.const 5) (i64.const -2)) (i64.const 5))
+(assert_return (invoke "rem_u" (i64.const -5) (i64.const -2)) (i64.const -5))
+(assert_return (invoke "rem_u" (i64.const 7) (i64.const 3)) (i64.const 1))
+(assert_return (invoke "rem_u" (i64.const -7) (i64.const 3)) (i64.const 0))
+(assert_return (invoke "rem_u" (i64.const 7) (i64.const -3)) (i64.const 7))
+(assert_return (invoke "rem_u" (i64.const -7) (i64.const -3)) (i64.const -7))
+(assert_return (invoke "rem_u" (i64.const 11) (i64.const 5)) (i64.const 1))
+(assert_return (invoke "rem_u" (i64.const 17) (i64.const 7)) (i64.const 3))
+(assert_return (invoke "rem_u" (i64.const 1) (i64.const 63)) (i64.const 1))
+(assert_return (invoke "rem_u" (i64.const 1) (i64.const 64)) (i64.const 1))
+(assert_return (invoke "rem_u" (i64.const 1) (i64.const 65)) (i64.const 1))
+(assert_return (invoke "rem_u" (i64.const -1) (i64.const 64)) (i64.const 63))
+(assert_return (invoke "rem_u" (i64.const -1) (i64.const 65)) (i64.const 15))
+(assert_return (invoke "rem_u" (i64.const 1) (i64.const 0x7fffffffffffffff)) (i64.const 1))
+(assert_return (invoke "rem_u" (i64.const 1) (i64.const 0x8000000000000000)) (i64.const 1))
+(assert_return (invoke "rem_u" (i64.const -1) (i64.const 0x8000000000000000)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "rem_u" (i64.const 0x8000000000000000) (i64.const 63)) (i64.const 8))
+(assert_return (invoke "rem_u" (i64.const 0x123456789abcdef) (i64.const 0xfedcba9876543210)) (i64.const 0x123456789abcdef))
+(assert_return (invoke "rem_u" (i64.const 0x8ff00ff00ff00ff0) (i64.const 0x100000001)) (i64.const 0x80000001))
+(assert_return (invoke "rem_u" (i64.const 0x8000000000000001) (i64.const 1000)) (i64.const 809))
+(assert_return (invoke "rem_u" (i64.const 0xf0f0ffff0f0fffff) (i64.const 0xfffff0f0fffff0f0)) (i64.const 0xf0f0ffff0f0fffff))
+(assert_return (invoke "rem_u" (i64.const 0xabcd987602468ace) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "rem_u" (i64.const 0xfe000000dc000000) (i64.const 4)) (i64.const 0))
+(assert_return (invoke "rem_u" (i64.const 0xabcd1234ef567809) (i64.const 53)) (i64.const 52))
+(assert_return (invoke "rem_u" (i64.const 0xabd1234ef567809c) (i64.const 63)) (i64.const 42))
+(assert_return (invoke "rem_u" (i64.const 0xabcd1234ef567809) (i64.const 245)) (i64.const 150))
+(assert_return (invoke "rem_u" (i64.const 0xabcd7294ef567809) (i64.const -19)) (i64.const 0xabcd7294ef567809))
+(assert_return (invoke "rem_u" (i64.const 0xabcd1234ef567809) (i64.const 0x8000000000000035)) (i64.const 0x2bcd1234ef5677d4))
+(assert_return (invoke "rem_u" (i64.const 0xfe00dc00) (i64.const 4)) (i64.const 0))
+(assert_return (invoke "rem_u" (i64.const 0x8000) (i64.const 37)) (i64.const 23))
+
+(assert_return (invoke "and" (i64.const 1) (i64.const 1)) (i64.const 1))
+(assert_return (invoke "and" (i64.const 1) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "and" (i64.const 0) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "and" (i64.const 0) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "and" (i64.const -1) (i64.const -1)) (i64.const -1))
+(assert_return (invoke "and" (i64.const -1) (i64.const 1)) (i64.const 1))
+(assert_return (invoke "and" (i64.const 1) (i64.const -1)) (i64.const 1))
+(assert_return (invoke "and" (i64.const 0x7fffffffffffffff) (i64.const 1)) (i64.const 1))
+(assert_return (invoke "and" (i64.const 0x8000000000000000) (i64.const -1)) (i64.const 0x8000000000000000))
+(assert_return (invoke "and" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i64.const 0x8000000000000000))
+(assert_return (invoke "and" (i64.const 0x7fffffffffffffff) (i64.const -1)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "and" (i64.const 0x8000000000000000) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "and" (i64.const 0x8000000000000000) (i64.const 2)) (i64.const 0))
+(assert_return (invoke "and" (i64.const 0x8000000000000000) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "and" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "and" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i64.const 0))
+(assert_return (invoke "and" (i64.const 5) (i64.const 2)) (i64.const 0))
+(assert_return (invoke "and" (i64.const -5) (i64.const 2)) (i64.const 2))
+(assert_return (invoke "and" (i64.const 5) (i64.const -2)) (i64.const 4))
+(assert_return (invoke "and" (i64.const -5) (i64.const -2)) (i64.const -6))
+(assert_return (invoke "and" (i64.const 7) (i64.const 3)) (i64.const 3))
+(assert_return (invoke "and" (i64.const -7) (i64.const 3)) (i64.const 1))
+(assert_return (invoke "and" (i64.const 7) (i64.const -3)) (i64.const 5))
+(assert_return (invoke "and" (i64.const -7) (i64.const -3)) (i64.const -7))
+(assert_return (invoke "and" (i64.const 11) (i64.const 5)) (i64.const 1))
+(assert_return (invoke "and" (i64.const 17) (i64.const 7)) (i64.const 1))
+(assert_return (invoke "and" (i64.const 1) (i64.const 63)) (i64.const 1))
+(assert_return (invoke "and" (i64.const 1) (i64.const 64)) (i64.const 0))
+(assert_return (invoke "and" (i64.const 1) (i64.const 65)) (i64.const 1))
+(assert_return (invoke "and" (i64.const -1) (i64.const 64)) (i64.const 64))
+(assert_return (invoke "and" (i64.const -1) (i64.const 65)) (i64.const 65))
+(assert_return (invoke "and" (i64.const 1) (i64.const 0x7fffffffffffffff)) (i64.const 1))
+(assert_return (invoke "and" (i64.const 1) (i64.const 0x8000000000000000)) (i64.const 0))
+(assert_return (invoke "and" (i64.const -1) (i64.const 0x8000000000000000)) (i64.const 0x8000000000000000))
+(assert_return (invoke "and" (i64.const 0x8000000000000000) (i64.const 63)) (i64.const 0))
+(assert_return (invoke "and" (i64.const 0x123456789abcdef) (i64.const 0xfedcba9876543210)) (i64.const 0))
+(assert_return (invoke "and" (i64.const 0x8ff00ff00ff00ff0) (i64.const 0x100000001)) (i64.const 0))
+(assert_return (invoke "and" (i64.const 0x8000000000000001) (i64.const 1000)) (i64.const 0))
+(assert_return (invoke "and" (i64.const 0xf0f0ffff0f0fffff) (i64.const 0xfffff0f0fffff0f0)) (i64.const 0xf0f0f0f00f0ff0f0))
+(assert_return (invoke "and" (i64.const 0xabcd987602468ace) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "and" (i64.const 0xfe000000dc000000) (i64.const 4)) (i64.const 0))
+(assert_return (invoke "and" (i64.const 0xabcd1234ef567809) (i64.const 53)) (i64.const 1))
+(assert_return (invoke "and" (i64.const 0xabd1234ef567809c) (i64.const 63)) (i64.const 28))
+(assert_return (invoke "and" (i64.const 0xabcd1234ef567809) (i64.const 245)) (i64.const 1))
+(assert_return (invoke "and" (i64.const 0xabcd7294ef567809) (i64.const -19)) (i64.const 0xabcd7294ef567809))
+(assert_return (invoke "and" (i64.const 0xabcd1234ef567809) (i64.const 0x8000000000000035)) (i64.const 0x8000000000000001))
+(assert_return (invoke "and" (i64.const 0xfe00dc00) (i64.const 4)) (i64.const 0))
+(assert_return (invoke "and" (i64.const 0x8000) (i64.const 37)) (i64.const 0))
+
+(assert_return (invoke "or" (i64.const 1) (i64.const 1)) (i64.const 1))
+(assert_return (invoke "or" (i64.const 1) (i64.const 0)) (i64.const 1))
+(assert_return (invoke "or" (i64.const 0) (i64.const 1)) (i64.const 1))
+(assert_return (invoke "or" (i64.const 0) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "or" (i64.const -1) (i64.const -1)) (i64.const -1))
+(assert_return (invoke "or" (i64.const -1) (i64.const 1)) (i64.const -1))
+(assert_return (invoke "or" (i64.const 1) (i64.const -1)) (i64.const -1))
+(assert_return (invoke "or" (i64.const 0x7fffffffffffffff) (i64.const 1)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "or" (i64.const 0x8000000000000000) (i64.const -1)) (i64.const -1))
+(assert_return (invoke "or" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i64.const 0x8000000000000000))
+(assert_return (invoke "or" (i64.const 0x7fffffffffffffff) (i64.const -1)) (i64.const -1))
+(assert_return (invoke "or" (i64.const 0x8000000000000000) (i64.const 1)) (i64.const 0x8000000000000001))
+(assert_return (invoke "or" (i64.const 0x8000000000000000) (i64.const 2)) (i64.const 0x8000000000000002))
+(assert_return (invoke "or" (i64.const 0x8000000000000000) (i64.const 0)) (i64.const 0x8000000000000000))
+(assert_return (invoke "or" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "or" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i64.const -1))
+(assert_return (invoke "or" (i64.const 5) (i64.const 2)) (i64.const 7))
+(assert_return (invoke "or" (i64.const -5) (i64.const 2)) (i64.const -5))
+(assert_return (invoke "or" (i64.const 5) (i64.const -2)) (i64.const -1))
+(assert_return (invoke "or" (i64.const -5) (i64.const -2)) (i64.const -1))
+(assert_return (invoke "or" (i64.const 7) (i64.const 3)) (i64.const 7))
+(assert_return (invoke "or" (i64.const -7) (i64.const 3)) (i64.const -5))
+(assert_return (invoke "or" (i64.const 7) (i64.const -3)) (i64.const -1))
+(assert_return (invoke "or" (i64.const -7) (i64.const -3)) (i64.const -3))
+(assert_return (invoke "or" (i64.const 11) (i64.const 5)) (i64.const 15))
+(assert_return (invoke "or" (i64.const 17) (i64.const 7)) (i64.const 23))
+(assert_return (invoke "or" (i64.const 1) (i64.const 63)) (i64.const 63))
+(assert_return (invoke "or" (i64.const 1) (i64.const 64)) (i64.const 65))
+(assert_return (invoke "or" (i64.const 1) (i64.const 65)) (i64.const 65))
+(assert_return (invoke "or" (i64.const -1) (i64.const 64)) (i64.const -1))
+(assert_return (invoke "or" (i64.const -1) (i64.const 65)) (i64.const -1))
+(assert_return (invoke "or" (i64.const 1) (i64.const 0x7fffffffffffffff)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "or" (i64.const 1) (i64.const 0x8000000000000000)) (i64.const 0x8000000000000001))
+(assert_return (invoke "or" (i64.const -1) (i64.const 0x8000000000000000)) (i64.const -1))
+(assert_return (invoke "or" (i64.const 0x8000000000000000) (i64.const 63)) (i64.const 0x800000000000003f))
+(assert_return (invoke "or" (i64.const 0x123456789abcdef) (i64.const 0xfedcba9876543210)) (i64.const -1))
+(assert_return (invoke "or" (i64.const 0x8ff00ff00ff00ff0) (i64.const 0x100000001)) (i64.const 0x8ff00ff10ff00ff1))
+(assert_return (invoke "or" (i64.const 0x8000000000000001) (i64.const 1000)) (i64.const 0x80000000000003e9))
+(assert_return (invoke "or" (i64.const 0xf0f0ffff0f0fffff) (i64.const 0xfffff0f0fffff0f0)) (i64.const -1))
+(assert_return (invoke "or" (i64.const 0xabcd987602468ace) (i64.const 1)) (i64.const 0xabcd987602468acf))
+(assert_return (invoke "or" (i64.const 0xfe000000dc000000) (i64.const 4)) (i64.const 0xfe000000dc000004))
+(assert_return (invoke "or" (i64.const 0xabcd1234ef567809) (i64.const 53)) (i64.const 0xabcd1234ef56783d))
+(assert_return (invoke "or" (i64.const 0xabd1234ef567809c) (i64.const 63)) (i64.const 0xabd1234ef56780bf))
+(assert_return (invoke "or" (i64.const 0xabcd1234ef567809) (i64.const 245)) (i64.const 0xabcd1234ef5678fd))
+(assert_return (invoke "or" (i64.const 0xabcd7294ef567809) (i64.const -19)) (i64.const -19))
+(assert_return (invoke "or" (i64.const 0xabcd1234ef567809) (i64.const 0x8000000000000035)) (i64.const 0xabcd1234ef56783d))
+(assert_return (invoke "or" (i64.const 0xfe00dc00) (i64.const 4)) (i64.const 0xfe00dc04))
+(assert_return (invoke "or" (i64.const 0x8000) (i64.const 37)) (i64.const 0x8025))
+
+(assert_return (invoke "xor" (i64.const 1) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "xor" (i64.const 1) (i64.const 0)) (i64.const 1))
+(assert_return (invoke "xor" (i64.const 0) (i64.const 1)) (i64.const 1))
+(assert_return (invoke "xor" (i64.const 0) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "xor" (i64.const -1) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "xor" (i64.const -1) (i64.const 1)) (i64.const -2))
+(assert_return (invoke "xor" (i64.const 1) (i64.const -1)) (i64.const -2))
+(assert_return (invoke "xor" (i64.const 0x7fffffffffffffff) (i64.const 1)) (i64.const 0x7ffffffffffffffe))
+(assert_return (invoke "xor" (i64.const 0x8000000000000000) (i64.const -1)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "xor" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i64.const 0))
+(assert_return (invoke "xor" (i64.const 0x7fffffffffffffff) (i64.const -1)) (i64.const 0x8000000000000000))
+(assert_return (invoke "xor" (i64.const 0x8000000000000000) (i64.const 1)) (i64.const 0x8000000000000001))
+(assert_return (invoke "xor" (i64.const 0x8000000000000000) (i64.const 2)) (i64.const 0x8000000000000002))
+(assert_return (invoke "xor" (i64.const 0x8000000000000000) (i64.const 0)) (i64.const 0x8000000000000000))
+(assert_return (invoke "xor" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i64.const 0))
+(assert_return (invoke "xor" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i64.const -1))
+(assert_return (invoke "xor" (i64.const 5) (i64.const 2)) (i64.const 7))
+(assert_return (invoke "xor" (i64.const -5) (i64.const 2)) (i64.const -7))
+(assert_return (invoke "xor" (i64.const 5) (i64.const -2)) (i64.const -5))
+(assert_return (invoke "xor" (i64.const -5) (i64.const -2)) (i64.const 5))
+(assert_return (invoke "xor" (i64.const 7) (i64.const 3)) (i64.const 4))
+(assert_return (invoke "xor" (i64.const -7) (i64.const 3)) (i64.const -6))
+(assert_return (invoke "xor" (i64.const 7) (i64.const -3)) (i64.const -6))
+(assert_return (invoke "xor" (i64.const -7) (i64.const -3)) (i64.const 4))
+(assert_return (invoke "xor" (i64.const 11) (i64.const 5)) (i64.const 14))
+(assert_return (invoke "xor" (i64.const 17) (i64.const 7)) (i64.const 22))
+(assert_return (invoke "xor" (i64.const 1) (i64.const 63)) (i64.const 62))
+(assert_return (invoke "xor" (i64.const 1) (i64.const 64)) (i64.const 65))
+(assert_return (invoke "xor" (i64.const 1) (i64.const 65)) (i64.const 64))
+(assert_return (invoke "xor" (i64.const -1) (i64.const 64)) (i64.const -65))
+(assert_return (invoke "xor" (i64.const -1) (i64.const 65)) (i64.const -66))
+(assert_return (invoke "xor" (i64.const 1) (i64.const 0x7fffffffffffffff)) (i64.const 0x7ffffffffffffffe))
+(assert_return (invoke "xor" (i64.const 1) (i64.const 0x8000000000000000)) (i64.const 0x8000000000000001))
+(assert_return (invoke "xor" (i64.const -1) (i64.const 0x8000000000000000)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "xor" (i64.const 0x8000000000000000) (i64.const 63)) (i64.const 0x800000000000003f))
+(assert_return (invoke "xor" (i64.const 0x123456789abcdef) (i64.const 0xfedcba9876543210)) (i64.const -1))
+(assert_return (invoke "xor" (i64.const 0x8ff00ff00ff00ff0) (i64.const 0x100000001)) (i64.const 0x8ff00ff10ff00ff1))
+(assert_return (invoke "xor" (i64.const 0x8000000000000001) (i64.const 1000)) (i64.const 0x80000000000003e9))
+(assert_return (invoke "xor" (i64.const 0xf0f0ffff0f0fffff) (i64.const 0xfffff0f0fffff0f0)) (i64.const 0xf0f0f0ff0f00f0f))
+(assert_return (invoke "xor" (i64.const 0xabcd987602468ace) (i64.const 1)) (i64.const 0xabcd987602468acf))
+(assert_return (invoke "xor" (i64.const 0xfe000000dc000000) (i64.const 4)) (i64.const 0xfe000000dc000004))
+(assert_return (invoke "xor" (i64.const 0xabcd1234ef567809) (i64.const 53)) (i64.const 0xabcd1234ef56783c))
+(assert_return (invoke "xor" (i64.const 0xabd1234ef567809c) (i64.const 63)) (i64.const 0xabd1234ef56780a3))
+(assert_return (invoke "xor" (i64.const 0xabcd1234ef567809) (i64.const 245)) (i64.const 0xabcd1234ef5678fc))
+(assert_return (invoke "xor" (i64.const 0xabcd7294ef567809) (i64.const -19)) (i64.const 0x54328d6b10a987e4))
+(assert_return (invoke "xor" (i64.const 0xabcd1234ef567809) (i64.const 0x8000000000000035)) (i64.const 0x2bcd1234ef56783c))
+(assert_return (invoke "xor" (i64.const 0xfe00dc00) (i64.const 4)) (i64.const 0xfe00dc04))
+(assert_return (invoke "xor" (i64.const 0x8000) (i64.const 37)) (i64.const 0x8025))
+
+(assert_return (invoke "shl" (i64.const 1) (i64.const 1)) (i64.const 2))
+(assert_return (invoke "shl" (i64.const 1) (i64.const 0)) (i64.const 1))
+(assert_return (invoke "shl" (i64.const 0) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "shl" (i64.const 0) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "shl" (i64.const -1) (i64.const -1)) (i64.const 0x8000000000000000))
+(assert_return (invoke "shl" (i64.const -1) (i64.const 1)) (i64.const -2))
+(assert_return (invoke "shl" (i64.const 1) (i64.const -1)) (i64.const 0x8000000000000000))
+(assert_return (invoke "shl" (i64.const 0x7fffffffffffffff) (i64.const 1)) (i64.const -2))
+(assert_return (invoke "shl" (i64.const 0x8000000000000000) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "shl" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i64.const 0x8000000000000000))
+(assert_return (invoke "shl" (i64.const 0x7fffffffffffffff) (i64.const -1)) (i64.const 0x8000000000000000))
+(assert_return (invoke "shl" (i64.const 0x8000000000000000) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "shl" (i64.const 0x8000000000000000) (i64.const 2)) (i64.const 0))
+(assert_return (invoke "shl" (i64.const 0x8000000000000000) (i64.const 0)) (i64.const 0x8000000000000000))
+(assert_return (invoke "shl" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i64.const 0x8000000000000000))
+(assert_return (invoke "shl" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "shl" (i64.const 5) (i64.const 2)) (i64.const 20))
+(assert_return (invoke "shl" (i64.const -5) (i64.const 2)) (i64.const -20))
+(assert_return (invoke "shl" (i64.const 5) (i64.const -2)) (i64.const 0x4000000000000000))
+(assert_return (invoke "shl" (i64.const -5) (i64.const -2)) (i64.const 0xc000000000000000))
+(assert_return (invoke "shl" (i64.const 7) (i64.const 3)) (i64.const 56))
+(assert_return (invoke "shl" (i64.const -7) (i64.const 3)) (i64.const -56))
+(assert_return (invoke "shl" (i64.const 7) (i64.const -3)) (i64.const 0xe000000000000000))
+(assert_return (invoke "shl" (i64.const -7) (i64.const -3)) (i64.const 0x2000000000000000))
+(assert_return (invoke "shl" (i64.const 11) (i64.const 5)) (i64.const 352))
+(assert_return (invoke "shl" (i64.const 17) (i64.const 7)) (i64.const 0x880))
+(assert_return (invoke "shl" (i64.const 1) (i64.const 63)) (i64.const 0x8000000000000000))
+(assert_return (invoke "shl" (i64.const 1) (i64.const 64)) (i64.const 1))
+(assert_return (invoke "shl" (i64.const 1) (i64.const 65)) (i64.const 2))
+(assert_return (invoke "shl" (i64.const -1) (i64.const 64)) (i64.const -1))
+(assert_return (invoke "shl" (i64.const -1) (i64.const 65)) (i64.const -2))
+(assert_return (invoke "shl" (i64.const 1) (i64.const 0x7fffffffffffffff)) (i64.const 0x8000000000000000))
+(assert_return (invoke "shl" (i64.const 1) (i64.const 0x8000000000000000)) (i64.const 1))
+(assert_return (invoke "shl" (i64.const -1) (i64.const 0x8000000000000000)) (i64.const -1))
+(assert_return (invoke "shl" (i64.const 0x8000000000000000) (i64.const 63)) (i64.const 0))
+(assert_return (invoke "shl" (i64.const 0x123456789abcdef) (i64.const 0xfedcba9876543210)) (i64.const 0x456789abcdef0000))
+(assert_return (invoke "shl" (i64.const 0x8ff00ff00ff00ff0) (i64.const 0x100000001)) (i64.const 0x1fe01fe01fe01fe0))
+(assert_return (invoke "shl" (i64.const 0x8000000000000001) (i64.const 1000)) (i64.const 0x10000000000))
+(assert_return (invoke "shl" (i64.const 0xf0f0ffff0f0fffff) (i64.const 0xfffff0f0fffff0f0)) (i64.const 0xffff000000000000))
+(assert_return (invoke "shl" (i64.const 0xabcd987602468ace) (i64.const 1)) (i64.const 0x579b30ec048d159c))
+(assert_return (invoke "shl" (i64.const 0xfe000000dc000000) (i64.const 4)) (i64.const 0xe000000dc0000000))
+(assert_return (invoke "shl" (i64.const 0xabcd1234ef567809) (i64.const 53)) (i64.const 0x120000000000000))
+(assert_return (invoke "shl" (i64.const 0xabd1234ef567809c) (i64.const 63)) (i64.const 0))
+(assert_return (invoke "shl" (i64.const 0xabcd1234ef567809) (i64.const 245)) (i64.const 0x120000000000000))
+(assert_return (invoke "shl" (i64.const 0xabcd7294ef567809) (i64.const -19)) (i64.const 0xcf01200000000000))
+(assert_return (invoke "shl" (i64.const 0xabcd1234ef567809) (i64.const 0x8000000000000035)) (i64.const 0x120000000000000))
+(assert_return (invoke "shl" (i64.const 0xfe00dc00) (i64.const 4)) (i64.const 0xfe00dc000))
+(assert_return (invoke "shl" (i64.const 0x8000) (i64.const 37)) (i64.const 0x10000000000000))
+
+(assert_return (invoke "shr_s" (i64.const 1) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "shr_s" (i64.const 1) (i64.const 0)) (i64.const 1))
+(assert_return (invoke "shr_s" (i64.const 0) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "shr_s" (i64.const 0) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "shr_s" (i64.const -1) (i64.const -1)) (i64.const -1))
+(assert_return (invoke "shr_s" (i64.const -1) (i64.const 1)) (i64.const -1))
+(assert_return (invoke "shr_s" (i64.const 1) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "shr_s" (i64.const 0x7fffffffffffffff) (i64.const 1)) (i64.const 0x3fffffffffffffff))
+(assert_return (invoke "shr_s" (i64.const 0x8000000000000000) (i64.const -1)) (i64.const -1))
+(assert_return (invoke "shr_s" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i64.const 0x8000000000000000))
+(assert_return (invoke "shr_s" (i64.const 0x7fffffffffffffff) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "shr_s" (i64.const 0x8000000000000000) (i64.const 1)) (i64.const 0xc000000000000000))
+(assert_return (invoke "shr_s" (i64.const 0x8000000000000000) (i64.const 2)) (i64.const 0xe000000000000000))
+(assert_return (invoke "shr_s" (i64.const 0x8000000000000000) (i64.const 0)) (i64.const 0x8000000000000000))
+(assert_return (invoke "shr_s" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i64.const 0))
+(assert_return (invoke "shr_s" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "shr_s" (i64.const 5) (i64.const 2)) (i64.const 1))
+(assert_return (invoke "shr_s" (i64.const -5) (i64.const 2)) (i64.const -2))
+(assert_return (invoke "shr_s" (i64.const 5) (i64.const -2)) (i64.const 0))
+(assert_return (invoke "shr_s" (i64.const -5) (i64.const -2)) (i64.const -1))
+(assert_return (invoke "shr_s" (i64.const 7) (i64.const 3)) (i64.const 0))
+(assert_return (invoke "shr_s" (i64.const -7) (i64.const 3)) (i64.const -1))
+(assert_return (invoke "shr_s" (i64.const 7) (i64.const -3)) (i64.const 0))
+(assert_return (invoke "shr_s" (i64.const -7) (i64.const -3)) (i64.const -1))
+(assert_return (invoke "shr_s" (i64.const 11) (i64.const 5)) (i64.const 0))
+(assert_return (invoke "shr_s" (i64.const 17) (i64.const 7)) (i64.const 0))
+(assert_return (invoke "shr_s" (i64.const 1) (i64.const 63)) (i64.const 0))
+(assert_return (invoke "shr_s" (i64.const 1) (i64.const 64)) (i64.const 1))
+(assert_return (invoke "shr_s" (i64.const 1) (i64.const 65)) (i64.const 0))
+(assert_return (invoke "shr_s" (i64.const -1) (i64.const 64)) (i64.const -1))
+(assert_return (invoke "shr_s" (i64.const -1) (i64.const 65)) (i64.const -1))
+(assert_return (invoke "shr_s" (i64.const 1) (i64.const 0x7fffffffffffffff)) (i64.const 0))
+(assert_return (invoke "shr_s" (i64.const 1) (i64.const 0x8000000000000000)) (i64.const 1))
+(assert_return (invoke "shr_s" (i64.const -1) (i64.const 0x8000000000000000)) (i64.const -1))
+(assert_return (invoke "shr_s" (i64.const 0x8000000000000000) (i64.const 63)) (i64.const -1))
+(assert_return (invoke "shr_s" (i64.const 0x123456789abcdef) (i64.const 0xfedcba9876543210)) (i64.const 0x123456789ab))
+(assert_return (invoke "shr_s" (i64.const 0x8ff00ff00ff00ff0) (i64.const 0x100000001)) (i64.const 0xc7f807f807f807f8))
+(assert_return (invoke "shr_s" (i64.const 0x8000000000000001) (i64.const 1000)) (i64.const 0xffffffffff800000))
+(assert_return (invoke "shr_s" (i64.const 0xf0f0ffff0f0fffff) (i64.const 0xfffff0f0fffff0f0)) (i64.const 0xfffffffffffff0f0))
+(assert_return (invoke "shr_s" (i64.const 0xabcd987602468ace) (i64.const 1)) (i64.const 0xd5e6cc3b01234567))
+(assert_return (invoke "shr_s" (i64.const 0xfe000000dc000000) (i64.const 4)) (i64.const 0xffe000000dc00000))
+(assert_return (invoke "shr_s" (i64.const 0xabcd1234ef567809) (i64.const 53)) (i64.const -674))
+(assert_return (invoke "shr_s" (i64.const 0xabd1234ef567809c) (i64.const 63)) (i64.const -1))
+(assert_return (invoke "shr_s" (i64.const 0xabcd1234ef567809) (i64.const 245)) (i64.const -674))
+(assert_return (invoke "shr_s" (i64.const 0xabcd7294ef567809) (i64.const -19)) (i64.const 0xfffffffffffd5e6b))
+(assert_return (invoke "shr_s" (i64.const 0xabcd1234ef567809) (i64.const 0x8000000000000035)) (i64.const -674))
+(assert_return (invoke "shr_s" (i64.const 0xfe00dc00) (i64.const 4)) (i64.const 0xfe00dc0))
+(assert_return (invoke "shr_s" (i64.const 0x8000) (i64.const 37)) (i64.const 0))
+
+(assert_return (invoke "shr_u" (i64.const 1) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "shr_u" (i64.const 1) (i64.const 0)) (i64.const 1))
+(assert_return (invoke "shr_u" (i64.const 0) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "shr_u" (i64.const 0) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "shr_u" (i64.const -1) (i64.const -1)) (i64.const 1))
+(assert_return (invoke "shr_u" (i64.const -1) (i64.const 1)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "shr_u" (i64.const 1) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "shr_u" (i64.const 0x7fffffffffffffff) (i64.const 1)) (i64.const 0x3fffffffffffffff))
+(assert_return (invoke "shr_u" (i64.const 0x8000000000000000) (i64.const -1)) (i64.const 1))
+(assert_return (invoke "shr_u" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i64.const 0x8000000000000000))
+(assert_return (invoke "shr_u" (i64.const 0x7fffffffffffffff) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "shr_u" (i64.const 0x8000000000000000) (i64.const 1)) (i64.const 0x4000000000000000))
+(assert_return (invoke "shr_u" (i64.const 0x8000000000000000) (i64.const 2)) (i64.const 0x2000000000000000))
+(assert_return (invoke "shr_u" (i64.const 0x8000000000000000) (i64.const 0)) (i64.const 0x8000000000000000))
+(assert_return (invoke "shr_u" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i64.const 0))
+(assert_return (invoke "shr_u" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "shr_u" (i64.const 5) (i64.const 2)) (i64.const 1))
+(assert_return (invoke "shr_u" (i64.const -5) (i64.const 2)) (i64.const 0x3ffffffffffffffe))
+(assert_return (invoke "shr_u" (i64.const 5) (i64.const -2)) (i64.const 0))
+(assert_return (invoke "shr_u" (i64.const -5) (i64.const -2)) (i64.const 3))
+(assert_return (invoke "shr_u" (i64.const 7) (i64.const 3)) (i64.const 0))
+(assert_return (invoke "shr_u" (i64.const -7) (i64.const 3)) (i64.const 0x1fffffffffffffff))
+(assert_return (invoke "shr_u" (i64.const 7) (i64.const -3)) (i64.const 0))
+(assert_return (invoke "shr_u" (i64.const -7) (i64.const -3)) (i64.const 7))
+(assert_return (invoke "shr_u" (i64.const 11) (i64.const 5)) (i64.const 0))
+(assert_return (invoke "shr_u" (i64.const 17) (i64.const 7)) (i64.const 0))
+(assert_return (invoke "shr_u" (i64.const 1) (i64.const 63)) (i64.const 0))
+(assert_return (invoke "shr_u" (i64.const 1) (i64.const 64)) (i64.const 1))
+(assert_return (invoke "shr_u" (i64.const 1) (i64.const 65)) (i64.const 0))
+(assert_return (invoke "shr_u" (i64.const -1) (i64.const 64)) (i64.const -1))
+(assert_return (invoke "shr_u" (i64.const -1) (i64.const 65)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "shr_u" (i64.const 1) (i64.const 0x7fffffffffffffff)) (i64.const 0))
+(assert_return (invoke "shr_u" (i64.const 1) (i64.const 0x8000000000000000)) (i64.const 1))
+(assert_return (invoke "shr_u" (i64.const -1) (i64.const 0x8000000000000000)) (i64.const -1))
+(assert_return (invoke "shr_u" (i64.const 0x8000000000000000) (i64.const 63)) (i64.const 1))
+(assert_return (invoke "shr_u" (i64.const 0x123456789abcdef) (i64.const 0xfedcba9876543210)) (i64.const 0x123456789ab))
+(assert_return (invoke "shr_u" (i64.const 0x8ff00ff00ff00ff0) (i64.const 0x100000001)) (i64.const 0x47f807f807f807f8))
+(assert_return (invoke "shr_u" (i64.const 0x8000000000000001) (i64.const 1000)) (i64.const 0x800000))
+(assert_return (invoke "shr_u" (i64.const 0xf0f0ffff0f0fffff) (i64.const 0xfffff0f0fffff0f0)) (i64.const 0xf0f0))
+(assert_return (invoke "shr_u" (i64.const 0xabcd987602468ace) (i64.const 1)) (i64.const 0x55e6cc3b01234567))
+(assert_return (invoke "shr_u" (i64.const 0xfe000000dc000000) (i64.const 4)) (i64.const 0xfe000000dc00000))
+(assert_return (invoke "shr_u" (i64.const 0xabcd1234ef567809) (i64.const 53)) (i64.const 0x55e))
+(assert_return (invoke "shr_u" (i64.const 0xabd1234ef567809c) (i64.const 63)) (i64.const 1))
+(assert_return (invoke "shr_u" (i64.const 0xabcd1234ef567809) (i64.const 245)) (i64.const 0x55e))
+(assert_return (invoke "shr_u" (i64.const 0xabcd7294ef567809) (i64.const -19)) (i64.const 0x55e6b))
+(assert_return (invoke "shr_u" (i64.const 0xabcd1234ef567809) (i64.const 0x8000000000000035)) (i64.const 0x55e))
+(assert_return (invoke "shr_u" (i64.const 0xfe00dc00) (i64.const 4)) (i64.const 0xfe00dc0))
+(assert_return (invoke "shr_u" (i64.const 0x8000) (i64.const 37)) (i64.const 0))
+
+(assert_return (invoke "rotl" (i64.const 1) (i64.const 1)) (i64.const 2))
+(assert_return (invoke "rotl" (i64.const 1) (i64.const 0)) (i64.const 1))
+(assert_return (invoke "rotl" (i64.const 0) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "rotl" (i64.const 0) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "rotl" (i64.const -1) (i64.const -1)) (i64.const -1))
+(assert_return (invoke "rotl" (i64.const -1) (i64.const 1)) (i64.const -1))
+(assert_return (invoke "rotl" (i64.const 1) (i64.const -1)) (i64.const 0x8000000000000000))
+(assert_return (invoke "rotl" (i64.const 0x7fffffffffffffff) (i64.const 1)) (i64.const -2))
+(assert_return (invoke "rotl" (i64.const 0x8000000000000000) (i64.const -1)) (i64.const 0x4000000000000000))
+(assert_return (invoke "rotl" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i64.const 0x8000000000000000))
+(assert_return (invoke "rotl" (i64.const 0x7fffffffffffffff) (i64.const -1)) (i64.const 0xbfffffffffffffff))
+(assert_return (invoke "rotl" (i64.const 0x8000000000000000) (i64.const 1)) (i64.const 1))
+(assert_return (invoke "rotl" (i64.const 0x8000000000000000) (i64.const 2)) (i64.const 2))
+(assert_return (invoke "rotl" (i64.const 0x8000000000000000) (i64.const 0)) (i64.const 0x8000000000000000))
+(assert_return (invoke "rotl" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i64.const 0xbfffffffffffffff))
+(assert_return (invoke "rotl" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "rotl" (i64.const 5) (i64.const 2)) (i64.const 20))
+(assert_return (invoke "rotl" (i64.const -5) (i64.const 2)) (i64.const -17))
+(assert_return (invoke "rotl" (i64.const 5) (i64.const -2)) (i64.const 0x4000000000000001))
+(assert_return (invoke "rotl" (i64.const -5) (i64.const -2)) (i64.const -2))
+(assert_return (invoke "rotl" (i64.const 7) (i64.const 3)) (i64.const 56))
+(assert_return (invoke "rotl" (i64.const -7) (i64.const 3)) (i64.const -49))
+(assert_return (invoke "rotl" (i64.const 7) (i64.const -3)) (i64.const 0xe000000000000000))
+(assert_return (invoke "rotl" (i64.const -7) (i64.const -3)) (i64.const 0x3fffffffffffffff))
+(assert_return (invoke "rotl" (i64.const 11) (i64.const 5)) (i64.const 352))
+(assert_return (invoke "rotl" (i64.const 17) (i64.const 7)) (i64.const 0x880))
+(assert_return (invoke "rotl" (i64.const 1) (i64.const 63)) (i64.const 0x8000000000000000))
+(assert_return (invoke "rotl" (i64.const 1) (i64.const 64)) (i64.const 1))
+(assert_return (invoke "rotl" (i64.const 1) (i64.const 65)) (i64.const 2))
+(assert_return (invoke "rotl" (i64.const -1) (i64.const 64)) (i64.const -1))
+(assert_return (invoke "rotl" (i64.const -1) (i64.const 65)) (i64.const -1))
+(assert_return (invoke "rotl" (i64.const 1) (i64.const 0x7fffffffffffffff)) (i64.const 0x8000000000000000))
+(assert_return (invoke "rotl" (i64.const 1) (i64.const 0x8000000000000000)) (i64.const 1))
+(assert_return (invoke "rotl" (i64.const -1) (i64.const 0x8000000000000000)) (i64.const -1))
+(assert_return (invoke "rotl" (i64.const 0x8000000000000000) (i64.const 63)) (i64.const 0x4000000000000000))
+(assert_return (invoke "rotl" (i64.const 0x123456789abcdef) (i64.const 0xfedcba9876543210)) (i64.const 0x456789abcdef0123))
+(assert_return (invoke "rotl" (i64.const 0x8ff00ff00ff00ff0) (i64.const 0x100000001)) (i64.const 0x1fe01fe01fe01fe1))
+(assert_return (invoke "rotl" (i64.const 0x8000000000000001) (i64.const 1000)) (i64.const 0x18000000000))
+(assert_return (invoke "rotl" (i64.const 0xf0f0ffff0f0fffff) (i64.const 0xfffff0f0fffff0f0)) (i64.const 0xfffff0f0ffff0f0f))
+(assert_return (invoke "rotl" (i64.const 0xabcd987602468ace) (i64.const 1)) (i64.const 0x579b30ec048d159d))
+(assert_return (invoke "rotl" (i64.const 0xfe000000dc000000) (i64.const 4)) (i64.const 0xe000000dc000000f))
+(assert_return (invoke "rotl" (i64.const 0xabcd1234ef567809) (i64.const 53)) (i64.const 0x13579a2469deacf))
+(assert_return (invoke "rotl" (i64.const 0xabd1234ef567809c) (i64.const 63)) (i64.const 0x55e891a77ab3c04e))
+(assert_return (invoke "rotl" (i64.const 0xabcd1234ef567809) (i64.const 245)) (i64.const 0x13579a2469deacf))
+(assert_return (invoke "rotl" (i64.const 0xabcd7294ef567809) (i64.const -19)) (i64.const 0xcf013579ae529dea))
+(assert_return (invoke "rotl" (i64.const 0xabcd1234ef567809) (i64.const 0x8000000000000035)) (i64.const 0x13579a2469deacf))
+(assert_return (invoke "rotl" (i64.const 0xfe00dc00) (i64.const 4)) (i64.const 0xfe00dc000))
+(assert_return (invoke "rotl" (i64.const 0x8000) (i64.const 37)) (i64.const 0x10000000000000))
+
+(assert_return (invoke "rotr" (i64.const 1) (i64.const 1)) (i64.const 0x8000000000000000))
+(assert_return (invoke "rotr" (i64.const 1) (i64.const 0)) (i64.const 1))
+(assert_return (invoke "rotr" (i64.const 0) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "rotr" (i64.const 0) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "rotr" (i64.const -1) (i64.const -1)) (i64.const -1))
+(assert_return (invoke "rotr" (i64.const -1) (i64.const 1)) (i64.const -1))
+(assert_return (invoke "rotr" (i64.const 1) (i64.const -1)) (i64.const 2))
+(assert_return (invoke "rotr" (i64.const 0x7fffffffffffffff) (i64.const 1)) (i64.const 0xbfffffffffffffff))
+(assert_return (invoke "rotr" (i64.const 0x8000000000000000) (i64.const -1)) (i64.const 1))
+(assert_return (invoke "rotr" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i64.const 0x8000000000000000))
+(assert_return (invoke "rotr" (i64.const 0x7fffffffffffffff) (i64.const -1)) (i64.const -2))
+(assert_return (invoke "rotr" (i64.const 0x8000000000000000) (i64.const 1)) (i64.const 0x4000000000000000))
+(assert_return (invoke "rotr" (i64.const 0x8000000000000000) (i64.const 2)) (i64.const 0x2000000000000000))
+(assert_return (invoke "rotr" (i64.const 0x8000000000000000) (i64.const 0)) (i64.const 0x8000000000000000))
+(assert_return (invoke "rotr" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i64.const -2))
+(assert_return (invoke "rotr" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "rotr" (i64.const 5) (i64.const 2)) (i64.const 0x4000000000000001))
+(assert_return (invoke "rotr" (i64.const -5) (i64.const 2)) (i64.const -2))
+(assert_return (invoke "rotr" (i64.const 5) (i64.const -2)) (i64.const 20))
+(assert_return (invoke "rotr" (i64.const -5) (i64.const -2)) (i64.const -17))
+(assert_return (invoke "rotr" (i64.const 7) (i64.const 3)) (i64.const 0xe000000000000000))
+(assert_return (invoke "rotr" (i64.const -7) (i64.const 3)) (i64.const 0x3fffffffffffffff))
+(assert_return (invoke "rotr" (i64.const 7) (i64.const -3)) (i64.const 56))
+(assert_return (invoke "rotr" (i64.const -7) (i64.const -3)) (i64.const -49))
+(assert_return (invoke "rotr" (i64.const 11) (i64.const 5)) (i64.const 0x5800000000000000))
+(assert_return (invoke "rotr" (i64.const 17) (i64.const 7)) (i64.const 0x2200000000000000))
+(assert_return (invoke "rotr" (i64.const 1) (i64.const 63)) (i64.const 2))
+(assert_return (invoke "rotr" (i64.const 1) (i64.const 64)) (i64.const 1))
+(assert_return (invoke "rotr" (i64.const 1) (i64.const 65)) (i64.const 0x8000000000000000))
+(assert_return (invoke "rotr" (i64.const -1) (i64.const 64)) (i64.const -1))
+(assert_return (invoke "rotr" (i64.const -1) (i64.const 65)) (i64.const -1))
+(assert_return (invoke "rotr" (i64.const 1) (i64.const 0x7fffffffffffffff)) (i64.const 2))
+(assert_return (invoke "rotr" (i64.const 1) (i64.const 0x8000000000000000)) (i64.const 1))
+(assert_return (invoke "rotr" (i64.const -1) (i64.const 0x8000000000000000)) (i64.const -1))
+(assert_return (invoke "rotr" (i64.const 0x8000000000000000) (i64.const 63)) (i64.const 1))
+(assert_return (invoke "rotr" (i64.const 0x123456789abcdef) (i64.const 0xfedcba9876543210)) (i64.const 0xcdef0123456789ab))
+(assert_return (invoke "rotr" (i64.const 0x8ff00ff00ff00ff0) (i64.const 0x100000001)) (i64.const 0x47f807f807f807f8))
+(assert_return (invoke "rotr" (i64.const 0x8000000000000001) (i64.const 1000)) (i64.const 0x1800000))
+(assert_return (invoke "rotr" (i64.const 0xf0f0ffff0f0fffff) (i64.const 0xfffff0f0fffff0f0)) (i64.const 0xffff0f0ffffff0f0))
+(assert_return (invoke "rotr" (i64.const 0xabcd987602468ace) (i64.const 1)) (i64.const 0x55e6cc3b01234567))
+(assert_return (invoke "rotr" (i64.const 0xfe000000dc000000) (i64.const 4)) (i64.const 0xfe000000dc00000))
+(assert_return (invoke "rotr" (i64.const 0xabcd1234ef567809) (i64.const 53)) (i64.const 0x6891a77ab3c04d5e))
+(assert_return (invoke "rotr" (i64.const 0xabd1234ef567809c) (i64.const 63)) (i64.const 0x57a2469deacf0139))
+(assert_return (invoke "rotr" (i64.const 0xabcd1234ef567809) (i64.const 245)) (i64.const 0x6891a77ab3c04d5e))
+(assert_return (invoke "rotr" (i64.const 0xabcd7294ef567809) (i64.const -19)) (i64.const 0x94a77ab3c04d5e6b))
+(assert_return (invoke "rotr" (i64.const 0xabcd1234ef567809) (i64.const 0x8000000000000035)) (i64.const 0x6891a77ab3c04d5e))
+(assert_return (invoke "rotr" (i64.const 0xfe00dc00) (i64.const 4)) (i64.const 0xfe00dc0))
+(assert_return (invoke "rotr" (i64.const 0x8000) (i64.const 37)) (i64.const 0x40000000000))
+
+(assert_return (invoke "clz" (i64.const 0)) (i64.const 64))
+(assert_return (invoke "clz" (i64.const 1)) (i64.const 63))
+(assert_return (invoke "clz" (i64.const 2)) (i64.const 62))
+(assert_return (invoke "clz" (i64.const -1)) (i64.const 0))
+(assert_return (invoke "clz" (i64.const 0x8000000000000000)) (i64.const 0))
+(assert_return (invoke "clz" (i64.const 0x7fffffffffffffff)) (i64.const 1))
+(assert_return (invoke "clz" (i64.const 0x8000)) (i64.const 48))
+(assert_return (invoke "clz" (i64.const 255)) (i64.const 56))
+(assert_return (invoke "clz" (i64.const 0x10000)) (i64.const 47))
+(assert_return (invoke "clz" (i64.const 0xaaaaaaaa55555555)) (i64.const 0))
+(assert_return (invoke "clz" (i64.const 0x99999999aaaaaaaa)) (i64.const 0))
+(assert_return (invoke "clz" (i64.const 0xdeadbeefdeadbeef)) (i64.const 0))
+(assert_return (invoke "clz" (i64.const 0x8000000000000000)) (i64.const 0))
+(assert_return (invoke "clz" (i64.const 0x7fffffffffffffff)) (i64.const 1))
+
+(assert_return (invoke "ctz" (i64.const 0)) (i64.const 64))
+(assert_return (invoke "ctz" (i64.const 1)) (i64.const 0))
+(assert_return (invoke "ctz" (i64.const 2)) (i64.const 1))
+(assert_return (invoke "ctz" (i64.const -1)) (i64.const 0))
+(assert_return (invoke "ctz" (i64.const 0x8000000000000000)) (i64.const 63))
+(assert_return (invoke "ctz" (i64.const 0x7fffffffffffffff)) (i64.const 0))
+(assert_return (invoke "ctz" (i64.const 0x8000)) (i64.const 15))
+(assert_return (invoke "ctz" (i64.const 255)) (i64.const 0))
+(assert_return (invoke "ctz" (i64.const 0x10000)) (i64.const 16))
+(assert_return (invoke "ctz" (i64.const 0xaaaaaaaa55555555)) (i64.const 0))
+(assert_return (invoke "ctz" (i64.const 0x99999999aaaaaaaa)) (i64.const 1))
+(assert_return (invoke "ctz" (i64.const 0xdeadbeefdeadbeef)) (i64.const 0))
+(assert_return (invoke "ctz" (i64.const 0x8000000000000000)) (i64.const 63))
+(assert_return (invoke "ctz" (i64.const 0x7fffffffffffffff)) (i64.const 0))
+
+(assert_return (invoke "popcnt" (i64.const 0)) (i64.const 0))
+(assert_return (invoke "popcnt" (i64.const 1)) (i64.const 1))
+(assert_return (invoke "popcnt" (i64.const 2)) (i64.const 1))
+(assert_return (invoke "popcnt" (i64.const -1)) (i64.const 64))
+(assert_return (invoke "popcnt" (i64.const 0x8000000000000000)) (i64.const 1))
+(assert_return (invoke "popcnt" (i64.const 0x7fffffffffffffff)) (i64.const 63))
+(assert_return (invoke "popcnt" (i64.const 0x8000)) (i64.const 1))
+(assert_return (invoke "popcnt" (i64.const 255)) (i64.const 8))
+(assert_return (invoke "popcnt" (i64.const 0x10000)) (i64.const 1))
+(assert_return (invoke "popcnt" (i64.const 0xaaaaaaaa55555555)) (i64.const 32))
+(assert_return (invoke "popcnt" (i64.const 0x99999999aaaaaaaa)) (i64.const 32))
+(assert_return (invoke "popcnt" (i64.const 0xdeadbeefdeadbeef)) (i64.const 48))
+(assert_return (invoke "popcnt" (i64.const 0x8000000000000000)) (i64.const 1))
+(assert_return (invoke "popcnt" (i64.const 0x7fffffffffffffff)) (i64.const 63))
+
+(assert_return (invoke "eqz" (i64.const 0)) (i32.const 1))
+(assert_return (invoke "eqz" (i64.const 1)) (i32.const 0))
+(assert_return (invoke "eqz" (i64.const 2)) (i32.const 0))
+(assert_return (invoke "eqz" (i64.const -1)) (i32.const 0))
+(assert_return (invoke "eqz" (i64.const 0x8000000000000000)) (i32.const 0))
+(assert_return (invoke "eqz" (i64.const 0x7fffffffffffffff)) (i32.const 0))
+(assert_return (invoke "eqz" (i64.const 0x8000)) (i32.const 0))
+(assert_return (invoke "eqz" (i64.const 255)) (i32.const 0))
+(assert_return (invoke "eqz" (i64.const 0x10000)) (i32.const 0))
+(assert_return (invoke "eqz" (i64.const 0xaaaaaaaa55555555)) (i32.const 0))
+(assert_return (invoke "eqz" (i64.const 0x99999999aaaaaaaa)) (i32.const 0))
+(assert_return (invoke "eqz" (i64.const 0xdeadbeefdeadbeef)) (i32.const 0))
+(assert_return (invoke "eqz" (i64.const 0x8000000000000000)) (i32.const 0))
+(assert_return (invoke "eqz" (i64.const 0x7fffffffffffffff)) (i32.const 0))
+
+(assert_return (invoke "eq" (i64.const 0) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "eq" (i64.const 1) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "eq" (i64.const -1) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "eq" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i32.const 1))
+(assert_return (invoke "eq" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i32.const 1))
+(assert_return (invoke "eq" (i64.const -1) (i64.const -1)) (i32.const 1))
+(assert_return (invoke "eq" (i64.const 1) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "eq" (i64.const 0) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "eq" (i64.const 0x8000000000000000) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "eq" (i64.const 0) (i64.const 0x8000000000000000)) (i32.const 0))
+(assert_return (invoke "eq" (i64.const 0x8000000000000000) (i64.const -1)) (i32.const 0))
+(assert_return (invoke "eq" (i64.const -1) (i64.const 0x8000000000000000)) (i32.const 0))
+(assert_return (invoke "eq" (i64.const 0x8000000000000000) (i64.const 0x7fffffffffffffff)) (i32.const 0))
+(assert_return (invoke "eq" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i32.const 0))
+
+(assert_return (invoke "ne" (i64.const 0) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "ne" (i64.const 1) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "ne" (i64.const -1) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "ne" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i32.const 0))
+(assert_return (invoke "ne" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i32.const 0))
+(assert_return (invoke "ne" (i64.const -1) (i64.const -1)) (i32.const 0))
+(assert_return (invoke "ne" (i64.const 1) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "ne" (i64.const 0) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "ne" (i64.const 0x8000000000000000) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "ne" (i64.const 0) (i64.const 0x8000000000000000)) (i32.const 1))
+(assert_return (invoke "ne" (i64.const 0x8000000000000000) (i64.const -1)) (i32.const 1))
+(assert_return (invoke "ne" (i64.const -1) (i64.const 0x8000000000000000)) (i32.const 1))
+(assert_return (invoke "ne" (i64.const 0x8000000000000000) (i64.const 0x7fffffffffffffff)) (i32.const 1))
+(assert_return (invoke "ne" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i32.const 1))
+
+(assert_return (invoke "lt_s" (i64.const 0) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "lt_s" (i64.const 1) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "lt_s" (i64.const -1) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "lt_s" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i32.const 0))
+(assert_return (invoke "lt_s" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i32.const 0))
+(assert_return (invoke "lt_s" (i64.const -1) (i64.const -1)) (i32.const 0))
+(assert_return (invoke "lt_s" (i64.const 1) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "lt_s" (i64.const 0) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "lt_s" (i64.const 0x8000000000000000) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "lt_s" (i64.const 0) (i64.const 0x8000000000000000)) (i32.const 0))
+(assert_return (invoke "lt_s" (i64.const 0x8000000000000000) (i64.const -1)) (i32.const 1))
+(assert_return (invoke "lt_s" (i64.const -1) (i64.const 0x8000000000000000)) (i32.const 0))
+(assert_return (invoke "lt_s" (i64.const 0x8000000000000000) (i64.const 0x7fffffffffffffff)) (i32.const 1))
+(assert_return (invoke "lt_s" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i32.const 0))
+
+(assert_return (invoke "lt_u" (i64.const 0) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "lt_u" (i64.const 1) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "lt_u" (i64.const -1) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "lt_u" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i32.const 0))
+(assert_return (invoke "lt_u" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i32.const 0))
+(assert_return (invoke "lt_u" (i64.const -1) (i64.const -1)) (i32.const 0))
+(assert_return (invoke "lt_u" (i64.const 1) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "lt_u" (i64.const 0) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "lt_u" (i64.const 0x8000000000000000) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "lt_u" (i64.const 0) (i64.const 0x8000000000000000)) (i32.const 1))
+(assert_return (invoke "lt_u" (i64.const 0x8000000000000000) (i64.const -1)) (i32.const 1))
+(assert_return (invoke "lt_u" (i64.const -1) (i64.const 0x8000000000000000)) (i32.const 0))
+(assert_return (invoke "lt_u" (i64.const 0x8000000000000000) (i64.const 0x7fffffffffffffff)) (i32.const 0))
+(assert_return (invoke "lt_u" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i32.const 1))
+
+(assert_return (invoke "le_s" (i64.const 0) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "le_s" (i64.const 1) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "le_s" (i64.const -1) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "le_s" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i32.const 1))
+(assert_return (invoke "le_s" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i32.const 1))
+(assert_return (invoke "le_s" (i64.const -1) (i64.const -1)) (i32.const 1))
+(assert_return (invoke "le_s" (i64.const 1) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "le_s" (i64.const 0) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "le_s" (i64.const 0x8000000000000000) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "le_s" (i64.const 0) (i64.const 0x8000000000000000)) (i32.const 0))
+(assert_return (invoke "le_s" (i64.const 0x8000000000000000) (i64.const -1)) (i32.const 1))
+(assert_return (invoke "le_s" (i64.const -1) (i64.const 0x8000000000000000)) (i32.const 0))
+(assert_return (invoke "le_s" (i64.const 0x8000000000000000) (i64.const 0x7fffffffffffffff)) (i32.const 1))
+(assert_return (invoke "le_s" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i32.const 0))
+
+(assert_return (invoke "le_u" (i64.const 0) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "le_u" (i64.const 1) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "le_u" (i64.const -1) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "le_u" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i32.const 1))
+(assert_return (invoke "le_u" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i32.const 1))
+(assert_return (invoke "le_u" (i64.const -1) (i64.const -1)) (i32.const 1))
+(assert_return (invoke "le_u" (i64.const 1) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "le_u" (i64.const 0) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "le_u" (i64.const 0x8000000000000000) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "le_u" (i64.const 0) (i64.const 0x8000000000000000)) (i32.const 1))
+(assert_return (invoke "le_u" (i64.const 0x8000000000000000) (i64.const -1)) (i32.const 1))
+(assert_return (invoke "le_u" (i64.const -1) (i64.const 0x8000000000000000)) (i32.const 0))
+(assert_return (invoke "le_u" (i64.const 0x8000000000000000) (i64.const 0x7fffffffffffffff)) (i32.const 0))
+(assert_return (invoke "le_u" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i32.const 1))
+
+(assert_return (invoke "gt_s" (i64.const 0) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "gt_s" (i64.const 1) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "gt_s" (i64.const -1) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "gt_s" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i32.const 0))
+(assert_return (invoke "gt_s" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i32.const 0))
+(assert_return (invoke "gt_s" (i64.const -1) (i64.const -1)) (i32.const 0))
+(assert_return (invoke "gt_s" (i64.const 1) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "gt_s" (i64.const 0) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "gt_s" (i64.const 0x8000000000000000) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "gt_s" (i64.const 0) (i64.const 0x8000000000000000)) (i32.const 1))
+(assert_return (invoke "gt_s" (i64.const 0x8000000000000000) (i64.const -1)) (i32.const 0))
+(assert_return (invoke "gt_s" (i64.const -1) (i64.const 0x8000000000000000)) (i32.const 1))
+(assert_return (invoke "gt_s" (i64.const 0x8000000000000000) (i64.const 0x7fffffffffffffff)) (i32.const 0))
+(assert_return (invoke "gt_s" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i32.const 1))
+
+(assert_return (invoke "gt_u" (i64.const 0) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "gt_u" (i64.const 1) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "gt_u" (i64.const -1) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "gt_u" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i32.const 0))
+(assert_return (invoke "gt_u" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i32.const 0))
+(assert_return (invoke "gt_u" (i64.const -1) (i64.const -1)) (i32.const 0))
+(assert_return (invoke "gt_u" (i64.const 1) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "gt_u" (i64.const 0) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "gt_u" (i64.const 0x8000000000000000) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "gt_u" (i64.const 0) (i64.const 0x8000000000000000)) (i32.const 0))
+(assert_return (invoke "gt_u" (i64.const 0x8000000000000000) (i64.const -1)) (i32.const 0))
+(assert_return (invoke "gt_u" (i64.const -1) (i64.const 0x8000000000000000)) (i32.const 1))
+(assert_return (invoke "gt_u" (i64.const 0x8000000000000000) (i64.const 0x7fffffffffffffff)) (i32.const 1))
+(assert_return (invoke "gt_u" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i32.const 0))
+
+(assert_return (invoke "ge_s" (i64.const 0) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "ge_s" (i64.const 1) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "ge_s" (i64.const -1) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "ge_s" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i32.const 1))
+(assert_return (invoke "ge_s" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i32.const 1))
+(assert_return (invoke "ge_s" (i64.const -1) (i64.const -1)) (i32.const 1))
+(assert_return (invoke "ge_s" (i64.const 1) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "ge_s" (i64.const 0) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "ge_s" (i64.const 0x8000000000000000) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "ge_s" (i64.const 0) (i64.const 0x8000000000000000)) (i32.const 1))
+(assert_return (invoke "ge_s" (i64.const 0x8000000000000000) (i64.const -1)) (i32.const 0))
+(assert_return (invoke "ge_s" (i64.const -1) (i64.const 0x8000000000000000)) (i32.const 1))
+(assert_return (invoke "ge_s" (i64.const 0x8000000000000000) (i64.const 0x7fffffffffffffff)) (i32.const 0))
+(assert_return (invoke "ge_s" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i32.const 1))
+
+(assert_return (invoke "ge_u" (i64.const 0) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "ge_u" (i64.const 1) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "ge_u" (i64.const -1) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "ge_u" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i32.const 1))
+(assert_return (invoke "ge_u" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i32.const 1))
+(assert_return (invoke "ge_u" (i64.const -1) (i64.const -1)) (i32.const 1))
+(assert_return (invoke "ge_u" (i64.const 1) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "ge_u" (i64.const 0) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "ge_u" (i64.const 0x8000000000000000) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "ge_u" (i64.const 0) (i64.const 0x8000000000000000)) (i32.const 0))
+(assert_return (invoke "ge_u" (i64.const 0x8000000000000000) (i64.const -1)) (i32.const 0))
+(assert_return (invoke "ge_u" (i64.const -1) (i64.const 0x8000000000000000)) (i32.const 1))
+(assert_return (invoke "ge_u" (i64.const 0x8000000000000000) (i64.const 0x7fffffffffffffff)) (i32.const 1))
+(assert_return (invoke "ge_u" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i32.const 0))
+
+;; Type check
+
+(assert_invalid (module (func (result i64) (i64.add (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i64) (i64.sub (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i64) (i64.mul (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i64) (i64.div_s (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i64) (i64.div_u (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i64) (i64.rem_s (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i64) (i64.rem_u (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i64) (i64.and (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i64) (i64.or (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i64) (i64.xor (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i64) (i64.shl (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i64) (i64.shr_s (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i64) (i64.shr_u (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i64) (i64.rotl (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i64) (i64.rotr (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i64) (i64.clz (i32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i64) (i64.ctz (i32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i64) (i64.popcnt (i32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i64.eqz (i32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i64.eq (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i64.ne (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i64.lt_s (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i64.lt_u (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i64.le_s (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i64.le_u (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i64.gt_s (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i64.gt_u (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i64.ge_s (i32.const 0) (f32.const 0)))) "type mismatch")
+(assert_invalid (module (func (result i32) (i64.ge_u (i32.const 0) (f32.const 0)))) "type mismatch")
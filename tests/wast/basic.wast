@@ -0,0 +1,69 @@
+;; Directives of the spec test scripts, run by `mini-wasm wast` and the test suite.
+
+(module $math
+  (func (export "add") (param i32 i32) (result i32)
+    (i32.add (local.get 0) (local.get 1)))
+  (func (export "pair") (param i64) (result i64 i32)
+    (local.get 0) (i32.const -1))
+  (func (export "nan") (result f32) (f32.const nan))
+  (func (export "quiet") (result f64) (f64.const -nan:0x8000000000001))
+  (func (export "nothing")))
+
+(assert_return (invoke "add" (i32.const 1) (i32.const 2)) (i32.const 3))
+(assert_return (invoke "add" (i32.const 0x7fffffff) (i32.const 0)) (i32.const 2147483647))
+(assert_return (invoke $math "pair" (i64.const -5)) (i64.const -5) (i32.const 0xffffffff))
+(assert_return (invoke "nan") (f32.const nan:canonical))
+(assert_return (invoke "nan") (f32.const nan:arithmetic))
+(assert_return (invoke "quiet") (f64.const nan:arithmetic))
+(assert_return (invoke "nothing"))
+(invoke "nothing")
+
+(register "math" $math)
+
+(module
+  (import "math" "add" (func $add (param i32 i32) (result i32)))
+  (import "spectest" "print_i32" (func $print (param i32)))
+  (memory 1)
+  (data (i32.const 0) "\2a\00\00\00")
+  (func (export "load") (param i32) (result i32)
+    (call $print (local.get 0))
+    (call $add (i32.load (local.get 0)) (i32.const 1))))
+
+(assert_return (invoke "load" (i32.const 0)) (i32.const 43))
+(assert_trap (invoke "load" (i32.const 65536)) "out of bounds memory access")
+(assert_trap
+  (module
+    (memory 1)
+    (func $start (drop (i32.load (i32.const 65536))))
+    (start $start))
+  "out of bounds memory access")
+
+(assert_invalid
+  (module (func (result i32) (i64.const 0)))
+  "type mismatch")
+(assert_malformed
+  (module quote "(func (result i32) (i32.const))")
+  "unexpected token")
+(assert_malformed
+  (module binary "\00asm" "\02\00\00\00")
+  "unknown binary version")
+(assert_unlinkable
+  (module (import "math" "missing" (func)))
+  "unknown import")
+
+(module binary
+  "\00asm" "\01\00\00\00"
+  "\01\05\01\60\00\01\7f"          ;; type section: [] -> [i32]
+  "\03\02\01\00"                   ;; function section
+  "\07\07\01\03\6f\6e\65\00\00"    ;; export "one"
+  "\0a\06\01\04\00\41\01\0b")      ;; code: i32.const 1
+(assert_return (invoke "one") (i32.const 1))
+
+(module
+  (func $count (export "count") (param i32) (result i32)
+    (if (result i32) (i32.eqz (local.get 0))
+      (then (i32.const 0))
+      (else (i32.add (i32.const 1) (call $count (i32.sub (local.get 0) (i32.const 1)))))))
+  (func $runaway (export "runaway") (call $runaway)))
+(assert_return (invoke "count" (i32.const 5000)) (i32.const 5000))
+(assert_exhaustion (invoke "runaway") "call stack exhausted")
@@ -0,0 +1,30 @@
+;; Tables of function references, which can be sized and grown but not yet accessed.
+
+(module
+  (table $t 1 10 funcref)
+  (table $unbounded 0 funcref)
+  (func (export "size") (result i32) (table.size $t))
+  (func (export "grow") (param i32) (result i32) (table.grow $t (ref.null func) (local.get 0)))
+  (func (export "grow-unbounded") (param i32) (result i32)
+    (table.grow $unbounded (ref.null func) (local.get 0)))
+  (func (export "null") (result funcref) (ref.null func))
+  (func (export "id") (param funcref) (result funcref) (local.get 0)))
+
+(assert_return (invoke "size") (i32.const 1))
+(assert_return (invoke "grow" (i32.const 0)) (i32.const 1))
+(assert_return (invoke "grow" (i32.const 2)) (i32.const 1))
+(assert_return (invoke "size") (i32.const 3))
+(assert_return (invoke "grow" (i32.const 8)) (i32.const -1))
+(assert_return (invoke "grow" (i32.const 7)) (i32.const 3))
+(assert_return (invoke "size") (i32.const 10))
+(assert_return (invoke "grow" (i32.const 1)) (i32.const -1))
+(assert_return (invoke "grow-unbounded" (i32.const 5)) (i32.const 0))
+(assert_return (invoke "grow-unbounded" (i32.const 0xffffffff)) (i32.const -1))
+(assert_return (invoke "null") (ref.null func))
+(assert_return (invoke "id" (ref.null func)) (ref.null func))
+
+(assert_invalid (module (func (result i32) (table.size 0))) "unknown table")
+(assert_invalid
+  (module (table 0 funcref) (func (result i32) (table.grow 0 (i32.const 0) (i32.const 1))))
+  "type mismatch")
+(assert_invalid (module (table 2 1 funcref)) "size minimum must not be greater than maximum")